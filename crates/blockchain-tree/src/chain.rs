@@ -21,7 +21,10 @@ use reth_provider::{
     DBProvider, FullExecutionDataProvider, HashedPostStateProvider, ProviderError,
     StateRootProvider, TryIntoHistoricalStateProvider,
 };
+#[cfg(feature = "scroll")]
+use reth_scroll_state_commitment::ParallelStateRoot;
 use reth_trie::{updates::TrieUpdates, TrieInput};
+#[cfg(not(feature = "scroll"))]
 use reth_trie_parallel::root::ParallelStateRoot;
 use std::{
     collections::BTreeMap,
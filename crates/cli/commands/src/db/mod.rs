@@ -11,6 +11,8 @@ mod clear;
 mod diff;
 mod get;
 mod list;
+#[cfg(feature = "scroll")]
+mod scroll_extensions;
 mod stats;
 /// DB List TUI
 mod tui;
@@ -50,6 +52,10 @@ pub enum Subcommands {
     Version,
     /// Returns the full database path
     Path,
+    /// Checks the Scroll account extensions against the accounts' bytecode, and optionally fixes
+    /// them
+    #[cfg(feature = "scroll")]
+    ScrollExtensions(scroll_extensions::Command),
 }
 
 /// `db_ro_exec` opens a database in read-only mode, and then execute with the provided command
@@ -148,6 +154,12 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
             Subcommands::Path => {
                 println!("{}", db_path.display());
             }
+            #[cfg(feature = "scroll")]
+            Subcommands::ScrollExtensions(command) => {
+                let Environment { provider_factory, .. } =
+                    self.env.init::<N>(command.access_rights())?;
+                command.execute(provider_factory)?;
+            }
         }
 
         Ok(())
@@ -172,4 +184,19 @@ mod tests {
         .unwrap();
         assert_eq!(cmd.env.datadir.resolve_datadir(cmd.env.chain.chain).as_ref(), Path::new(&path));
     }
+
+    #[cfg(feature = "scroll")]
+    #[test]
+    fn parse_scroll_extensions_access_rights() {
+        let access_rights = |args: &[&str]| {
+            let cmd = Command::<EthereumChainSpecParser>::try_parse_from(args).unwrap();
+            let Subcommands::ScrollExtensions(command) = cmd.command else {
+                panic!("unexpected subcommand")
+            };
+            command.access_rights().is_read_write()
+        };
+
+        assert!(!access_rights(&["reth", "scroll-extensions"]));
+        assert!(access_rights(&["reth", "scroll-extensions", "--fix"]));
+    }
 }
@@ -0,0 +1,243 @@
+use crate::common::AccessRights;
+use alloy_primitives::{map::HashMap, B256};
+use clap::Parser;
+use reth_db::tables;
+use reth_db_api::{
+    cursor::DbCursorRO,
+    table::Table,
+    transaction::{DbTx, DbTxMut},
+    DatabaseError,
+};
+use reth_primitives::{Account, GotExpected};
+use reth_provider::{
+    providers::ProviderNodeTypes, DBProvider, DatabaseProviderFactory, HeaderProvider,
+    ProviderError, ProviderFactory, StageCheckpointReader, StateCommitmentProvider, TrieWriter,
+};
+use reth_scroll_primitives::AccountExtension;
+use reth_stages::StageId;
+use reth_trie::{
+    prefix_set::{PrefixSetMut, TriePrefixSetsMut},
+    Nibbles,
+};
+use reth_trie_db::{DatabaseStateRoot, StateCommitment};
+use std::fmt::Debug;
+use tracing::{info, warn};
+
+/// Number of scanned accounts after which to log progress.
+const LOG_INTERVAL: usize = 1_000_000;
+
+/// The arguments for the `reth db scroll-extensions` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Rewrites the mismatching account extensions and updates the state trie of the affected
+    /// accounts.
+    ///
+    /// The changes are only committed if the updated state root matches the header of the last
+    /// block processed by the merkle stage.
+    #[arg(long)]
+    fix: bool,
+}
+
+impl Command {
+    /// Returns the access rights required by the command.
+    pub(crate) const fn access_rights(&self) -> AccessRights {
+        if self.fix {
+            AccessRights::RW
+        } else {
+            AccessRights::RO
+        }
+    }
+
+    /// Execute `db scroll-extensions` command
+    pub fn execute<N: ProviderNodeTypes>(
+        self,
+        provider_factory: ProviderFactory<N>,
+    ) -> eyre::Result<()> {
+        warn!("This command should be run without the node running!");
+
+        if !self.fix {
+            Audit::new(&provider_factory.provider()?)?;
+            return Ok(())
+        }
+
+        let provider_rw = provider_factory.database_provider_rw()?;
+        let audit = Audit::new(&provider_rw)?;
+        if audit.is_empty() {
+            return Ok(())
+        }
+
+        audit.fix(&provider_rw)?;
+        provider_rw.commit()?;
+
+        info!(target: "reth::cli", "Account extensions fixed");
+        Ok(())
+    }
+}
+
+/// The state root type of the provider's state commitment.
+type StateRoot<'a, Provider> =
+    <<Provider as StateCommitmentProvider>::StateCommitment as StateCommitment>::StateRoot<
+        'a,
+        <Provider as DBProvider>::Tx,
+    >;
+
+/// The accounts whose extension doesn't match their bytecode, along with the expected extension.
+///
+/// The expected extension is `None` if the account's bytecode is missing from the database.
+type Mismatches<K> = Vec<(K, Account, Option<AccountExtension>)>;
+
+/// The result of scanning the accounts tables for mismatching account extensions.
+#[derive(Debug)]
+struct Audit {
+    plain: Mismatches<<tables::PlainAccountState as Table>::Key>,
+    hashed: Mismatches<<tables::HashedAccounts as Table>::Key>,
+}
+
+impl Audit {
+    /// Scans the `PlainAccountState` and `HashedAccounts` tables and reports the accounts whose
+    /// extension doesn't match the one computed from their bytecode.
+    fn new<Provider: DBProvider>(provider: &Provider) -> eyre::Result<Self> {
+        let tx = provider.tx_ref();
+        let mut extensions = HashMap::default();
+
+        let audit = Self {
+            plain: scan::<tables::PlainAccountState, _>(tx, &mut extensions)?,
+            hashed: scan::<tables::HashedAccounts, _>(tx, &mut extensions)?,
+        };
+
+        info!(target: "reth::cli",
+            plain_mismatches = audit.plain.len(),
+            hashed_mismatches = audit.hashed.len(),
+            "Account extensions scanned"
+        );
+        Ok(audit)
+    }
+
+    /// Returns `true` if no mismatching account extension was found.
+    fn is_empty(&self) -> bool {
+        self.plain.is_empty() && self.hashed.is_empty()
+    }
+
+    /// Rewrites the mismatching account extensions and updates the state trie of the affected
+    /// hashed accounts.
+    ///
+    /// Returns an error if the bytecode of an account is missing, or if the updated state root
+    /// doesn't match the one of the last block processed by the merkle stage.
+    fn fix<Provider>(self, provider: &Provider) -> eyre::Result<()>
+    where
+        Provider: DBProvider<Tx: DbTxMut>
+            + HeaderProvider
+            + StageCheckpointReader
+            + StateCommitmentProvider
+            + TrieWriter,
+    {
+        let tx = provider.tx_ref();
+
+        for (address, account, expected) in self.plain {
+            let Some(extension) = expected else {
+                eyre::bail!("Bytecode of account {address} is missing from the database")
+            };
+            tx.put::<tables::PlainAccountState>(
+                address,
+                Account { account_extension: Some(extension), ..account },
+            )?;
+        }
+
+        if self.hashed.is_empty() {
+            return Ok(())
+        }
+
+        let mut account_prefix_set = PrefixSetMut::default();
+        for (hashed_address, account, expected) in self.hashed {
+            let Some(extension) = expected else {
+                eyre::bail!(
+                    "Bytecode of hashed account {hashed_address} is missing from the database"
+                )
+            };
+            tx.put::<tables::HashedAccounts>(
+                hashed_address,
+                Account { account_extension: Some(extension), ..account },
+            )?;
+            account_prefix_set.insert(Nibbles::unpack(hashed_address));
+        }
+
+        let block = provider
+            .get_stage_checkpoint(StageId::MerkleExecute)?
+            .map(|checkpoint| checkpoint.block_number)
+            .unwrap_or_default();
+        let expected_state_root = provider
+            .header_by_number(block)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(block.into()))?
+            .state_root;
+
+        info!(target: "reth::cli", block, "Updating state trie");
+
+        let prefix_sets = TriePrefixSetsMut { account_prefix_set, ..Default::default() }.freeze();
+        let (state_root, updates) =
+            StateRoot::<'_, Provider>::root_from_prefix_sets_with_updates(tx, prefix_sets)?;
+        if state_root != expected_state_root {
+            eyre::bail!(
+                "State root mismatch at block {block} after fixing account extensions: {}",
+                GotExpected { got: state_root, expected: expected_state_root }
+            )
+        }
+
+        provider.write_trie_updates(&updates)?;
+        Ok(())
+    }
+}
+
+/// Scans the accounts of the table and returns the ones whose extension doesn't match their
+/// bytecode.
+fn scan<T, TX>(
+    tx: &TX,
+    extensions: &mut HashMap<B256, Option<AccountExtension>>,
+) -> eyre::Result<Mismatches<T::Key>>
+where
+    T: Table<Key: Debug, Value = Account>,
+    TX: DbTx,
+{
+    let mut mismatches = Vec::new();
+
+    for (index, entry) in tx.cursor_read::<T>()?.walk(None)?.enumerate() {
+        let (key, account) = entry?;
+
+        let expected = expected_extension(tx, &account, extensions)?;
+        if expected.is_none() || account.account_extension != expected {
+            warn!(target: "reth::cli",
+                table = T::NAME,
+                ?key,
+                got = ?account.account_extension,
+                ?expected,
+                "Account extension mismatch"
+            );
+            mismatches.push((key, account, expected));
+        }
+
+        if index > 0 && index % LOG_INTERVAL == 0 {
+            info!(target: "reth::cli", table = T::NAME, scanned = index, "Scanning accounts");
+        }
+    }
+
+    Ok(mismatches)
+}
+
+/// Returns the account extension computed from the account's bytecode, or `None` if the bytecode
+/// is missing from the database.
+fn expected_extension<TX: DbTx>(
+    tx: &TX,
+    account: &Account,
+    extensions: &mut HashMap<B256, Option<AccountExtension>>,
+) -> Result<Option<AccountExtension>, DatabaseError> {
+    let Some(code_hash) = account.bytecode_hash else { return Ok(Some(AccountExtension::empty())) };
+
+    if let Some(extension) = extensions.get(&code_hash) {
+        return Ok(*extension)
+    }
+
+    let extension = tx
+        .get::<tables::Bytecodes>(code_hash)?
+        .map(|bytecode| AccountExtension::from_bytecode(&bytecode.original_bytes()));
+    extensions.insert(code_hash, extension);
+    Ok(extension)
+}
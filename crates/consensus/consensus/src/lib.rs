@@ -429,6 +429,89 @@ pub enum ConsensusError {
         /// The block's timestamp.
         timestamp: u64,
     },
+
+    /// Error when the block contains ommers, which are not supported by Scroll.
+    #[cfg(feature = "scroll")]
+    #[display("block contains {count} ommers")]
+    UnexpectedOmmers {
+        /// The number of ommers in the block.
+        count: usize,
+    },
+
+    /// Error when the block's base fee exceeds the maximum L2 base fee.
+    #[cfg(feature = "scroll")]
+    #[display("block base fee {base_fee} exceeds maximum {max_base_fee}")]
+    BaseFeeExceedsMaximum {
+        /// The block's base fee.
+        base_fee: u64,
+        /// The maximum allowed base fee.
+        max_base_fee: u64,
+    },
+
+    /// Error when an L1 message is included after an L2 transaction in the block.
+    #[cfg(feature = "scroll")]
+    #[display("L1 message at transaction index {tx_index} follows an L2 transaction")]
+    L1MessageAfterL2Transaction {
+        /// The index of the L1 message in the block.
+        tx_index: usize,
+    },
+
+    /// Error when the queue index of an L1 message isn't greater than the one of the previous L1
+    /// message, in the block or in the parent chain.
+    #[cfg(feature = "scroll")]
+    #[display(
+        "L1 message queue index {queue_index} is not greater than previous queue index {previous_queue_index}"
+    )]
+    L1MessageQueueIndexNotIncreasing {
+        /// The queue index of the previous L1 message.
+        previous_queue_index: u64,
+        /// The queue index of the L1 message.
+        queue_index: u64,
+    },
+
+    /// Error when the block includes more L1 messages than allowed per block.
+    #[cfg(feature = "scroll")]
+    #[display("block includes {count} L1 messages, exceeding maximum {max_count}")]
+    TooManyL1Messages {
+        /// The number of L1 messages in the block.
+        count: u64,
+        /// The maximum number of L1 messages per block.
+        max_count: u64,
+    },
+
+    /// Error when the L1 message is not known to the local copy of the L1 message queue, which
+    /// already indexed the messages past its queue index.
+    #[cfg(feature = "scroll")]
+    #[display("L1 message with queue index {queue_index} is unknown")]
+    L1MessageUnknown {
+        /// The queue index of the L1 message.
+        queue_index: u64,
+    },
+
+    /// Error when the L1 message differs from the message emitted by the L1 message queue.
+    #[cfg(feature = "scroll")]
+    #[display("L1 message with queue index {queue_index} differs from the L1 message queue")]
+    L1MessageMismatch {
+        /// The queue index of the L1 message.
+        queue_index: u64,
+    },
+
+    /// Error when the L1 message isn't indexed yet by the local copy of the L1 message queue.
+    ///
+    /// This doesn't make the block invalid: it can be validated once the queue caught up with L1.
+    #[cfg(feature = "scroll")]
+    #[display("L1 message with queue index {queue_index} is not indexed yet")]
+    L1MessageNotIndexed {
+        /// The queue index of the L1 message.
+        queue_index: u64,
+    },
+
+    /// Error when the local copy of the L1 message queue can't be read.
+    ///
+    /// This doesn't make the block invalid.
+    #[cfg(feature = "scroll")]
+    #[display("failed to read the L1 message queue: {_0}")]
+    L1MessageQueueProvider(alloc::boxed::Box<reth_storage_errors::provider::ProviderError>),
 }
 
 impl ConsensusError {
@@ -436,6 +519,13 @@ impl ConsensusError {
     pub const fn is_state_root_error(&self) -> bool {
         matches!(self, Self::BodyStateRootDiff(_))
     }
+
+    /// Returns `true` if the block couldn't be validated yet, in which case it isn't invalid and
+    /// can be validated again later.
+    #[cfg(feature = "scroll")]
+    pub const fn is_retriable(&self) -> bool {
+        matches!(self, Self::L1MessageNotIndexed { .. } | Self::L1MessageQueueProvider(_))
+    }
 }
 
 impl From<InvalidTransactionError> for ConsensusError {
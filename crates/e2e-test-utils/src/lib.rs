@@ -1,8 +1,8 @@
 //! Utilities for end-to-end tests.
+//!
+//! With the `scroll` feature, Scroll nodes are set up with the [`scroll`] helpers.
 
-// Don't use the crate if `scroll` feature is used.
 #![cfg_attr(feature = "scroll", allow(unused_crate_dependencies))]
-#![cfg(not(feature = "scroll"))]
 
 use node::NodeTestContext;
 use reth_chainspec::EthChainSpec;
@@ -51,6 +51,10 @@ mod rpc;
 /// Helper traits
 mod traits;
 
+/// Setup of the Scroll test nodes
+#[cfg(feature = "scroll")]
+pub mod scroll;
+
 /// Creates the initial setup with `num_nodes` started and interconnected.
 pub async fn setup<N>(
     num_nodes: usize,
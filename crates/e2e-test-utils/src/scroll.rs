@@ -0,0 +1,350 @@
+use crate::{network::NetworkTestContext, wallet::Wallet};
+use alloy_eips::{eip2718::Encodable2718, BlockNumberOrTag};
+use alloy_primitives::{Address, BlockNumber, Bytes, B256, U256};
+use alloy_rpc_types_engine::{ExecutionPayloadInputV2, ForkchoiceState, ForkchoiceUpdated};
+use eyre::OptionExt;
+use jsonrpsee::http_client::{transport::HttpBackend, HttpClient};
+use reth_chainspec::EthChainSpec;
+use reth_db::{test_utils::TempDatabase, DatabaseEnv};
+use reth_ethereum_engine_primitives::{EthEngineTypes, EthPayloadAttributes};
+use reth_network::NetworkHandle;
+use reth_node_builder::{
+    components::NodeComponentsBuilder, EngineNodeLauncher, FullNode, FullNodeTypesAdapter, Node,
+    NodeAdapter, NodeBuilder, NodeConfig, NodeHandle, NodeTypesWithDBAdapter,
+};
+use reth_node_core::args::{DiscoveryArgs, NetworkArgs, RpcServerArgs};
+use reth_payload_builder::PayloadId;
+use reth_payload_builder_primitives::PayloadBuilder;
+use reth_payload_primitives::BuiltPayload;
+use reth_primitives::{BlockExt, Receipt, SealedBlock, SealedHeader, Transaction};
+use reth_provider::{
+    providers::BlockchainProvider2, BlockReader, BlockReaderIdExt, DBProvider,
+    DatabaseProviderFactory, HeaderProvider, ReceiptProvider, StateProviderFactory, StateReader,
+    StateRootProvider, TransactionsProvider,
+};
+use reth_rpc_api::EngineApiClient;
+use reth_rpc_eth_api::helpers::EthTransactions;
+use reth_rpc_layer::AuthClientService;
+use reth_scroll_chainspec::ScrollChainSpec;
+use reth_scroll_evm::L1GasPriceOracle;
+use reth_scroll_forks::ScrollHardforks;
+use reth_scroll_node::{ScrollAddOns, ScrollL1MessageQueue, ScrollNode};
+use reth_scroll_payload::L1MessageProvider;
+use reth_scroll_rpc::{L1MessageRequest, ScrollDevApi, ScrollDevApiServer};
+use reth_scroll_state_commitment::{mpt::mpt_base_state, mpt_state_root};
+use reth_tasks::TaskManager;
+use reth_transaction_pool::TransactionPool;
+use reth_trie::{HashedPostState, KeccakKeyHasher};
+use std::{sync::Arc, time::Duration};
+use tracing::{span, Level};
+
+/// The interval at which the node is polled while waiting.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// The time after which waiting for the node fails.
+const WAIT_TIMEOUT: Duration = Duration::from_secs(30);
+
+type TmpDB = Arc<TempDatabase<DatabaseEnv>>;
+type ScrollTmpNodeAdapter = FullNodeTypesAdapter<
+    NodeTypesWithDBAdapter<ScrollNode, TmpDB>,
+    BlockchainProvider2<NodeTypesWithDBAdapter<ScrollNode, TmpDB>>,
+>;
+
+/// Type alias for the components of a Scroll test node.
+pub type ScrollAdapter = NodeAdapter<
+    ScrollTmpNodeAdapter,
+    <<ScrollNode as Node<ScrollTmpNodeAdapter>>::ComponentsBuilder as NodeComponentsBuilder<
+        ScrollTmpNodeAdapter,
+    >>::Components,
+>;
+
+/// Creates `num_nodes` interconnected Scroll nodes on the given chain spec, e.g.
+/// [`SCROLL_DEV`](reth_scroll_chainspec::SCROLL_DEV).
+///
+/// The nodes aren't launched in dev mode: their blocks are only built when advanced through the
+/// engine API by [`ScrollNodeTestContext::advance_block`].
+pub async fn setup_scroll(
+    num_nodes: usize,
+    chain_spec: Arc<ScrollChainSpec>,
+) -> eyre::Result<(Vec<ScrollNodeTestContext>, TaskManager, Wallet)> {
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+
+    let network_config = NetworkArgs {
+        discovery: DiscoveryArgs { disable_discovery: true, ..DiscoveryArgs::default() },
+        ..NetworkArgs::default()
+    };
+
+    // Create nodes and peer them
+    let mut nodes: Vec<ScrollNodeTestContext> = Vec::with_capacity(num_nodes);
+
+    for idx in 0..num_nodes {
+        let node_config = NodeConfig::new(chain_spec.clone())
+            .with_network(network_config.clone())
+            .with_unused_ports()
+            .with_rpc(RpcServerArgs::default().with_unused_ports().with_http());
+
+        let span = span!(Level::INFO, "node", idx);
+        let _enter = span.enter();
+        let NodeHandle { node, node_exit_future: _ } = NodeBuilder::new(node_config)
+            .testing_node(exec.clone())
+            .with_types_and_provider::<ScrollNode, BlockchainProvider2<_>>()
+            .with_components(ScrollNode.components_builder())
+            .with_add_ons(ScrollNode.add_ons())
+            .launch_with_fn(|builder| {
+                let launcher = EngineNodeLauncher::new(
+                    builder.task_executor().clone(),
+                    builder.config().datadir(),
+                    Default::default(),
+                );
+                builder.launch_with(launcher)
+            })
+            .await?;
+
+        let mut node = ScrollNodeTestContext::new(node);
+
+        // Connect each node in a chain.
+        if let Some(previous_node) = nodes.last_mut() {
+            previous_node.connect(&mut node).await;
+        }
+
+        // Connect last node with the first if there are more than two
+        if idx + 1 == num_nodes && num_nodes > 2 {
+            if let Some(first_node) = nodes.first_mut() {
+                node.connect(first_node).await;
+            }
+        }
+
+        nodes.push(node);
+    }
+
+    Ok((nodes, tasks, Wallet::default().with_chain_id(chain_spec.chain().id())))
+}
+
+/// An helper struct to handle the actions of a Scroll node.
+#[allow(missing_debug_implementations)]
+pub struct ScrollNodeTestContext {
+    /// The core structure representing the full node.
+    pub inner: FullNode<ScrollAdapter, ScrollAddOns<ScrollAdapter>>,
+    /// Context for testing network functionalities.
+    pub network: NetworkTestContext<NetworkHandle>,
+    /// The client of the authenticated engine API.
+    engine_api_client: HttpClient<AuthClientService<HttpBackend>>,
+}
+
+impl ScrollNodeTestContext {
+    /// Creates a new test node
+    pub fn new(node: FullNode<ScrollAdapter, ScrollAddOns<ScrollAdapter>>) -> Self {
+        Self {
+            network: NetworkTestContext::new(node.network.clone()),
+            engine_api_client: node.auth_server_handle().http_client(),
+            inner: node,
+        }
+    }
+
+    /// Establish a connection to the node
+    pub async fn connect(&mut self, node: &mut Self) {
+        self.network.add_peer(node.network.record()).await;
+        node.network.next_session_established().await;
+        self.network.next_session_established().await;
+    }
+
+    /// Injects a raw transaction into the node tx pool via RPC server
+    pub async fn inject_tx(&self, raw_tx: Bytes) -> eyre::Result<B256> {
+        let eth_api = self.inner.add_ons_handle.eth_api();
+        Ok(eth_api.send_raw_transaction(raw_tx).await?)
+    }
+
+    /// Appends an L1 message to the local L1 message queue, as the `scrolldev_enqueueL1Message`
+    /// method does. Returns the hash of its transaction.
+    pub fn enqueue_l1_message(&self, message: L1MessageRequest) -> eyre::Result<B256> {
+        Ok(ScrollDevApi::new(self.inner.provider.clone()).enqueue_l1_message(message)?)
+    }
+
+    /// Returns the header of the canonical head.
+    pub fn head(&self) -> eyre::Result<SealedHeader> {
+        self.inner
+            .provider
+            .sealed_header_by_number_or_tag(BlockNumberOrTag::Latest)?
+            .ok_or_eyre("missing head")
+    }
+
+    /// Advances the node forward one block through the engine API, including all the pending
+    /// transactions of the pool and the pending L1 messages.
+    ///
+    /// The payload is built on top of the canonical head, imported and made canonical.
+    pub async fn advance_block(&mut self) -> eyre::Result<SealedBlock> {
+        let head = self.head()?;
+        let transactions = self.inner.pool.pool_size().pending + self.pending_l1_messages(&head)?;
+
+        // trigger new payload building on top of the head
+        let attributes = EthPayloadAttributes {
+            timestamp: head.timestamp + 1,
+            prev_randao: B256::ZERO,
+            suggested_fee_recipient: Address::ZERO,
+            withdrawals: None,
+            parent_beacon_block_root: None,
+        };
+        let updated = EngineApiClient::<EthEngineTypes>::fork_choice_updated_v2(
+            &self.engine_api_client,
+            forkchoice_state(head.hash()),
+            Some(attributes),
+        )
+        .await?;
+        let payload_id = updated.payload_id.ok_or_eyre("missing payload id")?;
+
+        // wait for the payload builder to include the transactions and resolve the payload
+        self.wait_for_built_payload(payload_id, transactions).await?;
+        let envelope =
+            EngineApiClient::<EthEngineTypes>::get_payload_v2(&self.engine_api_client, payload_id)
+                .await?;
+        let payload = envelope.execution_payload.into_v1_payload();
+        let hash = payload.block_hash;
+
+        // import the payload and make it canonical
+        let status = EngineApiClient::<EthEngineTypes>::new_payload_v2(
+            &self.engine_api_client,
+            ExecutionPayloadInputV2 { execution_payload: payload, withdrawals: None },
+        )
+        .await?;
+        eyre::ensure!(status.status.is_valid(), "invalid payload {hash}: {status:?}");
+        let updated = self.update_forkchoice(hash).await?;
+        eyre::ensure!(updated.is_valid(), "invalid forkchoice {hash}: {updated:?}");
+
+        let block = self.inner.provider.block_by_hash(hash)?.ok_or_eyre("missing block")?;
+        Ok(block.seal(hash))
+    }
+
+    /// Sends forkchoice update to the engine api, making the block with the given hash the head.
+    ///
+    /// The node syncs the block from its peers if it's unknown.
+    pub async fn update_forkchoice(&self, hash: B256) -> eyre::Result<ForkchoiceUpdated> {
+        Ok(EngineApiClient::<EthEngineTypes>::fork_choice_updated_v2(
+            &self.engine_api_client,
+            forkchoice_state(hash),
+            None,
+        )
+        .await?)
+    }
+
+    /// Waits until the block with the given hash is the canonical head.
+    ///
+    /// Returns an error if it isn't after [`WAIT_TIMEOUT`].
+    pub async fn wait_for_head(&self, hash: B256) -> eyre::Result<()> {
+        let wait = async {
+            while self.head()?.hash() != hash {
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+            Ok::<_, eyre::Report>(())
+        };
+        tokio::time::timeout(WAIT_TIMEOUT, wait)
+            .await
+            .map_err(|_| eyre::eyre!("timed out waiting for head {hash}"))?
+    }
+
+    /// Returns the receipt of the transaction with the given hash.
+    pub fn receipt(&self, tx_hash: B256) -> eyre::Result<Receipt> {
+        self.inner.provider.receipt_by_hash(tx_hash)?.ok_or_eyre("missing receipt")
+    }
+
+    /// Asserts that the transaction with the given hash succeeded and that its receipt carries the
+    /// L1 data fee computed from the L1 gas price oracle of the parent block.
+    pub fn assert_receipt(&self, tx_hash: B256) -> eyre::Result<Receipt> {
+        let (tx, meta) = self
+            .inner
+            .provider
+            .transaction_by_hash_with_meta(tx_hash)?
+            .ok_or_eyre("missing transaction")?;
+        let receipt = self.receipt(tx_hash)?;
+        assert!(receipt.success, "transaction {tx_hash} failed");
+
+        // L1 messages are paid for on L1
+        let l1_fee = if matches!(tx.transaction, Transaction::L1Message(_)) {
+            U256::ZERO
+        } else {
+            let state = self.inner.provider.history_by_block_number(meta.block_number - 1)?;
+            let is_curie = self.inner.chain_spec().is_curie_active_at_block(meta.block_number);
+            L1GasPriceOracle::try_from_state(&state)?.l1_data_fee(&tx.encoded_2718(), is_curie)
+        };
+        assert_eq!(receipt.l1_fee, l1_fee, "unexpected L1 fee of transaction {tx_hash}");
+
+        Ok(receipt)
+    }
+
+    /// Asserts that the state root of the block with the given hash matches the root of the
+    /// Poseidon binary Merkle Patricia Trie of its state, or the root of the keccak Merkle
+    /// Patricia Trie from Euclid on.
+    pub fn assert_state_root(&self, hash: B256) -> eyre::Result<()> {
+        let header =
+            self.inner.provider.sealed_header_by_hash(hash)?.ok_or_eyre("missing header")?;
+        let root = if self.inner.chain_spec().is_euclid_active_at_timestamp(header.timestamp) {
+            self.mpt_state_root(header.number)?
+        } else {
+            let state = self.inner.provider.state_by_block_hash(hash)?;
+            state.state_root_from_state(HashedPostState::default())?
+        };
+        assert_eq!(root, header.state_root, "unexpected state root of block {hash}");
+        Ok(())
+    }
+
+    /// Returns the root of the keccak Merkle Patricia Trie committing to the state after the given
+    /// block, overlaying the trie tables with the state changes of the blocks since the one they
+    /// are at.
+    fn mpt_state_root(&self, number: BlockNumber) -> eyre::Result<B256> {
+        let provider = self.inner.provider.database_provider_ro()?;
+        let (base, mut state) = mpt_base_state(provider.tx_ref())?;
+        if base > number {
+            eyre::bail!("keccak trie tables ahead of the block")
+        }
+        for number in base + 1..=number {
+            let outcome = self.inner.provider.get_state(number)?.ok_or_eyre("missing state")?;
+            state.extend(HashedPostState::from_bundle_state::<KeccakKeyHasher>(
+                &outcome.bundle.state,
+            ));
+        }
+        Ok(mpt_state_root(provider.tx_ref(), &state)?.0)
+    }
+
+    /// Returns the number of L1 messages the next block on top of the given head must include.
+    fn pending_l1_messages(&self, head: &SealedHeader) -> eyre::Result<usize> {
+        let limit = self.inner.chain_spec().config.l1_config.num_l1_messages_per_block;
+        let queue = ScrollL1MessageQueue::new(self.inner.provider.clone());
+        // the L1 messages exceeding the block gas limit are skipped by the payload builder
+        Ok(queue
+            .pending_l1_messages(head.hash(), limit)?
+            .iter()
+            .filter(|l1_message| l1_message.gas_limit <= head.gas_limit)
+            .count())
+    }
+
+    /// Waits until the payload builder built a payload with at least the given number of
+    /// transactions.
+    ///
+    /// Returns an error if it didn't after [`WAIT_TIMEOUT`].
+    async fn wait_for_built_payload(
+        &self,
+        payload_id: PayloadId,
+        transactions: usize,
+    ) -> eyre::Result<()> {
+        let wait = async {
+            loop {
+                let payload =
+                    self.inner.payload_builder.best_payload(payload_id).await.transpose()?;
+                let built = payload.map_or(0, |payload| payload.block().body.transactions.len());
+                if built >= transactions {
+                    return Ok::<_, eyre::Report>(())
+                }
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        };
+        tokio::time::timeout(WAIT_TIMEOUT, wait)
+            .await
+            .map_err(|_| eyre::eyre!("timed out waiting for {transactions} built transactions"))?
+    }
+}
+
+/// Returns the forkchoice state making the block with the given hash the head, safe and
+/// finalized block.
+const fn forkchoice_state(hash: B256) -> ForkchoiceState {
+    ForkchoiceState { head_block_hash: hash, safe_block_hash: hash, finalized_block_hash: hash }
+}
@@ -3,7 +3,10 @@
 use alloy_primitives::{TxHash, B256};
 use alloy_rpc_types_engine::{CancunPayloadFields, ExecutionPayloadSidecar, ForkchoiceState};
 use eyre::OptionExt;
-use futures_util::{stream::Fuse, StreamExt};
+use futures_util::{
+    stream::{self, Fuse, Select},
+    StreamExt,
+};
 use reth_chainspec::EthereumHardforks;
 use reth_engine_primitives::{BeaconEngineMessage, EngineApiMessageVersion, EngineTypes};
 use reth_payload_builder::PayloadBuilderHandle;
@@ -19,7 +22,10 @@ use std::{
     time::{Duration, UNIX_EPOCH},
 };
 use tokio::{
-    sync::{mpsc::UnboundedSender, oneshot},
+    sync::{
+        mpsc::{self, UnboundedSender},
+        oneshot,
+    },
     time::Interval,
 };
 use tokio_stream::wrappers::ReceiverStream;
@@ -29,8 +35,8 @@ use tracing::error;
 #[derive(Debug)]
 pub enum MiningMode {
     /// In this mode a block is built as soon as
-    /// a valid transaction reaches the pool.
-    Instant(Fuse<ReceiverStream<TxHash>>),
+    /// a valid transaction reaches the pool, or a trigger is received.
+    Instant(Fuse<Select<ReceiverStream<TxHash>, ReceiverStream<TxHash>>>),
     /// In this mode a block is built at a fixed interval.
     Interval(Interval),
 }
@@ -38,8 +44,19 @@ pub enum MiningMode {
 impl MiningMode {
     /// Constructor for a [`MiningMode::Instant`]
     pub fn instant<Pool: TransactionPool>(pool: Pool) -> Self {
+        // the sender is dropped, so that only the pool triggers new blocks
+        let (_, triggers) = mpsc::channel(1);
+        Self::instant_with_triggers(pool, triggers)
+    }
+
+    /// Constructor for a [`MiningMode::Instant`] also building a block whenever the hash of a
+    /// transaction that doesn't go through the pool is received, e.g. the one of an L1 message.
+    pub fn instant_with_triggers<Pool: TransactionPool>(
+        pool: Pool,
+        triggers: mpsc::Receiver<TxHash>,
+    ) -> Self {
         let rx = pool.pending_transactions_listener();
-        Self::Instant(ReceiverStream::new(rx).fuse())
+        Self::Instant(stream::select(ReceiverStream::new(rx), ReceiverStream::new(triggers)).fuse())
     }
 
     /// Constructor for a [`MiningMode::Interval`]
@@ -31,10 +31,14 @@ where
             timestamp,
             prev_randao: B256::random(),
             suggested_fee_recipient: Address::random(),
+            #[cfg(not(feature = "scroll"))]
             withdrawals: self
                 .chain_spec
                 .is_shanghai_active_at_timestamp(timestamp)
                 .then(Default::default),
+            // Scroll doesn't support withdrawals
+            #[cfg(feature = "scroll")]
+            withdrawals: None,
             parent_beacon_block_root: self
                 .chain_spec
                 .is_cancun_active_at_timestamp(timestamp)
@@ -3,12 +3,25 @@ use alloy_eips::BlockNumHash;
 use reth_chain_state::ExecutedBlock;
 use reth_errors::ProviderError;
 use reth_primitives::EthPrimitives;
+#[cfg(feature = "scroll")]
+use reth_primitives::{GotExpected, SealedHeader};
 use reth_provider::{
     providers::ProviderNodeTypes, writer::UnifiedStorageWriter, BlockHashReader,
     ChainStateBlockWriter, DatabaseProviderFactory, ProviderFactory, StaticFileProviderFactory,
 };
+#[cfg(feature = "scroll")]
+use reth_provider::{HeaderProvider, ProviderResult, StageCheckpointReader, StageCheckpointWriter};
 use reth_prune::{PrunerError, PrunerOutput, PrunerWithFactory};
+#[cfg(feature = "scroll")]
+use reth_scroll_state_commitment::{
+    mpt::{unwind_mpt_state, update_mpt_state},
+    MPT_STAGE_ID,
+};
+#[cfg(feature = "scroll")]
+use reth_stages_api::StageCheckpoint;
 use reth_stages_api::{MetricEvent, MetricEventsSender};
+#[cfg(feature = "scroll")]
+use reth_storage_errors::provider::RootMismatch;
 use std::{
     sync::mpsc::{Receiver, SendError, Sender},
     time::Instant,
@@ -125,6 +138,8 @@ impl<N: PersistenceNodeTypes> PersistenceService<N> {
         let sf_provider = self.provider.static_file_provider();
 
         let new_tip_hash = provider_rw.block_hash(new_tip_num)?;
+        #[cfg(feature = "scroll")]
+        Self::unwind_mpt_tables(&provider_rw, new_tip_num)?;
         UnifiedStorageWriter::from(&provider_rw, &sf_provider).remove_blocks_above(new_tip_num)?;
         UnifiedStorageWriter::commit_unwind(provider_rw)?;
 
@@ -147,12 +162,67 @@ impl<N: PersistenceNodeTypes> PersistenceService<N> {
             let provider_rw = self.provider.database_provider_rw()?;
             let static_file_provider = self.provider.static_file_provider();
 
+            #[cfg(feature = "scroll")]
+            let last_header = blocks.last().map(|block| block.block().header.clone());
             UnifiedStorageWriter::from(&provider_rw, &static_file_provider).save_blocks(blocks)?;
+            #[cfg(feature = "scroll")]
+            if let Some(header) = last_header {
+                Self::update_mpt_tables(&provider_rw, &header)?;
+            }
             UnifiedStorageWriter::commit(provider_rw)?;
         }
         self.metrics.save_blocks_duration_seconds.record(start_time.elapsed());
         Ok(last_block_hash_num)
     }
+
+    /// Updates the keccak Merkle Patricia Trie committing to the Scroll state from Euclid on to the
+    /// state after the saved blocks, checking its root against the state root of the last one.
+    ///
+    /// The state of an existing chain is migrated to the trie by the pipeline before the engine
+    /// starts, so the trie is only built from scratch here for the first blocks of a new chain.
+    #[cfg(feature = "scroll")]
+    fn update_mpt_tables(
+        provider_rw: &<ProviderFactory<N> as DatabaseProviderFactory>::ProviderRW,
+        last: &SealedHeader,
+    ) -> ProviderResult<()> {
+        if let Some(root) = update_mpt_state(provider_rw, last)? {
+            if root != last.state_root {
+                return Err(ProviderError::StateRootMismatch(Box::new(RootMismatch {
+                    root: GotExpected { got: root, expected: last.state_root },
+                    block_number: last.number,
+                    block_hash: last.hash(),
+                })))
+            }
+        }
+        provider_rw.save_stage_checkpoint(MPT_STAGE_ID, StageCheckpoint::new(last.number))
+    }
+
+    /// Unwinds the keccak Merkle Patricia Trie committing to the Scroll state from Euclid on to the
+    /// state after the new tip, before the changesets of the blocks above it are removed.
+    #[cfg(feature = "scroll")]
+    fn unwind_mpt_tables(
+        provider_rw: &<ProviderFactory<N> as DatabaseProviderFactory>::ProviderRW,
+        new_tip_num: u64,
+    ) -> ProviderResult<()> {
+        let checkpoint = provider_rw.get_stage_checkpoint(MPT_STAGE_ID)?.unwrap_or_default();
+        if checkpoint.block_number <= new_tip_num {
+            return Ok(())
+        }
+
+        let header = provider_rw
+            .sealed_header(new_tip_num)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(new_tip_num.into()))?;
+        if let Some(root) = unwind_mpt_state(provider_rw, &header)? {
+            if root != header.state_root {
+                return Err(ProviderError::UnwindStateRootMismatch(Box::new(RootMismatch {
+                    root: GotExpected { got: root, expected: header.state_root },
+                    block_number: header.number,
+                    block_hash: header.hash(),
+                })))
+            }
+        }
+        provider_rw.save_stage_checkpoint(MPT_STAGE_ID, StageCheckpoint::new(new_tip_num))
+    }
 }
 
 /// One of the errors that can happen when using the persistence service.
@@ -39,14 +39,27 @@ use reth_primitives::{
     Block, EthPrimitives, GotExpected, NodePrimitives, SealedBlock, SealedBlockWithSenders,
     SealedHeader,
 };
+#[cfg(feature = "scroll")]
+use reth_provider::DBProvider;
 use reth_provider::{
     providers::ConsistentDbView, BlockReader, DatabaseProviderFactory, ExecutionOutcome,
     HashedPostStateProvider, ProviderError, StateCommitmentProvider, StateProviderBox,
     StateProviderFactory, StateReader, StateRootProvider, TransactionVariant,
 };
+#[cfg(feature = "scroll")]
+use reth_scroll_state_commitment::ParallelStateRoot;
+use reth_scroll_state_commitment::StateCommitmentSchemeProvider;
+#[cfg(feature = "scroll")]
+use reth_scroll_state_commitment::{mpt::mpt_base_state, mpt_state_root, StateCommitmentScheme};
 use reth_stages_api::ControlFlow;
+#[cfg(feature = "scroll")]
+use reth_trie::KeccakKeyHasher;
 use reth_trie::{updates::TrieUpdates, HashedPostState, TrieInput};
-use reth_trie_parallel::root::{ParallelStateRoot, ParallelStateRootError};
+#[cfg(feature = "scroll")]
+use reth_trie_db::DatabaseHashedPostState;
+#[cfg(not(feature = "scroll"))]
+use reth_trie_parallel::root::ParallelStateRoot;
+use reth_trie_parallel::root::ParallelStateRootError;
 use revm_primitives::ResultAndState;
 use std::{
     cmp::Ordering,
@@ -545,6 +558,7 @@ where
         + StateProviderFactory
         + StateReader<Receipt = reth_primitives::Receipt>
         + StateCommitmentProvider
+        + StateCommitmentSchemeProvider
         + HashedPostStateProvider
         + Clone
         + 'static,
@@ -2231,6 +2245,15 @@ where
             &block,
             PostExecutionInput::new(&output.receipts, &output.requests),
         ) {
+            // the L1 messages of the block can't be checked against the local copy of the L1
+            // message queue, which doesn't make the block invalid
+            #[cfg(feature = "scroll")]
+            if let ConsensusError::L1MessageQueueProvider(err) = err {
+                return Err((*err).into())
+            } else if err.is_retriable() {
+                return Err(err.into())
+            }
+
             // call post-block hook
             self.invalid_block_hook.on_invalid_block(
                 &parent_block,
@@ -2245,6 +2268,21 @@ where
 
         trace!(target: "engine::tree", block=?sealed_block.num_hash(), "Calculating block state root");
         let root_time = Instant::now();
+
+        // From Euclid on, the Scroll state is committed to by the keccak Merkle Patricia Trie, and
+        // the root of the zktrie isn't computed anymore.
+        #[cfg(feature = "scroll")]
+        let mpt_root = match self.provider.state_commitment_scheme(block.timestamp) {
+            StateCommitmentScheme::ZkTrie => None,
+            StateCommitmentScheme::Mpt => {
+                let state =
+                    HashedPostState::from_bundle_state::<KeccakKeyHasher>(&output.state.state);
+                Some(self.compute_mpt_state_root(block_number, block.parent_hash, state)?)
+            }
+        };
+        #[cfg(not(feature = "scroll"))]
+        let mpt_root: Option<B256> = None;
+
         let mut state_root_result = None;
 
         // TODO: switch to calculate state root using `StateRootTask`.
@@ -2255,7 +2293,7 @@ where
         // we are computing in parallel, because we initialize a different database transaction
         // per thread and it might end up with a different view of the database.
         let persistence_in_progress = self.persistence_state.in_progress();
-        if !persistence_in_progress {
+        if !persistence_in_progress && mpt_root.is_none() {
             state_root_result = match self
                 .compute_state_root_parallel(block.parent_hash, &hashed_state)
             {
@@ -2268,7 +2306,9 @@ where
             };
         }
 
-        let (state_root, trie_output) = if let Some(result) = state_root_result {
+        let (state_root, trie_output) = if let Some(state_root) = mpt_root {
+            (state_root, TrieUpdates::default())
+        } else if let Some(result) = state_root_result {
             result
         } else {
             debug!(target: "engine::tree", block=?sealed_block.num_hash(), persistence_in_progress, "Failed to compute state root in parallel");
@@ -2365,6 +2405,52 @@ where
         ParallelStateRoot::new(consistent_view, input).incremental_root_with_updates()
     }
 
+    /// Computes the root of the keccak Merkle Patricia Trie committing to the state after the
+    /// block, given the keccak hashed post state of its execution.
+    ///
+    /// The persisted trie is rolled back to the last ancestor of the block that isn't in memory,
+    /// and overlaid with the state changes of the in-memory ancestors and of the block.
+    ///
+    /// Returns an error if the trie tables are behind the persisted state, the block not being
+    /// accepted until its root is checked.
+    #[cfg(feature = "scroll")]
+    fn compute_mpt_state_root(
+        &self,
+        block_number: BlockNumber,
+        parent_hash: B256,
+        hashed_state: HashedPostState,
+    ) -> ProviderResult<B256> {
+        let provider = self.provider.database_provider_ro()?;
+        let (persisted, mut state) = mpt_base_state(provider.tx_ref())?;
+
+        // the blocks on top of the last persisted ancestor, newest first
+        let blocks = self
+            .state
+            .tree_state
+            .blocks_by_hash(parent_hash)
+            .map(|(_, blocks)| blocks)
+            .unwrap_or_default();
+        let historical = blocks.last().map_or(block_number, |block| block.block().number) - 1;
+        if historical > persisted {
+            return Err(ProviderError::StateForNumberNotFound(historical))
+        }
+
+        if historical < persisted {
+            state.extend(HashedPostState::from_reverts::<KeccakKeyHasher>(
+                provider.tx_ref(),
+                historical + 1,
+            )?);
+        }
+        for block in blocks.iter().rev() {
+            state.extend(HashedPostState::from_bundle_state::<KeccakKeyHasher>(
+                &block.execution_outcome().bundle.state,
+            ));
+        }
+        state.extend(hashed_state);
+
+        Ok(mpt_state_root(provider.tx_ref(), &state)?.0)
+    }
+
     /// Handles an error that occurred while inserting a block.
     ///
     /// If this is a validation error this will mark the block as invalid.
@@ -2376,6 +2462,16 @@ where
     ) -> Result<PayloadStatus, InsertBlockFatalError> {
         let (block, error) = error.split();
 
+        // the L1 messages of the block aren't indexed yet by the local copy of the L1 message
+        // queue, the block isn't marked as invalid and must be sent again once the queue caught up
+        #[cfg(feature = "scroll")]
+        if let InsertBlockErrorKindTwo::Consensus(err) = &error {
+            if err.is_retriable() {
+                debug!(target: "engine::tree", hash=?block.hash(), number=?block.number, %err, "Deferring block validation");
+                return Ok(PayloadStatus::from_status(PayloadStatusEnum::Syncing))
+            }
+        }
+
         // if invalid block, we check the validation error. Otherwise return the fatal
         // error.
         let validation_err = error.ensure_validation_error()?;
@@ -6,13 +6,12 @@ use reth_provider::{
     StateCommitmentProvider,
 };
 use reth_trie::{
-    proof::Proof, updates::TrieUpdates, HashedPostState, HashedStorage, MultiProof, Nibbles,
-    TrieInput,
+    updates::TrieUpdates, HashedPostState, HashedStorage, KeyHasher, MultiProof, TrieInput,
 };
-use reth_trie_db::DatabaseProof;
+use reth_trie_db::{DatabaseProof, StateCommitment};
 use reth_trie_parallel::root::ParallelStateRootError;
-use reth_trie_sparse::{SparseStateTrie, SparseStateTrieResult, SparseTrieError};
-use revm_primitives::{keccak256, EvmState, B256};
+use reth_trie_sparse::{SparseStateTrieInterface, SparseStateTrieResult};
+use revm_primitives::{EvmState, B256};
 use std::{
     collections::BTreeMap,
     sync::{
@@ -59,10 +58,14 @@ pub(crate) struct StateRootConfig<Factory> {
     pub input: Arc<TrieInput>,
 }
 
+/// The sparse state trie of the state commitment used by the provider factory.
+pub(crate) type SparseStateTrieOf<Factory> =
+    <<Factory as StateCommitmentProvider>::StateCommitment as StateCommitment>::SparseStateTrie;
+
 /// Messages used internally by the state root task
 #[derive(Debug)]
 #[allow(dead_code)]
-pub(crate) enum StateRootMessage {
+pub(crate) enum StateRootMessage<S> {
     /// New state update from transaction execution
     StateUpdate(EvmState),
     /// Proof calculation completed for a specific state update
@@ -77,7 +80,7 @@ pub(crate) enum StateRootMessage {
     /// State root calculation completed
     RootCalculated {
         /// The updated sparse trie
-        trie: Box<SparseStateTrie>,
+        trie: Box<S>,
         /// Time taken to calculate the root
         elapsed: Duration,
     },
@@ -157,21 +160,25 @@ impl ProofSequencer {
 /// fetches the proofs for relevant accounts from the database and reveal them
 /// to the tree.
 /// Then it updates relevant leaves according to the result of the transaction.
+///
+/// The keys of the state updates are hashed with the key hasher of the state commitment of the
+/// provider factory, which also determines the proofs and the sparse trie used for the
+/// calculation.
 #[derive(Debug)]
-pub(crate) struct StateRootTask<Factory> {
+pub(crate) struct StateRootTask<Factory: StateCommitmentProvider> {
     /// Task configuration.
     config: StateRootConfig<Factory>,
     /// Receiver for state root related messages.
-    rx: Receiver<StateRootMessage>,
+    rx: Receiver<StateRootMessage<SparseStateTrieOf<Factory>>>,
     /// Sender for state root related messages.
-    tx: Sender<StateRootMessage>,
+    tx: Sender<StateRootMessage<SparseStateTrieOf<Factory>>>,
     /// Proof targets that have been already fetched.
     fetched_proof_targets: HashMap<B256, HashSet<B256>>,
     /// Proof sequencing handler.
     proof_sequencer: ProofSequencer,
     /// The sparse trie used for the state root calculation. If [`None`], then update is in
     /// progress.
-    sparse_trie: Option<Box<SparseStateTrie>>,
+    sparse_trie: Option<Box<SparseStateTrieOf<Factory>>>,
 }
 
 #[allow(dead_code)]
@@ -187,8 +194,8 @@ where
     /// Creates a new state root task with the unified message channel
     pub(crate) fn new(
         config: StateRootConfig<Factory>,
-        tx: Sender<StateRootMessage>,
-        rx: Receiver<StateRootMessage>,
+        tx: Sender<StateRootMessage<SparseStateTrieOf<Factory>>>,
+        rx: Receiver<StateRootMessage<SparseStateTrieOf<Factory>>>,
     ) -> Self {
         Self {
             config,
@@ -196,7 +203,7 @@ where
             tx,
             fetched_proof_targets: Default::default(),
             proof_sequencer: ProofSequencer::new(),
-            sparse_trie: Some(Box::new(SparseStateTrie::default().with_updates(true))),
+            sparse_trie: Some(Box::new(SparseStateTrieOf::<Factory>::default().with_updates(true))),
         }
     }
 
@@ -224,19 +231,17 @@ where
         update: EvmState,
         fetched_proof_targets: &HashMap<B256, HashSet<B256>>,
         proof_sequence_number: u64,
-        state_root_message_sender: Sender<StateRootMessage>,
+        state_root_message_sender: Sender<StateRootMessage<SparseStateTrieOf<Factory>>>,
     ) -> HashMap<B256, HashSet<B256>> {
         let mut hashed_state_update = HashedPostState::default();
         for (address, account) in update {
             if account.is_touched() {
-                let hashed_address = keccak256(address);
+                let hashed_address = KeyHasherOf::<Factory>::hash_key(address);
 
                 let destroyed = account.is_selfdestructed();
                 let info = if account.is_empty() {
                     None
                 } else {
-                    // TODO (scroll): once we transition to the sdk pattern, a solution
-                    // needs to be found for this.
                     Some(reth_primitives::Account::from_account_info(account.info))
                 };
                 hashed_state_update.accounts.insert(hashed_address, info);
@@ -245,9 +250,12 @@ where
                     .storage
                     .into_iter()
                     .filter_map(|(slot, value)| {
-                        value
-                            .is_changed()
-                            .then(|| (keccak256(B256::from(slot)), value.present_value))
+                        value.is_changed().then(|| {
+                            (
+                                KeyHasherOf::<Factory>::hash_key(B256::from(slot)),
+                                value.present_value,
+                            )
+                        })
                     })
                     .peekable();
                 if destroyed || changed_storage_iter.peek().is_some() {
@@ -273,12 +281,13 @@ where
             };
 
             // TODO: replace with parallel proof
-            let result = Proof::overlay_multiproof(
-                provider.tx_ref(),
-                // TODO(alexey): this clone can be expensive, we should avoid it
-                input.as_ref().clone(),
-                targets,
-            );
+            let result =
+                <Factory::StateCommitment as StateCommitment>::StateProof::overlay_multiproof(
+                    provider.tx_ref(),
+                    // TODO(alexey): this clone can be expensive, we should avoid it
+                    input.as_ref().clone(),
+                    targets,
+                );
             match result {
                 Ok(proof) => {
                     let _ = state_root_message_sender.send(StateRootMessage::ProofCalculated {
@@ -500,14 +509,18 @@ fn get_proof_targets(
         .collect()
 }
 
+/// The key hasher of the state commitment used by the provider factory.
+type KeyHasherOf<Factory> =
+    <<Factory as StateCommitmentProvider>::StateCommitment as StateCommitment>::KeyHasher;
+
 /// Updates the sparse trie with the given proofs and state, and returns the updated trie and the
 /// time it took.
-fn update_sparse_trie(
-    mut trie: Box<SparseStateTrie>,
+fn update_sparse_trie<S: SparseStateTrieInterface>(
+    mut trie: Box<S>,
     multiproof: MultiProof,
     targets: HashMap<B256, HashSet<B256>>,
     state: HashedPostState,
-) -> SparseStateTrieResult<(Box<SparseStateTrie>, Duration)> {
+) -> SparseStateTrieResult<(Box<S>, Duration)> {
     let started_at = Instant::now();
 
     // Reveal new accounts and storage slots.
@@ -515,24 +528,15 @@ fn update_sparse_trie(
 
     // Update storage slots with new values and calculate storage roots.
     for (address, storage) in state.storages {
-        let storage_trie = trie.storage_trie_mut(&address).ok_or(SparseTrieError::Blind)?;
-
         if storage.wiped {
-            storage_trie.wipe();
+            trie.wipe_storage(address)?;
         }
 
         for (slot, value) in storage.storage {
-            let slot_nibbles = Nibbles::unpack(slot);
-            if value.is_zero() {
-                // TODO: handle blinded node error
-                storage_trie.remove_leaf(&slot_nibbles)?;
-            } else {
-                storage_trie
-                    .update_leaf(slot_nibbles, alloy_rlp::encode_fixed_size(&value).to_vec())?;
-            }
+            trie.update_storage_slot(address, slot, value)?;
         }
 
-        storage_trie.root();
+        trie.storage_root(address);
     }
 
     // Update accounts with new values
@@ -557,8 +561,8 @@ mod tests {
     use reth_testing_utils::generators::{self, Rng};
     use reth_trie::{test_utils::state_root, TrieInput};
     use revm_primitives::{
-        Account as RevmAccount, AccountInfo, AccountStatus, Address, EvmState, EvmStorageSlot,
-        HashMap, B256, KECCAK_EMPTY, U256,
+        keccak256, Account as RevmAccount, AccountInfo, AccountStatus, Address, EvmState,
+        EvmStorageSlot, HashMap, B256, KECCAK_EMPTY, U256,
     };
     use std::sync::Arc;
 
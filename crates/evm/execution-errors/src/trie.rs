@@ -101,6 +101,9 @@ pub enum TrieWitnessError {
     /// Unexpected empty root.
     #[display("unexpected empty root: {_0:?}")]
     UnexpectedEmptyRoot(Nibbles),
+    /// Invalid trie node.
+    #[display("invalid trie node: {_0:?}")]
+    InvalidNode(Nibbles),
 }
 
 impl From<TrieWitnessError> for ProviderError {
@@ -7,7 +7,7 @@ use crate::{
     hooks::OnComponentInitializedHook,
     BuilderContext, NodeAdapter,
 };
-use alloy_primitives::{BlockNumber, B256};
+use alloy_primitives::{BlockNumber, TxHash, B256};
 use eyre::{Context, OptionExt};
 use rayon::ThreadPoolBuilder;
 use reth_beacon_consensus::EthBeaconConsensus;
@@ -46,17 +46,21 @@ use reth_provider::{
     BlockHashReader, BlockNumReader, ChainSpecProvider, ProviderError, ProviderFactory,
     ProviderResult, StageCheckpointReader, StateProviderFactory, StaticFileProviderFactory,
 };
+#[cfg(feature = "scroll")]
+use reth_provider::{DBProvider, DatabaseProviderFactory};
 use reth_prune::{PruneModes, PrunerBuilder};
 use reth_rpc_api::clients::EthApiClient;
 use reth_rpc_builder::config::RethRpcServerConfig;
 use reth_rpc_layer::JwtSecret;
+#[cfg(feature = "scroll")]
+use reth_scroll_state_commitment::{mpt::mpt_checkpoint, StateCommitmentSchemeProvider};
 use reth_stages::{sets::DefaultStages, MetricEvent, PipelineBuilder, PipelineTarget, StageId};
 use reth_static_file::StaticFileProducer;
 use reth_tasks::TaskExecutor;
 use reth_tracing::tracing::{debug, error, info, warn};
 use reth_transaction_pool::TransactionPool;
 use tokio::sync::{
-    mpsc::{unbounded_channel, UnboundedSender},
+    mpsc::{self, unbounded_channel, UnboundedSender},
     oneshot, watch,
 };
 
@@ -362,9 +366,18 @@ impl<R, ChainSpec: EthChainSpec> LaunchContextWith<Attached<WithConfigs<ChainSpe
     }
 
     /// Returns the [`MiningMode`] intended for --dev mode.
-    pub fn dev_mining_mode(&self, pool: impl TransactionPool) -> MiningMode {
+    ///
+    /// In instant mode, a block is also built whenever a transaction hash is received from the
+    /// given triggers.
+    pub fn dev_mining_mode(
+        &self,
+        pool: impl TransactionPool,
+        triggers: Option<mpsc::Receiver<TxHash>>,
+    ) -> MiningMode {
         if let Some(interval) = self.node_config().dev.block_time {
             MiningMode::interval(interval)
+        } else if let Some(triggers) = triggers {
+            MiningMode::instant_with_triggers(pool, triggers)
         } else {
             MiningMode::instant(pool)
         }
@@ -814,6 +827,8 @@ where
     ///  * the pipeline was interrupted during its previous run
     ///  * a new stage was added
     ///  * stage data was dropped manually through `reth stage drop ...`
+    ///  * the state of a Scroll chain scheduling Euclid isn't migrated to the keccak Merkle
+    ///    Patricia Trie yet
     ///
     /// # Returns
     ///
@@ -850,6 +865,23 @@ where
             }
         }
 
+        // From Euclid on, the roots of the Scroll blocks are computed on top of the keccak Merkle
+        // Patricia Trie tables, which the state must be migrated to before validating any block.
+        #[cfg(feature = "scroll")]
+        if self.provider_factory().is_mpt_scheduled() {
+            let provider = self.provider_factory().database_provider_ro()?;
+            let mpt_checkpoint = mpt_checkpoint(provider.tx_ref())?.unwrap_or_default();
+            if mpt_checkpoint < first_stage_checkpoint {
+                debug!(
+                    target: "consensus::engine",
+                    first_stage_checkpoint,
+                    mpt_checkpoint,
+                    "Keccak Merkle Patricia Trie is behind the pipeline sync progress"
+                );
+                return self.blockchain_db().block_hash(first_stage_checkpoint);
+            }
+        }
+
         self.ensure_chain_specific_db_checks()?;
 
         Ok(None)
@@ -1,5 +1,6 @@
 //! Engine node related functionality.
 
+use alloy_primitives::TxHash;
 use futures::{future::Either, stream, stream_select, StreamExt};
 use reth_beacon_consensus::{
     hooks::{EngineHooks, StaticFileHook},
@@ -34,7 +35,10 @@ use reth_tasks::TaskExecutor;
 use reth_tokio_util::EventSender;
 use reth_tracing::tracing::{debug, error, info};
 use std::sync::Arc;
-use tokio::sync::{mpsc::unbounded_channel, oneshot};
+use tokio::sync::{
+    mpsc::{self, unbounded_channel},
+    oneshot,
+};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 use crate::{
@@ -55,6 +59,10 @@ pub struct EngineNodeLauncher {
     /// Temporary configuration for engine tree.
     /// After engine is stabilized, this should be configured through node builder.
     pub engine_tree_config: TreeConfig,
+
+    /// The receiver of the hashes of the transactions triggering a new block in instant --dev
+    /// mode, besides the ones reaching the pool.
+    pub dev_mining_triggers: Option<mpsc::Receiver<TxHash>>,
 }
 
 impl EngineNodeLauncher {
@@ -64,7 +72,18 @@ impl EngineNodeLauncher {
         data_dir: ChainPath<DataDirPath>,
         engine_tree_config: TreeConfig,
     ) -> Self {
-        Self { ctx: LaunchContext::new(task_executor, data_dir), engine_tree_config }
+        Self {
+            ctx: LaunchContext::new(task_executor, data_dir),
+            engine_tree_config,
+            dev_mining_triggers: None,
+        }
+    }
+
+    /// Sets the receiver of the hashes of the transactions that don't go through the pool, e.g. L1
+    /// messages, triggering a new block in instant --dev mode.
+    pub fn with_dev_mining_triggers(mut self, triggers: mpsc::Receiver<TxHash>) -> Self {
+        self.dev_mining_triggers = Some(triggers);
+        self
     }
 }
 
@@ -93,7 +112,7 @@ where
         self,
         target: NodeBuilderWithComponents<T, CB, AO>,
     ) -> eyre::Result<Self::Node> {
-        let Self { ctx, engine_tree_config } = self;
+        let Self { ctx, engine_tree_config, dev_mining_triggers } = self;
         let NodeBuilderWithComponents {
             adapter: NodeTypesAdapter { database },
             components_builder,
@@ -235,7 +254,7 @@ where
                 ctx.sync_metrics_tx(),
                 consensus_engine_tx.clone(),
                 Box::pin(consensus_engine_stream),
-                ctx.dev_mining_mode(ctx.components().pool()),
+                ctx.dev_mining_mode(ctx.components().pool(), dev_mining_triggers),
                 LocalPayloadAttributesBuilder::new(ctx.chain_spec()),
             );
 
@@ -44,7 +44,7 @@ pub struct Account {
     /// additional bitflag was available. See [reth codecs](reth_codecs::test_utils) for more
     /// details.
     #[cfg(feature = "scroll")]
-    pub account_extension: Option<reth_scroll_primitives::AccountExtension>,
+    pub account_extension: Option<reth_scroll_primitives_base::AccountExtension>,
 }
 
 impl Default for Account {
@@ -86,13 +86,13 @@ impl Account {
             nonce: info.nonce,
             bytecode_hash: (info.code_hash != KECCAK_EMPTY).then_some(info.code_hash),
             #[cfg(feature = "scroll")]
-            account_extension: Some(reth_scroll_primitives::AccountExtension {
+            account_extension: Some(reth_scroll_primitives_base::AccountExtension {
                 code_size: (info.poseidon_code_hash !=
-                    reth_scroll_primitives::poseidon::POSEIDON_EMPTY)
+                    reth_scroll_primitives_base::poseidon::POSEIDON_EMPTY)
                     .then_some(info.code_size as u64)
                     .unwrap_or_default(),
                 poseidon_code_hash: (info.poseidon_code_hash !=
-                    reth_scroll_primitives::poseidon::POSEIDON_EMPTY)
+                    reth_scroll_primitives_base::poseidon::POSEIDON_EMPTY)
                     .then_some(info.poseidon_code_hash),
             }),
         }
@@ -108,13 +108,13 @@ impl Account {
     }
 
     /// Returns the account poseidon code hash.
-    /// In the case of no bytecode returns [`reth_scroll_primitives::poseidon::POSEIDON_EMPTY`]
+    /// In the case of no bytecode returns [`reth_scroll_primitives_base::poseidon::POSEIDON_EMPTY`]
     pub fn get_poseidon_code_hash(&self) -> B256 {
         self.account_extension
             .as_ref()
             .unwrap()
             .poseidon_code_hash
-            .unwrap_or(reth_scroll_primitives::poseidon::POSEIDON_EMPTY)
+            .unwrap_or(reth_scroll_primitives_base::poseidon::POSEIDON_EMPTY)
     }
 }
 
@@ -232,7 +232,7 @@ impl From<&GenesisAccount> for Account {
             balance: value.balance,
             bytecode_hash: value.code.as_ref().map(keccak256),
             #[cfg(feature = "scroll")]
-            account_extension: Some(reth_scroll_primitives::AccountExtension::from_bytecode(
+            account_extension: Some(reth_scroll_primitives_base::AccountExtension::from_bytecode(
                 value.code.as_ref().unwrap_or_default(),
             )),
         }
@@ -266,7 +266,7 @@ impl From<Account> for AccountInfo {
                 .account_extension
                 .unwrap_or_default()
                 .poseidon_code_hash
-                .unwrap_or(reth_scroll_primitives::poseidon::POSEIDON_EMPTY),
+                .unwrap_or(reth_scroll_primitives_base::poseidon::POSEIDON_EMPTY),
         }
     }
 }
@@ -285,7 +285,7 @@ impl From<Account> for revm_primitives::shared::AccountInfo {
             poseidon_code_hash: reth_acc
                 .account_extension
                 .and_then(|acc| acc.poseidon_code_hash)
-                .unwrap_or(reth_scroll_primitives::poseidon::POSEIDON_EMPTY),
+                .unwrap_or(reth_scroll_primitives_base::poseidon::POSEIDON_EMPTY),
             code: None,
         }
     }
@@ -407,7 +407,7 @@ mod tests {
             balance: U256::from(1000),
             bytecode_hash: Some(B256::from_slice(&[0x11u8; 32])),
             #[cfg(feature = "scroll")]
-            account_extension: Some(reth_scroll_primitives::AccountExtension::from_bytecode(
+            account_extension: Some(reth_scroll_primitives_base::AccountExtension::from_bytecode(
                 &[0x11u8; 32],
             )),
         };
@@ -447,7 +447,7 @@ mod tests {
             balance: U256::from(1000),
             bytecode_hash: Some(bytecode_hash),
             #[cfg(feature = "scroll")]
-            account_extension: Some(reth_scroll_primitives::AccountExtension::from_bytecode(
+            account_extension: Some(reth_scroll_primitives_base::AccountExtension::from_bytecode(
                 &[0x11u8; 32],
             )),
         };
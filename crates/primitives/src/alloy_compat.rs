@@ -160,10 +160,10 @@ impl TryFrom<AnyRpcTransaction> for TransactionSigned {
                             inner
                                 .fields
                                 .clone()
-                                .deserialize_into::<reth_scroll_primitives::ScrollL1MessageTransactionFields>()
+                                .deserialize_into::<reth_scroll_primitives_base::ScrollL1MessageTransactionFields>()
                                 .map_err(|e| ConversionError::Custom(e.to_string()))?;
                         (
-                            Transaction::L1Message(reth_scroll_primitives::TxL1Message {
+                            Transaction::L1Message(reth_scroll_primitives_base::TxL1Message {
                                 queue_index: fields.queue_index,
                                 gas_limit: inner.gas_limit(),
                                 to: inner.to().ok_or_else(|| ConversionError::Custom(
@@ -174,7 +174,7 @@ impl TryFrom<AnyRpcTransaction> for TransactionSigned {
                                 sender: fields.sender,
                                 input: inner.input().clone(),
                             }),
-                            reth_scroll_primitives::TxL1Message::signature(),
+                            reth_scroll_primitives_base::TxL1Message::signature(),
                             _hash,
                         )
                     }
@@ -22,7 +22,7 @@
 #[cfg(feature = "optimism")]
 use op_alloy_consensus as _;
 #[cfg(feature = "scroll")]
-use reth_scroll_primitives as _;
+use reth_scroll_primitives_base as _;
 
 extern crate alloc;
 
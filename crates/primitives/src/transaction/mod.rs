@@ -26,7 +26,7 @@ use op_alloy_consensus::TxDeposit;
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 use reth_primitives_traits::{InMemorySize, SignedTransaction};
 #[cfg(all(feature = "scroll", not(feature = "optimism")))]
-use reth_scroll_primitives::l1_transaction::TxL1Message;
+use reth_scroll_primitives_base::l1_transaction::TxL1Message;
 use revm_primitives::{AuthorizationList, TxEnv};
 use serde::{Deserialize, Serialize};
 use signature::decode_with_eip155_chain_id;
@@ -571,7 +571,7 @@ impl reth_codecs::Compact for Transaction {
                         (Self::Deposit(tx), buf)
                     }
                     #[cfg(all(feature = "scroll", not(feature = "optimism")))]
-                    reth_scroll_primitives::L1_MESSAGE_TRANSACTION_TYPE => {
+                    reth_scroll_primitives_base::L1_MESSAGE_TRANSACTION_TYPE => {
                         let (tx, buf) = TxL1Message::from_compact(buf, buf.len());
                         (Self::L1Message(tx), buf)
                     }
@@ -1835,7 +1835,7 @@ pub mod serde_bincode_compat {
         #[cfg(all(feature = "optimism", not(feature = "scroll")))]
         Deposit(op_alloy_consensus::serde_bincode_compat::TxDeposit<'a>),
         #[cfg(all(feature = "scroll", not(feature = "optimism")))]
-        L1Message(Cow<'a, reth_scroll_primitives::l1_transaction::TxL1Message>),
+        L1Message(Cow<'a, reth_scroll_primitives_base::l1_transaction::TxL1Message>),
     }
 
     impl<'a> From<&'a super::Transaction> for Transaction<'a> {
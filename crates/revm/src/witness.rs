@@ -11,6 +11,8 @@ pub struct ExecutionWitnessRecord {
     /// the execution of the block, including during state root recomputation.
     ///
     /// `keccak(bytecodes) => bytecodes`
+    ///
+    /// With the `scroll` feature, the codes are additionally keyed by their Poseidon code hash.
     pub codes: B256HashMap<Bytes>,
     /// Map of all hashed account and storage keys (addresses and slots) to their preimages
     /// (unhashed account addresses and storage slots, respectively) that were required during
@@ -26,6 +28,22 @@ impl ExecutionWitnessRecord {
         &mut self,
         statedb: &State<DB>,
         #[cfg(feature = "scroll")] context: &reth_scroll_primitives::ScrollPostExecutionContext,
+    ) {
+        self.record_executed_state_with_key_hasher(
+            statedb,
+            |bytes| keccak256(bytes),
+            #[cfg(feature = "scroll")]
+            context,
+        )
+    }
+
+    /// Records the state after execution, hashing the account and storage keys with the provided
+    /// key hasher.
+    pub fn record_executed_state_with_key_hasher<DB>(
+        &mut self,
+        statedb: &State<DB>,
+        hash_key: impl Fn(&[u8]) -> B256,
+        #[cfg(feature = "scroll")] context: &reth_scroll_primitives::ScrollPostExecutionContext,
     ) {
         self.codes = statedb
             .cache
@@ -45,8 +63,25 @@ impl ExecutionWitnessRecord {
             )
             .collect();
 
+        // provers verify the bytecodes against the Poseidon code hashes stored in the trie
+        #[cfg(feature = "scroll")]
+        {
+            let poseidon_codes = self
+                .codes
+                .iter()
+                .map(|(hash, code)| {
+                    let poseidon_code_hash = context.get(hash).map_or_else(
+                        || reth_scroll_primitives::poseidon::hash_code(code),
+                        |(_, poseidon_code_hash)| *poseidon_code_hash,
+                    );
+                    (poseidon_code_hash, code.clone())
+                })
+                .collect::<Vec<_>>();
+            self.codes.extend(poseidon_codes);
+        }
+
         for (address, account) in &statedb.cache.accounts {
-            let hashed_address = keccak256(address);
+            let hashed_address = hash_key(address.as_slice());
             #[cfg(feature = "scroll")]
             let hashed_account = account
                 .account
@@ -67,7 +102,7 @@ impl ExecutionWitnessRecord {
 
                 for (slot, value) in &account.storage {
                     let slot = B256::from(*slot);
-                    let hashed_slot = keccak256(slot);
+                    let hashed_slot = hash_key(slot.as_slice());
                     storage.storage.insert(hashed_slot, *value);
 
                     self.keys.insert(hashed_slot, slot.into());
@@ -21,8 +21,8 @@ use reth_evm::{
 use reth_primitives::{Block, BlockExt, SealedBlockWithSenders};
 use reth_primitives_traits::SignedTransaction;
 use reth_provider::{
-    BlockReaderIdExt, ChainSpecProvider, HeaderProvider, StateProofProvider, StateProviderFactory,
-    TransactionVariant,
+    BlockReaderIdExt, ChainSpecProvider, HeaderProvider, KeyHasherProvider, StateProofProvider,
+    StateProviderFactory, TransactionVariant,
 };
 use reth_revm::{database::StateProviderDatabase, witness::ExecutionWitnessRecord};
 use reth_rpc_api::DebugApiServer;
@@ -621,8 +621,9 @@ where
                     .execute_with_state_closure(
                         (&(*block).clone().unseal(), block.difficulty).into(),
                         |statedb: &State<_>| {
-                            witness_record.record_executed_state(
+                            witness_record.record_executed_state_with_key_hasher(
                                 statedb,
+                                |bytes| state_provider.hash_key(bytes),
                                 #[cfg(feature = "scroll")]
                                 &statedb.database.post_execution_context,
                             );
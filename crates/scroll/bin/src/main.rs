@@ -1,11 +1,20 @@
 //! Scroll binary
 #![cfg(all(feature = "scroll", not(feature = "optimism")))]
 
+use alloy_provider::ProviderBuilder;
 use clap::Parser;
 use reth_node_builder::{engine_tree_config::TreeConfig, EngineNodeLauncher, Node};
 use reth_provider::providers::BlockchainProvider2;
 use reth_scroll_cli::{Cli, ScrollChainSpecParser, ScrollRollupArgs};
-use reth_scroll_node::{ScrollAddOns, ScrollNode};
+use reth_scroll_l1::{
+    BatchIndexer, BatchIndexerConfig, BlobArchive, L1MessageQueueConfig, L1MessageQueueIndexer,
+};
+use reth_scroll_node::{
+    ScrollAddOns, ScrollDerivationPipeline, ScrollNode, ScrollStateRootVerifierExEx,
+    ScrollWithdrawTrieExEx,
+};
+use reth_scroll_rpc::{ScrollApi, ScrollApiServer, ScrollDevApi, ScrollDevApiServer};
+use tokio::sync::mpsc;
 
 #[global_allocator]
 static ALLOC: reth_cli_util::allocator::Allocator = reth_cli_util::allocator::new_allocator();
@@ -20,23 +29,86 @@ fn main() {
 
     if let Err(err) = Cli::<ScrollChainSpecParser, ScrollRollupArgs>::parse().run(
         |builder, rollup_args| async move {
+            let withdraw_trie = rollup_args.withdraw_trie;
             let engine_tree_config = TreeConfig::default()
                 .with_persistence_threshold(rollup_args.persistence_threshold)
                 .with_memory_block_buffer_target(rollup_args.memory_block_buffer_target);
+            // in instant --dev mode, L1 messages enqueued through the dev API trigger new blocks
+            let (mining_triggers_tx, mining_triggers) = mpsc::channel(1);
             let handle = builder
                 .with_types_and_provider::<ScrollNode, BlockchainProvider2<_>>()
                 .with_components(ScrollNode.components_builder())
                 .with_add_ons(ScrollAddOns::default())
+                .install_exex_if(
+                    rollup_args.verify_state_roots,
+                    "scroll-state-root-verifier",
+                    |ctx| async move { Ok(ScrollStateRootVerifierExEx::new(ctx).run()) },
+                )
+                .install_exex_if(withdraw_trie, "scroll-withdraw-trie", |ctx| async move {
+                    Ok(ScrollWithdrawTrieExEx::new(ctx).run())
+                })
+                .extend_rpc_modules(move |ctx| {
+                    let api =
+                        ScrollApi::new(ctx.provider().clone()).with_withdraw_trie(withdraw_trie);
+                    ctx.modules.merge_configured(api.into_rpc())?;
+                    if ctx.config().dev.dev {
+                        let api = ScrollDevApi::new(ctx.provider().clone())
+                            .with_mining_triggers(mining_triggers_tx);
+                        ctx.modules.merge_configured(api.into_rpc())?;
+                    }
+                    Ok(())
+                })
                 .launch_with_fn(|builder| {
                     let launcher = EngineNodeLauncher::new(
                         builder.task_executor().clone(),
                         builder.config().datadir(),
                         engine_tree_config,
-                    );
+                    )
+                    .with_dev_mining_triggers(mining_triggers);
                     builder.launch_with(launcher)
                 })
                 .await?;
 
+            if let Some(l1_rpc_url) = rollup_args.l1_rpc_url {
+                let l1_provider = ProviderBuilder::new().on_builtin(&l1_rpc_url).await?;
+                let l1_config = handle.node.chain_spec().config.l1_config;
+                let config = L1MessageQueueConfig::new(l1_config.l1_message_queue_address)
+                    .with_start_block(rollup_args.l1_start_block)
+                    .with_confirmations(rollup_args.l1_confirmations);
+                let indexer = L1MessageQueueIndexer::new(
+                    l1_provider.clone(),
+                    handle.node.provider.clone(),
+                    config,
+                );
+                handle
+                    .node
+                    .task_executor
+                    .spawn_critical("l1 message queue indexer", Box::pin(indexer.run()));
+
+                let config = BatchIndexerConfig::new(l1_config.scroll_chain_address)
+                    .with_start_block(rollup_args.l1_start_block)
+                    .with_confirmations(rollup_args.l1_confirmations);
+                let indexer =
+                    BatchIndexer::new(l1_provider.clone(), handle.node.provider.clone(), config);
+                handle.node.task_executor.spawn_critical("batch indexer", Box::pin(indexer.run()));
+
+                if rollup_args.l1_derivation {
+                    let blob_dir = rollup_args
+                        .l1_blob_dir
+                        .unwrap_or_else(|| handle.node.data_dir.data_dir().join("blobs"));
+                    let pipeline = ScrollDerivationPipeline::new(
+                        l1_provider,
+                        BlobArchive::new(blob_dir),
+                        handle.node.provider.clone(),
+                        handle.node.engine_http_client(),
+                    );
+                    handle
+                        .node
+                        .task_executor
+                        .spawn_critical("l1 derivation", Box::pin(pipeline.run()));
+                }
+            }
+
             handle.node_exit_future.await
         },
     ) {
@@ -0,0 +1,101 @@
+//! Scroll L2 base fee, introduced by the [`Curie`](reth_scroll_forks::ScrollHardfork::Curie)
+//! hardfork.
+
+use alloy_primitives::U256;
+use reth_chainspec::BaseFeeParams;
+
+/// The maximum L2 base fee allowed in a block header, in wei (10 gwei).
+/// <https://github.com/scroll-tech/go-ethereum/blob/develop/consensus/misc/eip1559.go>
+pub const MAXIMUM_L2_BASE_FEE: u64 = 10_000_000_000;
+
+/// The precision of the [`ScrollBaseFeeParams::scalar`], matching the L1 gas price oracle.
+pub const L2_BASE_FEE_PRECISION: u64 = 1_000_000_000_000_000_000;
+
+/// The default L2 base fee scalar.
+pub const DEFAULT_L2_BASE_FEE_SCALAR: u64 = 34_000_000_000_000;
+
+/// The default L2 base fee overhead, in wei.
+pub const DEFAULT_L2_BASE_FEE_OVERHEAD: u64 = 15_680_000;
+
+/// The EIP-1559 parameters of Scroll, under which the base fee of a block is the base fee of its
+/// parent, whatever its gas usage.
+///
+/// The L2 base fee isn't derived from the gas usage of the parent but from the L1 base fee at the
+/// parent state, see [`ScrollBaseFeeParams`]. These parameters are used by the callers of
+/// [`EthChainSpec::base_fee_params_at_timestamp`](reth_chainspec::EthChainSpec) without access
+/// to the parent state, which assume that the L1 base fee didn't change: with a gas target equal
+/// to the gas limit and a change denominator exceeding any base fee, the EIP-1559 update is
+/// always zero.
+pub const SCROLL_BASE_FEE_PARAMS: BaseFeeParams =
+    BaseFeeParams { max_change_denominator: u64::MAX as u128, elasticity_multiplier: 1 };
+
+/// Parameters of the Scroll L2 base fee formula:
+///
+/// `l2_base_fee = min(l1_base_fee * scalar / L2_BASE_FEE_PRECISION + overhead,
+/// MAXIMUM_L2_BASE_FEE)`
+///
+/// where `l1_base_fee` is the L1 base fee stored in the L1 gas price oracle at the parent block.
+/// The scalar accounts for the finalization costs on L1, the overhead accounts for the sequencing
+/// and proving costs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScrollBaseFeeParams {
+    /// The scalar applied to the L1 base fee, with [`L2_BASE_FEE_PRECISION`].
+    pub scalar: u64,
+    /// The overhead added to the scaled L1 base fee, in wei.
+    pub overhead: u64,
+}
+
+impl ScrollBaseFeeParams {
+    /// Returns new [`ScrollBaseFeeParams`] from the provided scalar and overhead.
+    pub const fn new(scalar: u64, overhead: u64) -> Self {
+        Self { scalar, overhead }
+    }
+
+    /// Returns the L2 base fee derived from the provided L1 base fee, capped at
+    /// [`MAXIMUM_L2_BASE_FEE`].
+    pub fn l2_base_fee(&self, l1_base_fee: U256) -> u64 {
+        let base_fee =
+            l1_base_fee.saturating_mul(U256::from(self.scalar)) / U256::from(L2_BASE_FEE_PRECISION);
+        let base_fee = base_fee.saturating_add(U256::from(self.overhead));
+        base_fee.min(U256::from(MAXIMUM_L2_BASE_FEE)).to()
+    }
+}
+
+impl Default for ScrollBaseFeeParams {
+    fn default() -> Self {
+        Self::new(DEFAULT_L2_BASE_FEE_SCALAR, DEFAULT_L2_BASE_FEE_OVERHEAD)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_l2_base_fee() {
+        let params = ScrollBaseFeeParams::default();
+
+        // no L1 base fee only charges the overhead
+        assert_eq!(params.l2_base_fee(U256::ZERO), DEFAULT_L2_BASE_FEE_OVERHEAD);
+
+        // 10 gwei L1 base fee
+        let l1_base_fee = U256::from(10_000_000_000u64);
+        assert_eq!(params.l2_base_fee(l1_base_fee), 340_000 + DEFAULT_L2_BASE_FEE_OVERHEAD);
+
+        // the L2 base fee is capped
+        assert_eq!(params.l2_base_fee(U256::MAX), MAXIMUM_L2_BASE_FEE);
+    }
+
+    #[test]
+    fn test_eip1559_base_fee_params() {
+        let gas_limit = 10_000_000;
+        for gas_used in [0, 1, gas_limit / 2, gas_limit] {
+            for base_fee in [0, 1, DEFAULT_L2_BASE_FEE_OVERHEAD, MAXIMUM_L2_BASE_FEE, u64::MAX] {
+                assert_eq!(
+                    SCROLL_BASE_FEE_PARAMS.next_block_base_fee(gas_used, gas_limit, base_fee),
+                    base_fee
+                );
+            }
+        }
+    }
+}
@@ -59,6 +59,10 @@ pub struct ScrollHardforkInfo {
     pub darwin_time: Option<u64>,
     /// darwinV2 hardfork timestamp
     pub darwin_v2_time: Option<u64>,
+    /// euclid hardfork timestamp
+    pub euclid_time: Option<u64>,
+    /// euclidV2 hardfork timestamp
+    pub euclid_v2_time: Option<u64>,
 }
 
 impl ScrollHardforkInfo {
@@ -160,7 +164,8 @@ mod tests {
           "archimedesBlock": 0,
           "bernoulliBlock": 10,
           "curieBlock": 12,
-          "darwinTime": 0
+          "darwinTime": 0,
+          "euclidTime": 20
         }
         "#;
 
@@ -175,6 +180,8 @@ mod tests {
                 curie_block: Some(12),
                 darwin_time: Some(0),
                 darwin_v2_time: None,
+                euclid_time: Some(20),
+                euclid_v2_time: None,
             }
         );
     }
@@ -209,6 +216,8 @@ mod tests {
                 curie_block: Some(12),
                 darwin_time: Some(0),
                 darwin_v2_time: None,
+                euclid_time: None,
+                euclid_v2_time: None,
             }),
             scroll_chain_config: ScrollChainConfig {
                 fee_vault_address: Some(address!("5300000000000000000000000000000000000005")),
@@ -29,6 +29,12 @@ use std::sync::LazyLock;
 
 extern crate alloc;
 
+mod base_fee;
+pub use base_fee::{
+    ScrollBaseFeeParams, DEFAULT_L2_BASE_FEE_OVERHEAD, DEFAULT_L2_BASE_FEE_SCALAR,
+    L2_BASE_FEE_PRECISION, MAXIMUM_L2_BASE_FEE, SCROLL_BASE_FEE_PARAMS,
+};
+
 mod constants;
 pub use constants::{
     SCROLL_DEV_L1_CONFIG, SCROLL_DEV_L1_MESSAGE_QUEUE_ADDRESS, SCROLL_DEV_L1_PROXY_ADDRESS,
@@ -159,6 +165,24 @@ impl ScrollChainSpecBuilder {
         self
     }
 
+    /// Enable Euclid at genesis
+    pub fn euclid_activated(mut self) -> Self {
+        self = self.darwin_v2_activated();
+        self.inner = self
+            .inner
+            .with_fork(reth_scroll_forks::ScrollHardfork::Euclid, ForkCondition::Timestamp(0));
+        self
+    }
+
+    /// Enable `EuclidV2` at genesis
+    pub fn euclid_v2_activated(mut self) -> Self {
+        self = self.euclid_activated();
+        self.inner = self
+            .inner
+            .with_fork(reth_scroll_forks::ScrollHardfork::EuclidV2, ForkCondition::Timestamp(0));
+        self
+    }
+
     /// Build the resulting [`ScrollChainSpec`].
     ///
     /// # Panics
@@ -185,14 +209,16 @@ impl EthChainSpec for ScrollChainSpec {
         self.inner.chain()
     }
 
-    fn base_fee_params_at_block(&self, block_number: u64) -> BaseFeeParams {
-        // TODO(scroll): need to implement Scroll L2 formula related to https://github.com/scroll-tech/reth/issues/60
-        self.inner.base_fee_params_at_block(block_number)
+    /// Returns [`SCROLL_BASE_FEE_PARAMS`], keeping the parent base fee: the L2 base fee isn't
+    /// derived from the parent's gas usage, see [`ScrollChainSpec::next_block_base_fee`].
+    fn base_fee_params_at_block(&self, _block_number: u64) -> BaseFeeParams {
+        SCROLL_BASE_FEE_PARAMS
     }
 
-    fn base_fee_params_at_timestamp(&self, timestamp: u64) -> BaseFeeParams {
-        // TODO(scroll): need to implement Scroll L2 formula related to https://github.com/scroll-tech/reth/issues/60
-        self.inner.base_fee_params_at_timestamp(timestamp)
+    /// Returns [`SCROLL_BASE_FEE_PARAMS`], keeping the parent base fee: the L2 base fee isn't
+    /// derived from the parent's gas usage, see [`ScrollChainSpec::next_block_base_fee`].
+    fn base_fee_params_at_timestamp(&self, _timestamp: u64) -> BaseFeeParams {
+        SCROLL_BASE_FEE_PARAMS
     }
 
     fn deposit_contract(&self) -> Option<&DepositContract> {
@@ -229,6 +255,16 @@ impl EthChainSpec for ScrollChainSpec {
 }
 
 impl ScrollChainSpec {
+    /// Returns the L2 base fee of the block following `parent`, derived from the L1 base fee
+    /// stored in the L1 gas price oracle at the state of the parent block.
+    ///
+    /// Returns `None` if [`Curie`](reth_scroll_forks::ScrollHardfork::Curie) isn't active at the
+    /// next block, blocks don't have a base fee before Curie.
+    pub fn next_block_base_fee(&self, parent: &Header, parent_l1_base_fee: U256) -> Option<u64> {
+        self.is_curie_active_at_block(parent.number + 1)
+            .then(|| ScrollBaseFeeParams::default().l2_base_fee(parent_l1_base_fee))
+    }
+
     fn make_genesis_header(&self) -> Header {
         Header {
             gas_limit: self.genesis.gas_limit,
@@ -318,6 +354,8 @@ impl From<Genesis> for ScrollChainSpec {
             (EthereumHardfork::Shanghai.boxed(), genesis.config.shanghai_time),
             (ScrollHardfork::Darwin.boxed(), hard_fork_info.darwin_time),
             (ScrollHardfork::DarwinV2.boxed(), hard_fork_info.darwin_v2_time),
+            (ScrollHardfork::Euclid.boxed(), hard_fork_info.euclid_time),
+            (ScrollHardfork::EuclidV2.boxed(), hard_fork_info.euclid_v2_time),
         ];
 
         let mut time_hardforks = time_hardfork_opts
@@ -456,6 +494,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn next_block_base_fee() {
+        let scroll_mainnet =
+            ScrollChainSpecBuilder::scroll_mainnet().build(ScrollChainConfig::mainnet());
+        let l1_base_fee = U256::from(10_000_000_000u64);
+
+        // pre curie blocks don't have a base fee
+        let parent = Header { number: 7096834, ..Default::default() };
+        assert_eq!(scroll_mainnet.next_block_base_fee(&parent, l1_base_fee), None);
+
+        // curie block
+        let parent = Header { number: 7096835, ..Default::default() };
+        assert_eq!(
+            scroll_mainnet.next_block_base_fee(&parent, l1_base_fee),
+            Some(ScrollBaseFeeParams::default().l2_base_fee(l1_base_fee))
+        );
+    }
+
     #[test]
     fn is_bernoulli_active() {
         let scroll_mainnet =
@@ -472,6 +528,8 @@ mod tests {
         "curieBlock": 20,
         "darwinTime": 30,
         "darwinV2Time": 31,
+        "euclidTime": 40,
+        "euclidV2Time": 41,
         "scroll": {
             "feeVaultAddress": "0x5300000000000000000000000000000000000005",
             "l1Config": {
@@ -494,6 +552,10 @@ mod tests {
         assert_eq!(actual_darwin_timestamp, Some(serde_json::Value::from(30)).as_ref());
         let actual_darwin_v2_timestamp = genesis.config.extra_fields.get("darwinV2Time");
         assert_eq!(actual_darwin_v2_timestamp, Some(serde_json::Value::from(31)).as_ref());
+        let actual_euclid_timestamp = genesis.config.extra_fields.get("euclidTime");
+        assert_eq!(actual_euclid_timestamp, Some(serde_json::Value::from(40)).as_ref());
+        let actual_euclid_v2_timestamp = genesis.config.extra_fields.get("euclidV2Time");
+        assert_eq!(actual_euclid_v2_timestamp, Some(serde_json::Value::from(41)).as_ref());
         let scroll_object = genesis.config.extra_fields.get("scroll").unwrap();
         assert_eq!(
             scroll_object,
@@ -514,11 +576,16 @@ mod tests {
         assert!(!chain_spec.is_fork_active_at_block(ScrollHardfork::Curie, 0));
         assert!(!chain_spec.is_fork_active_at_timestamp(ScrollHardfork::Darwin, 0));
         assert!(!chain_spec.is_fork_active_at_timestamp(ScrollHardfork::DarwinV2, 0));
+        assert!(!chain_spec.is_fork_active_at_timestamp(ScrollHardfork::Euclid, 0));
+        assert!(!chain_spec.is_fork_active_at_timestamp(ScrollHardfork::EuclidV2, 0));
 
         assert!(chain_spec.is_fork_active_at_block(ScrollHardfork::Bernoulli, 10));
         assert!(chain_spec.is_fork_active_at_block(ScrollHardfork::Curie, 20));
         assert!(chain_spec.is_fork_active_at_timestamp(ScrollHardfork::Darwin, 30));
         assert!(chain_spec.is_fork_active_at_timestamp(ScrollHardfork::DarwinV2, 31));
+        assert!(!chain_spec.is_euclid_active_at_timestamp(39));
+        assert!(chain_spec.is_euclid_active_at_timestamp(40));
+        assert!(chain_spec.is_euclid_v2_active_at_timestamp(41));
     }
 
     #[test]
@@ -545,6 +612,8 @@ mod tests {
                     (String::from("curieBlock"), 0.into()),
                     (String::from("darwinTime"), 0.into()),
                     (String::from("darwinV2Time"), 0.into()),
+                    (String::from("euclidTime"), 0.into()),
+                    (String::from("euclidV2Time"), 0.into()),
                     (
                         String::from("scroll"),
                         serde_json::json!({
@@ -584,6 +653,8 @@ mod tests {
             ScrollHardfork::Curie.boxed(),
             ScrollHardfork::Darwin.boxed(),
             ScrollHardfork::DarwinV2.boxed(),
+            ScrollHardfork::Euclid.boxed(),
+            ScrollHardfork::EuclidV2.boxed(),
         ];
 
         assert!(expected_hardforks
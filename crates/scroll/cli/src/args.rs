@@ -1,6 +1,8 @@
 use reth_node_builder::engine_tree_config::{
     DEFAULT_MEMORY_BLOCK_BUFFER_TARGET, DEFAULT_PERSISTENCE_THRESHOLD,
 };
+use reth_scroll_l1::DEFAULT_L1_CONFIRMATIONS;
+use std::path::PathBuf;
 
 /// Rollup arguments for the Scroll node.
 #[derive(Debug, clap::Args)]
@@ -12,4 +14,47 @@ pub struct ScrollRollupArgs {
     /// Configure the target number of blocks to keep in memory.
     #[arg(long = "engine.memory-block-buffer-target", default_value_t = DEFAULT_MEMORY_BLOCK_BUFFER_TARGET)]
     pub memory_block_buffer_target: u64,
+
+    /// The L1 JSON-RPC endpoint followed to index the L1 message queue and the batches committed
+    /// to L1, which drive the `safe` and `finalized` blocks.
+    ///
+    /// L1 is not followed if no endpoint is provided.
+    #[arg(long = "scroll.l1-rpc-url")]
+    pub l1_rpc_url: Option<String>,
+
+    /// The number of L1 blocks built on top of an L1 block before its L1 messages and batches are
+    /// indexed.
+    #[arg(long = "scroll.l1-confirmations", default_value_t = DEFAULT_L1_CONFIRMATIONS)]
+    pub l1_confirmations: u64,
+
+    /// The L1 block from which the L1 message queue and the batches are indexed, usually the
+    /// deployment block of the rollup contracts.
+    #[arg(long = "scroll.l1-start-block", default_value_t = 0)]
+    pub l1_start_block: u64,
+
+    /// Rebuilds the chain purely from the batches committed to L1 instead of following L2 peers,
+    /// checking the state roots against the ones finalized on L1.
+    ///
+    /// Requires an L1 JSON-RPC endpoint.
+    #[arg(long = "scroll.l1-derivation", requires = "l1_rpc_url")]
+    pub l1_derivation: bool,
+
+    /// The directory holding the blobs of the batch commit transactions, one file per blob named
+    /// after the versioned hash of the blob, used by the L1 derivation.
+    ///
+    /// Defaults to the `blobs` directory of the chain data directory.
+    #[arg(long = "scroll.l1-blob-dir", requires = "l1_derivation")]
+    pub l1_blob_dir: Option<PathBuf>,
+
+    /// Checks the state roots of the committed chain against the ones of the batches finalized
+    /// on L1, stopping the node on a mismatch.
+    ///
+    /// Requires an L1 JSON-RPC endpoint.
+    #[arg(long = "scroll.verify-state-roots", requires = "l1_rpc_url")]
+    pub verify_state_roots: bool,
+
+    /// Indexes the withdraw trie of the `L2MessageQueue` predeploy and serves the Merkle proofs of
+    /// the L2 to L1 messages through the `scroll_getWithdrawalProof` RPC method.
+    #[arg(long = "scroll.withdraw-trie")]
+    pub withdraw_trie: bool,
 }
@@ -20,6 +20,10 @@ pub enum Commands<
     #[command(name = "init")]
     Init(init_cmd::InitCommand<Spec>),
     /// Initialize the database from a state dump file.
+    ///
+    /// Accepts l2geth state dumps, whose code sizes and Poseidon code hashes are checked against
+    /// the accounts' bytecode. The state is only committed if its Poseidon root matches the
+    /// header.
     #[command(name = "init-state")]
     InitState(init_state::InitStateCommand<Spec>),
     /// This syncs RLP encoded blocks from a file.
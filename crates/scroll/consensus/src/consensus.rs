@@ -0,0 +1,431 @@
+//! Scroll [`Consensus`] implementation.
+
+use crate::{
+    validation::{
+        validate_against_parent_gas_limit, validate_against_parent_timestamp,
+        validate_block_post_execution, validate_header_base_fee, validate_l1_messages,
+        validate_l1_messages_against_queue,
+    },
+    L1MessageQueueProvider,
+};
+use alloy_consensus::{Header, EMPTY_OMMER_ROOT_HASH};
+use alloy_primitives::U256;
+use reth_consensus::{Consensus, ConsensusError, HeaderValidator, PostExecutionInput};
+use reth_consensus_common::validation::{
+    validate_against_parent_hash_number, validate_body_against_header, validate_header_gas,
+};
+use reth_primitives::{BlockBody, BlockWithSenders, GotExpected, SealedBlock, SealedHeader};
+use reth_scroll_chainspec::ScrollChainSpec;
+use std::{sync::Arc, time::SystemTime};
+
+/// Scroll consensus implementation.
+///
+/// Provides the header and body checks performed by the Scroll sequencer on the L2 blocks, as
+/// outlined in [l2geth](https://github.com/scroll-tech/go-ethereum). The L1 messages included in
+/// the blocks are checked against the L1 message queue provided by the [`L1MessageQueueProvider`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScrollConsensus<L1MessageQueue = ()> {
+    /// Configuration
+    chain_spec: Arc<ScrollChainSpec>,
+    /// The local copy of the L1 message queue.
+    l1_message_queue: L1MessageQueue,
+}
+
+impl ScrollConsensus {
+    /// Create a new instance of [`ScrollConsensus`]
+    pub const fn new(chain_spec: Arc<ScrollChainSpec>) -> Self {
+        Self { chain_spec, l1_message_queue: () }
+    }
+}
+
+impl<L1MessageQueue> ScrollConsensus<L1MessageQueue> {
+    /// Configures the L1 message queue the L1 messages included in the blocks are checked against.
+    pub fn with_l1_message_queue<T>(self, l1_message_queue: T) -> ScrollConsensus<T> {
+        let Self { chain_spec, .. } = self;
+        ScrollConsensus { chain_spec, l1_message_queue }
+    }
+}
+
+impl<L1MessageQueue: L1MessageQueueProvider> Consensus for ScrollConsensus<L1MessageQueue> {
+    fn validate_body_against_header(
+        &self,
+        body: &BlockBody,
+        header: &SealedHeader,
+    ) -> Result<(), ConsensusError> {
+        validate_body_against_header(body, header)
+    }
+
+    fn validate_block_pre_execution(&self, block: &SealedBlock) -> Result<(), ConsensusError> {
+        // Check that the block doesn't contain any ommers
+        if !block.body.ommers.is_empty() {
+            return Err(ConsensusError::UnexpectedOmmers { count: block.body.ommers.len() })
+        }
+
+        // Check ommers hash
+        let ommers_hash = block.body.calculate_ommers_root();
+        if block.header.ommers_hash != ommers_hash {
+            return Err(ConsensusError::BodyOmmersHashDiff(
+                GotExpected { got: ommers_hash, expected: block.header.ommers_hash }.into(),
+            ))
+        }
+
+        // Check transaction root
+        if let Err(error) = block.ensure_transaction_root_valid() {
+            return Err(ConsensusError::BodyTransactionRootDiff(error.into()))
+        }
+
+        // Scroll doesn't support withdrawals
+        if block.body.withdrawals.is_some() {
+            return Err(ConsensusError::WithdrawalsRootUnexpected)
+        }
+
+        // Check the L1 messages ordering
+        validate_l1_messages(
+            &block.body.transactions,
+            self.chain_spec.config.l1_config.num_l1_messages_per_block,
+        )?;
+
+        Ok(())
+    }
+
+    fn validate_block_post_execution(
+        &self,
+        block: &BlockWithSenders,
+        input: PostExecutionInput<'_>,
+    ) -> Result<(), ConsensusError> {
+        validate_block_post_execution(block, &self.chain_spec, input.receipts)?;
+        validate_l1_messages_against_queue(block, &self.l1_message_queue)
+    }
+}
+
+impl<L1MessageQueue: L1MessageQueueProvider> HeaderValidator for ScrollConsensus<L1MessageQueue> {
+    fn validate_header(&self, header: &SealedHeader) -> Result<(), ConsensusError> {
+        validate_header_gas(header.header())?;
+        validate_header_base_fee(header.header(), &self.chain_spec)?;
+
+        if header.ommers_hash != EMPTY_OMMER_ROOT_HASH {
+            return Err(ConsensusError::BodyOmmersHashDiff(
+                GotExpected { got: header.ommers_hash, expected: EMPTY_OMMER_ROOT_HASH }.into(),
+            ))
+        }
+
+        // Scroll doesn't support withdrawals, blobs or requests.
+        if header.withdrawals_root.is_some() {
+            return Err(ConsensusError::WithdrawalsRootUnexpected)
+        } else if header.blob_gas_used.is_some() {
+            return Err(ConsensusError::BlobGasUsedUnexpected)
+        } else if header.excess_blob_gas.is_some() {
+            return Err(ConsensusError::ExcessBlobGasUnexpected)
+        } else if header.parent_beacon_block_root.is_some() {
+            return Err(ConsensusError::ParentBeaconBlockRootUnexpected)
+        } else if header.requests_hash.is_some() {
+            return Err(ConsensusError::RequestsHashUnexpected)
+        }
+
+        Ok(())
+    }
+
+    fn validate_header_against_parent(
+        &self,
+        header: &SealedHeader,
+        parent: &SealedHeader,
+    ) -> Result<(), ConsensusError> {
+        validate_against_parent_hash_number(header, parent)?;
+        validate_against_parent_timestamp(header, parent, &self.chain_spec)?;
+        validate_against_parent_gas_limit(header, parent)?;
+
+        Ok(())
+    }
+
+    fn validate_header_with_total_difficulty(
+        &self,
+        header: &Header,
+        _total_difficulty: U256,
+    ) -> Result<(), ConsensusError> {
+        // Check if timestamp is in the future. Clock can drift but this can be consensus issue.
+        let present_timestamp =
+            SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs();
+
+        if header.exceeds_allowed_future_timestamp(present_timestamp) {
+            return Err(ConsensusError::TimestampIsInFuture {
+                timestamp: header.timestamp,
+                present_timestamp,
+            })
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MAXIMUM_L2_BASE_FEE;
+    use alloy_primitives::{map::HashMap, Address, PrimitiveSignature as Signature, B256};
+    use reth_primitives::{Block, Transaction, TransactionSigned};
+    use reth_scroll_chainspec::{ScrollChainConfig, ScrollChainSpecBuilder};
+    use reth_scroll_primitives::TxL1Message;
+    use reth_storage_errors::provider::ProviderResult;
+
+    const CURIE_BLOCK_NUMBER: u64 = 7096836;
+
+    fn consensus() -> ScrollConsensus {
+        ScrollConsensus::new(Arc::new(
+            ScrollChainSpecBuilder::scroll_mainnet().build(ScrollChainConfig::mainnet()),
+        ))
+    }
+
+    fn sealed(header: Header) -> SealedHeader {
+        SealedHeader::new(header, B256::ZERO)
+    }
+
+    fn l1_message(queue_index: u64) -> TransactionSigned {
+        TransactionSigned::new_unhashed(
+            Transaction::L1Message(TxL1Message {
+                queue_index,
+                sender: Address::random(),
+                ..Default::default()
+            }),
+            TxL1Message::signature(),
+        )
+    }
+
+    fn l2_transaction() -> TransactionSigned {
+        TransactionSigned::new_unhashed(
+            Transaction::Legacy(Default::default()),
+            Signature::test_signature(),
+        )
+    }
+
+    #[test]
+    fn test_valid_gas_limit_increase() {
+        let parent = sealed(Header { gas_limit: 1024 * 10, ..Default::default() });
+        let child = sealed(Header { gas_limit: parent.gas_limit + 5, ..Default::default() });
+
+        assert_eq!(validate_against_parent_gas_limit(&child, &parent), Ok(()));
+    }
+
+    #[test]
+    fn test_invalid_gas_limit_increase_exceeding_limit() {
+        let parent = sealed(Header { gas_limit: 1024 * 10, ..Default::default() });
+        let child = sealed(Header {
+            gas_limit: parent.gas_limit + parent.gas_limit / 1024 + 1,
+            ..Default::default()
+        });
+
+        assert_eq!(
+            validate_against_parent_gas_limit(&child, &parent),
+            Err(ConsensusError::GasLimitInvalidIncrease {
+                parent_gas_limit: parent.gas_limit,
+                child_gas_limit: child.gas_limit,
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_gas_limit_below_minimum() {
+        let parent = sealed(Header { gas_limit: 5001, ..Default::default() });
+        let child = sealed(Header { gas_limit: 4999, ..Default::default() });
+
+        assert_eq!(
+            validate_against_parent_gas_limit(&child, &parent),
+            Err(ConsensusError::GasLimitInvalidMinimum { child_gas_limit: child.gas_limit })
+        );
+    }
+
+    #[test]
+    fn test_timestamp_against_parent() {
+        let consensus = consensus();
+        let chain_spec = &consensus.chain_spec;
+
+        // pre curie blocks must have strictly increasing timestamps
+        let parent = Header { number: 1, timestamp: 10, ..Default::default() };
+        let child = Header { number: 2, timestamp: 10, ..Default::default() };
+        assert_eq!(
+            validate_against_parent_timestamp(&child, &parent, chain_spec),
+            Err(ConsensusError::TimestampIsInPast { parent_timestamp: 10, timestamp: 10 })
+        );
+
+        // post curie blocks can share the timestamp of their parent
+        let parent = Header { number: CURIE_BLOCK_NUMBER, timestamp: 10, ..Default::default() };
+        let child = Header { number: CURIE_BLOCK_NUMBER + 1, timestamp: 10, ..Default::default() };
+        assert_eq!(validate_against_parent_timestamp(&child, &parent, chain_spec), Ok(()));
+
+        let child = Header { number: CURIE_BLOCK_NUMBER + 1, timestamp: 9, ..Default::default() };
+        assert_eq!(
+            validate_against_parent_timestamp(&child, &parent, chain_spec),
+            Err(ConsensusError::TimestampIsInPast { parent_timestamp: 10, timestamp: 9 })
+        );
+    }
+
+    #[test]
+    fn test_curie_base_fee() {
+        let consensus = consensus();
+
+        // pre curie header doesn't need a base fee
+        let header = sealed(Header { number: CURIE_BLOCK_NUMBER - 1, ..Default::default() });
+        assert_eq!(consensus.validate_header(&header), Ok(()));
+
+        // curie header requires a base fee
+        let header = sealed(Header { number: CURIE_BLOCK_NUMBER, ..Default::default() });
+        assert_eq!(consensus.validate_header(&header), Err(ConsensusError::BaseFeeMissing));
+
+        // curie base fee is capped
+        let header = sealed(Header {
+            number: CURIE_BLOCK_NUMBER,
+            base_fee_per_gas: Some(MAXIMUM_L2_BASE_FEE + 1),
+            ..Default::default()
+        });
+        assert_eq!(
+            consensus.validate_header(&header),
+            Err(ConsensusError::BaseFeeExceedsMaximum {
+                base_fee: MAXIMUM_L2_BASE_FEE + 1,
+                max_base_fee: MAXIMUM_L2_BASE_FEE,
+            })
+        );
+
+        let header = sealed(Header {
+            number: CURIE_BLOCK_NUMBER,
+            base_fee_per_gas: Some(MAXIMUM_L2_BASE_FEE),
+            ..Default::default()
+        });
+        assert_eq!(consensus.validate_header(&header), Ok(()));
+    }
+
+    #[test]
+    fn test_header_withdrawals_root_unexpected() {
+        let consensus = consensus();
+        let header = sealed(Header { withdrawals_root: Some(B256::ZERO), ..Default::default() });
+
+        assert_eq!(
+            consensus.validate_header(&header),
+            Err(ConsensusError::WithdrawalsRootUnexpected)
+        );
+    }
+
+    #[test]
+    fn test_l1_messages_ordering() {
+        // valid ordering
+        let transactions = vec![l1_message(0), l1_message(1), l1_message(2), l2_transaction()];
+        assert_eq!(validate_l1_messages(&transactions, 10), Ok(()));
+
+        // l1 message after an l2 transaction
+        let transactions = vec![l1_message(0), l2_transaction(), l1_message(1)];
+        assert_eq!(
+            validate_l1_messages(&transactions, 10),
+            Err(ConsensusError::L1MessageAfterL2Transaction { tx_index: 2 })
+        );
+
+        // non increasing queue index
+        let transactions = vec![l1_message(3), l1_message(3)];
+        assert_eq!(
+            validate_l1_messages(&transactions, 10),
+            Err(ConsensusError::L1MessageQueueIndexNotIncreasing {
+                previous_queue_index: 3,
+                queue_index: 3
+            })
+        );
+
+        // skipped l1 messages
+        let transactions = vec![l1_message(0), l1_message(1), l1_message(5)];
+        assert_eq!(validate_l1_messages(&transactions, 10), Ok(()));
+
+        // too many l1 messages
+        let transactions = vec![l1_message(0), l1_message(1), l1_message(2)];
+        assert_eq!(
+            validate_l1_messages(&transactions, 2),
+            Err(ConsensusError::TooManyL1Messages { count: 3, max_count: 2 })
+        );
+    }
+
+    #[derive(Debug, Default)]
+    struct MockL1MessageQueue {
+        next_queue_indices: HashMap<B256, u64>,
+        l1_messages: HashMap<u64, TxL1Message>,
+    }
+
+    impl L1MessageQueueProvider for MockL1MessageQueue {
+        fn next_l1_message_queue_index(&self, block_hash: B256) -> ProviderResult<Option<u64>> {
+            Ok(self.next_queue_indices.get(&block_hash).copied())
+        }
+
+        fn l1_message(&self, queue_index: u64) -> ProviderResult<Option<TxL1Message>> {
+            Ok(self.l1_messages.get(&queue_index).cloned())
+        }
+
+        fn l1_message_queue_head(&self) -> ProviderResult<u64> {
+            Ok(self.l1_messages.keys().max().map_or(0, |queue_index| queue_index + 1))
+        }
+    }
+
+    #[test]
+    fn test_l1_messages_against_queue() {
+        let parent_hash = B256::random();
+        let queued = (0..4).map(l1_message).collect::<Vec<_>>();
+        let mut queue = MockL1MessageQueue::default();
+        queue.next_queue_indices.insert(parent_hash, 2);
+        for tx in &queued {
+            let Transaction::L1Message(l1_message) = &tx.transaction else { unreachable!() };
+            queue.l1_messages.insert(l1_message.queue_index, l1_message.clone());
+        }
+
+        let block = |transactions: Vec<TransactionSigned>| {
+            let header = Header { parent_hash, ..Default::default() };
+            let body = BlockBody { transactions, ..Default::default() };
+            BlockWithSenders::new_unchecked(Block { header, body }, Vec::new())
+        };
+
+        // l1 messages following the parent chain
+        let valid = block(vec![queued[2].clone(), queued[3].clone(), l2_transaction()]);
+        assert_eq!(validate_l1_messages_against_queue(&valid, &queue), Ok(()));
+
+        // the checks are skipped without the queue or for an unknown parent
+        let unchecked = block(vec![l1_message(7)]);
+        assert_eq!(validate_l1_messages_against_queue(&unchecked, &()), Ok(()));
+        queue.next_queue_indices.clear();
+        assert_eq!(validate_l1_messages_against_queue(&unchecked, &queue), Ok(()));
+        queue.next_queue_indices.insert(parent_hash, 2);
+
+        // l1 message already included in the parent chain
+        let included = block(vec![queued[1].clone()]);
+        assert_eq!(
+            validate_l1_messages_against_queue(&included, &queue),
+            Err(ConsensusError::L1MessageQueueIndexNotIncreasing {
+                previous_queue_index: 1,
+                queue_index: 1
+            })
+        );
+
+        // l1 messages known to the queue can be skipped
+        let skipped = block(vec![queued[3].clone()]);
+        assert_eq!(validate_l1_messages_against_queue(&skipped, &queue), Ok(()));
+        let skipped = block(vec![l1_message(4)]);
+        assert_eq!(
+            validate_l1_messages_against_queue(&skipped, &queue),
+            Err(ConsensusError::L1MessageNotIndexed { queue_index: 4 })
+        );
+
+        // l1 message differing from the queue
+        let forged = block(vec![l1_message(2)]);
+        assert_eq!(
+            validate_l1_messages_against_queue(&forged, &queue),
+            Err(ConsensusError::L1MessageMismatch { queue_index: 2 })
+        );
+
+        // l1 messages past the indexed head of the queue can't be validated yet, forged or not
+        let Transaction::L1Message(message) = queued[3].transaction.clone() else { unreachable!() };
+        queue.l1_messages.remove(&3);
+        let not_indexed = Err(ConsensusError::L1MessageNotIndexed { queue_index: 3 });
+        assert_eq!(validate_l1_messages_against_queue(&valid, &queue), not_indexed);
+        let forged = block(vec![queued[2].clone(), l1_message(3)]);
+        assert_eq!(validate_l1_messages_against_queue(&forged, &queue), not_indexed);
+        assert!(not_indexed.unwrap_err().is_retriable());
+
+        // l1 message missing from the indexed queue
+        queue.l1_messages.insert(3, message);
+        queue.l1_messages.remove(&2);
+        let unknown = validate_l1_messages_against_queue(&valid, &queue).unwrap_err();
+        assert_eq!(unknown, ConsensusError::L1MessageUnknown { queue_index: 2 });
+        assert!(!unknown.is_retriable());
+    }
+}
@@ -0,0 +1,44 @@
+//! Source of the L1 message queue the L1 messages included in the blocks are checked against.
+
+use alloy_primitives::B256;
+use core::fmt::Debug;
+use reth_scroll_primitives::TxL1Message;
+use reth_storage_errors::provider::ProviderResult;
+
+/// Provides the local copy of the L1 message queue to the
+/// [`ScrollConsensus`](crate::ScrollConsensus).
+#[auto_impl::auto_impl(&, Arc)]
+pub trait L1MessageQueueProvider: Debug + Send + Sync {
+    /// Returns the queue index following the last L1 message included in the chain up to and
+    /// including the block with hash `block_hash`.
+    ///
+    /// Returns `None` if the block or the L1 message queue is unknown to the provider, in which
+    /// case the L1 messages of the child block aren't checked against the queue.
+    fn next_l1_message_queue_index(&self, block_hash: B256) -> ProviderResult<Option<u64>>;
+
+    /// Returns the L1 message emitted by the L1 message queue with the given queue index, or
+    /// `None` if the message wasn't indexed.
+    fn l1_message(&self, queue_index: u64) -> ProviderResult<Option<TxL1Message>>;
+
+    /// Returns the queue index following the last L1 message indexed by the provider.
+    ///
+    /// The L1 messages with a greater or equal queue index may exist on L1 but haven't been
+    /// indexed yet.
+    fn l1_message_queue_head(&self) -> ProviderResult<u64>;
+}
+
+/// The unit type doesn't provide the L1 message queue, the L1 messages included in the blocks
+/// aren't checked against the queue.
+impl L1MessageQueueProvider for () {
+    fn next_l1_message_queue_index(&self, _block_hash: B256) -> ProviderResult<Option<u64>> {
+        Ok(None)
+    }
+
+    fn l1_message(&self, _queue_index: u64) -> ProviderResult<Option<TxL1Message>> {
+        Ok(None)
+    }
+
+    fn l1_message_queue_head(&self) -> ProviderResult<u64> {
+        Ok(0)
+    }
+}
@@ -2,6 +2,9 @@
 
 #![cfg(feature = "scroll")]
 
+pub use consensus::ScrollConsensus;
+mod consensus;
+
 mod curie;
 pub use curie::{
     apply_curie_hard_fork, BLOB_SCALAR_SLOT, COMMIT_SCALAR_SLOT,
@@ -9,3 +12,15 @@ pub use curie::{
     L1_BASE_FEE_SLOT, L1_BLOB_BASE_FEE_SLOT, L1_GAS_PRICE_ORACLE_ADDRESS, OVER_HEAD_SLOT,
     SCALAR_SLOT,
 };
+
+pub use l1_message_queue::L1MessageQueueProvider;
+mod l1_message_queue;
+
+pub use validation::{
+    validate_against_parent_gas_limit, validate_against_parent_timestamp,
+    validate_block_post_execution, validate_header_base_fee, validate_l1_messages,
+    validate_l1_messages_against_queue, GAS_LIMIT_BOUND_DIVISOR,
+};
+mod validation;
+
+pub use reth_scroll_chainspec::MAXIMUM_L2_BASE_FEE;
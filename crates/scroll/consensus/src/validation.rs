@@ -0,0 +1,253 @@
+//! Scroll block validation rules.
+
+use crate::L1MessageQueueProvider;
+use alloy_consensus::Header;
+use reth_chainspec::EthereumHardforks;
+use reth_consensus::ConsensusError;
+use reth_primitives::{
+    gas_spent_by_transactions, BlockWithSenders, GotExpected, Receipt, SealedHeader, Transaction,
+    TransactionSigned,
+};
+use reth_primitives_traits::constants::MINIMUM_GAS_LIMIT;
+use reth_scroll_chainspec::{ScrollChainSpec, MAXIMUM_L2_BASE_FEE};
+use reth_scroll_forks::ScrollHardforks;
+use reth_storage_errors::provider::ProviderError;
+
+/// The bound divisor of the gas limit, used in update calculations.
+pub const GAS_LIMIT_BOUND_DIVISOR: u64 = 1024;
+
+/// Checks the gas limit for consistency between parent and self headers.
+///
+/// The maximum allowable difference between self and parent gas limits is determined by the
+/// parent's gas limit divided by the [`GAS_LIMIT_BOUND_DIVISOR`]. Scroll doesn't apply the London
+/// elasticity multiplier to the parent gas limit.
+pub fn validate_against_parent_gas_limit(
+    header: &SealedHeader,
+    parent: &SealedHeader,
+) -> Result<(), ConsensusError> {
+    let parent_gas_limit = parent.gas_limit;
+
+    // Check for an increase in gas limit beyond the allowed threshold.
+    if header.gas_limit > parent_gas_limit {
+        if header.gas_limit - parent_gas_limit >= parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR {
+            return Err(ConsensusError::GasLimitInvalidIncrease {
+                parent_gas_limit,
+                child_gas_limit: header.gas_limit,
+            })
+        }
+    }
+    // Check for a decrease in gas limit beyond the allowed threshold.
+    else if parent_gas_limit - header.gas_limit >= parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR {
+        return Err(ConsensusError::GasLimitInvalidDecrease {
+            parent_gas_limit,
+            child_gas_limit: header.gas_limit,
+        })
+    }
+    // Check if the self gas limit is below the minimum required limit.
+    else if header.gas_limit < MINIMUM_GAS_LIMIT {
+        return Err(ConsensusError::GasLimitInvalidMinimum { child_gas_limit: header.gas_limit })
+    }
+
+    Ok(())
+}
+
+/// Validates the timestamp against the parent.
+///
+/// Before [`Curie`](reth_scroll_forks::ScrollHardfork::Curie), blocks are produced at a fixed
+/// period and the timestamp must be strictly greater than the parent's. From Curie onward, the
+/// block time is dynamic and a block may share the timestamp of its parent.
+pub fn validate_against_parent_timestamp(
+    header: &Header,
+    parent: &Header,
+    chain_spec: &ScrollChainSpec,
+) -> Result<(), ConsensusError> {
+    let is_valid = if chain_spec.is_curie_active_at_block(header.number) {
+        header.timestamp >= parent.timestamp
+    } else {
+        header.timestamp > parent.timestamp
+    };
+
+    if !is_valid {
+        return Err(ConsensusError::TimestampIsInPast {
+            parent_timestamp: parent.timestamp,
+            timestamp: header.timestamp,
+        })
+    }
+    Ok(())
+}
+
+/// Validates the base fee of the header with regard to the
+/// [`Curie`](reth_scroll_forks::ScrollHardfork::Curie) rules: the base fee must be set once Curie
+/// is active and can't exceed [`MAXIMUM_L2_BASE_FEE`].
+///
+/// As in l2geth, the exact base fee derived by [`ScrollChainSpec::next_block_base_fee`] isn't
+/// enforced: it depends on the L1 base fee of the parent state and the sequencer is allowed to set
+/// any base fee below the maximum.
+pub fn validate_header_base_fee(
+    header: &Header,
+    chain_spec: &ScrollChainSpec,
+) -> Result<(), ConsensusError> {
+    if chain_spec.is_curie_active_at_block(header.number) {
+        let base_fee = header.base_fee_per_gas.ok_or(ConsensusError::BaseFeeMissing)?;
+        if base_fee > MAXIMUM_L2_BASE_FEE {
+            return Err(ConsensusError::BaseFeeExceedsMaximum {
+                base_fee,
+                max_base_fee: MAXIMUM_L2_BASE_FEE,
+            })
+        }
+    }
+    Ok(())
+}
+
+/// Validates the L1 messages included in the block:
+///
+/// - L1 messages must be placed at the start of the block, before any L2 transaction.
+/// - The queue index of the L1 messages must be strictly increasing. The L1 messages that can't be
+///   included, e.g. because their execution fails, are skipped and leave a gap in the queue index.
+/// - The block can't include more than `max_l1_messages` L1 messages.
+pub fn validate_l1_messages(
+    transactions: &[TransactionSigned],
+    max_l1_messages: u64,
+) -> Result<(), ConsensusError> {
+    let mut previous_queue_index = None;
+    let mut seen_l2_transaction = false;
+    let mut count = 0;
+
+    for (tx_index, tx) in transactions.iter().enumerate() {
+        let Transaction::L1Message(l1_message) = &tx.transaction else {
+            seen_l2_transaction = true;
+            continue
+        };
+
+        if seen_l2_transaction {
+            return Err(ConsensusError::L1MessageAfterL2Transaction { tx_index })
+        }
+
+        let queue_index = l1_message.queue_index;
+        if let Some(previous_queue_index) = previous_queue_index {
+            if queue_index <= previous_queue_index {
+                return Err(ConsensusError::L1MessageQueueIndexNotIncreasing {
+                    previous_queue_index,
+                    queue_index,
+                })
+            }
+        }
+        previous_queue_index = Some(queue_index);
+        count += 1;
+    }
+
+    if count > max_l1_messages {
+        return Err(ConsensusError::TooManyL1Messages { count, max_count: max_l1_messages })
+    }
+
+    Ok(())
+}
+
+/// Validates the L1 messages included in the block against the local copy of the L1 message
+/// queue:
+///
+/// - The first L1 message must follow the last L1 message included in the parent chain.
+/// - Each L1 message must match the message emitted by the L1 message queue.
+/// - The L1 messages skipped between two included L1 messages must be known to the queue.
+///
+/// The L1 messages are expected to be placed at the start of the block and the checks are skipped
+/// if the queue index following the parent chain is unknown to the provider.
+///
+/// The local copy of the queue may lag behind L1, in which case the L1 messages that aren't
+/// indexed yet can't be checked and [`ConsensusError::L1MessageNotIndexed`] is returned: the
+/// block isn't invalid and must be validated again once the queue caught up. Errors reading the
+/// queue are returned as [`ConsensusError::L1MessageQueueProvider`], which doesn't make the block
+/// invalid either.
+pub fn validate_l1_messages_against_queue<Q: L1MessageQueueProvider>(
+    block: &BlockWithSenders,
+    l1_message_queue: &Q,
+) -> Result<(), ConsensusError> {
+    let Some(mut next_queue_index) = l1_message_queue
+        .next_l1_message_queue_index(block.header.parent_hash)
+        .map_err(l1_message_queue_provider)?
+    else {
+        return Ok(())
+    };
+    let queue_head = l1_message_queue.l1_message_queue_head().map_err(l1_message_queue_provider)?;
+
+    let l1_messages = block.body.transactions.iter().map_while(|tx| match &tx.transaction {
+        Transaction::L1Message(l1_message) => Some(l1_message),
+        _ => None,
+    });
+    for l1_message in l1_messages {
+        let queue_index = l1_message.queue_index;
+        if queue_index < next_queue_index {
+            return Err(ConsensusError::L1MessageQueueIndexNotIncreasing {
+                previous_queue_index: next_queue_index - 1,
+                queue_index,
+            })
+        }
+        // the queue is indexed contiguously, the skipped L1 messages are known if the included
+        // one is
+        if queue_index >= queue_head {
+            return Err(ConsensusError::L1MessageNotIndexed { queue_index })
+        }
+        if queue_index > next_queue_index {
+            tracing::debug!(
+                block_number = block.header.number,
+                skipped = ?(next_queue_index..queue_index),
+                "block skips L1 messages"
+            );
+        }
+
+        let queued = l1_message_queue
+            .l1_message(queue_index)
+            .map_err(l1_message_queue_provider)?
+            .ok_or(ConsensusError::L1MessageUnknown { queue_index })?;
+        if queued != *l1_message {
+            return Err(ConsensusError::L1MessageMismatch { queue_index })
+        }
+
+        next_queue_index = queue_index + 1;
+    }
+
+    Ok(())
+}
+
+fn l1_message_queue_provider(err: ProviderError) -> ConsensusError {
+    ConsensusError::L1MessageQueueProvider(Box::new(err))
+}
+
+/// Validate a block with regard to execution results:
+///
+/// - Compares the receipts root in the block header to the block body
+/// - Compares the gas used in the block header to the actual gas usage after execution
+pub fn validate_block_post_execution(
+    block: &BlockWithSenders,
+    chain_spec: &ScrollChainSpec,
+    receipts: &[Receipt],
+) -> Result<(), ConsensusError> {
+    // verify the block gas used
+    let cumulative_gas_used = receipts.last().map(|r| r.cumulative_gas_used).unwrap_or(0);
+    if block.gas_used != cumulative_gas_used {
+        return Err(ConsensusError::BlockGasUsed {
+            gas: GotExpected { got: cumulative_gas_used, expected: block.gas_used },
+            gas_spent_by_tx: gas_spent_by_transactions(receipts),
+        });
+    }
+
+    // verify the receipts logs bloom and root
+    if chain_spec.is_byzantium_active_at_block(block.header.number) {
+        if let Err(error) = reth_ethereum_consensus::verify_receipts(
+            block.header.receipts_root,
+            block.header.logs_bloom,
+            receipts,
+        ) {
+            tracing::debug!(
+                %error,
+                ?receipts,
+                header_receipt_root = ?block.header.receipts_root,
+                header_bloom = ?block.header.logs_bloom,
+                "failed to verify receipts"
+            );
+            return Err(error);
+        }
+    }
+
+    Ok(())
+}
@@ -1,9 +1,11 @@
+use crate::L1BaseFeeProvider;
 use reth_chainspec::{ChainSpecProvider, Head};
 use reth_evm::{ConfigureEvm, ConfigureEvmEnv, NextBlockEnvAttributes};
 use reth_primitives::{transaction::FillTxEnv, TransactionSigned};
 use reth_revm::{inspector_handle_register, Database, Evm, GetInspector, TxEnv};
 use reth_scroll_chainspec::ScrollChainSpec;
 use reth_scroll_forks::ScrollHardfork;
+use reth_storage_errors::provider::ProviderError;
 use revm::{
     precompile::{Address, Bytes},
     primitives::{
@@ -11,19 +13,31 @@ use revm::{
     },
     EvmBuilder,
 };
-use std::{convert::Infallible, sync::Arc};
+use std::{fmt, sync::Arc};
 
 /// Scroll EVM configuration.
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct ScrollEvmConfig {
     /// The chain spec for Scroll.
     chain_spec: Arc<ScrollChainSpec>,
+    /// The provider of the L1 base fee the base fee of the next block is derived from.
+    l1_base_fee_provider: Option<Arc<dyn L1BaseFeeProvider>>,
 }
 
 impl ScrollEvmConfig {
     /// Returns a new instance of [`ScrollEvmConfig`].
     pub const fn new(chain_spec: Arc<ScrollChainSpec>) -> Self {
-        Self { chain_spec }
+        Self { chain_spec, l1_base_fee_provider: None }
+    }
+
+    /// Configures the provider of the L1 base fee at the parent state, from which
+    /// [`ConfigureEvmEnv::next_cfg_and_block_env`] derives the base fee of the next block.
+    pub fn with_l1_base_fee_provider(
+        mut self,
+        l1_base_fee_provider: impl L1BaseFeeProvider + 'static,
+    ) -> Self {
+        self.l1_base_fee_provider = Some(Arc::new(l1_base_fee_provider));
+        self
     }
 
     /// Returns the spec id at the given head.
@@ -37,6 +51,64 @@ impl ScrollEvmConfig {
             SpecId::PRE_BERNOULLI
         }
     }
+
+    /// Returns the configured [`CfgEnvWithHandlerCfg`] and [`BlockEnv`] for the block following
+    /// `parent`, given the L1 base fee stored in the L1 gas price oracle at the parent state.
+    ///
+    /// From Curie, the base fee of the block is derived from the L1 base fee, see
+    /// [`ScrollChainSpec::next_block_base_fee`]. Blocks don't have a base fee before Curie.
+    pub fn next_cfg_and_block_env_with_l1_base_fee(
+        &self,
+        parent: &alloy_consensus::Header,
+        attributes: NextBlockEnvAttributes,
+        parent_l1_base_fee: U256,
+    ) -> (CfgEnvWithHandlerCfg, BlockEnv) {
+        // configure evm env based on parent block
+        let cfg = CfgEnv::default().with_chain_id(self.chain_spec.chain().id());
+
+        // fetch spec id from next head number and timestamp
+        let spec_id = self.spec_id_at_head(&Head {
+            number: parent.number + 1,
+            timestamp: attributes.timestamp,
+            ..Default::default()
+        });
+
+        let coinbase = if let Some(vault_address) = self.chain_spec.config.fee_vault_address {
+            vault_address
+        } else {
+            attributes.suggested_fee_recipient
+        };
+
+        let basefee =
+            self.chain_spec.next_block_base_fee(parent, parent_l1_base_fee).unwrap_or_default();
+
+        let block_env = BlockEnv {
+            number: U256::from(parent.number + 1),
+            coinbase,
+            timestamp: U256::from(attributes.timestamp),
+            difficulty: U256::ZERO,
+            prevrandao: Some(attributes.prev_randao),
+            gas_limit: U256::from(parent.gas_limit),
+            basefee: U256::from(basefee),
+            blob_excess_gas_and_price: None,
+        };
+
+        let cfg_with_handler_cfg = CfgEnvWithHandlerCfg {
+            cfg_env: cfg,
+            handler_cfg: HandlerCfg { spec_id, is_scroll: true },
+        };
+
+        (cfg_with_handler_cfg, block_env)
+    }
+}
+
+impl fmt::Debug for ScrollEvmConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScrollEvmConfig")
+            .field("chain_spec", &self.chain_spec)
+            .field("l1_base_fee_provider", &self.l1_base_fee_provider.is_some())
+            .finish()
+    }
 }
 
 impl ConfigureEvm for ScrollEvmConfig {
@@ -64,7 +136,7 @@ impl ConfigureEvm for ScrollEvmConfig {
 
 impl ConfigureEvmEnv for ScrollEvmConfig {
     type Header = alloy_consensus::Header;
-    type Error = Infallible;
+    type Error = ProviderError;
 
     fn fill_tx_env(&self, tx_env: &mut TxEnv, transaction: &TransactionSigned, sender: Address) {
         transaction.fill_tx_env(tx_env, sender);
@@ -123,46 +195,32 @@ impl ConfigureEvmEnv for ScrollEvmConfig {
         block_env.blob_excess_gas_and_price = None;
     }
 
+    /// Returns the configured [`CfgEnvWithHandlerCfg`] and [`BlockEnv`] for the block following
+    /// `parent`.
+    ///
+    /// The base fee of the block is derived from the L1 base fee at the parent state, read from
+    /// the configured [`L1BaseFeeProvider`]. Without provider, the L1 base fee is assumed to be
+    /// unchanged since the parent block was built, which keeps the parent base fee.
     fn next_cfg_and_block_env(
         &self,
         parent: &Self::Header,
         attributes: NextBlockEnvAttributes,
     ) -> Result<(CfgEnvWithHandlerCfg, BlockEnv), Self::Error> {
-        // configure evm env based on parent block
-        let cfg = CfgEnv::default().with_chain_id(self.chain_spec.chain().id());
-
-        // fetch spec id from next head number and timestamp
-        let spec_id = self.spec_id_at_head(&Head {
-            number: parent.number + 1,
-            timestamp: attributes.timestamp,
-            ..Default::default()
-        });
-
-        let coinbase = if let Some(vault_address) = self.chain_spec.config.fee_vault_address {
-            vault_address
-        } else {
-            attributes.suggested_fee_recipient
-        };
-
-        let block_env = BlockEnv {
-            number: U256::from(parent.number + 1),
-            coinbase,
-            timestamp: U256::from(attributes.timestamp),
-            difficulty: U256::ZERO,
-            prevrandao: Some(attributes.prev_randao),
-            gas_limit: U256::from(parent.gas_limit),
-            // calculate basefee based on parent block's gas usage
-            // TODO(scroll): update with correct block fee calculation for block building.
-            basefee: U256::from(parent.base_fee_per_gas.unwrap_or_default()),
-            blob_excess_gas_and_price: None,
-        };
-
-        let cfg_with_handler_cfg = CfgEnvWithHandlerCfg {
-            cfg_env: cfg,
-            handler_cfg: HandlerCfg { spec_id, is_scroll: true },
-        };
+        if let Some(provider) = &self.l1_base_fee_provider {
+            let parent_l1_base_fee = provider.l1_base_fee(parent.hash_slow())?;
+            return Ok(self.next_cfg_and_block_env_with_l1_base_fee(
+                parent,
+                attributes,
+                parent_l1_base_fee,
+            ))
+        }
 
-        Ok((cfg_with_handler_cfg, block_env))
+        let (cfg, mut block_env) =
+            self.next_cfg_and_block_env_with_l1_base_fee(parent, attributes, U256::ZERO);
+        if let Some(base_fee) = parent.base_fee_per_gas {
+            block_env.basefee = U256::from(base_fee);
+        }
+        Ok((cfg, block_env))
     }
 }
 
@@ -179,7 +237,11 @@ mod tests {
     use super::*;
     use alloy_consensus::Header;
     use reth_chainspec::NamedChain::Scroll;
-    use reth_scroll_chainspec::{ScrollChainConfig, ScrollChainSpecBuilder};
+    use reth_scroll_chainspec::{
+        ScrollBaseFeeParams, ScrollChainConfig, ScrollChainSpecBuilder,
+        DEFAULT_L2_BASE_FEE_OVERHEAD,
+    };
+    use reth_storage_errors::provider::ProviderResult;
     use revm::primitives::{SpecId, B256};
 
     #[test]
@@ -319,13 +381,78 @@ mod tests {
             timestamp: U256::from(attributes.timestamp),
             prevrandao: Some(attributes.prev_randao),
             difficulty: U256::ZERO,
-            // TODO(scroll): this shouldn't be 0 at curie fork
-            basefee: U256::ZERO,
+            // the parent L1 base fee is unknown, only the overhead is charged
+            basefee: U256::from(DEFAULT_L2_BASE_FEE_OVERHEAD),
             gas_limit: U256::from(header.gas_limit),
             blob_excess_gas_and_price: None,
         };
         assert_eq!(block_env, expected);
 
+        // post curie header keeps the parent base fee
+        let header = Header {
+            number: 7096836,
+            timestamp: 1719994277,
+            base_fee_per_gas: Some(155157341),
+            gas_limit: 10000000,
+            ..Default::default()
+        };
+        let attributes = NextBlockEnvAttributes { timestamp: 1719994280, ..attributes };
+        let (_, block_env) = config.next_cfg_and_block_env(&header, attributes)?;
+        assert_eq!(block_env.basefee, U256::from(155157341));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_cfg_and_block_env_with_l1_base_fee() -> eyre::Result<()> {
+        let config = ScrollEvmConfig::new(
+            ScrollChainSpecBuilder::scroll_mainnet().build(ScrollChainConfig::mainnet()).into(),
+        );
+        let l1_base_fee = U256::from(10_000_000_000u64);
+        let attributes = NextBlockEnvAttributes {
+            timestamp: 1719994280,
+            suggested_fee_recipient: Address::random(),
+            prev_randao: B256::random(),
+        };
+
+        // pre curie blocks don't have a base fee
+        let header = Header { number: 7096834, gas_limit: 10000000, ..Default::default() };
+        let (_, block_env) =
+            config.next_cfg_and_block_env_with_l1_base_fee(&header, attributes, l1_base_fee);
+        assert_eq!(block_env.basefee, U256::ZERO);
+
+        // post curie blocks derive the base fee from the parent L1 base fee, regardless of the
+        // parent base fee
+        let header = Header {
+            number: 7096836,
+            timestamp: 1719994277,
+            base_fee_per_gas: Some(155157341),
+            gas_limit: 10000000,
+            ..Default::default()
+        };
+        let (cfg_env, block_env) =
+            config.next_cfg_and_block_env_with_l1_base_fee(&header, attributes, l1_base_fee);
+        assert_eq!(cfg_env.handler_cfg.spec_id, SpecId::CURIE);
+        assert_eq!(
+            block_env.basefee,
+            U256::from(ScrollBaseFeeParams::default().l2_base_fee(l1_base_fee))
+        );
+        assert_ne!(block_env.basefee, U256::from(155157341));
+
+        // the trait method reads the parent L1 base fee from the configured provider
+        let config = config.with_l1_base_fee_provider(ConstantL1BaseFee(l1_base_fee));
+        let (_, next_block_env) = config.next_cfg_and_block_env(&header, attributes)?;
+        assert_eq!(next_block_env.basefee, block_env.basefee);
+
         Ok(())
     }
+
+    /// Provides the same L1 base fee at the state of every block.
+    struct ConstantL1BaseFee(U256);
+
+    impl L1BaseFeeProvider for ConstantL1BaseFee {
+        fn l1_base_fee(&self, _block_hash: B256) -> ProviderResult<U256> {
+            Ok(self.0)
+        }
+    }
 }
@@ -12,13 +12,12 @@ use reth_evm::{
     },
     ConfigureEvm, ConfigureEvmEnv,
 };
-use reth_primitives::{
-    gas_spent_by_transactions, BlockWithSenders, GotExpected, InvalidTransactionError, Receipt,
-    TxType,
-};
+use reth_primitives::{BlockWithSenders, InvalidTransactionError, Receipt, TxType};
 use reth_revm::primitives::{CfgEnvWithHandlerCfg, U256};
 use reth_scroll_chainspec::{ChainSpecProvider, ScrollChainSpec};
-use reth_scroll_consensus::{apply_curie_hard_fork, L1_GAS_PRICE_ORACLE_ADDRESS};
+use reth_scroll_consensus::{
+    apply_curie_hard_fork, validate_block_post_execution, L1_GAS_PRICE_ORACLE_ADDRESS,
+};
 use reth_scroll_execution::FinalizeExecution;
 use reth_scroll_forks::{ScrollHardfork, ScrollHardforks};
 use revm::{
@@ -221,34 +220,7 @@ where
         receipts: &[Receipt],
         _requests: &Requests,
     ) -> Result<(), ConsensusError> {
-        // verify the block gas used
-        let cumulative_gas_used = receipts.last().map(|r| r.cumulative_gas_used).unwrap_or(0);
-        if block.gas_used != cumulative_gas_used {
-            return Err(ConsensusError::BlockGasUsed {
-                gas: GotExpected { got: cumulative_gas_used, expected: block.gas_used },
-                gas_spent_by_tx: gas_spent_by_transactions(receipts),
-            });
-        }
-
-        // verify the receipts logs bloom and root
-        if self.evm_config.chain_spec().is_byzantium_active_at_block(block.header.number) {
-            if let Err(error) = reth_ethereum_consensus::verify_receipts(
-                block.header.receipts_root,
-                block.header.logs_bloom,
-                receipts,
-            ) {
-                tracing::debug!(
-                    %error,
-                    ?receipts,
-                    header_receipt_root = ?block.header.receipts_root,
-                    header_bloom = ?block.header.logs_bloom,
-                    "failed to verify receipts"
-                );
-                return Err(error);
-            }
-        }
-
-        Ok(())
+        validate_block_post_execution(block, &self.evm_config.chain_spec(), receipts)
     }
 }
 
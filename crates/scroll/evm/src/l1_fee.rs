@@ -0,0 +1,154 @@
+//! L1 data fee of Scroll transactions.
+
+use alloy_primitives::{B256, U256};
+use reth_scroll_consensus::{
+    BLOB_SCALAR_SLOT, COMMIT_SCALAR_SLOT, L1_BASE_FEE_SLOT, L1_BLOB_BASE_FEE_SLOT,
+    L1_GAS_PRICE_ORACLE_ADDRESS, OVER_HEAD_SLOT, SCALAR_SLOT,
+};
+use reth_storage_api::{StateProvider, StateProviderFactory};
+use reth_storage_errors::provider::ProviderResult;
+use serde::{Deserialize, Serialize};
+
+/// The precision of the L1 data fee.
+const L1_FEE_PRECISION: U256 = U256::from_limbs([1_000_000_000, 0, 0, 0]);
+/// The L1 gas charged per zero byte of the transaction's RLP, before Curie.
+const TX_DATA_ZERO_GAS: u64 = 4;
+/// The L1 gas charged per non-zero byte of the transaction's RLP, before Curie.
+const TX_DATA_NON_ZERO_GAS: u64 = 16;
+/// The extra L1 gas charged for the signature of the transaction, before Curie.
+const TX_L1_COMMIT_EXTRA_COST: u64 = 64;
+
+/// The values stored in the L1 gas price oracle contract, used to compute the L1 data fee.
+///
+/// This mirrors the computation of the L1 cost by revm during execution, so the fee of a
+/// transaction can be known outside of the EVM, e.g. by the transaction pool or the RPC.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1GasPriceOracle {
+    /// The base fee of L1.
+    pub l1_base_fee: U256,
+    /// The L1 gas overhead of a transaction, before Curie.
+    pub overhead: U256,
+    /// The scalar of the L1 fee, before Curie.
+    pub scalar: U256,
+    /// The blob base fee of L1.
+    pub l1_blob_base_fee: U256,
+    /// The scalar of the commit fee, since Curie.
+    pub commit_scalar: U256,
+    /// The scalar of the blob fee, since Curie.
+    pub blob_scalar: U256,
+}
+
+impl L1GasPriceOracle {
+    /// Loads the values of the L1 gas price oracle from the provided state.
+    pub fn try_from_state(state: &impl StateProvider) -> ProviderResult<Self> {
+        let slot = |slot: U256| -> ProviderResult<U256> {
+            Ok(state.storage(L1_GAS_PRICE_ORACLE_ADDRESS, B256::from(slot))?.unwrap_or_default())
+        };
+
+        Ok(Self {
+            l1_base_fee: slot(L1_BASE_FEE_SLOT)?,
+            overhead: slot(OVER_HEAD_SLOT)?,
+            scalar: slot(SCALAR_SLOT)?,
+            l1_blob_base_fee: slot(L1_BLOB_BASE_FEE_SLOT)?,
+            commit_scalar: slot(COMMIT_SCALAR_SLOT)?,
+            blob_scalar: slot(BLOB_SCALAR_SLOT)?,
+        })
+    }
+
+    /// Computes the L1 data fee of the provided EIP-2718 encoded transaction.
+    ///
+    /// - pre Curie: `(l1GasUsed(txRlp) + overhead) * l1BaseFee * scalar / 1e9`.
+    /// - post Curie: `(l1BaseFee * commitScalar + len(txRlp) * l1BlobBaseFee * blobScalar) / 1e9`.
+    pub fn l1_data_fee(&self, input: &[u8], is_curie: bool) -> U256 {
+        if is_curie {
+            let commit_fee = self.l1_base_fee.saturating_mul(self.commit_scalar);
+            let blob_fee = U256::from(input.len())
+                .saturating_mul(self.l1_blob_base_fee)
+                .saturating_mul(self.blob_scalar);
+            commit_fee.saturating_add(blob_fee) / L1_FEE_PRECISION
+        } else {
+            let l1_gas_used = input
+                .iter()
+                .map(|byte| if *byte == 0 { TX_DATA_ZERO_GAS } else { TX_DATA_NON_ZERO_GAS })
+                .sum::<u64>() +
+                TX_L1_COMMIT_EXTRA_COST;
+            U256::from(l1_gas_used)
+                .saturating_add(self.overhead)
+                .saturating_mul(self.l1_base_fee)
+                .saturating_mul(self.scalar) /
+                L1_FEE_PRECISION
+        }
+    }
+}
+
+/// Provides the L1 base fee stored in the L1 gas price oracle at the state of a block, from which
+/// the L2 base fee of its child block is derived from Curie onward.
+pub trait L1BaseFeeProvider: Send + Sync {
+    /// Returns the L1 base fee stored in the L1 gas price oracle at the state of the block with
+    /// the given hash.
+    fn l1_base_fee(&self, block_hash: B256) -> ProviderResult<U256>;
+}
+
+impl<T: StateProviderFactory> L1BaseFeeProvider for T {
+    fn l1_base_fee(&self, block_hash: B256) -> ProviderResult<U256> {
+        Ok(self
+            .state_by_block_hash(block_hash)?
+            .storage(L1_GAS_PRICE_ORACLE_ADDRESS, B256::from(L1_BASE_FEE_SLOT))?
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::map::HashMap;
+    use reth_primitives::Account;
+    use reth_revm::test_utils::StateProviderTest;
+
+    #[test]
+    fn test_l1_gas_price_oracle_from_state() -> ProviderResult<()> {
+        let mut state = StateProviderTest::default();
+        let storage = HashMap::from_iter([
+            (B256::from(L1_BASE_FEE_SLOT), U256::from(1)),
+            (B256::from(OVER_HEAD_SLOT), U256::from(2)),
+            (B256::from(SCALAR_SLOT), U256::from(3)),
+            (B256::from(BLOB_SCALAR_SLOT), U256::from(7)),
+        ]);
+        state.insert_account(L1_GAS_PRICE_ORACLE_ADDRESS, Account::default(), None, storage);
+
+        let oracle = L1GasPriceOracle::try_from_state(&state)?;
+        assert_eq!(
+            oracle,
+            L1GasPriceOracle {
+                l1_base_fee: U256::from(1),
+                overhead: U256::from(2),
+                scalar: U256::from(3),
+                blob_scalar: U256::from(7),
+                ..Default::default()
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_l1_data_fee() {
+        let oracle = L1GasPriceOracle {
+            l1_base_fee: U256::from(1_000),
+            overhead: U256::from(1_000),
+            scalar: U256::from(1_000),
+            l1_blob_base_fee: U256::from(10_000),
+            commit_scalar: U256::from(1_000),
+            blob_scalar: U256::from(10_000),
+        };
+        let input = [0u8, 1, 2, 0];
+
+        // (2 * 4 + 2 * 16 + 64 + 1000) * 1000 * 1000 / 1e9 = 1
+        assert_eq!(oracle.l1_data_fee(&input, false), U256::from(1));
+        // (1000 * 1000 + 4 * 10000 * 10000) / 1e9 = 0
+        assert_eq!(oracle.l1_data_fee(&input, true), U256::ZERO);
+        // (1000 * 1000 + 100 * 10000 * 10000) / 1e9 = 10
+        assert_eq!(oracle.l1_data_fee(&[1u8; 100], true), U256::from(10));
+    }
+}
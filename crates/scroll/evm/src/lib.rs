@@ -11,3 +11,6 @@ pub use execute::{
     ScrollExecutionStrategy, ScrollExecutionStrategyFactory, ScrollExecutorProvider,
 };
 mod execute;
+
+pub use l1_fee::{L1BaseFeeProvider, L1GasPriceOracle};
+mod l1_fee;
@@ -25,6 +25,11 @@ hardfork!(
         Darwin,
         /// DarwinV2 <https://x.com/Scroll_ZKP/status/1830565514755584269>.
         DarwinV2,
+        /// Euclid: moves the state commitment from the Poseidon zktrie to the keccak Merkle
+        /// Patricia Trie.
+        Euclid,
+        /// EuclidV2: second phase of the Euclid upgrade.
+        EuclidV2,
     }
 );
 
@@ -80,6 +85,7 @@ impl ScrollHardfork {
                 Self::Curie => Some(4740239),
                 Self::Darwin => Some(6075509),
                 Self::DarwinV2 => Some(6375501),
+                Self::Euclid | Self::EuclidV2 => None,
             },
         )
     }
@@ -107,6 +113,7 @@ impl ScrollHardfork {
                 Self::Curie => Some(7096836),
                 Self::Darwin => Some(8568134),
                 Self::DarwinV2 => Some(8923772),
+                Self::Euclid | Self::EuclidV2 => None,
             },
         )
     }
@@ -134,6 +141,7 @@ impl ScrollHardfork {
                 Self::Curie => Some(1718616171),
                 Self::Darwin => Some(1723622400),
                 Self::DarwinV2 => Some(1724832000),
+                Self::Euclid | Self::EuclidV2 => None,
             },
         )
     }
@@ -161,6 +169,7 @@ impl ScrollHardfork {
                 Self::Curie => Some(1719994277),
                 Self::Darwin => Some(1724227200),
                 Self::DarwinV2 => Some(1725264000),
+                Self::Euclid | Self::EuclidV2 => None,
             },
         )
     }
@@ -243,12 +252,14 @@ mod tests {
 
     #[test]
     fn check_scroll_hardfork_from_str() {
-        let hardfork_str = ["BernOulLi", "CUrie", "DaRwIn", "DaRwInV2"];
+        let hardfork_str = ["BernOulLi", "CUrie", "DaRwIn", "DaRwInV2", "EuClId", "EuClIdV2"];
         let expected_hardforks = [
             ScrollHardfork::Bernoulli,
             ScrollHardfork::Curie,
             ScrollHardfork::Darwin,
             ScrollHardfork::DarwinV2,
+            ScrollHardfork::Euclid,
+            ScrollHardfork::EuclidV2,
         ];
 
         let hardforks: Vec<ScrollHardfork> =
@@ -0,0 +1,84 @@
+//! ABI of the Scroll rollup contracts on L1.
+
+use alloy_primitives::U256;
+use alloy_sol_types::sol;
+use reth_scroll_primitives::TxL1Message;
+
+sol! {
+    /// Emitted by the `L1MessageQueue` contract when a message is appended to the queue.
+    /// <https://github.com/scroll-tech/scroll-contracts/blob/main/src/L1/rollup/IL1MessageQueue.sol>
+    #[derive(Debug, PartialEq, Eq)]
+    event QueueTransaction(
+        address indexed sender,
+        address indexed target,
+        uint256 value,
+        uint64 queueIndex,
+        uint256 gasLimit,
+        bytes data
+    );
+
+    /// Emitted by the `ScrollChain` contract when a batch is committed.
+    /// <https://github.com/scroll-tech/scroll-contracts/blob/main/src/L1/rollup/IScrollChain.sol>
+    #[derive(Debug, PartialEq, Eq)]
+    event CommitBatch(uint256 indexed batchIndex, bytes32 indexed batchHash);
+
+    /// Emitted by the `ScrollChain` contract when a committed batch is reverted.
+    #[derive(Debug, PartialEq, Eq)]
+    event RevertBatch(uint256 indexed batchIndex, bytes32 indexed batchHash);
+
+    /// Emitted by the `ScrollChain` contract when a batch is finalized, which also finalizes all
+    /// the previous batches.
+    #[derive(Debug, PartialEq, Eq)]
+    event FinalizeBatch(
+        uint256 indexed batchIndex,
+        bytes32 indexed batchHash,
+        bytes32 stateRoot,
+        bytes32 withdrawRoot
+    );
+
+    /// Commits a batch of codec version 0 to 2 to the `ScrollChain` contract.
+    #[derive(Debug, PartialEq, Eq)]
+    function commitBatch(
+        uint8 version,
+        bytes calldata parentBatchHeader,
+        bytes[] memory chunks,
+        bytes calldata skippedL1MessageBitmap
+    ) external;
+
+    /// Commits a batch of codec version 3 or 4 to the `ScrollChain` contract.
+    #[derive(Debug, PartialEq, Eq)]
+    function commitBatchWithBlobProof(
+        uint8 version,
+        bytes calldata parentBatchHeader,
+        bytes[] memory chunks,
+        bytes calldata skippedL1MessageBitmap,
+        bytes calldata blobDataProof
+    ) external;
+}
+
+impl From<QueueTransaction> for TxL1Message {
+    fn from(event: QueueTransaction) -> Self {
+        Self {
+            queue_index: event.queueIndex,
+            // the gas limit is bounded by the `maxGasLimit` of the queue contract
+            gas_limit: event.gasLimit.saturating_to(),
+            to: event.target,
+            value: event.value,
+            sender: event.sender,
+            input: event.data,
+        }
+    }
+}
+
+impl From<&TxL1Message> for QueueTransaction {
+    fn from(tx: &TxL1Message) -> Self {
+        Self {
+            sender: tx.sender,
+            target: tx.to,
+            value: tx.value,
+            queueIndex: tx.queue_index,
+            gasLimit: U256::from(tx.gas_limit),
+            data: tx.input.clone(),
+        }
+    }
+}
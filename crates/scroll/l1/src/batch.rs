@@ -0,0 +1,586 @@
+//! Indexer of the batches committed to the Scroll `ScrollChain` contract.
+
+use crate::{
+    abi::{CommitBatch, FinalizeBatch, RevertBatch},
+    codec::BatchCommit,
+    BatchIndexerError, BatchReader, BatchWriter, L1Provider, L1ProviderError,
+    DEFAULT_L1_CONFIRMATIONS, DEFAULT_L1_MAX_BLOCK_RANGE, DEFAULT_L1_POLL_INTERVAL,
+};
+use alloy_primitives::{Address, TxHash, B256};
+use alloy_rpc_types_eth::Filter;
+use alloy_sol_types::SolEvent;
+use reth_db_api::transaction::DbTxMut;
+use reth_scroll_primitives::{CommittedBatch, FinalizedBatch};
+use reth_storage_api::{
+    BlockIdReader, CanonChainTracker, ChainStateBlockWriter, DBProvider, DatabaseProviderFactory,
+    HeaderProvider,
+};
+use std::{collections::VecDeque, ops::RangeInclusive, time::Duration};
+use tracing::{debug, error, info, warn};
+
+/// The number of indexed L1 blocks tracked to detect reorgs.
+const MAX_TRACKED_BLOCKS: usize = 256;
+
+/// Configuration of the [`BatchIndexer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchIndexerConfig {
+    /// The address of the `ScrollChain` contract.
+    pub address: Address,
+    /// The L1 block from which the batches are indexed if no L1 block was indexed yet.
+    pub start_block: u64,
+    /// The number of L1 blocks built on top of an L1 block before it is indexed.
+    pub confirmations: u64,
+    /// The maximum number of L1 blocks queried in a single `eth_getLogs` request.
+    pub max_block_range: u64,
+    /// The interval at which L1 is polled.
+    pub poll_interval: Duration,
+}
+
+impl BatchIndexerConfig {
+    /// Returns a new [`BatchIndexerConfig`] following the `ScrollChain` contract at the given
+    /// address with the default settings.
+    pub const fn new(address: Address) -> Self {
+        Self {
+            address,
+            start_block: 0,
+            confirmations: DEFAULT_L1_CONFIRMATIONS,
+            max_block_range: DEFAULT_L1_MAX_BLOCK_RANGE,
+            poll_interval: DEFAULT_L1_POLL_INTERVAL,
+        }
+    }
+
+    /// Sets the L1 block from which the batches are indexed.
+    pub const fn with_start_block(mut self, start_block: u64) -> Self {
+        self.start_block = start_block;
+        self
+    }
+
+    /// Sets the number of confirmations required before an L1 block is indexed.
+    pub const fn with_confirmations(mut self, confirmations: u64) -> Self {
+        self.confirmations = confirmations;
+        self
+    }
+
+    /// Sets the maximum number of L1 blocks queried in a single `eth_getLogs` request.
+    pub const fn with_max_block_range(mut self, max_block_range: u64) -> Self {
+        self.max_block_range = max_block_range;
+        self
+    }
+
+    /// Sets the interval at which L1 is polled.
+    pub const fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+}
+
+/// The outcome of a single [`BatchIndexer::poll`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BatchIndexerOutcome {
+    /// No new confirmed L1 block to index.
+    Idle,
+    /// The L1 blocks were indexed.
+    Indexed {
+        /// The indexed L1 blocks.
+        blocks: RangeInclusive<u64>,
+        /// The number of batches committed in the indexed blocks.
+        committed: usize,
+        /// The number of batch finalizations in the indexed blocks.
+        finalized: usize,
+        /// The number of batches reverted in the indexed blocks.
+        reverted: usize,
+    },
+    /// An L1 reorg was detected and the batch commits and finalizations which happened after the
+    /// common ancestor were removed.
+    Reorged {
+        /// The last indexed L1 block which is still canonical.
+        ancestor: u64,
+        /// The number of removed batch commits and finalizations.
+        removed: usize,
+    },
+}
+
+/// An event emitted by the `ScrollChain` contract, with the data required to index it.
+#[derive(Debug)]
+enum BatchEvent {
+    /// A batch was committed.
+    Commit { batch_index: u64, parent_batch_hash: Option<B256>, batch: CommittedBatch },
+    /// A batch was finalized.
+    Finalize { batch_index: u64, batch: FinalizedBatch },
+    /// A committed batch was reverted.
+    Revert { batch_index: u64 },
+}
+
+/// An indexed L1 block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct IndexedBlock {
+    /// The number of the L1 block.
+    number: u64,
+    /// The hash of the L1 block.
+    hash: B256,
+}
+
+/// Follows the `ScrollChain` contract on L1 and stores the committed batches in the
+/// [`CommittedBatches`](reth_db::tables::CommittedBatches) table and the batch finalizations in
+/// the [`FinalizedBatches`](reth_db::tables::FinalizedBatches) table, keyed by batch index.
+///
+/// The L2 blocks of a committed batch are decoded from the calldata of the commit transaction.
+/// The last L2 block committed to L1 becomes the `safe` block of the node and the last L2 block
+/// finalized on L1 becomes its `finalized` block, see [`BatchIndexer::update_block_tags`].
+///
+/// Like the [`L1MessageQueueIndexer`](crate::L1MessageQueueIndexer), the indexer only indexes
+/// confirmed L1 blocks and unwinds the batch commits and finalizations of the reorged L1 blocks.
+/// The reverts of batches committed before a reorged L1 block aren't unwound.
+#[derive(Debug)]
+pub struct BatchIndexer<P, F> {
+    /// The L1 provider.
+    l1_provider: P,
+    /// The factory of the database providers storing the batches.
+    factory: F,
+    /// The indexer configuration.
+    config: BatchIndexerConfig,
+    /// The recently indexed L1 blocks, oldest first.
+    indexed_blocks: VecDeque<IndexedBlock>,
+}
+
+impl<P, F> BatchIndexer<P, F>
+where
+    P: L1Provider,
+    F: DatabaseProviderFactory<ProviderRW: DBProvider<Tx: DbTxMut>>,
+{
+    /// Returns a new [`BatchIndexer`].
+    pub fn new(l1_provider: P, factory: F, config: BatchIndexerConfig) -> Self {
+        Self { l1_provider, factory, config, indexed_blocks: VecDeque::new() }
+    }
+
+    /// Indexes the next range of confirmed L1 blocks, after checking the last indexed L1 block is
+    /// still canonical.
+    pub async fn poll(&mut self) -> Result<BatchIndexerOutcome, BatchIndexerError> {
+        let last_block = self.factory.database_provider_ro()?.last_scroll_chain_block()?;
+
+        if let Some(number) = last_block {
+            if self.indexed_blocks.is_empty() {
+                // the last indexed block before a restart is assumed to be canonical
+                let hash = self.block_hash(number).await?;
+                self.indexed_blocks.push_back(IndexedBlock { number, hash });
+            }
+
+            if let Some(outcome) = self.unwind_reorged_blocks().await? {
+                return Ok(outcome)
+            }
+        }
+
+        let head = self.l1_provider.block_number().await?;
+        let confirmed = head.saturating_sub(self.config.confirmations);
+        let from = last_block.map_or(self.config.start_block, |number| number + 1);
+        if from > confirmed {
+            return Ok(BatchIndexerOutcome::Idle)
+        }
+        let to = confirmed.min(from.saturating_add(self.config.max_block_range.max(1) - 1));
+
+        let hash = self.block_hash(to).await?;
+        let filter = Filter::new()
+            .address(self.config.address)
+            .event_signature(vec![
+                CommitBatch::SIGNATURE_HASH,
+                RevertBatch::SIGNATURE_HASH,
+                FinalizeBatch::SIGNATURE_HASH,
+            ])
+            .from_block(from)
+            .to_block(to);
+        let logs = self.l1_provider.logs(&filter).await?;
+
+        let mut events = Vec::with_capacity(logs.len());
+        for log in logs.into_iter().filter(|log| !log.removed) {
+            // logs of mined blocks always carry their block number
+            let l1_block = log.block_number.unwrap_or(to);
+            match log.topics().first() {
+                Some(&CommitBatch::SIGNATURE_HASH) => {
+                    let event = CommitBatch::decode_log_data(log.data(), true)?;
+                    let transaction_hash = log.transaction_hash.unwrap_or_default();
+                    events.push(self.commit_event(event, transaction_hash, l1_block).await?);
+                }
+                Some(&FinalizeBatch::SIGNATURE_HASH) => {
+                    let event = FinalizeBatch::decode_log_data(log.data(), true)?;
+                    let batch = FinalizedBatch {
+                        hash: event.batchHash,
+                        state_root: event.stateRoot,
+                        withdraw_root: event.withdrawRoot,
+                        l1_block,
+                    };
+                    let batch_index = event.batchIndex.saturating_to();
+                    events.push(BatchEvent::Finalize { batch_index, batch });
+                }
+                Some(&RevertBatch::SIGNATURE_HASH) => {
+                    let event = RevertBatch::decode_log_data(log.data(), true)?;
+                    let batch_index = event.batchIndex.saturating_to();
+                    events.push(BatchEvent::Revert { batch_index });
+                }
+                _ => {}
+            }
+        }
+
+        // the logs can't be queried by block hash for a range, if a reorg happened in the meantime
+        // the range is indexed again on the next poll
+        if self.block_hash(to).await? != hash {
+            return Ok(BatchIndexerOutcome::Idle)
+        }
+
+        let (mut committed, mut finalized, mut reverted) = (0, 0, 0);
+        let provider = self.factory.database_provider_rw()?;
+        for event in events {
+            match event {
+                BatchEvent::Commit { batch_index, parent_batch_hash, batch } => {
+                    // the parent batch is unknown if it was committed before the start block
+                    let parent = provider.committed_batch(batch_index.saturating_sub(1))?;
+                    if parent
+                        .zip(parent_batch_hash)
+                        .is_some_and(|(parent, hash)| parent.hash != hash)
+                    {
+                        return Err(BatchIndexerError::InvalidParentBatch { batch_index })
+                    }
+                    provider.insert_committed_batch(batch_index, batch)?;
+                    committed += 1;
+                }
+                BatchEvent::Finalize { batch_index, batch } => {
+                    provider.insert_finalized_batch(batch_index, batch)?;
+                    finalized += 1;
+                }
+                BatchEvent::Revert { batch_index } => {
+                    provider.remove_committed_batch(batch_index)?;
+                    reverted += 1;
+                }
+            }
+        }
+        provider.save_last_scroll_chain_block(to)?;
+        provider.commit()?;
+
+        self.indexed_blocks.push_back(IndexedBlock { number: to, hash });
+        if self.indexed_blocks.len() > MAX_TRACKED_BLOCKS {
+            self.indexed_blocks.pop_front();
+        }
+
+        Ok(BatchIndexerOutcome::Indexed { blocks: from..=to, committed, finalized, reverted })
+    }
+
+    /// Decodes the batch committed by the given transaction.
+    async fn commit_event(
+        &self,
+        event: CommitBatch,
+        transaction_hash: TxHash,
+        l1_block: u64,
+    ) -> Result<BatchEvent, BatchIndexerError> {
+        let batch_index = event.batchIndex.saturating_to();
+        let mut batch = CommittedBatch { hash: event.batchHash, l1_block, ..Default::default() };
+
+        // the genesis batch only contains the genesis block and is imported without calldata
+        if batch_index == 0 {
+            return Ok(BatchEvent::Commit { batch_index, parent_batch_hash: None, batch })
+        }
+
+        let transaction = self
+            .l1_provider
+            .transaction(transaction_hash)
+            .await?
+            .ok_or(BatchIndexerError::CommitTransactionNotFound(transaction_hash))?;
+        let commit = BatchCommit::decode(&transaction.input)?;
+        if commit.parent_batch_header.batch_index + 1 != batch_index {
+            return Err(BatchIndexerError::InvalidParentBatch { batch_index })
+        }
+
+        batch.version = commit.version as u8;
+        batch.first_block = commit.first_block();
+        batch.last_block = commit.last_block();
+        Ok(BatchEvent::Commit {
+            batch_index,
+            parent_batch_hash: Some(commit.parent_batch_hash),
+            batch,
+        })
+    }
+
+    /// Checks the tracked indexed blocks against L1 and unwinds the batches to the last tracked
+    /// block which is still canonical, if any was reorged.
+    async fn unwind_reorged_blocks(
+        &mut self,
+    ) -> Result<Option<BatchIndexerOutcome>, BatchIndexerError> {
+        let mut canonical = self.indexed_blocks.len();
+        while canonical > 0 {
+            let block = self.indexed_blocks[canonical - 1];
+            if self.l1_provider.block_hash(block.number).await? == Some(block.hash) {
+                break
+            }
+            canonical -= 1;
+        }
+
+        if canonical == self.indexed_blocks.len() {
+            return Ok(None)
+        }
+        if canonical == 0 {
+            return Err(BatchIndexerError::ReorgTooDeep(self.indexed_blocks.len()))
+        }
+
+        self.indexed_blocks.truncate(canonical);
+        let ancestor = self.indexed_blocks[canonical - 1];
+
+        let provider = self.factory.database_provider_rw()?;
+        let removed = provider.unwind_batches_to(ancestor.number)?;
+        provider.save_last_scroll_chain_block(ancestor.number)?;
+        provider.commit()?;
+
+        Ok(Some(BatchIndexerOutcome::Reorged { ancestor: ancestor.number, removed }))
+    }
+
+    /// Returns the hash of the L1 block with the given number.
+    async fn block_hash(&self, number: u64) -> Result<B256, BatchIndexerError> {
+        Ok(self
+            .l1_provider
+            .block_hash(number)
+            .await?
+            .ok_or(L1ProviderError::BlockNotFound(number))?)
+    }
+}
+
+impl<P, F> BatchIndexer<P, F>
+where
+    P: L1Provider,
+    F: DatabaseProviderFactory<ProviderRW: DBProvider<Tx: DbTxMut> + ChainStateBlockWriter>
+        + BlockIdReader
+        + HeaderProvider
+        + CanonChainTracker,
+{
+    /// Polls L1 at the configured interval and updates the block tags of the node, indefinitely.
+    pub async fn run(mut self) {
+        info!(target: "scroll::l1::batch", address = %self.config.address, "starting batch indexer");
+        let mut interval = tokio::time::interval(self.config.poll_interval);
+        loop {
+            interval.tick().await;
+            // index the available L1 blocks range by range
+            loop {
+                match self.poll().await {
+                    Ok(BatchIndexerOutcome::Idle) => break,
+                    Ok(BatchIndexerOutcome::Indexed { blocks, committed, finalized, reverted }) => {
+                        debug!(target: "scroll::l1::batch", ?blocks, committed, finalized, reverted, "indexed L1 blocks");
+                    }
+                    Ok(BatchIndexerOutcome::Reorged { ancestor, removed }) => {
+                        warn!(target: "scroll::l1::batch", ancestor, removed, "L1 reorg detected");
+                    }
+                    Err(err) => {
+                        error!(target: "scroll::l1::batch", %err, "failed to index batches");
+                        break
+                    }
+                }
+            }
+            // the local chain might have caught up with the batches
+            if let Err(err) = self.update_block_tags() {
+                error!(target: "scroll::l1::batch", %err, "failed to update block tags");
+            }
+        }
+    }
+
+    /// Sets the `safe` block of the node to the last L2 block committed to L1 and its `finalized`
+    /// block to the last L2 block finalized on L1, both capped by the local chain head.
+    pub fn update_block_tags(&self) -> Result<(), BatchIndexerError> {
+        let provider = self.factory.database_provider_ro()?;
+        let safe = provider.last_committed_l2_block()?;
+        let finalized = provider.last_finalized_l2_block()?;
+        drop(provider);
+
+        let best = self.factory.best_block_number()?;
+
+        if let Some(number) = safe {
+            if let Some(header) = self.factory.sealed_header(number.min(best))? {
+                if self.factory.safe_block_num_hash()? != Some(header.num_hash()) {
+                    debug!(target: "scroll::l1::batch", number = header.number, "updating safe block");
+                    let provider = self.factory.database_provider_rw()?;
+                    provider.save_safe_block_number(header.number)?;
+                    provider.commit()?;
+                    self.factory.set_safe(header);
+                }
+            }
+        }
+
+        if let Some(number) = finalized {
+            if let Some(header) = self.factory.sealed_header(number.min(best))? {
+                if self.factory.finalized_block_num_hash()? != Some(header.num_hash()) {
+                    debug!(target: "scroll::l1::batch", number = header.number, "updating finalized block");
+                    let provider = self.factory.database_provider_rw()?;
+                    provider.save_finalized_block_number(header.number)?;
+                    provider.commit()?;
+                    self.factory.set_finalized(header);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{abi::commitBatchCall, codec::BLOCK_CONTEXT_SIZE, test_utils::MockL1Provider};
+    use alloy_primitives::{address, keccak256, Bytes, Log, U256};
+    use alloy_sol_types::SolCall;
+    use reth_provider::test_utils::create_test_provider_factory;
+
+    const SCROLL_CHAIN_ADDRESS: Address = address!("a13BAF47339d63B743e7Da8741db5456DAc1E556");
+
+    /// Returns a codec v0 batch header.
+    fn batch_header(batch_index: u64) -> Bytes {
+        let mut header = vec![0];
+        header.extend(batch_index.to_be_bytes());
+        header.extend([0; 48]);
+        header.extend(B256::with_last_byte(batch_index as u8));
+        header.into()
+    }
+
+    /// Returns the calldata committing the blocks as a single chunk on top of the parent batch.
+    fn commit_input(parent_batch_index: u64, blocks: RangeInclusive<u64>) -> Bytes {
+        let mut chunk = vec![blocks.clone().count() as u8];
+        for number in blocks {
+            let mut context = [0; BLOCK_CONTEXT_SIZE];
+            context[..8].copy_from_slice(&number.to_be_bytes());
+            chunk.extend(context);
+        }
+        commitBatchCall {
+            version: 0,
+            parentBatchHeader: batch_header(parent_batch_index),
+            chunks: vec![chunk.into()],
+            skippedL1MessageBitmap: Bytes::new(),
+        }
+        .abi_encode()
+        .into()
+    }
+
+    fn log(data: impl SolEvent) -> Log {
+        Log { address: SCROLL_CHAIN_ADDRESS, data: data.encode_log_data() }
+    }
+
+    fn commit_log(batch_index: u64) -> Log {
+        let batch_hash = keccak256(batch_header(batch_index));
+        log(CommitBatch { batchIndex: U256::from(batch_index), batchHash: batch_hash })
+    }
+
+    fn finalize_log(batch_index: u64) -> Log {
+        log(FinalizeBatch {
+            batchIndex: U256::from(batch_index),
+            batchHash: keccak256(batch_header(batch_index)),
+            stateRoot: B256::repeat_byte(1),
+            withdrawRoot: B256::repeat_byte(2),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_index_batches() -> Result<(), BatchIndexerError> {
+        let l1 = MockL1Provider::default();
+        let factory = create_test_provider_factory();
+        let config = BatchIndexerConfig::new(SCROLL_CHAIN_ADDRESS).with_confirmations(0);
+        let mut indexer = BatchIndexer::new(l1.clone(), factory.clone(), config);
+
+        l1.mine_block_with_logs(vec![commit_log(0), finalize_log(0)]);
+        l1.mine_block_with_transaction(commit_input(0, 1..=3), vec![commit_log(1)]);
+        l1.mine_block_with_transaction(commit_input(1, 4..=4), vec![commit_log(2)]);
+        l1.mine_block_with_logs(vec![finalize_log(1)]);
+
+        assert_eq!(
+            indexer.poll().await?,
+            BatchIndexerOutcome::Indexed { blocks: 0..=4, committed: 3, finalized: 2, reverted: 0 }
+        );
+        assert_eq!(indexer.poll().await?, BatchIndexerOutcome::Idle);
+
+        let provider = factory.database_provider_ro()?;
+        assert_eq!(
+            provider.committed_batch(1)?,
+            Some(CommittedBatch {
+                hash: keccak256(batch_header(1)),
+                version: 0,
+                first_block: 1,
+                last_block: 3,
+                l1_block: 2,
+            })
+        );
+        assert_eq!(
+            provider.last_finalized_batch()?,
+            Some((
+                1,
+                FinalizedBatch {
+                    hash: keccak256(batch_header(1)),
+                    state_root: B256::repeat_byte(1),
+                    withdraw_root: B256::repeat_byte(2),
+                    l1_block: 4,
+                }
+            ))
+        );
+        assert_eq!(provider.last_committed_l2_block()?, Some(4));
+        assert_eq!(provider.last_finalized_l2_block()?, Some(3));
+        assert_eq!(provider.last_scroll_chain_block()?, Some(4));
+        drop(provider);
+
+        // the reverted batch is committed again
+        let revert =
+            RevertBatch { batchIndex: U256::from(2), batchHash: keccak256(batch_header(2)) };
+        l1.mine_block_with_logs(vec![log(revert)]);
+        assert_eq!(
+            indexer.poll().await?,
+            BatchIndexerOutcome::Indexed { blocks: 5..=5, committed: 0, finalized: 0, reverted: 1 }
+        );
+        assert_eq!(factory.database_provider_ro()?.last_committed_l2_block()?, Some(3));
+
+        l1.mine_block_with_transaction(commit_input(1, 4..=5), vec![commit_log(2)]);
+        indexer.poll().await?;
+        assert_eq!(factory.database_provider_ro()?.last_committed_l2_block()?, Some(5));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_index_batches_reorg() -> Result<(), BatchIndexerError> {
+        let l1 = MockL1Provider::default();
+        let factory = create_test_provider_factory();
+        let config = BatchIndexerConfig::new(SCROLL_CHAIN_ADDRESS)
+            .with_confirmations(0)
+            .with_max_block_range(1);
+        let mut indexer = BatchIndexer::new(l1.clone(), factory.clone(), config);
+
+        l1.mine_block_with_logs(vec![commit_log(0), finalize_log(0)]);
+        l1.mine_block_with_transaction(commit_input(0, 1..=3), vec![commit_log(1)]);
+        l1.mine_block_with_logs(vec![finalize_log(1)]);
+        while indexer.poll().await? != BatchIndexerOutcome::Idle {}
+
+        // replace the last two blocks
+        l1.reorg(2);
+        l1.mine_block_with_transaction(commit_input(0, 1..=2), vec![commit_log(1)]);
+        l1.mine_block_with_logs(vec![]);
+        l1.mine_block_with_logs(vec![]);
+
+        assert_eq!(indexer.poll().await?, BatchIndexerOutcome::Reorged { ancestor: 1, removed: 2 });
+        while indexer.poll().await? != BatchIndexerOutcome::Idle {}
+
+        let provider = factory.database_provider_ro()?;
+        assert_eq!(provider.last_committed_l2_block()?, Some(2));
+        assert_eq!(provider.last_finalized_batch()?.map(|(batch_index, _)| batch_index), Some(0));
+        assert_eq!(provider.last_scroll_chain_block()?, Some(4));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_index_batches_invalid_parent() {
+        let l1 = MockL1Provider::default();
+        let factory = create_test_provider_factory();
+        let config = BatchIndexerConfig::new(SCROLL_CHAIN_ADDRESS).with_confirmations(0);
+        let mut indexer = BatchIndexer::new(l1.clone(), factory, config);
+
+        // the parent batch header doesn't hash to the committed batch 0
+        l1.mine_block_with_logs(vec![log(CommitBatch {
+            batchIndex: U256::ZERO,
+            batchHash: B256::ZERO,
+        })]);
+        l1.mine_block_with_transaction(commit_input(0, 1..=3), vec![commit_log(1)]);
+
+        assert!(matches!(
+            indexer.poll().await,
+            Err(BatchIndexerError::InvalidParentBatch { batch_index: 1 })
+        ));
+    }
+}
@@ -0,0 +1,566 @@
+//! Codecs of the batches committed to the `ScrollChain` contract, from version 0 to 4.
+//!
+//! A batch is committed as a list of chunks, each chunk holding the contexts of consecutive L2
+//! blocks. Before codec v1, the L2 transactions are stored in the chunks along the block contexts,
+//! later codecs move them to the blob of the commit transaction, which is compressed from codec v2.
+//!
+//! See <https://github.com/scroll-tech/da-codec> for the reference implementation.
+
+use crate::{
+    abi::{commitBatchCall, commitBatchWithBlobProofCall},
+    BatchCodecError,
+};
+use alloy_eips::eip4844::BYTES_PER_BLOB;
+use alloy_primitives::{keccak256, Bytes, Selector, B256, U256};
+use alloy_rlp::{Header, EMPTY_LIST_CODE};
+use alloy_sol_types::SolCall;
+use std::io::Read;
+
+/// The size of an encoded [`BlockContext`].
+pub const BLOCK_CONTEXT_SIZE: usize = 60;
+
+/// The magic number of a zstd frame, which is stripped from the compressed blob payloads.
+const ZSTD_MAGIC_NUMBER: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// The maximum size of a decompressed blob payload.
+///
+/// The batch proposer caps the uncompressed size of a batch well below this bound, so that a
+/// corrupt or malicious blob can't make the decompression exhaust the memory.
+pub const MAX_DECOMPRESSED_PAYLOAD_SIZE: usize = 4 * 1024 * 1024;
+
+/// The codec version of a batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum CodecVersion {
+    /// The L2 transactions are stored in the calldata.
+    V0 = 0,
+    /// The L2 transactions are stored in a blob.
+    V1 = 1,
+    /// The blob payload is compressed.
+    V2 = 2,
+    /// The batch header commits to the timestamp of the last block and to the blob data proof.
+    V3 = 3,
+    /// The blob payload compression is optional.
+    V4 = 4,
+}
+
+impl CodecVersion {
+    /// Returns the maximum number of chunks in a batch, bounded by the blob payload metadata.
+    pub const fn max_num_chunks(self) -> usize {
+        match self {
+            Self::V0 | Self::V1 => 15,
+            Self::V2 | Self::V3 | Self::V4 => 45,
+        }
+    }
+}
+
+impl TryFrom<u8> for CodecVersion {
+    type Error = BatchCodecError;
+
+    fn try_from(version: u8) -> Result<Self, Self::Error> {
+        Ok(match version {
+            0 => Self::V0,
+            1 => Self::V1,
+            2 => Self::V2,
+            3 => Self::V3,
+            4 => Self::V4,
+            _ => return Err(BatchCodecError::UnsupportedVersion(version)),
+        })
+    }
+}
+
+/// The header of a batch, whose hash identifies the batch on L1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchHeader {
+    /// The codec version of the batch.
+    pub version: CodecVersion,
+    /// The index of the batch.
+    pub batch_index: u64,
+    /// The number of L1 messages popped from the L1 message queue by the batch, including the
+    /// skipped ones.
+    pub l1_message_popped: u64,
+    /// The number of L1 messages popped from the L1 message queue up to and including the batch.
+    pub total_l1_message_popped: u64,
+    /// The hash of the chunks of the batch.
+    pub data_hash: B256,
+    /// The versioned hash of the blob holding the L2 transactions, from codec v1.
+    pub blob_versioned_hash: Option<B256>,
+    /// The hash of the parent batch header.
+    pub parent_batch_hash: B256,
+    /// The timestamp of the last L2 block of the batch, from codec v3.
+    pub last_block_timestamp: Option<u64>,
+    /// The bitmap of the L1 messages skipped by the batch, before codec v3.
+    pub skipped_l1_message_bitmap: Bytes,
+    /// The evaluation point and value of the blob data proof, from codec v3.
+    pub blob_data_proof: Option<[B256; 2]>,
+}
+
+impl BatchHeader {
+    /// The size of the fields of a codec v0 header, followed by the skipped L1 messages bitmap.
+    const V0_FIXED_SIZE: usize = 89;
+    /// The size of the fields of a codec v1 and v2 header, followed by the skipped L1 messages
+    /// bitmap.
+    const V1_FIXED_SIZE: usize = 121;
+    /// The size of a codec v3 and v4 header.
+    const V3_SIZE: usize = 193;
+
+    /// Decodes a batch header.
+    pub fn decode(data: &[u8]) -> Result<Self, BatchCodecError> {
+        let invalid_length =
+            || BatchCodecError::InvalidLength { kind: "batch header", len: data.len() };
+        let version = CodecVersion::try_from(*data.first().ok_or_else(invalid_length)?)?;
+        let valid_length = match version {
+            CodecVersion::V0 => {
+                data.len() >= Self::V0_FIXED_SIZE && (data.len() - Self::V0_FIXED_SIZE) % 32 == 0
+            }
+            CodecVersion::V1 | CodecVersion::V2 => {
+                data.len() >= Self::V1_FIXED_SIZE && (data.len() - Self::V1_FIXED_SIZE) % 32 == 0
+            }
+            CodecVersion::V3 | CodecVersion::V4 => data.len() == Self::V3_SIZE,
+        };
+        if !valid_length {
+            return Err(invalid_length())
+        }
+
+        let mut header = Self {
+            version,
+            batch_index: read_u64(data, 1),
+            l1_message_popped: read_u64(data, 9),
+            total_l1_message_popped: read_u64(data, 17),
+            data_hash: B256::from_slice(&data[25..57]),
+            blob_versioned_hash: None,
+            parent_batch_hash: B256::ZERO,
+            last_block_timestamp: None,
+            skipped_l1_message_bitmap: Bytes::new(),
+            blob_data_proof: None,
+        };
+        match version {
+            CodecVersion::V0 => {
+                header.parent_batch_hash = B256::from_slice(&data[57..89]);
+                header.skipped_l1_message_bitmap = Bytes::copy_from_slice(&data[89..]);
+            }
+            CodecVersion::V1 | CodecVersion::V2 => {
+                header.blob_versioned_hash = Some(B256::from_slice(&data[57..89]));
+                header.parent_batch_hash = B256::from_slice(&data[89..121]);
+                header.skipped_l1_message_bitmap = Bytes::copy_from_slice(&data[121..]);
+            }
+            CodecVersion::V3 | CodecVersion::V4 => {
+                header.blob_versioned_hash = Some(B256::from_slice(&data[57..89]));
+                header.parent_batch_hash = B256::from_slice(&data[89..121]);
+                header.last_block_timestamp = Some(read_u64(data, 121));
+                header.blob_data_proof =
+                    Some([B256::from_slice(&data[129..161]), B256::from_slice(&data[161..193])]);
+            }
+        }
+        Ok(header)
+    }
+}
+
+/// The context of an L2 block committed in a chunk.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlockContext {
+    /// The number of the block.
+    pub number: u64,
+    /// The timestamp of the block.
+    pub timestamp: u64,
+    /// The base fee of the block, zero before Curie.
+    pub base_fee: U256,
+    /// The gas limit of the block.
+    pub gas_limit: u64,
+    /// The number of transactions of the block, including the L1 messages but not the skipped
+    /// ones.
+    pub num_transactions: u16,
+    /// The number of L1 messages popped by the block, including the skipped ones.
+    pub num_l1_messages: u16,
+}
+
+impl BlockContext {
+    /// Decodes a block context from the first [`BLOCK_CONTEXT_SIZE`] bytes of the data.
+    fn decode(data: &[u8]) -> Self {
+        Self {
+            number: read_u64(data, 0),
+            timestamp: read_u64(data, 8),
+            base_fee: U256::from_be_slice(&data[16..48]),
+            gas_limit: read_u64(data, 48),
+            num_transactions: u16::from_be_bytes([data[56], data[57]]),
+            num_l1_messages: u16::from_be_bytes([data[58], data[59]]),
+        }
+    }
+
+    /// Returns the number of L2 transactions of the block, given the number of L1 messages popped
+    /// by the block which were skipped.
+    pub const fn num_l2_transactions(&self, num_skipped_l1_messages: u16) -> usize {
+        (self.num_transactions as usize + num_skipped_l1_messages as usize)
+            .saturating_sub(self.num_l1_messages as usize)
+    }
+}
+
+/// A chunk of consecutive L2 blocks committed in a batch.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Chunk {
+    /// The contexts of the blocks of the chunk.
+    pub blocks: Vec<BlockContext>,
+    /// The EIP-2718 encoded L2 transactions of the chunk. Only stored in the chunk before codec
+    /// v1, see [`decode_blob_payload`] for the later codecs.
+    pub l2_transactions: Vec<Bytes>,
+}
+
+impl Chunk {
+    /// Decodes a chunk of the given codec version.
+    pub fn decode(version: CodecVersion, data: &[u8]) -> Result<Self, BatchCodecError> {
+        let invalid_length = || BatchCodecError::InvalidLength { kind: "chunk", len: data.len() };
+        let (&num_blocks, data) = data.split_first().ok_or_else(invalid_length)?;
+        let contexts_size = num_blocks as usize * BLOCK_CONTEXT_SIZE;
+        if num_blocks == 0 || data.len() < contexts_size {
+            return Err(invalid_length())
+        }
+
+        let (contexts, mut transactions) = data.split_at(contexts_size);
+        let blocks = contexts.chunks_exact(BLOCK_CONTEXT_SIZE).map(BlockContext::decode).collect();
+
+        let mut l2_transactions = Vec::new();
+        if version == CodecVersion::V0 {
+            // each transaction is prefixed with its length
+            while !transactions.is_empty() {
+                let len = transactions
+                    .get(..4)
+                    .map(|len| u32::from_be_bytes(len.try_into().unwrap()) as usize)
+                    .ok_or_else(invalid_length)?;
+                let transaction = transactions.get(4..4 + len).ok_or_else(invalid_length)?;
+                l2_transactions.push(Bytes::copy_from_slice(transaction));
+                transactions = &transactions[4 + len..];
+            }
+        } else if !transactions.is_empty() {
+            return Err(invalid_length())
+        }
+
+        Ok(Self { blocks, l2_transactions })
+    }
+}
+
+/// A batch commit, decoded from the calldata of the commit transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchCommit {
+    /// The codec version of the batch.
+    pub version: CodecVersion,
+    /// The header of the parent batch.
+    pub parent_batch_header: BatchHeader,
+    /// The hash of the header of the parent batch.
+    pub parent_batch_hash: B256,
+    /// The chunks of the batch.
+    pub chunks: Vec<Chunk>,
+    /// The bitmap of the L1 messages skipped by the batch.
+    pub skipped_l1_message_bitmap: Bytes,
+}
+
+impl BatchCommit {
+    /// Decodes the calldata of a `commitBatch` or `commitBatchWithBlobProof` call.
+    pub fn decode(input: &[u8]) -> Result<Self, BatchCodecError> {
+        let selector = input.get(..4).map(Selector::from_slice).unwrap_or_default();
+        let (version, parent_batch_header, chunks, skipped_l1_message_bitmap) = match selector.0 {
+            commitBatchCall::SELECTOR => {
+                let call = commitBatchCall::abi_decode(input, true)?;
+                let version = CodecVersion::try_from(call.version)?;
+                if version > CodecVersion::V2 {
+                    return Err(BatchCodecError::UnsupportedVersion(call.version))
+                }
+                (version, call.parentBatchHeader, call.chunks, call.skippedL1MessageBitmap)
+            }
+            commitBatchWithBlobProofCall::SELECTOR => {
+                let call = commitBatchWithBlobProofCall::abi_decode(input, true)?;
+                let version = CodecVersion::try_from(call.version)?;
+                if version < CodecVersion::V3 {
+                    return Err(BatchCodecError::UnsupportedVersion(call.version))
+                }
+                (version, call.parentBatchHeader, call.chunks, call.skippedL1MessageBitmap)
+            }
+            _ => return Err(BatchCodecError::UnknownSelector(selector)),
+        };
+
+        if chunks.len() > version.max_num_chunks() {
+            return Err(BatchCodecError::TooManyChunks(chunks.len()))
+        }
+        let chunks = chunks
+            .iter()
+            .map(|chunk| Chunk::decode(version, chunk))
+            .collect::<Result<Vec<_>, _>>()?;
+        if chunks.is_empty() {
+            return Err(BatchCodecError::InvalidLength { kind: "batch", len: 0 })
+        }
+
+        Ok(Self {
+            version,
+            parent_batch_hash: keccak256(&parent_batch_header),
+            parent_batch_header: BatchHeader::decode(&parent_batch_header)?,
+            chunks,
+            skipped_l1_message_bitmap,
+        })
+    }
+
+    /// Returns the number of the first L2 block of the batch.
+    pub fn first_block(&self) -> u64 {
+        self.chunks.first().and_then(|chunk| chunk.blocks.first()).map_or(0, |block| block.number)
+    }
+
+    /// Returns the number of the last L2 block of the batch.
+    pub fn last_block(&self) -> u64 {
+        self.chunks.last().and_then(|chunk| chunk.blocks.last()).map_or(0, |block| block.number)
+    }
+}
+
+/// Decodes the EIP-2718 encoded L2 transactions of each chunk of a batch from the blob of the
+/// batch, for codec v1 and later.
+pub fn decode_blob_payload(
+    version: CodecVersion,
+    blob: &[u8],
+) -> Result<Vec<Vec<Bytes>>, BatchCodecError> {
+    if blob.len() != BYTES_PER_BLOB {
+        return Err(BatchCodecError::InvalidLength { kind: "blob", len: blob.len() })
+    }
+    // the first byte of each field element is unused to stay in the BLS modulus
+    let raw = blob.chunks_exact(32).flat_map(|element| &element[1..]).copied().collect::<Vec<_>>();
+
+    let payload = match version {
+        CodecVersion::V0 => return Err(BatchCodecError::UnsupportedVersion(version as u8)),
+        CodecVersion::V1 => raw,
+        CodecVersion::V2 | CodecVersion::V3 => decompress(&raw)?,
+        CodecVersion::V4 => match raw[0] {
+            0 => raw[1..].to_vec(),
+            1 => decompress(&raw[1..])?,
+            flag => return Err(BatchCodecError::InvalidCompressionFlag(flag)),
+        },
+    };
+
+    // the payload starts with the number of chunks and the size of each chunk
+    let max_num_chunks = version.max_num_chunks();
+    let metadata_size = 2 + 4 * max_num_chunks;
+    let invalid_length =
+        || BatchCodecError::InvalidLength { kind: "blob payload", len: payload.len() };
+    if payload.len() < metadata_size {
+        return Err(invalid_length())
+    }
+    let num_chunks = u16::from_be_bytes([payload[0], payload[1]]) as usize;
+    if num_chunks > max_num_chunks {
+        return Err(BatchCodecError::TooManyChunks(num_chunks))
+    }
+
+    let mut offset = metadata_size;
+    (0..num_chunks)
+        .map(|index| {
+            let size =
+                u32::from_be_bytes(payload[2 + 4 * index..6 + 4 * index].try_into().unwrap());
+            let chunk = payload.get(offset..offset + size as usize).ok_or_else(invalid_length)?;
+            offset += size as usize;
+            split_transactions(chunk)
+        })
+        .collect()
+}
+
+/// Decompresses a zstd frame stripped from its magic number, failing if the decompressed payload
+/// exceeds [`MAX_DECOMPRESSED_PAYLOAD_SIZE`].
+fn decompress(data: &[u8]) -> Result<Vec<u8>, BatchCodecError> {
+    let frame = ZSTD_MAGIC_NUMBER.iter().chain(data).copied().collect::<Vec<_>>();
+    let decoder = zstd::stream::read::Decoder::with_buffer(frame.as_slice())?.single_frame();
+    let mut decompressed = Vec::new();
+    decoder.take(MAX_DECOMPRESSED_PAYLOAD_SIZE as u64 + 1).read_to_end(&mut decompressed)?;
+    if decompressed.len() > MAX_DECOMPRESSED_PAYLOAD_SIZE {
+        return Err(BatchCodecError::PayloadTooLarge(MAX_DECOMPRESSED_PAYLOAD_SIZE))
+    }
+    Ok(decompressed)
+}
+
+/// Splits the concatenated EIP-2718 encoded transactions.
+fn split_transactions(mut data: &[u8]) -> Result<Vec<Bytes>, BatchCodecError> {
+    let mut transactions = Vec::new();
+    while !data.is_empty() {
+        // typed transactions are prefixed with their type, legacy transactions are RLP lists
+        let type_size = usize::from(data[0] < EMPTY_LIST_CODE);
+        let mut payload = &data[type_size..];
+        let header = Header::decode(&mut payload)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString.into())
+        }
+        let size = data.len() - payload.len() + header.payload_length;
+        if size > data.len() {
+            return Err(alloy_rlp::Error::InputTooShort.into())
+        }
+        let (transaction, rest) = data.split_at(size);
+        transactions.push(Bytes::copy_from_slice(transaction));
+        data = rest;
+    }
+    Ok(transactions)
+}
+
+/// Reads a big endian `u64` at the given offset.
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_be_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::b256;
+
+    fn block_context(number: u64, num_transactions: u16, num_l1_messages: u16) -> Vec<u8> {
+        let mut context = Vec::with_capacity(BLOCK_CONTEXT_SIZE);
+        context.extend(number.to_be_bytes());
+        context.extend((1_700_000_000 + number).to_be_bytes());
+        context.extend(U256::from(1_000_000u64).to_be_bytes::<32>());
+        context.extend(10_000_000u64.to_be_bytes());
+        context.extend(num_transactions.to_be_bytes());
+        context.extend(num_l1_messages.to_be_bytes());
+        context
+    }
+
+    fn batch_header_v0(batch_index: u64) -> Vec<u8> {
+        let mut header = vec![0];
+        header.extend(batch_index.to_be_bytes());
+        header.extend(2u64.to_be_bytes());
+        header.extend(10u64.to_be_bytes());
+        header.extend(B256::repeat_byte(1));
+        header.extend(B256::repeat_byte(2));
+        header
+    }
+
+    /// Packs the payload in the 31 low bytes of the field elements of a blob.
+    fn blob(payload: &[u8]) -> Vec<u8> {
+        let mut blob = vec![0; BYTES_PER_BLOB];
+        for (element, data) in blob.chunks_exact_mut(32).zip(payload.chunks(31)) {
+            element[1..=data.len()].copy_from_slice(data);
+        }
+        blob
+    }
+
+    #[test]
+    fn test_decode_batch_header() {
+        let header = BatchHeader::decode(&batch_header_v0(7)).unwrap();
+        assert_eq!(header.version, CodecVersion::V0);
+        assert_eq!(header.batch_index, 7);
+        assert_eq!(header.l1_message_popped, 2);
+        assert_eq!(header.total_l1_message_popped, 10);
+        assert_eq!(header.parent_batch_hash, B256::repeat_byte(2));
+        assert_eq!(header.blob_versioned_hash, None);
+        assert!(header.skipped_l1_message_bitmap.is_empty());
+
+        let mut data = vec![3];
+        data.extend(8u64.to_be_bytes());
+        data.extend([0; 16]);
+        data.extend(B256::repeat_byte(1));
+        data.extend(B256::repeat_byte(2));
+        data.extend(B256::repeat_byte(3));
+        data.extend(1_700_000_000u64.to_be_bytes());
+        data.extend(B256::repeat_byte(4));
+        data.extend(B256::repeat_byte(5));
+        let header = BatchHeader::decode(&data).unwrap();
+        assert_eq!(header.version, CodecVersion::V3);
+        assert_eq!(header.batch_index, 8);
+        assert_eq!(header.blob_versioned_hash, Some(B256::repeat_byte(2)));
+        assert_eq!(header.parent_batch_hash, B256::repeat_byte(3));
+        assert_eq!(header.last_block_timestamp, Some(1_700_000_000));
+        assert_eq!(header.blob_data_proof, Some([B256::repeat_byte(4), B256::repeat_byte(5)]));
+
+        // a bitmap must be a multiple of 32 bytes
+        let mut data = batch_header_v0(7);
+        data.push(0);
+        assert!(matches!(
+            BatchHeader::decode(&data),
+            Err(BatchCodecError::InvalidLength { kind: "batch header", len: 90 })
+        ));
+        data[0] = 5;
+        assert!(matches!(BatchHeader::decode(&data), Err(BatchCodecError::UnsupportedVersion(5))));
+    }
+
+    #[test]
+    fn test_decode_commit_batch() {
+        let transaction = [0xc3, 0x01, 0x02, 0x03];
+        let mut chunk = vec![2];
+        chunk.extend(block_context(11, 2, 1));
+        chunk.extend(block_context(12, 0, 0));
+        chunk.extend((transaction.len() as u32).to_be_bytes());
+        chunk.extend(transaction);
+        let mut last_chunk = vec![1];
+        last_chunk.extend(block_context(13, 0, 0));
+
+        let parent_batch_header = batch_header_v0(4);
+        let input = commitBatchCall {
+            version: 0,
+            parentBatchHeader: parent_batch_header.clone().into(),
+            chunks: vec![chunk.into(), last_chunk.into()],
+            skippedL1MessageBitmap: Bytes::new(),
+        }
+        .abi_encode();
+
+        let commit = BatchCommit::decode(&input).unwrap();
+        assert_eq!(commit.version, CodecVersion::V0);
+        assert_eq!(commit.parent_batch_header.batch_index, 4);
+        assert_eq!(commit.parent_batch_hash, keccak256(&parent_batch_header));
+        assert_eq!(commit.first_block(), 11);
+        assert_eq!(commit.last_block(), 13);
+        assert_eq!(commit.chunks[0].blocks[0].num_l2_transactions(0), 1);
+        assert_eq!(commit.chunks[0].blocks[1].base_fee, U256::from(1_000_000u64));
+        assert_eq!(commit.chunks[0].l2_transactions, vec![Bytes::from(transaction)]);
+        assert!(commit.chunks[1].l2_transactions.is_empty());
+
+        // codec v3 batches are committed with a blob proof
+        let input = commitBatchCall {
+            version: 3,
+            parentBatchHeader: parent_batch_header.into(),
+            chunks: vec![],
+            skippedL1MessageBitmap: Bytes::new(),
+        }
+        .abi_encode();
+        assert!(matches!(BatchCommit::decode(&input), Err(BatchCodecError::UnsupportedVersion(3))));
+
+        let selector = b256!("deadbeef00000000000000000000000000000000000000000000000000000000");
+        assert!(matches!(
+            BatchCommit::decode(selector.as_slice()),
+            Err(BatchCodecError::UnknownSelector(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_blob_payload() {
+        let legacy = [0xc2, 0x80, 0x80];
+        let typed = [0x02, 0xc1, 0x05];
+
+        let mut payload = vec![0, 2];
+        payload.extend(6u32.to_be_bytes());
+        payload.extend(3u32.to_be_bytes());
+        payload.resize(2 + 4 * CodecVersion::V2.max_num_chunks(), 0);
+        payload.extend(legacy);
+        payload.extend(typed);
+        payload.extend(typed);
+        let expected =
+            vec![vec![Bytes::from(legacy), Bytes::from(typed)], vec![Bytes::from(typed)]];
+
+        // compressed payloads are stripped from the zstd magic number
+        let compressed = zstd::bulk::compress(&payload, 0).unwrap();
+        assert_eq!(compressed[..4], ZSTD_MAGIC_NUMBER);
+        let blob_v2 = blob(&compressed[4..]);
+        assert_eq!(decode_blob_payload(CodecVersion::V2, &blob_v2).unwrap(), expected);
+
+        // codec v4 payloads are prefixed with a compression flag
+        let blob_v4 = blob(&[[0].as_slice(), &payload].concat());
+        assert_eq!(decode_blob_payload(CodecVersion::V4, &blob_v4).unwrap(), expected);
+        let blob_v4 = blob(&[[1].as_slice(), &compressed[4..]].concat());
+        assert_eq!(decode_blob_payload(CodecVersion::V4, &blob_v4).unwrap(), expected);
+        let blob_v4 = blob(&[[2].as_slice(), &payload].concat());
+        assert!(matches!(
+            decode_blob_payload(CodecVersion::V4, &blob_v4),
+            Err(BatchCodecError::InvalidCompressionFlag(2))
+        ));
+
+        assert!(matches!(
+            decode_blob_payload(CodecVersion::V0, &blob_v2),
+            Err(BatchCodecError::UnsupportedVersion(0))
+        ));
+
+        // payloads decompressing above the maximum size are rejected
+        let oversized =
+            zstd::bulk::compress(&vec![0; MAX_DECOMPRESSED_PAYLOAD_SIZE + 1], 0).unwrap();
+        assert!(matches!(
+            decode_blob_payload(CodecVersion::V2, &blob(&oversized[4..])),
+            Err(BatchCodecError::PayloadTooLarge(MAX_DECOMPRESSED_PAYLOAD_SIZE))
+        ));
+    }
+}
@@ -0,0 +1,328 @@
+//! Derivation of the L2 blocks of the batches committed to the Scroll `ScrollChain` contract.
+
+use crate::{
+    abi::CommitBatch,
+    codec::{decode_blob_payload, BatchCommit, BlockContext, CodecVersion},
+    BatchDerivationError, BatchReader, L1BlobProvider, L1MessageReader, L1Provider,
+};
+use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_rpc_types_eth::Filter;
+use alloy_sol_types::SolEvent;
+use reth_scroll_primitives::{CommittedBatch, TxL1Message};
+use reth_storage_api::DatabaseProviderFactory;
+
+/// An L2 block derived from a batch committed to L1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivedBlock {
+    /// The context of the block, committed in a chunk of the batch.
+    pub context: BlockContext,
+    /// The L1 messages included in the block in queue order, without the skipped ones.
+    pub l1_messages: Vec<TxL1Message>,
+    /// The EIP-2718 encoded L2 transactions of the block, following the L1 messages.
+    pub transactions: Vec<Bytes>,
+}
+
+/// The L2 blocks of a batch committed to L1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivedBatch {
+    /// The index of the batch.
+    pub index: u64,
+    /// The committed batch, as indexed by the [`BatchIndexer`](crate::BatchIndexer).
+    pub batch: CommittedBatch,
+    /// The blocks of the batch, ordered by number.
+    pub blocks: Vec<DerivedBlock>,
+}
+
+/// Derives the L2 blocks of the batches indexed by the [`BatchIndexer`](crate::BatchIndexer)
+/// purely from L1 data.
+///
+/// The block contexts and, before codec v1, the L2 transactions are decoded from the calldata of
+/// the commit transaction, later codecs store the L2 transactions in the blob of the transaction
+/// which is read from the [`L1BlobProvider`]. The L1 messages popped by the blocks are read from
+/// the local copy of the L1 message queue indexed by the
+/// [`L1MessageQueueIndexer`](crate::L1MessageQueueIndexer).
+#[derive(Debug)]
+pub struct BatchDeriver<P, B, F> {
+    /// The L1 provider.
+    l1_provider: P,
+    /// The provider of the blobs of the commit transactions.
+    blob_provider: B,
+    /// The factory of the database providers storing the batches and the L1 messages.
+    factory: F,
+    /// The address of the `ScrollChain` contract.
+    address: Address,
+}
+
+impl<P, B, F> BatchDeriver<P, B, F>
+where
+    P: L1Provider,
+    B: L1BlobProvider,
+    F: DatabaseProviderFactory,
+{
+    /// Returns a new [`BatchDeriver`] for the batches committed to the `ScrollChain` contract at
+    /// the given address.
+    pub const fn new(l1_provider: P, blob_provider: B, factory: F, address: Address) -> Self {
+        Self { l1_provider, blob_provider, factory, address }
+    }
+
+    /// Derives the blocks of the committed batch with the given index, or returns `None` if the
+    /// batch wasn't indexed yet.
+    ///
+    /// The genesis batch only commits the genesis block, which isn't derived.
+    pub async fn derive(
+        &self,
+        batch_index: u64,
+    ) -> Result<Option<DerivedBatch>, BatchDerivationError> {
+        let Some(batch) = self.factory.database_provider_ro()?.committed_batch(batch_index)? else {
+            return Ok(None)
+        };
+        if batch_index == 0 {
+            return Ok(Some(DerivedBatch { index: batch_index, batch, blocks: Vec::new() }))
+        }
+
+        let filter = Filter::new()
+            .address(self.address)
+            .event_signature(CommitBatch::SIGNATURE_HASH)
+            .topic1(B256::from(U256::from(batch_index)))
+            .topic2(batch.hash)
+            .from_block(batch.l1_block)
+            .to_block(batch.l1_block);
+        let transaction_hash = self
+            .l1_provider
+            .logs(&filter)
+            .await?
+            .into_iter()
+            .find(|log| !log.removed)
+            .and_then(|log| log.transaction_hash)
+            .ok_or(BatchDerivationError::CommitNotFound(batch_index))?;
+        let transaction = self
+            .l1_provider
+            .transaction(transaction_hash)
+            .await?
+            .ok_or(BatchDerivationError::CommitTransactionNotFound(transaction_hash))?;
+        let commit = BatchCommit::decode(&transaction.input)?;
+
+        let chunk_transactions = if commit.version == CodecVersion::V0 {
+            commit.chunks.iter().map(|chunk| chunk.l2_transactions.clone()).collect()
+        } else {
+            // batches are committed with a single blob
+            let versioned_hash =
+                transaction.blob_versioned_hashes.first().copied().unwrap_or_default();
+            let blob = self
+                .blob_provider
+                .blob(versioned_hash)
+                .await?
+                .ok_or(BatchDerivationError::BlobNotFound(versioned_hash))?;
+            decode_blob_payload(commit.version, &blob)?
+        };
+        if chunk_transactions.len() != commit.chunks.len() {
+            return Err(BatchDerivationError::TransactionCountMismatch { batch_index })
+        }
+
+        let provider = self.factory.database_provider_ro()?;
+        let first_queue_index = commit.parent_batch_header.total_l1_message_popped;
+        let mut queue_index = first_queue_index;
+        let mut blocks = Vec::new();
+        for (chunk, transactions) in commit.chunks.into_iter().zip(chunk_transactions) {
+            let mut transactions = transactions.into_iter();
+            for context in chunk.blocks {
+                let mut l1_messages = Vec::with_capacity(context.num_l1_messages as usize);
+                let mut num_skipped = 0;
+                for _ in 0..context.num_l1_messages {
+                    let position = queue_index - first_queue_index;
+                    if is_skipped(&commit.skipped_l1_message_bitmap, position) {
+                        num_skipped += 1;
+                    } else {
+                        let l1_message = provider
+                            .l1_message(queue_index)?
+                            .ok_or(BatchDerivationError::L1MessageNotFound(queue_index))?;
+                        l1_messages.push(l1_message);
+                    }
+                    queue_index += 1;
+                }
+
+                let num_transactions = context.num_l2_transactions(num_skipped);
+                let transactions = transactions.by_ref().take(num_transactions).collect::<Vec<_>>();
+                if transactions.len() != num_transactions {
+                    return Err(BatchDerivationError::TransactionCountMismatch { batch_index })
+                }
+                blocks.push(DerivedBlock { context, l1_messages, transactions });
+            }
+            if transactions.next().is_some() {
+                return Err(BatchDerivationError::TransactionCountMismatch { batch_index })
+            }
+        }
+
+        Ok(Some(DerivedBatch { index: batch_index, batch, blocks }))
+    }
+}
+
+/// Returns whether the L1 message at the given position among the L1 messages popped by a batch
+/// is skipped, according to the bitmap of the batch made of big endian 256-bit words.
+fn is_skipped(bitmap: &[u8], position: u64) -> bool {
+    let offset = (position / 256) as usize * 32;
+    bitmap
+        .get(offset..offset + 32)
+        .is_some_and(|word| U256::from_be_slice(word).bit((position % 256) as usize))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        abi::commitBatchCall, codec::BLOCK_CONTEXT_SIZE, test_utils::MockL1Provider, BatchWriter,
+        L1MessageWriter,
+    };
+    use alloy_eips::eip4844::BYTES_PER_BLOB;
+    use alloy_primitives::{address, keccak256, Log};
+    use alloy_sol_types::SolCall;
+    use reth_provider::{
+        test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
+        ProviderFactory,
+    };
+
+    const SCROLL_CHAIN_ADDRESS: Address = address!("a13BAF47339d63B743e7Da8741db5456DAc1E556");
+
+    /// An RLP encoded legacy transaction stand-in.
+    const TRANSACTION: [u8; 4] = [0xc3, 0x01, 0x02, 0x03];
+
+    fn l1_message(queue_index: u64) -> TxL1Message {
+        TxL1Message { queue_index, gas_limit: 21_000, ..Default::default() }
+    }
+
+    /// Returns a codec v0 parent batch header which popped the first `total_l1_message_popped`
+    /// L1 messages.
+    fn parent_batch_header(total_l1_message_popped: u64) -> Bytes {
+        let mut header = vec![0];
+        header.extend(0u64.to_be_bytes());
+        header.extend(0u64.to_be_bytes());
+        header.extend(total_l1_message_popped.to_be_bytes());
+        header.extend([0; 64]);
+        header.into()
+    }
+
+    fn block_context(number: u64, num_transactions: u16, num_l1_messages: u16) -> Vec<u8> {
+        let mut context = vec![0; BLOCK_CONTEXT_SIZE];
+        context[..8].copy_from_slice(&number.to_be_bytes());
+        context[56..58].copy_from_slice(&num_transactions.to_be_bytes());
+        context[58..].copy_from_slice(&num_l1_messages.to_be_bytes());
+        context
+    }
+
+    /// Indexes the batch committed in a new L1 block and the L1 messages it pops.
+    fn commit(
+        l1: &MockL1Provider,
+        factory: &ProviderFactory<MockNodeTypesWithDB>,
+        call: commitBatchCall,
+        blob: Option<Bytes>,
+        l1_messages: impl IntoIterator<Item = TxL1Message>,
+    ) {
+        let hash = keccak256(&call.parentBatchHeader);
+        let log = Log {
+            address: SCROLL_CHAIN_ADDRESS,
+            data: CommitBatch { batchIndex: U256::from(1), batchHash: hash }.encode_log_data(),
+        };
+        let input = call.abi_encode().into();
+        let l1_block = match blob {
+            Some(blob) => l1.mine_block_with_blob_transaction(input, blob, vec![log]),
+            None => l1.mine_block_with_transaction(input, vec![log]),
+        };
+
+        let provider = factory.database_provider_rw().unwrap();
+        let batch = CommittedBatch { hash, l1_block, ..Default::default() };
+        provider.insert_committed_batch(1, batch).unwrap();
+        provider.insert_l1_messages(l1_messages).unwrap();
+        provider.commit().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_derive_calldata_batch() -> Result<(), BatchDerivationError> {
+        let l1 = MockL1Provider::default();
+        let factory = create_test_provider_factory();
+        let deriver =
+            BatchDeriver::new(l1.clone(), l1.clone(), factory.clone(), SCROLL_CHAIN_ADDRESS);
+        assert_eq!(deriver.derive(1).await?, None);
+
+        // the first block pops the L1 messages 2 and 3 and skips the message 2
+        let mut chunk = vec![2];
+        chunk.extend(block_context(1, 2, 2));
+        chunk.extend(block_context(2, 1, 0));
+        for _ in 0..2 {
+            chunk.extend((TRANSACTION.len() as u32).to_be_bytes());
+            chunk.extend(TRANSACTION);
+        }
+        let call = commitBatchCall {
+            version: 0,
+            parentBatchHeader: parent_batch_header(2),
+            chunks: vec![chunk.into()],
+            skippedL1MessageBitmap: U256::from(1).to_be_bytes::<32>().into(),
+        };
+        commit(&l1, &factory, call, None, [l1_message(3)]);
+
+        let batch = deriver.derive(1).await?.unwrap();
+        assert_eq!(batch.index, 1);
+        assert_eq!(batch.blocks.len(), 2);
+        assert_eq!(batch.blocks[0].context.number, 1);
+        assert_eq!(batch.blocks[0].l1_messages, vec![l1_message(3)]);
+        assert_eq!(batch.blocks[0].transactions, vec![Bytes::from(TRANSACTION)]);
+        assert!(batch.blocks[1].l1_messages.is_empty());
+        assert_eq!(batch.blocks[1].transactions, vec![Bytes::from(TRANSACTION)]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_derive_blob_batch() -> Result<(), BatchDerivationError> {
+        let l1 = MockL1Provider::default();
+        let factory = create_test_provider_factory();
+        let deriver =
+            BatchDeriver::new(l1.clone(), l1.clone(), factory.clone(), SCROLL_CHAIN_ADDRESS);
+
+        let mut chunk = vec![1];
+        chunk.extend(block_context(1, 3, 1));
+        let call = commitBatchCall {
+            version: 1,
+            parentBatchHeader: parent_batch_header(0),
+            chunks: vec![chunk.into()],
+            skippedL1MessageBitmap: Bytes::new(),
+        };
+
+        // the uncompressed payload holds the two transactions of the single chunk
+        let mut payload = vec![0, 1];
+        payload.extend((2 * TRANSACTION.len() as u32).to_be_bytes());
+        payload.extend([0; 4 * 14]);
+        payload.extend(TRANSACTION);
+        payload.extend(TRANSACTION);
+        let mut blob = vec![0; BYTES_PER_BLOB];
+        for (element, data) in blob.chunks_exact_mut(32).zip(payload.chunks(31)) {
+            element[1..=data.len()].copy_from_slice(data);
+        }
+
+        // the L1 message popped by the block isn't indexed yet
+        commit(&l1, &factory, call, Some(blob.into()), []);
+        assert!(matches!(deriver.derive(1).await, Err(BatchDerivationError::L1MessageNotFound(0))));
+
+        let provider = factory.database_provider_rw()?;
+        provider.insert_l1_messages([l1_message(0)])?;
+        provider.commit()?;
+
+        let batch = deriver.derive(1).await?.unwrap();
+        assert_eq!(batch.blocks.len(), 1);
+        assert_eq!(batch.blocks[0].l1_messages, vec![l1_message(0)]);
+        assert_eq!(batch.blocks[0].transactions, vec![Bytes::from(TRANSACTION); 2]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_skipped() {
+        let mut bitmap = [0; 64];
+        bitmap[31] = 0b101;
+        bitmap[32] = 0x80;
+        assert!(is_skipped(&bitmap, 0));
+        assert!(!is_skipped(&bitmap, 1));
+        assert!(is_skipped(&bitmap, 2));
+        assert!(is_skipped(&bitmap, 511));
+        assert!(!is_skipped(&bitmap, 512));
+    }
+}
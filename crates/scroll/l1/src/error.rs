@@ -0,0 +1,165 @@
+use alloy_primitives::{Selector, TxHash, B256};
+use alloy_transport::TransportError;
+use reth_storage_errors::provider::ProviderError;
+
+/// Result type returned by the [`L1Provider`](crate::L1Provider).
+pub type L1ProviderResult<T> = Result<T, L1ProviderError>;
+
+/// Errors returned by the [`L1Provider`](crate::L1Provider).
+#[derive(Debug, thiserror::Error)]
+pub enum L1ProviderError {
+    /// An error occurred while querying the L1 JSON-RPC endpoint.
+    #[error(transparent)]
+    Rpc(#[from] TransportError),
+    /// The L1 block is not known to the provider.
+    #[error("L1 block {0} not found")]
+    BlockNotFound(u64),
+    /// An error occurred while reading a blob from a local archive.
+    #[error("failed to read blob: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Errors returned by the [`L1MessageQueueIndexer`](crate::L1MessageQueueIndexer).
+#[derive(Debug, thiserror::Error)]
+pub enum L1MessageQueueError {
+    /// An error occurred while querying L1.
+    #[error(transparent)]
+    L1Provider(#[from] L1ProviderError),
+    /// An error occurred while reading or writing the local copy of the queue.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    /// The log could not be decoded into a `QueueTransaction` event.
+    #[error("failed to decode QueueTransaction log: {0}")]
+    Decode(#[from] alloy_sol_types::Error),
+    /// The queue index of the message doesn't follow the last indexed message.
+    #[error("L1 message queue index gap: expected {expected}, got {got}")]
+    QueueIndexGap {
+        /// The expected queue index.
+        expected: u64,
+        /// The queue index of the message emitted by the queue.
+        got: u64,
+    },
+    /// The L1 reorg is deeper than the indexed blocks tracked by the indexer.
+    #[error("L1 reorg deeper than the {0} tracked L1 blocks")]
+    ReorgTooDeep(usize),
+}
+
+/// Errors returned when decoding a batch committed to the `ScrollChain` contract.
+#[derive(Debug, thiserror::Error)]
+pub enum BatchCodecError {
+    /// The codec version isn't supported.
+    #[error("unsupported batch codec version {0}")]
+    UnsupportedVersion(u8),
+    /// The encoded data has an invalid length.
+    #[error("invalid {kind} length {len}")]
+    InvalidLength {
+        /// The kind of the encoded data.
+        kind: &'static str,
+        /// The length of the encoded data.
+        len: usize,
+    },
+    /// The batch contains more chunks than allowed by the codec.
+    #[error("too many chunks in batch: {0}")]
+    TooManyChunks(usize),
+    /// The flag indicating whether the blob payload is compressed is invalid.
+    #[error("invalid blob compression flag {0}")]
+    InvalidCompressionFlag(u8),
+    /// The calldata of the commit transaction isn't a call to a known commit function.
+    #[error("unknown batch commit function selector {0}")]
+    UnknownSelector(Selector),
+    /// The calldata of the commit transaction could not be decoded.
+    #[error(transparent)]
+    Abi(#[from] alloy_sol_types::Error),
+    /// A transaction of the batch could not be decoded.
+    #[error(transparent)]
+    Rlp(#[from] alloy_rlp::Error),
+    /// The blob payload could not be decompressed.
+    #[error("failed to decompress blob payload: {0}")]
+    Decompression(#[from] std::io::Error),
+    /// The decompressed blob payload exceeds the maximum size.
+    #[error("decompressed blob payload exceeds {0} bytes")]
+    PayloadTooLarge(usize),
+}
+
+/// Errors returned by the [`BatchIndexer`](crate::BatchIndexer).
+#[derive(Debug, thiserror::Error)]
+pub enum BatchIndexerError {
+    /// An error occurred while querying L1.
+    #[error(transparent)]
+    L1Provider(#[from] L1ProviderError),
+    /// An error occurred while reading or writing the indexed batches.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    /// The log could not be decoded into a `ScrollChain` event.
+    #[error("failed to decode ScrollChain log: {0}")]
+    Decode(#[from] alloy_sol_types::Error),
+    /// The committed batch could not be decoded.
+    #[error(transparent)]
+    Codec(#[from] BatchCodecError),
+    /// The transaction committing the batch is not known to the L1 provider.
+    #[error("batch commit transaction {0} not found")]
+    CommitTransactionNotFound(TxHash),
+    /// The parent batch header committed with the batch isn't the header of the previous batch.
+    #[error("invalid parent batch of batch {batch_index}")]
+    InvalidParentBatch {
+        /// The index of the committed batch.
+        batch_index: u64,
+    },
+    /// The L1 reorg is deeper than the indexed blocks tracked by the indexer.
+    #[error("L1 reorg deeper than the {0} tracked L1 blocks")]
+    ReorgTooDeep(usize),
+}
+
+/// Errors returned by the [`BatchDeriver`](crate::BatchDeriver).
+#[derive(Debug, thiserror::Error)]
+pub enum BatchDerivationError {
+    /// An error occurred while querying L1.
+    #[error(transparent)]
+    L1Provider(#[from] L1ProviderError),
+    /// An error occurred while reading the indexed batches or L1 messages.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    /// The committed batch could not be decoded.
+    #[error(transparent)]
+    Codec(#[from] BatchCodecError),
+    /// The `CommitBatch` event of the indexed batch is not known to the L1 provider.
+    #[error("commit of batch {0} not found")]
+    CommitNotFound(u64),
+    /// The transaction committing the batch is not known to the L1 provider.
+    #[error("batch commit transaction {0} not found")]
+    CommitTransactionNotFound(TxHash),
+    /// The blob holding the L2 transactions of the batch is not known to the blob provider.
+    #[error("blob {0} not found")]
+    BlobNotFound(B256),
+    /// The L1 message popped by the batch wasn't indexed yet.
+    #[error("L1 message {0} not found")]
+    L1MessageNotFound(u64),
+    /// The number of L2 transactions of the batch doesn't match its block contexts.
+    #[error("invalid number of L2 transactions in batch {batch_index}")]
+    TransactionCountMismatch {
+        /// The index of the batch.
+        batch_index: u64,
+    },
+}
+
+/// Errors returned by the [`StateRootVerifier`](crate::StateRootVerifier).
+#[derive(Debug, thiserror::Error)]
+pub enum StateRootVerifierError {
+    /// An error occurred while reading the indexed batches or the local chain.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    /// The state root of the local chain doesn't match the state root finalized on L1.
+    #[error(
+        "state root mismatch for batch {batch_index} at block {number}: got {got}, expected {expected}"
+    )]
+    StateRootMismatch {
+        /// The index of the finalized batch.
+        batch_index: u64,
+        /// The number of the last block of the batch.
+        number: u64,
+        /// The state root of the local chain.
+        got: B256,
+        /// The state root finalized on L1.
+        expected: B256,
+    },
+}
@@ -0,0 +1,47 @@
+//! Indexers following the Scroll rollup contracts on L1.
+//!
+//! The [`L1MessageQueueIndexer`] follows the `L1MessageQueue` contract through an [`L1Provider`]
+//! and keeps an authoritative local copy of the L1 message queue in the
+//! [`L1Messages`](reth_db::tables::L1Messages) table, which is read through the
+//! [`L1MessageReader`].
+//!
+//! The [`BatchIndexer`] follows the `ScrollChain` contract and records the batches of L2 blocks
+//! committed to and finalized on L1, which are read through the [`BatchReader`] and drive the
+//! `safe` and `finalized` blocks of the node. The [`BatchDeriver`] derives the L2 blocks of the
+//! committed batches purely from L1 data, and the [`StateRootVerifier`] checks the local chain
+//! against the state roots of the finalized batches.
+
+pub mod abi;
+
+mod batch;
+pub use batch::{BatchIndexer, BatchIndexerConfig, BatchIndexerOutcome};
+
+pub mod codec;
+
+mod derivation;
+pub use derivation::{BatchDeriver, DerivedBatch, DerivedBlock};
+
+mod error;
+pub use error::{
+    BatchCodecError, BatchDerivationError, BatchIndexerError, L1MessageQueueError, L1ProviderError,
+    L1ProviderResult, StateRootVerifierError,
+};
+
+mod message_queue;
+pub use message_queue::{
+    L1MessageQueueConfig, L1MessageQueueIndexer, L1MessageQueueOutcome, DEFAULT_L1_CONFIRMATIONS,
+    DEFAULT_L1_MAX_BLOCK_RANGE, DEFAULT_L1_POLL_INTERVAL,
+};
+
+mod provider;
+pub use provider::{BlobArchive, L1BlobProvider, L1Provider, L1Transaction};
+
+mod storage;
+pub use storage::{BatchReader, BatchWriter, L1MessageReader, L1MessageWriter};
+
+mod verifier;
+pub use verifier::StateRootVerifier;
+
+/// Test utilities for the L1 indexers.
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
@@ -0,0 +1,384 @@
+//! Indexer of the Scroll `L1MessageQueue` contract.
+
+use crate::{
+    abi::QueueTransaction, L1MessageQueueError, L1MessageReader, L1MessageWriter, L1Provider,
+    L1ProviderError,
+};
+use alloy_primitives::{Address, B256};
+use alloy_rpc_types_eth::Filter;
+use alloy_sol_types::SolEvent;
+use reth_db_api::transaction::DbTxMut;
+use reth_scroll_primitives::TxL1Message;
+use reth_storage_api::{DBProvider, DatabaseProviderFactory};
+use std::{collections::VecDeque, ops::RangeInclusive, time::Duration};
+use tracing::{debug, error, info, warn};
+
+/// The default number of L1 blocks after which an L1 block is considered final by the indexer.
+pub const DEFAULT_L1_CONFIRMATIONS: u64 = 6;
+
+/// The default maximum number of L1 blocks queried in a single `eth_getLogs` request.
+pub const DEFAULT_L1_MAX_BLOCK_RANGE: u64 = 1_000;
+
+/// The default interval at which L1 is polled.
+pub const DEFAULT_L1_POLL_INTERVAL: Duration = Duration::from_secs(12);
+
+/// The number of indexed L1 blocks tracked to detect reorgs.
+const MAX_TRACKED_BLOCKS: usize = 256;
+
+/// Configuration of the [`L1MessageQueueIndexer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct L1MessageQueueConfig {
+    /// The address of the `L1MessageQueue` contract.
+    pub address: Address,
+    /// The L1 block from which the queue is indexed if no L1 block was indexed yet.
+    pub start_block: u64,
+    /// The number of L1 blocks built on top of an L1 block before it is indexed.
+    pub confirmations: u64,
+    /// The maximum number of L1 blocks queried in a single `eth_getLogs` request.
+    pub max_block_range: u64,
+    /// The interval at which L1 is polled.
+    pub poll_interval: Duration,
+}
+
+impl L1MessageQueueConfig {
+    /// Returns a new [`L1MessageQueueConfig`] following the `L1MessageQueue` contract at the given
+    /// address with the default settings.
+    pub const fn new(address: Address) -> Self {
+        Self {
+            address,
+            start_block: 0,
+            confirmations: DEFAULT_L1_CONFIRMATIONS,
+            max_block_range: DEFAULT_L1_MAX_BLOCK_RANGE,
+            poll_interval: DEFAULT_L1_POLL_INTERVAL,
+        }
+    }
+
+    /// Sets the L1 block from which the queue is indexed.
+    pub const fn with_start_block(mut self, start_block: u64) -> Self {
+        self.start_block = start_block;
+        self
+    }
+
+    /// Sets the number of confirmations required before an L1 block is indexed.
+    pub const fn with_confirmations(mut self, confirmations: u64) -> Self {
+        self.confirmations = confirmations;
+        self
+    }
+
+    /// Sets the maximum number of L1 blocks queried in a single `eth_getLogs` request.
+    pub const fn with_max_block_range(mut self, max_block_range: u64) -> Self {
+        self.max_block_range = max_block_range;
+        self
+    }
+
+    /// Sets the interval at which L1 is polled.
+    pub const fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+}
+
+/// The outcome of a single [`L1MessageQueueIndexer::poll`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum L1MessageQueueOutcome {
+    /// No new confirmed L1 block to index.
+    Idle,
+    /// The L1 blocks were indexed.
+    Indexed {
+        /// The indexed L1 blocks.
+        blocks: RangeInclusive<u64>,
+        /// The number of L1 messages emitted in the indexed blocks.
+        messages: usize,
+    },
+    /// An L1 reorg was detected and the L1 messages emitted after the common ancestor were
+    /// removed.
+    Reorged {
+        /// The last indexed L1 block which is still canonical.
+        ancestor: u64,
+        /// The number of removed L1 messages.
+        removed: usize,
+    },
+}
+
+/// An indexed L1 block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct IndexedBlock {
+    /// The number of the L1 block.
+    number: u64,
+    /// The hash of the L1 block.
+    hash: B256,
+    /// The queue index following the last L1 message emitted up to the L1 block.
+    next_queue_index: u64,
+}
+
+/// Follows the `L1MessageQueue` contract on L1 and stores the L1 messages of the queue in the
+/// [`L1Messages`](reth_db::tables::L1Messages) table, keyed by queue index.
+///
+/// The indexer only indexes L1 blocks with at least
+/// [`confirmations`](L1MessageQueueConfig::confirmations) blocks built on top of them. Shallower
+/// reorgs are detected by tracking the hashes of the recently indexed L1 blocks, in which case the
+/// L1 messages emitted after the last canonical indexed block are removed and indexed again.
+#[derive(Debug)]
+pub struct L1MessageQueueIndexer<P, F> {
+    /// The L1 provider.
+    l1_provider: P,
+    /// The factory of the database providers storing the queue.
+    factory: F,
+    /// The indexer configuration.
+    config: L1MessageQueueConfig,
+    /// The recently indexed L1 blocks, oldest first.
+    indexed_blocks: VecDeque<IndexedBlock>,
+}
+
+impl<P, F> L1MessageQueueIndexer<P, F>
+where
+    P: L1Provider,
+    F: DatabaseProviderFactory<ProviderRW: DBProvider<Tx: DbTxMut>>,
+{
+    /// Returns a new [`L1MessageQueueIndexer`].
+    pub fn new(l1_provider: P, factory: F, config: L1MessageQueueConfig) -> Self {
+        Self { l1_provider, factory, config, indexed_blocks: VecDeque::new() }
+    }
+
+    /// Polls L1 at the configured interval, indefinitely.
+    pub async fn run(mut self) {
+        info!(target: "scroll::l1::message_queue", address = %self.config.address, "starting L1 message queue indexer");
+        let mut interval = tokio::time::interval(self.config.poll_interval);
+        loop {
+            interval.tick().await;
+            // index the available L1 blocks range by range
+            loop {
+                match self.poll().await {
+                    Ok(L1MessageQueueOutcome::Idle) => break,
+                    Ok(L1MessageQueueOutcome::Indexed { blocks, messages }) => {
+                        debug!(target: "scroll::l1::message_queue", ?blocks, messages, "indexed L1 blocks");
+                    }
+                    Ok(L1MessageQueueOutcome::Reorged { ancestor, removed }) => {
+                        warn!(target: "scroll::l1::message_queue", ancestor, removed, "L1 reorg detected");
+                    }
+                    Err(err) => {
+                        error!(target: "scroll::l1::message_queue", %err, "failed to index L1 message queue");
+                        break
+                    }
+                }
+            }
+        }
+    }
+
+    /// Indexes the next range of confirmed L1 blocks, after checking the last indexed L1 block is
+    /// still canonical.
+    pub async fn poll(&mut self) -> Result<L1MessageQueueOutcome, L1MessageQueueError> {
+        let provider = self.factory.database_provider_ro()?;
+        let last_block = provider.last_l1_message_queue_block()?;
+        let next_queue_index = provider.next_l1_message_queue_index()?;
+        drop(provider);
+
+        if let Some(number) = last_block {
+            if self.indexed_blocks.is_empty() {
+                // the last indexed block before a restart is assumed to be canonical
+                let hash = self.block_hash(number).await?;
+                self.indexed_blocks.push_back(IndexedBlock { number, hash, next_queue_index });
+            }
+
+            if let Some(outcome) = self.unwind_reorged_blocks().await? {
+                return Ok(outcome)
+            }
+        }
+
+        let head = self.l1_provider.block_number().await?;
+        let confirmed = head.saturating_sub(self.config.confirmations);
+        let from = last_block.map_or(self.config.start_block, |number| number + 1);
+        if from > confirmed {
+            return Ok(L1MessageQueueOutcome::Idle)
+        }
+        let to = confirmed.min(from.saturating_add(self.config.max_block_range.max(1) - 1));
+
+        let hash = self.block_hash(to).await?;
+        let filter = Filter::new()
+            .address(self.config.address)
+            .event_signature(QueueTransaction::SIGNATURE_HASH)
+            .from_block(from)
+            .to_block(to);
+        let logs = self.l1_provider.logs(&filter).await?;
+
+        // the logs can't be queried by block hash for a range, if a reorg happened in the meantime
+        // the range is indexed again on the next poll
+        if self.block_hash(to).await? != hash {
+            return Ok(L1MessageQueueOutcome::Idle)
+        }
+
+        let mut messages = Vec::with_capacity(logs.len());
+        let mut expected = next_queue_index;
+        for log in logs.into_iter().filter(|log| !log.removed) {
+            let message: TxL1Message = QueueTransaction::decode_log_data(log.data(), true)?.into();
+            if message.queue_index != expected {
+                return Err(L1MessageQueueError::QueueIndexGap {
+                    expected,
+                    got: message.queue_index,
+                })
+            }
+            expected += 1;
+            messages.push(message);
+        }
+
+        let count = messages.len();
+        let provider = self.factory.database_provider_rw()?;
+        provider.insert_l1_messages(messages)?;
+        provider.save_last_l1_message_queue_block(to)?;
+        provider.commit()?;
+
+        self.indexed_blocks.push_back(IndexedBlock {
+            number: to,
+            hash,
+            next_queue_index: expected,
+        });
+        if self.indexed_blocks.len() > MAX_TRACKED_BLOCKS {
+            self.indexed_blocks.pop_front();
+        }
+
+        Ok(L1MessageQueueOutcome::Indexed { blocks: from..=to, messages: count })
+    }
+
+    /// Checks the tracked indexed blocks against L1 and unwinds the queue to the last tracked block
+    /// which is still canonical, if any was reorged.
+    async fn unwind_reorged_blocks(
+        &mut self,
+    ) -> Result<Option<L1MessageQueueOutcome>, L1MessageQueueError> {
+        let mut canonical = self.indexed_blocks.len();
+        while canonical > 0 {
+            let block = self.indexed_blocks[canonical - 1];
+            if self.l1_provider.block_hash(block.number).await? == Some(block.hash) {
+                break
+            }
+            canonical -= 1;
+        }
+
+        if canonical == self.indexed_blocks.len() {
+            return Ok(None)
+        }
+        if canonical == 0 {
+            return Err(L1MessageQueueError::ReorgTooDeep(self.indexed_blocks.len()))
+        }
+
+        self.indexed_blocks.truncate(canonical);
+        let ancestor = self.indexed_blocks[canonical - 1];
+
+        let provider = self.factory.database_provider_rw()?;
+        let removed = provider.remove_l1_messages_from(ancestor.next_queue_index)?;
+        provider.save_last_l1_message_queue_block(ancestor.number)?;
+        provider.commit()?;
+
+        Ok(Some(L1MessageQueueOutcome::Reorged { ancestor: ancestor.number, removed }))
+    }
+
+    /// Returns the hash of the L1 block with the given number.
+    async fn block_hash(&self, number: u64) -> Result<B256, L1MessageQueueError> {
+        Ok(self
+            .l1_provider
+            .block_hash(number)
+            .await?
+            .ok_or(L1ProviderError::BlockNotFound(number))?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockL1Provider;
+    use alloy_primitives::{address, Bytes, U256};
+    use reth_provider::test_utils::create_test_provider_factory;
+
+    const QUEUE_ADDRESS: Address = address!("0d7E906BD9cAFa154b048cFa766Cc1E54E39AF9B");
+
+    fn l1_message(queue_index: u64) -> TxL1Message {
+        TxL1Message {
+            queue_index,
+            gas_limit: 21_000,
+            to: Address::random(),
+            value: U256::from(queue_index),
+            sender: Address::random(),
+            input: Bytes::from(queue_index.to_be_bytes()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_index_l1_messages() -> Result<(), L1MessageQueueError> {
+        let l1 = MockL1Provider::default();
+        let factory = create_test_provider_factory();
+        let config = L1MessageQueueConfig::new(QUEUE_ADDRESS).with_confirmations(2);
+        let mut indexer = L1MessageQueueIndexer::new(l1.clone(), factory.clone(), config);
+
+        let messages = (0..3).map(l1_message).collect::<Vec<_>>();
+        l1.mine_block(QUEUE_ADDRESS, &messages[..2]);
+        l1.mine_block(Address::random(), &[l1_message(10)]);
+        l1.mine_block(QUEUE_ADDRESS, &messages[2..]);
+
+        // only blocks with enough confirmations are indexed
+        assert_eq!(
+            indexer.poll().await?,
+            L1MessageQueueOutcome::Indexed { blocks: 0..=1, messages: 2 }
+        );
+        assert_eq!(indexer.poll().await?, L1MessageQueueOutcome::Idle);
+
+        l1.mine_block(QUEUE_ADDRESS, &[]);
+        l1.mine_block(QUEUE_ADDRESS, &[]);
+        assert_eq!(
+            indexer.poll().await?,
+            L1MessageQueueOutcome::Indexed { blocks: 2..=3, messages: 1 }
+        );
+
+        let provider = factory.database_provider_ro()?;
+        assert_eq!(provider.l1_messages(..)?, messages);
+        assert_eq!(provider.next_l1_message_queue_index()?, 3);
+        assert_eq!(provider.last_l1_message_queue_block()?, Some(3));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_index_l1_messages_reorg() -> Result<(), L1MessageQueueError> {
+        let l1 = MockL1Provider::default();
+        let factory = create_test_provider_factory();
+        let config =
+            L1MessageQueueConfig::new(QUEUE_ADDRESS).with_confirmations(0).with_max_block_range(1);
+        let mut indexer = L1MessageQueueIndexer::new(l1.clone(), factory.clone(), config);
+
+        l1.mine_block(QUEUE_ADDRESS, &[l1_message(0)]);
+        l1.mine_block(QUEUE_ADDRESS, &[l1_message(1)]);
+        l1.mine_block(QUEUE_ADDRESS, &[l1_message(2), l1_message(3)]);
+        while indexer.poll().await? != L1MessageQueueOutcome::Idle {}
+
+        // replace the last block
+        l1.reorg(1);
+        let reorged = l1_message(2);
+        l1.mine_block(QUEUE_ADDRESS, &[reorged.clone()]);
+        l1.mine_block(QUEUE_ADDRESS, &[]);
+
+        assert_eq!(
+            indexer.poll().await?,
+            L1MessageQueueOutcome::Reorged { ancestor: 2, removed: 2 }
+        );
+        while indexer.poll().await? != L1MessageQueueOutcome::Idle {}
+
+        let provider = factory.database_provider_ro()?;
+        assert_eq!(provider.next_l1_message_queue_index()?, 3);
+        assert_eq!(provider.l1_message(2)?, Some(reorged));
+        assert_eq!(provider.last_l1_message_queue_block()?, Some(4));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_index_l1_messages_gap() {
+        let l1 = MockL1Provider::default();
+        let factory = create_test_provider_factory();
+        let config = L1MessageQueueConfig::new(QUEUE_ADDRESS).with_confirmations(0);
+        let mut indexer = L1MessageQueueIndexer::new(l1.clone(), factory, config);
+
+        l1.mine_block(QUEUE_ADDRESS, &[l1_message(0), l1_message(2)]);
+        assert!(matches!(
+            indexer.poll().await,
+            Err(L1MessageQueueError::QueueIndexGap { expected: 1, got: 2 })
+        ));
+    }
+}
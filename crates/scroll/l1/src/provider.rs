@@ -0,0 +1,108 @@
+use crate::L1ProviderResult;
+use alloy_consensus::Transaction;
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::{Bytes, TxHash, B256};
+use alloy_provider::Provider;
+use alloy_rpc_types_eth::{BlockTransactionsKind, Filter, Log};
+use std::{future::Future, path::PathBuf};
+
+/// The data of an L1 transaction required by the L1 indexers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct L1Transaction {
+    /// The input of the transaction.
+    pub input: Bytes,
+    /// The versioned hashes of the blobs carried by the transaction.
+    pub blob_versioned_hashes: Vec<B256>,
+}
+
+/// The L1 execution client data required by the L1 indexers.
+pub trait L1Provider: Send + Sync {
+    /// Returns the number of the latest L1 block.
+    fn block_number(&self) -> impl Future<Output = L1ProviderResult<u64>> + Send;
+
+    /// Returns the hash of the L1 block with the given number, or `None` if the block is unknown.
+    fn block_hash(
+        &self,
+        number: u64,
+    ) -> impl Future<Output = L1ProviderResult<Option<B256>>> + Send;
+
+    /// Returns the logs matching the given filter, ordered by block number and log index.
+    fn logs(&self, filter: &Filter) -> impl Future<Output = L1ProviderResult<Vec<Log>>> + Send;
+
+    /// Returns the L1 transaction with the given hash, or `None` if the transaction is unknown.
+    fn transaction(
+        &self,
+        hash: TxHash,
+    ) -> impl Future<Output = L1ProviderResult<Option<L1Transaction>>> + Send;
+}
+
+/// The blobs carried by the L1 transactions, required to derive the L2 blocks of the batches
+/// committed from codec v1.
+///
+/// Blobs are served by the L1 consensus layer rather than by the L1 execution client, and are
+/// pruned by L1 nodes after a few weeks.
+pub trait L1BlobProvider: Send + Sync {
+    /// Returns the blob with the given versioned hash, or `None` if the blob is unknown.
+    fn blob(
+        &self,
+        versioned_hash: B256,
+    ) -> impl Future<Output = L1ProviderResult<Option<Bytes>>> + Send;
+}
+
+/// Follows L1 through a JSON-RPC endpoint.
+impl<P> L1Provider for P
+where
+    P: Provider,
+{
+    async fn block_number(&self) -> L1ProviderResult<u64> {
+        Ok(self.get_block_number().await?)
+    }
+
+    async fn block_hash(&self, number: u64) -> L1ProviderResult<Option<B256>> {
+        let block = self
+            .get_block_by_number(BlockNumberOrTag::Number(number), BlockTransactionsKind::Hashes)
+            .await?;
+        Ok(block.map(|block| block.header.hash))
+    }
+
+    async fn logs(&self, filter: &Filter) -> L1ProviderResult<Vec<Log>> {
+        Ok(self.get_logs(filter).await?)
+    }
+
+    async fn transaction(&self, hash: TxHash) -> L1ProviderResult<Option<L1Transaction>> {
+        let transaction = self.get_transaction_by_hash(hash).await?;
+        Ok(transaction.map(|transaction| L1Transaction {
+            input: transaction.input().clone(),
+            blob_versioned_hashes: transaction
+                .blob_versioned_hashes()
+                .map(Vec::from)
+                .unwrap_or_default(),
+        }))
+    }
+}
+
+/// A local archive of blobs, holding one file per blob named after the hex encoded versioned hash
+/// of the blob, e.g. `0x01…ab.blob`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlobArchive {
+    /// The directory holding the blobs.
+    dir: PathBuf,
+}
+
+impl BlobArchive {
+    /// Returns a new [`BlobArchive`] reading the blobs from the given directory.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+impl L1BlobProvider for BlobArchive {
+    async fn blob(&self, versioned_hash: B256) -> L1ProviderResult<Option<Bytes>> {
+        let path = self.dir.join(format!("{versioned_hash}.blob"));
+        match tokio::fs::read(&path).await {
+            Ok(blob) => Ok(Some(blob.into())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
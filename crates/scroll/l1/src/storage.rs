@@ -0,0 +1,215 @@
+use reth_db::tables::{self, ChainStateKey};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW},
+    transaction::{DbTx, DbTxMut},
+};
+use reth_scroll_primitives::{CommittedBatch, FinalizedBatch, TxL1Message};
+use reth_storage_api::DBProvider;
+use reth_storage_errors::provider::ProviderResult;
+use std::ops::RangeBounds;
+
+/// Reads the local copy of the L1 message queue.
+pub trait L1MessageReader {
+    /// Returns the L1 message with the given queue index.
+    fn l1_message(&self, queue_index: u64) -> ProviderResult<Option<TxL1Message>>;
+
+    /// Returns the L1 messages in the given queue index range, ordered by queue index.
+    fn l1_messages(&self, range: impl RangeBounds<u64>) -> ProviderResult<Vec<TxL1Message>>;
+
+    /// Returns the queue index following the last indexed L1 message.
+    fn next_l1_message_queue_index(&self) -> ProviderResult<u64>;
+
+    /// Returns the number of the last L1 block indexed for the L1 message queue.
+    fn last_l1_message_queue_block(&self) -> ProviderResult<Option<u64>>;
+}
+
+/// Writes the local copy of the L1 message queue.
+pub trait L1MessageWriter {
+    /// Inserts the L1 messages, indexed by queue index.
+    fn insert_l1_messages(
+        &self,
+        messages: impl IntoIterator<Item = TxL1Message>,
+    ) -> ProviderResult<()>;
+
+    /// Removes the L1 messages starting from the given queue index. Returns the number of
+    /// removed messages.
+    fn remove_l1_messages_from(&self, queue_index: u64) -> ProviderResult<usize>;
+
+    /// Saves the number of the last L1 block indexed for the L1 message queue.
+    fn save_last_l1_message_queue_block(&self, block_number: u64) -> ProviderResult<()>;
+}
+
+impl<P: DBProvider> L1MessageReader for P {
+    fn l1_message(&self, queue_index: u64) -> ProviderResult<Option<TxL1Message>> {
+        Ok(self.tx_ref().get::<tables::L1Messages>(queue_index)?)
+    }
+
+    fn l1_messages(&self, range: impl RangeBounds<u64>) -> ProviderResult<Vec<TxL1Message>> {
+        let mut cursor = self.tx_ref().cursor_read::<tables::L1Messages>()?;
+        self.cursor_collect(&mut cursor, range)
+    }
+
+    fn next_l1_message_queue_index(&self) -> ProviderResult<u64> {
+        let last = self.tx_ref().cursor_read::<tables::L1Messages>()?.last()?;
+        Ok(last.map_or(0, |(queue_index, _)| queue_index + 1))
+    }
+
+    fn last_l1_message_queue_block(&self) -> ProviderResult<Option<u64>> {
+        Ok(self.tx_ref().get::<tables::ChainState>(ChainStateKey::LastL1MessageQueueBlock)?)
+    }
+}
+
+impl<P> L1MessageWriter for P
+where
+    P: DBProvider<Tx: DbTxMut>,
+{
+    fn insert_l1_messages(
+        &self,
+        messages: impl IntoIterator<Item = TxL1Message>,
+    ) -> ProviderResult<()> {
+        for message in messages {
+            self.tx_ref().put::<tables::L1Messages>(message.queue_index, message)?;
+        }
+        Ok(())
+    }
+
+    fn remove_l1_messages_from(&self, queue_index: u64) -> ProviderResult<usize> {
+        Ok(self.remove::<tables::L1Messages>(queue_index..)?)
+    }
+
+    fn save_last_l1_message_queue_block(&self, block_number: u64) -> ProviderResult<()> {
+        Ok(self
+            .tx_ref()
+            .put::<tables::ChainState>(ChainStateKey::LastL1MessageQueueBlock, block_number)?)
+    }
+}
+
+/// Reads the batches committed to and finalized on L1.
+pub trait BatchReader {
+    /// Returns the committed batch with the given index.
+    fn committed_batch(&self, batch_index: u64) -> ProviderResult<Option<CommittedBatch>>;
+
+    /// Returns the finalization of the batch with the given index. Only the last batch finalized
+    /// at once is recorded.
+    fn finalized_batch(&self, batch_index: u64) -> ProviderResult<Option<FinalizedBatch>>;
+
+    /// Returns the index and the last committed batch.
+    fn last_committed_batch(&self) -> ProviderResult<Option<(u64, CommittedBatch)>>;
+
+    /// Returns the index and the finalization of the last finalized batch.
+    fn last_finalized_batch(&self) -> ProviderResult<Option<(u64, FinalizedBatch)>>;
+
+    /// Returns the number of the last L2 block committed to L1.
+    fn last_committed_l2_block(&self) -> ProviderResult<Option<u64>> {
+        Ok(self.last_committed_batch()?.map(|(_, batch)| batch.last_block))
+    }
+
+    /// Returns the number of the last L2 block finalized on L1, or `None` if the last finalized
+    /// batch wasn't committed in the indexed L1 blocks.
+    fn last_finalized_l2_block(&self) -> ProviderResult<Option<u64>> {
+        let Some((batch_index, _)) = self.last_finalized_batch()? else { return Ok(None) };
+        Ok(self.committed_batch(batch_index)?.map(|batch| batch.last_block))
+    }
+
+    /// Returns the number of the last L1 block indexed for the `ScrollChain` contract.
+    fn last_scroll_chain_block(&self) -> ProviderResult<Option<u64>>;
+}
+
+/// Writes the batches committed to and finalized on L1.
+pub trait BatchWriter {
+    /// Inserts the committed batch with the given index.
+    fn insert_committed_batch(&self, batch_index: u64, batch: CommittedBatch)
+        -> ProviderResult<()>;
+
+    /// Removes the committed batch with the given index, reverted on L1. Returns whether the
+    /// batch was committed.
+    fn remove_committed_batch(&self, batch_index: u64) -> ProviderResult<bool>;
+
+    /// Inserts the finalization of the batch with the given index.
+    fn insert_finalized_batch(&self, batch_index: u64, batch: FinalizedBatch)
+        -> ProviderResult<()>;
+
+    /// Removes the batch commits and finalizations which happened after the given L1 block.
+    /// Returns the number of removed commits and finalizations.
+    fn unwind_batches_to(&self, l1_block: u64) -> ProviderResult<usize>;
+
+    /// Saves the number of the last L1 block indexed for the `ScrollChain` contract.
+    fn save_last_scroll_chain_block(&self, block_number: u64) -> ProviderResult<()>;
+}
+
+impl<P: DBProvider> BatchReader for P {
+    fn committed_batch(&self, batch_index: u64) -> ProviderResult<Option<CommittedBatch>> {
+        Ok(self.tx_ref().get::<tables::CommittedBatches>(batch_index)?)
+    }
+
+    fn finalized_batch(&self, batch_index: u64) -> ProviderResult<Option<FinalizedBatch>> {
+        Ok(self.tx_ref().get::<tables::FinalizedBatches>(batch_index)?)
+    }
+
+    fn last_committed_batch(&self) -> ProviderResult<Option<(u64, CommittedBatch)>> {
+        Ok(self.tx_ref().cursor_read::<tables::CommittedBatches>()?.last()?)
+    }
+
+    fn last_finalized_batch(&self) -> ProviderResult<Option<(u64, FinalizedBatch)>> {
+        Ok(self.tx_ref().cursor_read::<tables::FinalizedBatches>()?.last()?)
+    }
+
+    fn last_scroll_chain_block(&self) -> ProviderResult<Option<u64>> {
+        Ok(self.tx_ref().get::<tables::ChainState>(ChainStateKey::LastScrollChainBlock)?)
+    }
+}
+
+impl<P> BatchWriter for P
+where
+    P: DBProvider<Tx: DbTxMut>,
+{
+    fn insert_committed_batch(
+        &self,
+        batch_index: u64,
+        batch: CommittedBatch,
+    ) -> ProviderResult<()> {
+        Ok(self.tx_ref().put::<tables::CommittedBatches>(batch_index, batch)?)
+    }
+
+    fn remove_committed_batch(&self, batch_index: u64) -> ProviderResult<bool> {
+        Ok(self.tx_ref().delete::<tables::CommittedBatches>(batch_index, None)?)
+    }
+
+    fn insert_finalized_batch(
+        &self,
+        batch_index: u64,
+        batch: FinalizedBatch,
+    ) -> ProviderResult<()> {
+        Ok(self.tx_ref().put::<tables::FinalizedBatches>(batch_index, batch)?)
+    }
+
+    fn unwind_batches_to(&self, l1_block: u64) -> ProviderResult<usize> {
+        let mut removed = 0;
+
+        let mut cursor = self.tx_ref().cursor_write::<tables::CommittedBatches>()?;
+        while let Some((_, batch)) = cursor.last()? {
+            if batch.l1_block <= l1_block {
+                break
+            }
+            cursor.delete_current()?;
+            removed += 1;
+        }
+
+        let mut cursor = self.tx_ref().cursor_write::<tables::FinalizedBatches>()?;
+        while let Some((_, batch)) = cursor.last()? {
+            if batch.l1_block <= l1_block {
+                break
+            }
+            cursor.delete_current()?;
+            removed += 1;
+        }
+
+        Ok(removed)
+    }
+
+    fn save_last_scroll_chain_block(&self, block_number: u64) -> ProviderResult<()> {
+        Ok(self
+            .tx_ref()
+            .put::<tables::ChainState>(ChainStateKey::LastScrollChainBlock, block_number)?)
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{abi::QueueTransaction, L1BlobProvider, L1Provider, L1ProviderResult, L1Transaction};
+use alloy_eips::eip4844::VERSIONED_HASH_VERSION_KZG;
+use alloy_primitives::{keccak256, map::HashMap, Address, Bytes, TxHash, B256};
+use alloy_rpc_types_eth::{Filter, FilteredParams, Log};
+use alloy_sol_types::SolEvent;
+use parking_lot::Mutex;
+use reth_scroll_primitives::TxL1Message;
+use std::sync::Arc;
+
+/// An in-memory L1 chain, standing in for an L1 node in tests.
+///
+/// The chain starts with an empty genesis block. Blocks are mined on top of the chain with
+/// [`MockL1Provider::mine_block`] and reorged with [`MockL1Provider::reorg`], mined blocks never
+/// reuse the hash of a reorged block.
+#[derive(Debug, Clone)]
+pub struct MockL1Provider {
+    inner: Arc<Mutex<MockL1Chain>>,
+}
+
+#[derive(Debug)]
+struct MockL1Chain {
+    /// The blocks of the chain, indexed by number.
+    blocks: Vec<MockL1Block>,
+    /// The number of blocks mined, including the reorged ones.
+    mined: u64,
+    /// The transactions mined with [`MockL1Provider::mine_block_with_transaction`], by hash.
+    transactions: HashMap<TxHash, L1Transaction>,
+    /// The blobs carried by the mined transactions, by versioned hash.
+    blobs: HashMap<B256, Bytes>,
+}
+
+#[derive(Debug)]
+struct MockL1Block {
+    hash: B256,
+    logs: Vec<Log>,
+}
+
+impl Default for MockL1Provider {
+    fn default() -> Self {
+        let genesis = MockL1Block { hash: keccak256([0u8; 8]), logs: Vec::new() };
+        let chain = MockL1Chain {
+            blocks: vec![genesis],
+            mined: 1,
+            transactions: HashMap::default(),
+            blobs: HashMap::default(),
+        };
+        Self { inner: Arc::new(Mutex::new(chain)) }
+    }
+}
+
+impl MockL1Provider {
+    /// Mines a block emitting a `QueueTransaction` event from `address` for each of the L1
+    /// messages. Returns the number of the block.
+    pub fn mine_block(&self, address: Address, messages: &[TxL1Message]) -> u64 {
+        let logs = messages
+            .iter()
+            .map(|message| alloy_primitives::Log {
+                address,
+                data: QueueTransaction::from(message).encode_log_data(),
+            })
+            .collect::<Vec<_>>();
+        self.mine_block_with_logs(logs)
+    }
+
+    /// Mines a block emitting the logs. Returns the number of the block.
+    pub fn mine_block_with_logs(&self, logs: Vec<alloy_primitives::Log>) -> u64 {
+        self.mine(logs, None, None)
+    }
+
+    /// Mines a block with a single transaction with the given input, emitting the logs. Returns
+    /// the number of the block.
+    pub fn mine_block_with_transaction(
+        &self,
+        input: Bytes,
+        logs: Vec<alloy_primitives::Log>,
+    ) -> u64 {
+        self.mine(logs, Some(input), None)
+    }
+
+    /// Mines a block with a single transaction with the given input and carrying the blob,
+    /// emitting the logs. Returns the number of the block.
+    pub fn mine_block_with_blob_transaction(
+        &self,
+        input: Bytes,
+        blob: Bytes,
+        logs: Vec<alloy_primitives::Log>,
+    ) -> u64 {
+        self.mine(logs, Some(input), Some(blob))
+    }
+
+    fn mine(
+        &self,
+        logs: Vec<alloy_primitives::Log>,
+        input: Option<Bytes>,
+        blob: Option<Bytes>,
+    ) -> u64 {
+        let mut chain = self.inner.lock();
+        let number = chain.blocks.len() as u64;
+        let hash = keccak256(chain.mined.to_be_bytes());
+        chain.mined += 1;
+
+        let transaction_hash = input.map(|input| {
+            let mut transaction = L1Transaction { input, blob_versioned_hashes: Vec::new() };
+            if let Some(blob) = blob {
+                // the versioned hash of a blob is derived from its KZG commitment, which isn't
+                // computed by the mock
+                let mut versioned_hash = keccak256(&blob);
+                versioned_hash[0] = VERSIONED_HASH_VERSION_KZG;
+                transaction.blob_versioned_hashes.push(versioned_hash);
+                chain.blobs.insert(versioned_hash, blob);
+            }
+            let transaction_hash = keccak256(hash);
+            chain.transactions.insert(transaction_hash, transaction);
+            transaction_hash
+        });
+
+        let logs = logs
+            .into_iter()
+            .enumerate()
+            .map(|(index, inner)| Log {
+                inner,
+                block_hash: Some(hash),
+                block_number: Some(number),
+                transaction_hash,
+                transaction_index: Some(index as u64),
+                log_index: Some(index as u64),
+                ..Default::default()
+            })
+            .collect();
+        chain.blocks.push(MockL1Block { hash, logs });
+        number
+    }
+
+    /// Removes the last `depth` blocks of the chain.
+    pub fn reorg(&self, depth: u64) {
+        let mut chain = self.inner.lock();
+        let len = chain.blocks.len().saturating_sub(depth as usize).max(1);
+        chain.blocks.truncate(len);
+    }
+}
+
+impl L1Provider for MockL1Provider {
+    async fn block_number(&self) -> L1ProviderResult<u64> {
+        Ok(self.inner.lock().blocks.len() as u64 - 1)
+    }
+
+    async fn block_hash(&self, number: u64) -> L1ProviderResult<Option<B256>> {
+        Ok(self.inner.lock().blocks.get(number as usize).map(|block| block.hash))
+    }
+
+    async fn logs(&self, filter: &Filter) -> L1ProviderResult<Vec<Log>> {
+        let params = FilteredParams::new(Some(filter.clone()));
+        let chain = self.inner.lock();
+        Ok(chain
+            .blocks
+            .iter()
+            .flat_map(|block| block.logs.iter())
+            .filter(|log| {
+                log.block_number.is_some_and(|number| params.filter_block_range(number)) &&
+                    params.filter_address(&log.address()) &&
+                    params.filter_topics(log.topics())
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn transaction(&self, hash: TxHash) -> L1ProviderResult<Option<L1Transaction>> {
+        Ok(self.inner.lock().transactions.get(&hash).cloned())
+    }
+}
+
+impl L1BlobProvider for MockL1Provider {
+    async fn blob(&self, versioned_hash: B256) -> L1ProviderResult<Option<Bytes>> {
+        Ok(self.inner.lock().blobs.get(&versioned_hash).cloned())
+    }
+}
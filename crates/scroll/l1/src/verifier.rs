@@ -0,0 +1,153 @@
+//! Verification of the local chain against the state roots finalized on L1.
+
+use crate::{BatchReader, StateRootVerifierError};
+use metrics::{Counter, Gauge};
+use reth_metrics::Metrics;
+use reth_storage_api::{DatabaseProviderFactory, HeaderProvider};
+use reth_storage_errors::provider::ProviderError;
+use tracing::{debug, error};
+
+/// Metrics of the [`StateRootVerifier`].
+#[derive(Metrics)]
+#[metrics(scope = "scroll.state_root_verifier")]
+struct StateRootVerifierMetrics {
+    /// The number of finalized batches whose state root matched the local chain.
+    verified_batches: Counter,
+    /// The index of the last finalized batch whose state root matched the local chain.
+    last_verified_batch: Gauge,
+    /// The number of finalized batches whose state root didn't match the local chain.
+    state_root_mismatches: Counter,
+}
+
+/// Checks the state roots of the local chain against the state roots of the batches finalized on
+/// L1, as indexed by the [`BatchIndexer`](crate::BatchIndexer).
+///
+/// The state root after the last block of each finalized batch must match the state root
+/// finalized on L1. Only the last batch finalized at once carries a state root, the previous ones
+/// are skipped.
+#[derive(Debug)]
+pub struct StateRootVerifier<F> {
+    /// The factory of the database providers storing the batches and the local chain.
+    factory: F,
+    /// The index of the next batch to verify once finalized.
+    next_batch: u64,
+    /// The verifier metrics.
+    metrics: StateRootVerifierMetrics,
+}
+
+impl<F> StateRootVerifier<F>
+where
+    F: DatabaseProviderFactory + HeaderProvider,
+{
+    /// Returns a new [`StateRootVerifier`], verifying all the finalized batches from the genesis
+    /// batch.
+    pub fn new(factory: F) -> Self {
+        Self { factory, next_batch: 0, metrics: StateRootVerifierMetrics::default() }
+    }
+
+    /// Returns the index of the next batch to verify once finalized.
+    pub const fn next_batch(&self) -> u64 {
+        self.next_batch
+    }
+
+    /// Verifies the finalized batches whose last block is at most `head`, in order. Returns the
+    /// number of verified batches.
+    pub fn verify(&mut self, head: u64) -> Result<usize, StateRootVerifierError> {
+        let provider = self.factory.database_provider_ro()?;
+        let Some((last_finalized, _)) = provider.last_finalized_batch()? else { return Ok(0) };
+
+        let mut verified = 0;
+        while self.next_batch <= last_finalized {
+            let batch_index = self.next_batch;
+            let finalized = provider.finalized_batch(batch_index)?;
+            let committed = provider.committed_batch(batch_index)?;
+            if let Some((finalized, committed)) = finalized.zip(committed) {
+                if committed.last_block > head {
+                    break
+                }
+                let header = self
+                    .factory
+                    .sealed_header(committed.last_block)?
+                    .ok_or_else(|| ProviderError::HeaderNotFound(committed.last_block.into()))?;
+                if header.state_root != finalized.state_root {
+                    self.metrics.state_root_mismatches.increment(1);
+                    error!(target: "scroll::l1::verifier", batch_index, number = header.number, got = %header.state_root, expected = %finalized.state_root, "state root mismatch");
+                    return Err(StateRootVerifierError::StateRootMismatch {
+                        batch_index,
+                        number: header.number,
+                        got: header.state_root,
+                        expected: finalized.state_root,
+                    })
+                }
+
+                debug!(target: "scroll::l1::verifier", batch_index, number = header.number, state_root = %header.state_root, "verified finalized batch");
+                self.metrics.verified_batches.increment(1);
+                self.metrics.last_verified_batch.set(batch_index as f64);
+                verified += 1;
+            }
+            self.next_batch += 1;
+        }
+
+        Ok(verified)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BatchWriter;
+    use alloy_primitives::B256;
+    use reth_primitives::SealedBlockWithSenders;
+    use reth_provider::{test_utils::create_test_provider_factory, BlockWriter, StorageLocation};
+    use reth_scroll_primitives::{CommittedBatch, FinalizedBatch};
+
+    #[test]
+    fn test_verify_state_roots() -> Result<(), StateRootVerifierError> {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw()?;
+        provider.insert_block(SealedBlockWithSenders::default(), StorageLocation::Database)?;
+        provider.commit()?;
+        let genesis = factory.sealed_header(0)?.unwrap();
+        let mut verifier = StateRootVerifier::new(factory.clone());
+        assert_eq!(verifier.verify(0)?, 0);
+
+        // the genesis batch is finalized with the genesis state root
+        let provider = factory.database_provider_rw()?;
+        provider.insert_committed_batch(0, CommittedBatch::default())?;
+        let batch = FinalizedBatch { state_root: genesis.state_root, ..Default::default() };
+        provider.insert_finalized_batch(0, batch)?;
+        provider.commit()?;
+        assert_eq!(verifier.verify(0)?, 1);
+        assert_eq!(verifier.next_batch(), 1);
+
+        // the batch 2 is finalized along the batch 1, whose blocks aren't part of the local chain
+        let provider = factory.database_provider_rw()?;
+        let batch = CommittedBatch { first_block: 1, last_block: 1, ..Default::default() };
+        provider.insert_committed_batch(1, batch)?;
+        let batch = CommittedBatch { first_block: 2, last_block: 2, ..Default::default() };
+        provider.insert_committed_batch(2, batch)?;
+        let batch = FinalizedBatch { state_root: B256::repeat_byte(1), ..Default::default() };
+        provider.insert_finalized_batch(2, batch)?;
+        provider.commit()?;
+        assert_eq!(verifier.verify(0)?, 0);
+        assert_eq!(verifier.next_batch(), 2);
+
+        // the last block of the batch 2 isn't part of the local chain
+        assert!(matches!(
+            verifier.verify(2),
+            Err(StateRootVerifierError::Provider(ProviderError::HeaderNotFound(_)))
+        ));
+        let provider = factory.database_provider_rw()?;
+        let batch = CommittedBatch { first_block: 0, last_block: 0, ..Default::default() };
+        provider.insert_committed_batch(2, batch)?;
+        provider.commit()?;
+
+        // the state root of the genesis block doesn't match the one finalized for batch 2
+        assert!(matches!(
+            verifier.verify(2),
+            Err(StateRootVerifierError::StateRootMismatch { batch_index: 2, number: 0, .. })
+        ));
+
+        Ok(())
+    }
+}
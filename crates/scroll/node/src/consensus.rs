@@ -1,14 +1,23 @@
-use reth_consensus::noop::NoopConsensus;
+use crate::{ScrollL1MessageQueue, ScrollPrimitives};
 use reth_node_builder::{components::ConsensusBuilder, BuilderContext, FullNodeTypes};
+use reth_node_types::NodeTypes;
+use reth_scroll_chainspec::ScrollChainSpec;
+use reth_scroll_consensus::ScrollConsensus;
+use std::sync::Arc;
 
 /// The consensus builder for Scroll.
 #[derive(Debug)]
 pub struct ScrollConsensusBuilder;
 
-impl<Node: FullNodeTypes> ConsensusBuilder<Node> for ScrollConsensusBuilder {
-    type Consensus = NoopConsensus;
+impl<Node> ConsensusBuilder<Node> for ScrollConsensusBuilder
+where
+    Node:
+        FullNodeTypes<Types: NodeTypes<ChainSpec = ScrollChainSpec, Primitives = ScrollPrimitives>>,
+{
+    type Consensus = Arc<dyn reth_consensus::Consensus>;
 
-    async fn build_consensus(self, _ctx: &BuilderContext<Node>) -> eyre::Result<Self::Consensus> {
-        Ok(NoopConsensus::default())
+    async fn build_consensus(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Consensus> {
+        let l1_message_queue = ScrollL1MessageQueue::new(ctx.provider().clone());
+        Ok(Arc::new(ScrollConsensus::new(ctx.chain_spec()).with_l1_message_queue(l1_message_queue)))
     }
 }
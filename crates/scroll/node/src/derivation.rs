@@ -0,0 +1,328 @@
+use alloy_consensus::{Header, EMPTY_OMMER_ROOT_HASH};
+use alloy_eips::eip2718::Decodable2718;
+use alloy_primitives::{B256, U256};
+use alloy_rpc_types_engine::{ForkchoiceState, PayloadStatusEnum};
+use reth_ethereum_engine_primitives::EthEngineTypes;
+use reth_evm::execute::{
+    BlockExecutionError, BlockExecutionStrategy, BlockExecutionStrategyFactory, ExecuteOutput,
+};
+use reth_primitives::{
+    proofs, Block, BlockBody, BlockExt, BlockWithSenders, SealedBlock, SealedHeader, Transaction,
+    TransactionSigned,
+};
+use reth_primitives_traits::SignedTransaction;
+use reth_provider::{
+    BlockNumReader, ChainSpecProvider, DatabaseProviderFactory, ExecutionOutcome,
+    HashedPostStateProvider, HeaderProvider, ProviderError, StateProviderFactory,
+    StateRootProvider,
+};
+use reth_rpc_api::EngineApiClient;
+use reth_rpc_types_compat::engine::payload::block_to_payload_v1;
+use reth_scroll_chainspec::ScrollChainSpec;
+use reth_scroll_evm::ScrollExecutionStrategyFactory;
+use reth_scroll_forks::ScrollHardforks;
+use reth_scroll_l1::{
+    BatchDerivationError, BatchDeriver, BatchReader, DerivedBlock, L1BlobProvider, L1Provider,
+    StateRootVerifier, StateRootVerifierError,
+};
+use reth_scroll_primitives::TxL1Message;
+use reth_scroll_storage::ScrollStateProviderDatabase;
+use reth_tracing::tracing::{debug, error, info};
+use std::time::Duration;
+
+/// The default interval at which the derivation pipeline checks for new batches.
+pub const DEFAULT_DERIVATION_POLL_INTERVAL: Duration = Duration::from_secs(12);
+
+/// Errors returned by the [`ScrollDerivationPipeline`].
+#[derive(Debug, thiserror::Error)]
+pub enum DerivationError {
+    /// The blocks of the batch could not be derived from L1.
+    #[error(transparent)]
+    Derivation(#[from] BatchDerivationError),
+    /// An error occurred while reading the local chain.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    /// A derived block could not be executed.
+    #[error(transparent)]
+    Execution(#[from] BlockExecutionError),
+    /// An L2 transaction committed to L1 could not be decoded.
+    #[error("failed to decode L2 transaction: {0}")]
+    Transaction(#[from] alloy_eips::eip2718::Eip2718Error),
+    /// The signer of an L2 transaction committed to L1 could not be recovered.
+    #[error("invalid signature of L2 transaction {0}")]
+    InvalidSignature(B256),
+    /// The engine API request failed.
+    #[error(transparent)]
+    EngineApi(#[from] jsonrpsee::core::ClientError),
+    /// The engine rejected the derived block.
+    #[error("derived block {number} rejected by the engine: {status}")]
+    InvalidBlock {
+        /// The number of the block.
+        number: u64,
+        /// The status of the block returned by the engine.
+        status: PayloadStatusEnum,
+    },
+    /// The local chain could not be checked against the state roots finalized on L1.
+    #[error(transparent)]
+    Verification(#[from] StateRootVerifierError),
+}
+
+/// Rebuilds the Scroll chain purely from the batches committed to L1, without any L2 peer.
+///
+/// The blocks of each batch are derived by the [`BatchDeriver`], executed on top of the local
+/// head with the [`ScrollExecutionStrategy`](reth_scroll_evm::ScrollExecutionStrategy) to seal
+/// them and inserted through the engine API, which validates them like any other block. Once a
+/// batch is finalized on L1, the state root after its last block is checked against the state
+/// root finalized on L1, a mismatch stops the derivation.
+///
+/// The derived blocks carry the transactions, timestamps, gas limits and base fees committed to
+/// L1 but not the header fields which aren't, e.g. the extra data, so their hashes differ from
+/// the ones of the blocks of the sequencer. Batches reverted on L1 after their blocks were
+/// derived aren't unwound.
+#[derive(Debug)]
+pub struct ScrollDerivationPipeline<P, B, Provider, Engine> {
+    /// The deriver of the blocks of the committed batches.
+    deriver: BatchDeriver<P, B, Provider>,
+    /// The provider of the local chain.
+    provider: Provider,
+    /// The factory of the execution strategies of the derived blocks.
+    strategy_factory: ScrollExecutionStrategyFactory,
+    /// The engine API client used to insert the derived blocks.
+    engine: Engine,
+    /// The interval at which new batches are checked for.
+    poll_interval: Duration,
+    /// The index of the next batch to derive, unknown until the first poll.
+    next_batch: Option<u64>,
+    /// The verifier of the state roots of the finalized batches.
+    verifier: StateRootVerifier<Provider>,
+}
+
+impl<P, B, Provider, Engine> ScrollDerivationPipeline<P, B, Provider, Engine>
+where
+    P: L1Provider,
+    B: L1BlobProvider,
+    Provider: DatabaseProviderFactory
+        + StateProviderFactory
+        + HashedPostStateProvider
+        + HeaderProvider
+        + BlockNumReader
+        + ChainSpecProvider<ChainSpec = ScrollChainSpec>
+        + Clone,
+    Engine: EngineApiClient<EthEngineTypes> + Send + Sync,
+{
+    /// Returns a new [`ScrollDerivationPipeline`] deriving the batches indexed in the local chain
+    /// from the given L1 and blob providers.
+    pub fn new(l1_provider: P, blob_provider: B, provider: Provider, engine: Engine) -> Self {
+        let chain_spec = provider.chain_spec();
+        let address = chain_spec.config.l1_config.scroll_chain_address;
+        Self {
+            deriver: BatchDeriver::new(l1_provider, blob_provider, provider.clone(), address),
+            verifier: StateRootVerifier::new(provider.clone()),
+            provider,
+            strategy_factory: ScrollExecutionStrategyFactory::new(chain_spec),
+            engine,
+            poll_interval: DEFAULT_DERIVATION_POLL_INTERVAL,
+            next_batch: None,
+        }
+    }
+
+    /// Sets the interval at which new batches are checked for.
+    pub const fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Derives the batches at the configured interval, until the state root of a finalized batch
+    /// doesn't match L1.
+    pub async fn run(mut self) {
+        info!(target: "scroll::derivation", "starting L1 derivation");
+        let mut interval = tokio::time::interval(self.poll_interval);
+        loop {
+            interval.tick().await;
+            // derive the available batches one by one
+            loop {
+                match self.poll().await {
+                    Ok(Some(batch_index)) => {
+                        debug!(target: "scroll::derivation", batch_index, "derived batch");
+                    }
+                    Ok(None) => break,
+                    Err(
+                        err @ DerivationError::Verification(
+                            StateRootVerifierError::StateRootMismatch { .. },
+                        ),
+                    ) => {
+                        error!(target: "scroll::derivation", %err, "local chain diverged from L1, stopping L1 derivation");
+                        return
+                    }
+                    Err(err) => {
+                        error!(target: "scroll::derivation", %err, "failed to derive batch");
+                        break
+                    }
+                }
+            }
+        }
+    }
+
+    /// Derives the blocks of the next committed batch which aren't part of the local chain yet
+    /// and checks the state roots of the finalized batches. Returns the index of the derived
+    /// batch, or `None` if the next batch wasn't committed yet.
+    pub async fn poll(&mut self) -> Result<Option<u64>, DerivationError> {
+        self.verify_finalized_batches()?;
+
+        let head = self.provider.best_block_number()?;
+        let batch_index = match self.next_batch {
+            Some(batch_index) => batch_index,
+            None => match self.first_batch_after(head)? {
+                Some(batch_index) => batch_index,
+                None => return Ok(None),
+            },
+        };
+        let Some(batch) = self.deriver.derive(batch_index).await? else {
+            self.next_batch = Some(batch_index);
+            return Ok(None)
+        };
+
+        for block in batch.blocks {
+            if block.context.number <= head {
+                continue
+            }
+            let parent_number = block.context.number - 1;
+            let parent = self
+                .provider
+                .sealed_header(parent_number)?
+                .ok_or_else(|| ProviderError::HeaderNotFound(parent_number.into()))?;
+            let block = self.execute(parent, block)?;
+            self.insert(block).await?;
+        }
+        self.next_batch = Some(batch_index + 1);
+
+        self.verify_finalized_batches()?;
+        Ok(Some(batch_index))
+    }
+
+    /// Returns the index of the first committed batch with blocks after the given block, by
+    /// walking back from the last committed batch, or `None` if no batch was committed yet.
+    fn first_batch_after(&self, number: u64) -> Result<Option<u64>, DerivationError> {
+        let provider = self.provider.database_provider_ro()?;
+        let Some((mut batch_index, last_batch)) = provider.last_committed_batch()? else {
+            return Ok(None)
+        };
+        if last_batch.last_block <= number {
+            return Ok(Some(batch_index + 1))
+        }
+        // the batches committed before the start block of the batch indexer are unknown
+        while batch_index > 0 {
+            match provider.committed_batch(batch_index - 1)? {
+                Some(batch) if batch.last_block > number => batch_index -= 1,
+                _ => break,
+            }
+        }
+        Ok(Some(batch_index))
+    }
+
+    /// Checks the state roots of the batches finalized on L1 whose blocks are part of the local
+    /// chain against the local chain.
+    fn verify_finalized_batches(&mut self) -> Result<(), DerivationError> {
+        let head = self.provider.best_block_number()?;
+        self.verifier.verify(head)?;
+        Ok(())
+    }
+
+    /// Executes the derived block on top of its parent and seals it.
+    fn execute(
+        &self,
+        parent: SealedHeader,
+        block: DerivedBlock,
+    ) -> Result<SealedBlock, DerivationError> {
+        let DerivedBlock { context, l1_messages, transactions: l2_transactions } = block;
+        let chain_spec = self.provider.chain_spec();
+
+        let mut transactions = Vec::with_capacity(l1_messages.len() + l2_transactions.len());
+        let mut senders = Vec::with_capacity(transactions.capacity());
+        for l1_message in l1_messages {
+            senders.push(l1_message.sender);
+            transactions.push(TransactionSigned::new_unhashed(
+                Transaction::L1Message(l1_message),
+                TxL1Message::signature(),
+            ));
+        }
+        for transaction in l2_transactions {
+            let transaction = TransactionSigned::decode_2718(&mut transaction.as_ref())?;
+            let sender = transaction
+                .recover_signer()
+                .ok_or_else(|| DerivationError::InvalidSignature(transaction.hash()))?;
+            senders.push(sender);
+            transactions.push(transaction);
+        }
+
+        let number = context.number;
+        let header = Header {
+            parent_hash: parent.hash(),
+            ommers_hash: EMPTY_OMMER_ROOT_HASH,
+            beneficiary: chain_spec.config.fee_vault_address.unwrap_or_default(),
+            transactions_root: proofs::calculate_transaction_root(&transactions),
+            timestamp: context.timestamp,
+            base_fee_per_gas: chain_spec
+                .is_curie_active_at_block(number)
+                .then(|| context.base_fee.saturating_to()),
+            number,
+            gas_limit: context.gas_limit,
+            ..Default::default()
+        };
+        let body = BlockBody { transactions, ommers: Vec::new(), withdrawals: None };
+        let block = BlockWithSenders::new_unchecked(Block { header, body }, senders);
+
+        let state_provider = self.provider.state_by_block_hash(parent.hash())?;
+        let mut strategy = self
+            .strategy_factory
+            .create_strategy(ScrollStateProviderDatabase::new(&state_provider));
+        strategy.apply_pre_execution_changes(&block, U256::MAX)?;
+        let ExecuteOutput { receipts, gas_used } =
+            strategy.execute_transactions(&block, U256::MAX)?;
+        strategy.apply_post_execution_changes(&block, U256::MAX, &receipts)?;
+        let state = strategy.finish();
+
+        let outcome = ExecutionOutcome::new(
+            state,
+            vec![receipts.into_iter().map(Some).collect()].into(),
+            number,
+            Vec::new(),
+        );
+        let hashed_state = self.provider.hashed_post_state(outcome.state());
+
+        let BlockWithSenders { mut block, .. } = block;
+        block.header.state_root = state_provider.state_root(hashed_state)?;
+        block.header.receipts_root =
+            outcome.receipts_root_slow(number).expect("number is in range");
+        block.header.logs_bloom = outcome.block_logs_bloom(number).expect("number is in range");
+        block.header.gas_used = gas_used;
+
+        Ok(block.seal_slow())
+    }
+
+    /// Inserts the sealed block through the engine API and makes it the head of the chain.
+    async fn insert(&self, block: SealedBlock) -> Result<(), DerivationError> {
+        let number = block.number;
+        let hash = block.hash();
+
+        let status = self.engine.new_payload_v1(block_to_payload_v1(block)).await?;
+        if !status.is_valid() {
+            return Err(DerivationError::InvalidBlock { number, status: status.status })
+        }
+
+        // the safe and finalized blocks are driven by the batch indexer
+        let state = ForkchoiceState {
+            head_block_hash: hash,
+            safe_block_hash: B256::ZERO,
+            finalized_block_hash: B256::ZERO,
+        };
+        let updated = self.engine.fork_choice_updated_v1(state, None).await?;
+        if !updated.is_valid() {
+            let status = updated.payload_status.status;
+            return Err(DerivationError::InvalidBlock { number, status })
+        }
+
+        debug!(target: "scroll::derivation", number, %hash, "inserted derived block");
+        Ok(())
+    }
+}
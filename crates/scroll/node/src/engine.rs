@@ -1,4 +1,7 @@
-use alloy_rpc_types_engine::{ExecutionPayload, ExecutionPayloadSidecar, PayloadError};
+use alloy_primitives::U256;
+use alloy_rpc_types_engine::{
+    ExecutionPayload, ExecutionPayloadSidecar, ExecutionPayloadV1, PayloadError,
+};
 use reth_ethereum_engine_primitives::EthPayloadAttributes;
 use reth_node_builder::{
     rpc::EngineValidatorBuilder, AddOnsContext, EngineApiMessageVersion,
@@ -6,8 +9,12 @@ use reth_node_builder::{
     PayloadOrAttributes,
 };
 use reth_node_types::NodeTypesWithEngine;
-use reth_primitives::{Block, SealedBlock};
+use reth_payload_primitives::{MessageValidationKind, VersionSpecificValidationError};
+use reth_primitives::{Block, BlockExt, SealedBlock};
+use reth_rpc_types_compat::engine::payload::try_payload_v1_to_block;
 use reth_scroll_chainspec::ScrollChainSpec;
+use reth_scroll_forks::ScrollHardforks;
+use std::sync::Arc;
 
 /// Builder for [`ScrollEngineValidator`].
 #[derive(Debug, Default, Clone)]
@@ -17,20 +24,63 @@ impl<Node, Types> EngineValidatorBuilder<Node> for ScrollEngineValidatorBuilder
 where
     Types: NodeTypesWithEngine<ChainSpec = ScrollChainSpec>,
     Node: FullNodeComponents<Types = Types>,
-    NoopEngineValidator: EngineValidator<Types::Engine>,
+    ScrollEngineValidator: EngineValidator<Types::Engine>,
 {
-    type Validator = NoopEngineValidator;
+    type Validator = ScrollEngineValidator;
 
-    async fn build(self, _ctx: &AddOnsContext<'_, Node>) -> eyre::Result<Self::Validator> {
-        Ok(NoopEngineValidator)
+    async fn build(self, ctx: &AddOnsContext<'_, Node>) -> eyre::Result<Self::Validator> {
+        Ok(ScrollEngineValidator::new(ctx.config.chain.clone()))
     }
 }
 
-/// Noop engine validator used as default for Scroll.
+/// Validator for the Scroll engine API.
+///
+/// Scroll blocks don't support withdrawals, blobs or the beacon block root, which restricts the
+/// engine API to its V1 and V2 methods, without any withdrawals.
 #[derive(Debug, Clone)]
-pub struct NoopEngineValidator;
+pub struct ScrollEngineValidator {
+    /// Chain spec to validate against.
+    chain_spec: Arc<ScrollChainSpec>,
+}
+
+impl ScrollEngineValidator {
+    /// Instantiates a new validator.
+    pub const fn new(chain_spec: Arc<ScrollChainSpec>) -> Self {
+        Self { chain_spec }
+    }
+
+    /// Returns the chain spec used by the validator.
+    #[inline]
+    pub fn chain_spec(&self) -> &ScrollChainSpec {
+        &self.chain_spec
+    }
 
-impl<Types> EngineValidator<Types> for NoopEngineValidator
+    /// Converts the [`ExecutionPayloadV1`] into a [`Block`].
+    ///
+    /// Before [`Curie`](reth_scroll_forks::ScrollHardfork::Curie), Scroll blocks don't have a base
+    /// fee, which is encoded as a zero base fee in the payload.
+    fn try_payload_v1_to_block(
+        &self,
+        mut payload: ExecutionPayloadV1,
+    ) -> Result<Block, PayloadError> {
+        if self.chain_spec.is_curie_active_at_block(payload.block_number) {
+            return try_payload_v1_to_block(payload)
+        }
+
+        if !payload.base_fee_per_gas.is_zero() {
+            return Err(PayloadError::BaseFee(payload.base_fee_per_gas))
+        }
+
+        // the conversion rejects zero base fees, set a placeholder value and remove it afterwards
+        payload.base_fee_per_gas = U256::from(1);
+        let mut block = try_payload_v1_to_block(payload)?;
+        block.header.base_fee_per_gas = None;
+
+        Ok(block)
+    }
+}
+
+impl<Types> EngineValidator<Types> for ScrollEngineValidator
 where
     Types: EngineTypes<PayloadAttributes = EthPayloadAttributes>,
 {
@@ -38,25 +88,192 @@ where
 
     fn validate_version_specific_fields(
         &self,
-        _version: EngineApiMessageVersion,
-        _payload_or_attrs: PayloadOrAttributes<'_, EthPayloadAttributes>,
+        version: EngineApiMessageVersion,
+        payload_or_attrs: PayloadOrAttributes<'_, EthPayloadAttributes>,
     ) -> Result<(), EngineObjectValidationError> {
-        Ok(())
+        validate_scroll_version_specific_fields(
+            version,
+            payload_or_attrs.message_validation_kind(),
+            payload_or_attrs.withdrawals().is_some(),
+            payload_or_attrs.parent_beacon_block_root().is_some(),
+        )
     }
 
     fn ensure_well_formed_attributes(
         &self,
-        _version: EngineApiMessageVersion,
-        _attributes: &EthPayloadAttributes,
+        version: EngineApiMessageVersion,
+        attributes: &EthPayloadAttributes,
     ) -> Result<(), EngineObjectValidationError> {
-        Ok(())
+        validate_scroll_version_specific_fields(
+            version,
+            MessageValidationKind::PayloadAttributes,
+            attributes.withdrawals.is_some(),
+            attributes.parent_beacon_block_root.is_some(),
+        )
     }
 
     fn ensure_well_formed_payload(
         &self,
-        _payload: ExecutionPayload,
-        _sidecar: ExecutionPayloadSidecar,
+        payload: ExecutionPayload,
+        sidecar: ExecutionPayloadSidecar,
     ) -> Result<SealedBlock, PayloadError> {
-        Ok(SealedBlock::default())
+        let expected_hash = payload.block_hash();
+
+        let payload = match payload {
+            ExecutionPayload::V1(payload) => payload,
+            // withdrawals are not supported on Scroll
+            ExecutionPayload::V2(_) => return Err(PayloadError::PreShanghaiBlockWithWithdrawals),
+            // blobs are not supported on Scroll
+            ExecutionPayload::V3(_) => return Err(PayloadError::PreCancunBlockWithBlobGasUsed),
+        };
+        if sidecar.cancun().is_some() {
+            return Err(PayloadError::PreCancunWithCancunFields)
+        }
+
+        // First parse the block
+        let sealed_block = self.try_payload_v1_to_block(payload)?.seal_slow();
+
+        // Ensure the hash included in the payload matches the block hash
+        if expected_hash != sealed_block.hash() {
+            return Err(PayloadError::BlockHash {
+                execution: sealed_block.hash(),
+                consensus: expected_hash,
+            })
+        }
+
+        if sealed_block.has_blob_transactions() {
+            return Err(PayloadError::PreCancunBlockWithBlobTransactions)
+        }
+        if sealed_block.has_eip7702_transactions() {
+            return Err(PayloadError::PrePragueBlockWithEip7702Transactions)
+        }
+
+        Ok(sealed_block)
+    }
+}
+
+/// Validates the presence of the version specific fields for Scroll.
+///
+/// Scroll doesn't activate any of the Shanghai withdrawals, Cancun or Prague engine changes: the
+/// withdrawals and parent beacon block root must always be absent and the V3 and V4 methods are
+/// unsupported.
+fn validate_scroll_version_specific_fields(
+    version: EngineApiMessageVersion,
+    kind: MessageValidationKind,
+    has_withdrawals: bool,
+    has_parent_beacon_block_root: bool,
+) -> Result<(), EngineObjectValidationError> {
+    match version {
+        EngineApiMessageVersion::V1 | EngineApiMessageVersion::V2 => {
+            if has_withdrawals {
+                let error = if version == EngineApiMessageVersion::V1 {
+                    VersionSpecificValidationError::WithdrawalsNotSupportedInV1
+                } else {
+                    VersionSpecificValidationError::HasWithdrawalsPreShanghai
+                };
+                return Err(kind.to_error(error))
+            }
+            if has_parent_beacon_block_root {
+                return Err(kind.to_error(
+                    VersionSpecificValidationError::ParentBeaconBlockRootNotSupportedBeforeV3,
+                ))
+            }
+        }
+        EngineApiMessageVersion::V3 | EngineApiMessageVersion::V4 => {
+            return Err(EngineObjectValidationError::UnsupportedFork)
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use reth_ethereum_engine_primitives::EthEngineTypes;
+    use reth_primitives::BlockBody;
+    use reth_rpc_types_compat::engine::payload::block_to_payload_v1;
+    use reth_scroll_chainspec::SCROLL_MAINNET;
+
+    const CURIE_BLOCK_NUMBER: u64 = 7096836;
+
+    fn validator() -> ScrollEngineValidator {
+        ScrollEngineValidator::new(SCROLL_MAINNET.clone())
+    }
+
+    fn ensure_well_formed_payload(payload: ExecutionPayload) -> Result<SealedBlock, PayloadError> {
+        <ScrollEngineValidator as EngineValidator<EthEngineTypes>>::ensure_well_formed_payload(
+            &validator(),
+            payload,
+            ExecutionPayloadSidecar::none(),
+        )
+    }
+
+    fn sealed_block(header: Header) -> SealedBlock {
+        Block { header, body: BlockBody::default() }.seal_slow()
+    }
+
+    #[test]
+    fn test_pre_curie_payload_without_base_fee() {
+        let block = sealed_block(Header { number: CURIE_BLOCK_NUMBER - 1, ..Default::default() });
+        let payload = ExecutionPayload::V1(block_to_payload_v1(block.clone()));
+
+        assert_eq!(ensure_well_formed_payload(payload).unwrap(), block);
+    }
+
+    #[test]
+    fn test_curie_payload_with_base_fee() {
+        let block = sealed_block(Header {
+            number: CURIE_BLOCK_NUMBER,
+            base_fee_per_gas: Some(1),
+            ..Default::default()
+        });
+        let payload = ExecutionPayload::V1(block_to_payload_v1(block.clone()));
+
+        assert_eq!(ensure_well_formed_payload(payload).unwrap(), block);
+    }
+
+    #[test]
+    fn test_invalid_block_hash() {
+        let block = sealed_block(Header { number: 1, ..Default::default() });
+        let mut payload = block_to_payload_v1(block);
+        payload.gas_used += 1;
+
+        assert!(matches!(
+            ensure_well_formed_payload(ExecutionPayload::V1(payload)),
+            Err(PayloadError::BlockHash { .. })
+        ));
+    }
+
+    #[test]
+    fn test_version_specific_fields() {
+        assert!(validate_scroll_version_specific_fields(
+            EngineApiMessageVersion::V1,
+            MessageValidationKind::Payload,
+            false,
+            false
+        )
+        .is_ok());
+        assert!(matches!(
+            validate_scroll_version_specific_fields(
+                EngineApiMessageVersion::V2,
+                MessageValidationKind::Payload,
+                true,
+                false
+            ),
+            Err(EngineObjectValidationError::Payload(
+                VersionSpecificValidationError::HasWithdrawalsPreShanghai
+            ))
+        ));
+        assert!(matches!(
+            validate_scroll_version_specific_fields(
+                EngineApiMessageVersion::V3,
+                MessageValidationKind::PayloadAttributes,
+                false,
+                true
+            ),
+            Err(EngineObjectValidationError::UnsupportedFork)
+        ));
     }
 }
@@ -22,7 +22,8 @@ where
     ) -> eyre::Result<(Self::EVM, Self::Executor)> {
         let chain_spec = ctx.chain_spec();
         let strategy_factory = ScrollExecutionStrategyFactory::new(chain_spec);
-        let evm_config = strategy_factory.evm_config();
+        let evm_config =
+            strategy_factory.evm_config().with_l1_base_fee_provider(ctx.provider().clone());
 
         let executor = BasicBlockExecutorProvider::new(strategy_factory);
 
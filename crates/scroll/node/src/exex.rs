@@ -0,0 +1,113 @@
+use crate::ScrollPrimitives;
+use alloy_eips::BlockNumHash;
+use futures::TryStreamExt;
+use reth_exex::{ExExContext, ExExEvent, ExExHead};
+use reth_node_builder::{FullNodeComponents, NodeTypes};
+use reth_provider::{BlockHashReader, ProviderError};
+use reth_scroll_l1::StateRootVerifier;
+use reth_scroll_withdraw_trie::WithdrawTrieIndexer;
+use reth_tracing::tracing::{debug, info};
+use std::fmt::Debug;
+
+/// Execution extension checking the state roots of the committed chain against the state roots
+/// of the batches finalized on L1, as indexed by the
+/// [`BatchIndexer`](reth_scroll_l1::BatchIndexer).
+///
+/// Once all the blocks of a finalized batch are committed, the state root after its last block
+/// is checked by the [`StateRootVerifier`]. A mismatch is reported through the verifier metrics
+/// and returned as an error, which stops the node.
+pub struct ScrollStateRootVerifierExEx<Node: FullNodeComponents> {
+    /// The context of the execution extension.
+    ctx: ExExContext<Node>,
+    /// The verifier of the state roots of the finalized batches.
+    verifier: StateRootVerifier<Node::Provider>,
+}
+
+impl<Node> ScrollStateRootVerifierExEx<Node>
+where
+    Node: FullNodeComponents<Types: NodeTypes<Primitives = ScrollPrimitives>>,
+{
+    /// Returns a new [`ScrollStateRootVerifierExEx`].
+    pub fn new(ctx: ExExContext<Node>) -> Self {
+        let verifier = StateRootVerifier::new(ctx.provider().clone());
+        Self { ctx, verifier }
+    }
+
+    /// Verifies the finalized batches as the chain is committed, until the state root of a
+    /// finalized batch doesn't match L1.
+    pub async fn run(mut self) -> eyre::Result<()> {
+        info!(target: "scroll::exex::verifier", "starting state root verifier");
+        while let Some(notification) = self.ctx.notifications.try_next().await? {
+            let Some(chain) = notification.committed_chain() else { continue };
+            let tip = chain.tip().num_hash();
+            self.verifier.verify(tip.number)?;
+            self.ctx.events.send(ExExEvent::FinishedHeight(tip))?;
+        }
+        Ok(())
+    }
+}
+
+impl<Node: FullNodeComponents> Debug for ScrollStateRootVerifierExEx<Node> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScrollStateRootVerifierExEx")
+            .field("next_batch", &self.verifier.next_batch())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Execution extension indexing the withdraw trie of the `L2MessageQueue` predeploy with the
+/// [`WithdrawTrieIndexer`], which serves the Merkle proofs of the L2 to L1 messages.
+///
+/// The committed blocks are indexed from the last indexed block on, backfilling the whole chain on
+/// the first run, and the reverted blocks are unwound.
+pub struct ScrollWithdrawTrieExEx<Node: FullNodeComponents> {
+    /// The context of the execution extension.
+    ctx: ExExContext<Node>,
+    /// The indexer of the withdraw trie.
+    indexer: WithdrawTrieIndexer<Node::Provider>,
+}
+
+impl<Node> ScrollWithdrawTrieExEx<Node>
+where
+    Node: FullNodeComponents<Types: NodeTypes<Primitives = ScrollPrimitives>>,
+{
+    /// Returns a new [`ScrollWithdrawTrieExEx`].
+    pub fn new(ctx: ExExContext<Node>) -> Self {
+        let indexer = WithdrawTrieIndexer::new(ctx.provider().clone());
+        Self { ctx, indexer }
+    }
+
+    /// Indexes the withdraw trie as the chain is committed and reverted, indefinitely.
+    pub async fn run(mut self) -> eyre::Result<()> {
+        let number = self.indexer.last_block()?.unwrap_or_default();
+        let hash = self
+            .ctx
+            .provider()
+            .block_hash(number)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?;
+        info!(target: "scroll::exex::withdraw_trie", number, "starting withdraw trie indexer");
+        self.ctx.set_notifications_with_head(ExExHead { block: BlockNumHash::new(number, hash) });
+
+        while let Some(notification) = self.ctx.notifications.try_next().await? {
+            if let Some(chain) = notification.reverted_chain() {
+                let removed = self.indexer.unwind(chain.first().number - 1)?;
+                debug!(target: "scroll::exex::withdraw_trie", range = ?chain.range(), removed, "unwound withdraw trie");
+            }
+            if let Some(chain) = notification.committed_chain() {
+                let blocks = chain.blocks_and_receipts().map(|(block, receipts)| {
+                    (block.number, receipts.iter().flatten().flat_map(|receipt| &receipt.logs))
+                });
+                let appended = self.indexer.index_blocks(blocks)?;
+                debug!(target: "scroll::exex::withdraw_trie", range = ?chain.range(), appended, "indexed withdraw trie");
+                self.ctx.events.send(ExExEvent::FinishedHeight(chain.tip().num_hash()))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<Node: FullNodeComponents> Debug for ScrollWithdrawTrieExEx<Node> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScrollWithdrawTrieExEx").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,150 @@
+use alloy_primitives::B256;
+use parking_lot::Mutex;
+use reth_db::{tables, transaction::DbTx};
+use reth_primitives::{Block, Transaction};
+use reth_provider::{BlockReader, DBProvider, DatabaseProviderFactory, ProviderResult};
+use reth_scroll_consensus::L1MessageQueueProvider;
+use reth_scroll_l1::L1MessageReader;
+use reth_scroll_payload::L1MessageProvider;
+use reth_scroll_primitives::TxL1Message;
+use schnellru::{ByLength, LruMap};
+use std::{fmt, sync::Arc};
+
+/// The number of blocks for which the queue index following their L1 messages is cached.
+const NEXT_QUEUE_INDICES_CACHE_SIZE: u32 = 1024;
+
+/// The local copy of the L1 message queue, indexed by the
+/// [`L1MessageQueueIndexer`](reth_scroll_l1::L1MessageQueueIndexer).
+///
+/// Provides the L1 messages pending inclusion to the payload builder and the L1 message queue the
+/// blocks are checked against to the consensus. Both are disabled until the L1 message queue is
+/// indexed.
+#[derive(Clone)]
+pub struct ScrollL1MessageQueue<Provider> {
+    /// The provider of the chain and of the L1 message queue.
+    provider: Provider,
+    /// The queue index following the last L1 message included in the chain up to a block, by
+    /// block hash.
+    next_queue_indices: Arc<Mutex<LruMap<B256, u64, ByLength>>>,
+}
+
+impl<Provider> ScrollL1MessageQueue<Provider> {
+    /// Returns a new [`ScrollL1MessageQueue`].
+    pub fn new(provider: Provider) -> Self {
+        Self {
+            provider,
+            next_queue_indices: Arc::new(Mutex::new(LruMap::new(ByLength::new(
+                NEXT_QUEUE_INDICES_CACHE_SIZE,
+            )))),
+        }
+    }
+}
+
+impl<Provider> ScrollL1MessageQueue<Provider>
+where
+    Provider: DatabaseProviderFactory + BlockReader<Block = Block>,
+{
+    /// Returns the queue index following the last L1 message included in the chain up to the
+    /// block with the given hash.
+    ///
+    /// The queue index is read from the
+    /// [`NextL1MessageQueueIndices`](tables::NextL1MessageQueueIndices) table for the persisted
+    /// blocks. The chain is walked back from the blocks that aren't persisted yet, until a
+    /// persisted block or a block including L1 messages.
+    ///
+    /// Returns `None` if the L1 message queue isn't indexed or if the block is unknown.
+    fn next_queue_index(&self, block_hash: B256) -> ProviderResult<Option<u64>> {
+        let provider = self.provider.database_provider_ro()?;
+        if provider.last_l1_message_queue_block()?.is_none() {
+            return Ok(None)
+        }
+
+        let mut hash = block_hash;
+        let mut visited = Vec::new();
+        let next_queue_index = loop {
+            if let Some(next_queue_index) = self.next_queue_indices.lock().get(&hash).copied() {
+                break next_queue_index
+            }
+
+            let persisted = match provider.tx_ref().get::<tables::HeaderNumbers>(hash)? {
+                Some(number) => {
+                    provider.tx_ref().get::<tables::NextL1MessageQueueIndices>(number)?
+                }
+                None => None,
+            };
+            if let Some(next_queue_index) = persisted {
+                break next_queue_index
+            }
+
+            let Some(block) = self.provider.block_by_hash(hash)? else { return Ok(None) };
+            visited.push(hash);
+
+            let last_queue_index = block
+                .body
+                .transactions
+                .iter()
+                .filter_map(|tx| match &tx.transaction {
+                    Transaction::L1Message(l1_message) => Some(l1_message.queue_index),
+                    _ => None,
+                })
+                .last();
+            if let Some(queue_index) = last_queue_index {
+                break queue_index + 1
+            }
+            if block.header.number == 0 {
+                break 0
+            }
+            hash = block.header.parent_hash;
+        };
+
+        let mut next_queue_indices = self.next_queue_indices.lock();
+        for hash in visited {
+            next_queue_indices.insert(hash, next_queue_index);
+        }
+
+        Ok(Some(next_queue_index))
+    }
+}
+
+impl<Provider> L1MessageProvider for ScrollL1MessageQueue<Provider>
+where
+    Provider: DatabaseProviderFactory + BlockReader<Block = Block>,
+{
+    fn pending_l1_messages(
+        &self,
+        parent_hash: B256,
+        limit: u64,
+    ) -> ProviderResult<Vec<TxL1Message>> {
+        let Some(next_queue_index) = self.next_queue_index(parent_hash)? else {
+            return Ok(Vec::new())
+        };
+        self.provider
+            .database_provider_ro()?
+            .l1_messages(next_queue_index..next_queue_index.saturating_add(limit))
+    }
+}
+
+impl<Provider> L1MessageQueueProvider for ScrollL1MessageQueue<Provider>
+where
+    Provider: DatabaseProviderFactory + BlockReader<Block = Block>,
+{
+    fn next_l1_message_queue_index(&self, block_hash: B256) -> ProviderResult<Option<u64>> {
+        self.next_queue_index(block_hash)
+    }
+
+    fn l1_message(&self, queue_index: u64) -> ProviderResult<Option<TxL1Message>> {
+        self.provider.database_provider_ro()?.l1_message(queue_index)
+    }
+
+    fn l1_message_queue_head(&self) -> ProviderResult<u64> {
+        self.provider.database_provider_ro()?.next_l1_message_queue_index()
+    }
+}
+
+impl<Provider> fmt::Debug for ScrollL1MessageQueue<Provider> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScrollL1MessageQueue")
+            .field("next_queue_indices", &self.next_queue_indices.lock().len())
+            .finish_non_exhaustive()
+    }
+}
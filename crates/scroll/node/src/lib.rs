@@ -6,8 +6,11 @@ use reth_node_builder::{
     components::ComponentsBuilder, FullNodeTypes, Node, NodeAdapter, NodeComponentsBuilder,
 };
 use reth_node_types::{NodeTypesWithDB, NodeTypesWithEngine};
-use reth_primitives::EthPrimitives;
+use reth_provider::StateReader;
 use reth_scroll_chainspec::ScrollChainSpec;
+pub use reth_scroll_primitives::{
+    ScrollAccount, ScrollPrimitives, ScrollReceipt, ScrollTransactionSigned,
+};
 
 mod addons;
 pub use addons::ScrollAddOns;
@@ -15,12 +18,21 @@ pub use addons::ScrollAddOns;
 mod consensus;
 pub use consensus::ScrollConsensusBuilder;
 
+mod derivation;
+pub use derivation::{DerivationError, ScrollDerivationPipeline, DEFAULT_DERIVATION_POLL_INTERVAL};
+
 mod engine;
 pub use engine::ScrollEngineValidatorBuilder;
 
 mod execution;
 pub use execution::ScrollExecutorBuilder;
 
+mod exex;
+pub use exex::{ScrollStateRootVerifierExEx, ScrollWithdrawTrieExEx};
+
+mod l1_message;
+pub use l1_message::ScrollL1MessageQueue;
+
 mod network;
 pub use network::ScrollNetworkBuilder;
 
@@ -33,6 +45,9 @@ pub use pool::ScrollPoolBuilder;
 mod storage;
 pub use storage::ScrollStorage;
 
+mod txpool;
+pub use txpool::{ScrollL1BlockInfo, ScrollTransactionPool, ScrollTransactionValidator};
+
 mod types;
 
 /// The Scroll node implementation.
@@ -41,11 +56,11 @@ pub struct ScrollNode;
 
 impl<N> Node<N> for ScrollNode
 where
-    N: FullNodeTypes,
+    N: FullNodeTypes<Provider: StateReader>,
     N::Types: NodeTypesWithDB
         + NodeTypesWithEngine<
             ChainSpec = ScrollChainSpec,
-            Primitives = EthPrimitives,
+            Primitives = ScrollPrimitives,
             Engine = EthEngineTypes,
             Storage = ScrollStorage,
         >,
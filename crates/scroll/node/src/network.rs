@@ -1,7 +1,7 @@
+use crate::ScrollPrimitives;
 use reth_network::PeersInfo;
 use reth_node_builder::{components::NetworkBuilder, BuilderContext, FullNodeTypes};
 use reth_node_types::NodeTypes;
-use reth_primitives::EthPrimitives;
 use reth_scroll_chainspec::ScrollChainSpec;
 use reth_tracing::tracing::info;
 use reth_transaction_pool::TransactionPool;
@@ -13,7 +13,7 @@ pub struct ScrollNetworkBuilder;
 impl<Node, Pool> NetworkBuilder<Node, Pool> for ScrollNetworkBuilder
 where
     Node: FullNodeTypes,
-    Node::Types: NodeTypes<ChainSpec = ScrollChainSpec, Primitives = EthPrimitives>,
+    Node::Types: NodeTypes<ChainSpec = ScrollChainSpec, Primitives = ScrollPrimitives>,
     Pool: TransactionPool + Unpin + 'static,
 {
     async fn build_network(
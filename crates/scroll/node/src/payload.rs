@@ -1,15 +1,17 @@
+use crate::{ScrollL1MessageQueue, ScrollPrimitives};
+use reth_basic_payload_builder::{BasicPayloadJobGenerator, BasicPayloadJobGeneratorConfig};
 use reth_ethereum_engine_primitives::{
     EthBuiltPayload, EthPayloadAttributes, EthPayloadBuilderAttributes,
 };
 use reth_node_builder::{
-    components::PayloadServiceBuilder, BuilderContext, FullNodeTypes, PayloadTypes,
+    components::PayloadServiceBuilder, BuilderContext, FullNodeTypes, PayloadBuilderConfig,
+    PayloadTypes,
 };
 use reth_node_types::NodeTypesWithEngine;
-use reth_payload_builder::{
-    test_utils::TestPayloadJobGenerator, PayloadBuilderHandle, PayloadBuilderService,
-};
-use reth_primitives::EthPrimitives;
-use reth_provider::CanonStateSubscriptions;
+use reth_payload_builder::{PayloadBuilderHandle, PayloadBuilderService};
+use reth_provider::{CanonStateSubscriptions, StateReader};
+use reth_scroll_chainspec::ScrollChainSpec;
+use reth_scroll_evm::ScrollEvmConfig;
 use reth_transaction_pool::TransactionPool;
 
 /// Payload builder for Scroll.
@@ -18,27 +20,43 @@ pub struct ScrollPayloadBuilder;
 
 impl<Node, Pool> PayloadServiceBuilder<Node, Pool> for ScrollPayloadBuilder
 where
-    Node: FullNodeTypes,
-    Node::Types: NodeTypesWithEngine<Primitives = EthPrimitives>,
+    Node: FullNodeTypes<Provider: StateReader>,
+    Node::Types: NodeTypesWithEngine<ChainSpec = ScrollChainSpec, Primitives = ScrollPrimitives>,
     <Node::Types as NodeTypesWithEngine>::Engine: PayloadTypes<
         BuiltPayload = EthBuiltPayload,
         PayloadAttributes = EthPayloadAttributes,
         PayloadBuilderAttributes = EthPayloadBuilderAttributes,
     >,
-    Pool: TransactionPool,
+    Pool: TransactionPool + Unpin + 'static,
 {
     async fn spawn_payload_service(
         self,
         ctx: &BuilderContext<Node>,
-        _pool: Pool,
+        pool: Pool,
     ) -> eyre::Result<
         PayloadBuilderHandle<<<Node as FullNodeTypes>::Types as NodeTypesWithEngine>::Engine>,
     > {
-        let test_payload_generator = TestPayloadJobGenerator::default();
-        let (payload_service, payload_builder) = PayloadBuilderService::new(
-            test_payload_generator,
-            ctx.provider().canonical_state_stream(),
+        let evm_config = ScrollEvmConfig::new(ctx.chain_spec())
+            .with_l1_base_fee_provider(ctx.provider().clone());
+        let payload_builder = reth_scroll_payload::ScrollPayloadBuilder::new(evm_config)
+            .with_l1_messages(ScrollL1MessageQueue::new(ctx.provider().clone()));
+        let conf = ctx.payload_builder_config();
+
+        let payload_job_config = BasicPayloadJobGeneratorConfig::default()
+            .interval(conf.interval())
+            .deadline(conf.deadline())
+            .max_payload_tasks(conf.max_payload_tasks())
+            .extradata(conf.extradata_bytes());
+
+        let payload_generator = BasicPayloadJobGenerator::with_builder(
+            ctx.provider().clone(),
+            pool,
+            ctx.task_executor().clone(),
+            payload_job_config,
+            payload_builder,
         );
+        let (payload_service, payload_builder) =
+            PayloadBuilderService::new(payload_generator, ctx.provider().canonical_state_stream());
 
         ctx.task_executor().spawn_critical("payload builder service", Box::pin(payload_service));
 
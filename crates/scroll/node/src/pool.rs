@@ -1,5 +1,16 @@
+use crate::{
+    txpool::{ScrollTransactionPool, ScrollTransactionValidator},
+    ScrollPrimitives,
+};
 use reth_node_builder::{components::PoolBuilder, BuilderContext, FullNodeTypes};
-use reth_transaction_pool::noop::NoopTransactionPool;
+use reth_node_types::NodeTypes;
+use reth_provider::CanonStateSubscriptions;
+use reth_scroll_chainspec::ScrollChainSpec;
+use reth_tracing::tracing::{debug, info};
+use reth_transaction_pool::{
+    blobstore::DiskFileBlobStore, CoinbaseTipOrdering, TransactionValidationTaskExecutor,
+};
+use std::sync::Arc;
 
 /// Pool builder for Scroll.
 #[derive(Debug)]
@@ -7,11 +18,66 @@ pub struct ScrollPoolBuilder;
 
 impl<Node> PoolBuilder<Node> for ScrollPoolBuilder
 where
-    Node: FullNodeTypes,
+    Node:
+        FullNodeTypes<Types: NodeTypes<ChainSpec = ScrollChainSpec, Primitives = ScrollPrimitives>>,
 {
-    type Pool = NoopTransactionPool;
+    type Pool = ScrollTransactionPool<Node::Provider, DiskFileBlobStore>;
 
-    async fn build_pool(self, _ctx: &BuilderContext<Node>) -> eyre::Result<Self::Pool> {
-        Ok(NoopTransactionPool::default())
+    async fn build_pool(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Pool> {
+        let data_dir = ctx.config().datadir();
+        let blob_store = DiskFileBlobStore::open(data_dir.blobstore(), Default::default())?;
+
+        let validator = TransactionValidationTaskExecutor::eth_builder(Arc::new(
+            ctx.chain_spec().inner.clone(),
+        ))
+        .no_eip4844()
+        .with_head_timestamp(ctx.head().timestamp)
+        .with_additional_tasks(ctx.config().txpool.additional_validation_tasks)
+        .build_with_tasks(ctx.provider().clone(), ctx.task_executor().clone(), blob_store.clone())
+        .map(|validator| ScrollTransactionValidator::new(validator, ctx.chain_spec()));
+
+        let transaction_pool = reth_transaction_pool::Pool::new(
+            validator,
+            CoinbaseTipOrdering::default(),
+            blob_store,
+            ctx.pool_config(),
+        );
+        info!(target: "reth::cli", "Transaction pool initialized");
+        let transactions_path = data_dir.txpool_transactions();
+
+        // spawn txpool maintenance task
+        {
+            let pool = transaction_pool.clone();
+            let chain_events = ctx.provider().canonical_state_stream();
+            let client = ctx.provider().clone();
+            let transactions_backup_config =
+                reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(transactions_path);
+
+            ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
+                "local transactions backup task",
+                |shutdown| {
+                    reth_transaction_pool::maintain::backup_local_transactions_task(
+                        shutdown,
+                        pool.clone(),
+                        transactions_backup_config,
+                    )
+                },
+            );
+
+            // spawn the maintenance task
+            ctx.task_executor().spawn_critical(
+                "txpool maintenance task",
+                reth_transaction_pool::maintain::maintain_transaction_pool_future(
+                    client,
+                    pool,
+                    chain_events,
+                    ctx.task_executor().clone(),
+                    Default::default(),
+                ),
+            );
+            debug!(target: "reth::cli", "Spawned txpool maintenance task");
+        }
+
+        Ok(transaction_pool)
     }
 }
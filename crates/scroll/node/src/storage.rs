@@ -1,13 +1,23 @@
-use reth_db::transaction::{DbTx, DbTxMut};
+use crate::ScrollPrimitives;
+use reth_db::{
+    cursor::DbCursorRW,
+    tables,
+    transaction::{DbTx, DbTxMut},
+    DbTxUnwindExt,
+};
 use reth_ethereum_forks::EthereumHardforks;
 use reth_node_types::NodeTypes;
-use reth_primitives::{BlockBody, EthPrimitives};
+use reth_primitives::{BlockBody, Transaction};
 use reth_provider::{
     providers::ChainStorage, BlockBodyReader, BlockBodyWriter, ChainSpecProvider, DBProvider,
     EthStorage, ProviderResult, ReadBodyInput,
 };
 
 /// Storage implementation for Scroll.
+///
+/// Along with the Ethereum block bodies, the queue index following the last L1 message included in
+/// the chain up to each block is written to the
+/// [`NextL1MessageQueueIndices`](tables::NextL1MessageQueueIndices) table.
 #[derive(Debug, Default, Clone)]
 pub struct ScrollStorage(EthStorage);
 
@@ -20,6 +30,35 @@ where
         provider: &Provider,
         bodies: Vec<(u64, Option<BlockBody>)>,
     ) -> ProviderResult<()> {
+        let Some(from_block) = bodies.first().map(|(block, _)| *block) else { return Ok(()) };
+
+        // the queue index following the parent chain is unknown if the parent block was written
+        // before the table was introduced, in which case it's only known from the next block
+        // including L1 messages
+        let mut next_queue_index = match from_block.checked_sub(1) {
+            Some(parent) => provider.tx_ref().get::<tables::NextL1MessageQueueIndices>(parent)?,
+            None => Some(0),
+        };
+
+        let mut cursor = provider.tx_ref().cursor_write::<tables::NextL1MessageQueueIndices>()?;
+        for (block_number, body) in &bodies {
+            let last_queue_index = body
+                .iter()
+                .flat_map(|body| &body.transactions)
+                .filter_map(|tx| match &tx.transaction {
+                    Transaction::L1Message(l1_message) => Some(l1_message.queue_index),
+                    _ => None,
+                })
+                .last();
+            if let Some(queue_index) = last_queue_index {
+                next_queue_index = Some(queue_index + 1);
+            }
+
+            if let Some(next_queue_index) = next_queue_index {
+                cursor.upsert(*block_number, next_queue_index)?;
+            }
+        }
+
         self.0.write_block_bodies(provider, bodies)
     }
 
@@ -28,6 +67,7 @@ where
         provider: &Provider,
         block: alloy_primitives::BlockNumber,
     ) -> ProviderResult<()> {
+        provider.tx_ref().unwind_table_by_num::<tables::NextL1MessageQueueIndices>(block)?;
         self.0.remove_block_bodies_above(provider, block)
     }
 }
@@ -47,24 +87,81 @@ where
     }
 }
 
-impl ChainStorage<EthPrimitives> for ScrollStorage {
+impl ChainStorage<ScrollPrimitives> for ScrollStorage {
     fn reader<TX, Types>(
         &self,
-    ) -> impl reth_provider::ChainStorageReader<reth_provider::DatabaseProvider<TX, Types>, EthPrimitives>
+    ) -> impl reth_provider::ChainStorageReader<
+        reth_provider::DatabaseProvider<TX, Types>,
+        ScrollPrimitives,
+    >
     where
         TX: DbTx + 'static,
-        Types: reth_provider::providers::NodeTypesForProvider<Primitives = EthPrimitives>,
+        Types: reth_provider::providers::NodeTypesForProvider<Primitives = ScrollPrimitives>,
     {
         self
     }
 
     fn writer<TX, Types>(
         &self,
-    ) -> impl reth_provider::ChainStorageWriter<reth_provider::DatabaseProvider<TX, Types>, EthPrimitives>
+    ) -> impl reth_provider::ChainStorageWriter<
+        reth_provider::DatabaseProvider<TX, Types>,
+        ScrollPrimitives,
+    >
     where
         TX: DbTxMut + DbTx + 'static,
-        Types: NodeTypes<Primitives = EthPrimitives>,
+        Types: NodeTypes<Primitives = ScrollPrimitives>,
     {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::cursor::DbCursorRO;
+    use reth_primitives::TransactionSigned;
+    use reth_provider::{test_utils::create_test_provider_factory, DatabaseProviderFactory};
+    use reth_scroll_primitives::TxL1Message;
+
+    fn body(queue_indices: impl IntoIterator<Item = u64>) -> BlockBody {
+        let transactions = queue_indices
+            .into_iter()
+            .map(|queue_index| {
+                TransactionSigned::new_unhashed(
+                    Transaction::L1Message(TxL1Message { queue_index, ..Default::default() }),
+                    TxL1Message::signature(),
+                )
+            })
+            .collect();
+        BlockBody { transactions, ..Default::default() }
+    }
+
+    #[test]
+    fn write_and_remove_next_l1_message_queue_indices() -> eyre::Result<()> {
+        let factory = create_test_provider_factory();
+        let provider = factory.database_provider_rw()?;
+        let storage = ScrollStorage::default();
+
+        storage.write_block_bodies(
+            &provider,
+            vec![(0, None), (1, Some(body([0, 1]))), (2, Some(body([]))), (3, Some(body([2])))],
+        )?;
+        let next_queue_indices = provider
+            .tx_ref()
+            .cursor_read::<tables::NextL1MessageQueueIndices>()?
+            .walk(None)?
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(next_queue_indices, vec![(0, 0), (1, 2), (2, 2), (3, 3)]);
+
+        storage.remove_block_bodies_above(&provider, 1)?;
+        storage.write_block_bodies(&provider, vec![(2, Some(body([2, 3])))])?;
+        let next_queue_indices = provider
+            .tx_ref()
+            .cursor_read::<tables::NextL1MessageQueueIndices>()?
+            .walk(None)?
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(next_queue_indices, vec![(0, 0), (1, 2), (2, 4)]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,363 @@
+//! Scroll transaction pool types
+use alloy_consensus::constants::{EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID};
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::B256;
+use parking_lot::RwLock;
+use reth_chainspec::ChainSpec;
+use reth_primitives::{GotExpected, InvalidTransactionError, SealedBlock, TransactionSigned};
+use reth_provider::{BlockReaderIdExt, ProviderResult, StateProviderFactory};
+use reth_scroll_chainspec::ScrollChainSpec;
+use reth_scroll_evm::L1GasPriceOracle;
+use reth_scroll_forks::ScrollHardforks;
+use reth_scroll_primitives::L1_MESSAGE_TRANSACTION_TYPE;
+use reth_transaction_pool::{
+    CoinbaseTipOrdering, EthPoolTransaction, EthPooledTransaction, EthTransactionValidator, Pool,
+    TransactionOrigin, TransactionValidationOutcome, TransactionValidationTaskExecutor,
+    TransactionValidator,
+};
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+/// Type alias for default scroll transaction pool
+pub type ScrollTransactionPool<Client, S> = Pool<
+    TransactionValidationTaskExecutor<ScrollTransactionValidator<Client, EthPooledTransaction>>,
+    CoinbaseTipOrdering<EthPooledTransaction>,
+    S,
+>;
+
+/// Validator for Scroll transactions.
+#[derive(Debug, Clone)]
+pub struct ScrollTransactionValidator<Client, Tx> {
+    /// The type that performs the actual validation.
+    inner: EthTransactionValidator<Client, Tx>,
+    /// The Scroll chain spec, used for the hardforks activation.
+    chain_spec: Arc<ScrollChainSpec>,
+    /// Additional block info required for validation.
+    block_info: Arc<ScrollL1BlockInfo>,
+    /// If true, ensure that the transaction's sender has enough balance to cover the L1 data fee
+    /// derived from the L1 gas price oracle state at the current head.
+    require_l1_data_gas_fee: bool,
+}
+
+impl<Client, Tx> ScrollTransactionValidator<Client, Tx> {
+    /// Returns the configured chain spec
+    pub fn chain_spec(&self) -> Arc<ChainSpec> {
+        self.inner.chain_spec()
+    }
+
+    /// Returns the configured Scroll chain spec
+    pub fn scroll_chain_spec(&self) -> &Arc<ScrollChainSpec> {
+        &self.chain_spec
+    }
+
+    /// Returns the configured client
+    pub fn client(&self) -> &Client {
+        self.inner.client()
+    }
+
+    /// Returns the current block number.
+    fn block_number(&self) -> u64 {
+        self.block_info.number.load(Ordering::Relaxed)
+    }
+
+    /// Whether to ensure that the transaction's sender has enough balance to also cover the L1 data
+    /// fee.
+    pub fn require_l1_data_gas_fee(self, require_l1_data_gas_fee: bool) -> Self {
+        Self { require_l1_data_gas_fee, ..self }
+    }
+
+    /// Returns whether this validator also requires the transaction's sender to have enough balance
+    /// to cover the L1 data fee.
+    pub const fn requires_l1_data_gas_fee(&self) -> bool {
+        self.require_l1_data_gas_fee
+    }
+}
+
+impl<Client, Tx> ScrollTransactionValidator<Client, Tx>
+where
+    Client: StateProviderFactory + BlockReaderIdExt<Block = reth_primitives::Block>,
+    Tx: EthPoolTransaction,
+{
+    /// Create a new [`ScrollTransactionValidator`].
+    pub fn new(
+        inner: EthTransactionValidator<Client, Tx>,
+        chain_spec: Arc<ScrollChainSpec>,
+    ) -> Self {
+        let this = Self::with_block_info(inner, chain_spec, ScrollL1BlockInfo::default());
+        if let Ok(Some(header)) =
+            this.inner.client().sealed_header_by_number_or_tag(alloy_eips::BlockNumberOrTag::Latest)
+        {
+            this.update_l1_block_info(header.number, header.hash());
+        }
+
+        this
+    }
+
+    /// Create a new [`ScrollTransactionValidator`] with the given [`ScrollL1BlockInfo`].
+    pub fn with_block_info(
+        inner: EthTransactionValidator<Client, Tx>,
+        chain_spec: Arc<ScrollChainSpec>,
+        block_info: ScrollL1BlockInfo,
+    ) -> Self {
+        Self { inner, chain_spec, block_info: Arc::new(block_info), require_l1_data_gas_fee: true }
+    }
+
+    /// Update the L1 block info by loading the L1 gas price oracle values at the given block.
+    ///
+    /// If the values can't be loaded, the error is kept and returned when validating transactions
+    /// against this block, rather than computing the L1 data fee from stale values.
+    fn update_l1_block_info(&self, number: u64, hash: B256) {
+        self.block_info.number.store(number, Ordering::Relaxed);
+        *self.block_info.l1_gas_price_oracle.write() = self
+            .inner
+            .client()
+            .state_by_block_hash(hash)
+            .and_then(|state| L1GasPriceOracle::try_from_state(&state));
+    }
+
+    /// Validates a single transaction.
+    ///
+    /// See also [`TransactionValidator::validate_transaction`]
+    ///
+    /// This behaves the same as [`EthTransactionValidator::validate_one`], but in addition:
+    /// - rejects L1 messages, EIP-4844 and EIP-7702 transactions, which can't be submitted to the
+    ///   pool.
+    /// - rejects EIP-2930 and EIP-1559 transactions before the Curie hardfork.
+    /// - ensures that the account has enough balance to cover the L1 data fee.
+    pub fn validate_one(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Tx,
+    ) -> TransactionValidationOutcome<Tx> {
+        if transaction.tx_type() == L1_MESSAGE_TRANSACTION_TYPE ||
+            transaction.is_eip4844() ||
+            transaction.is_eip7702()
+        {
+            return TransactionValidationOutcome::Invalid(
+                transaction,
+                InvalidTransactionError::TxTypeNotSupported.into(),
+            )
+        }
+
+        // EIP-2930 and EIP-1559 transactions are only supported from Curie onward
+        let is_curie = self.chain_spec.is_curie_active_at_block(self.block_number() + 1);
+        if !is_curie {
+            match transaction.tx_type() {
+                EIP2930_TX_TYPE_ID => {
+                    return TransactionValidationOutcome::Invalid(
+                        transaction,
+                        InvalidTransactionError::Eip2930Disabled.into(),
+                    )
+                }
+                EIP1559_TX_TYPE_ID => {
+                    return TransactionValidationOutcome::Invalid(
+                        transaction,
+                        InvalidTransactionError::Eip1559Disabled.into(),
+                    )
+                }
+                _ => {}
+            }
+        }
+
+        let outcome = self.inner.validate_one(origin, transaction);
+
+        if !self.requires_l1_data_gas_fee() {
+            // no need to check L1 gas fee
+            return outcome
+        }
+
+        // ensure that the account has enough balance to cover the L1 gas cost
+        if let TransactionValidationOutcome::Valid {
+            balance,
+            state_nonce,
+            transaction: valid_tx,
+            propagate,
+        } = outcome
+        {
+            let oracle = match &*self.block_info.l1_gas_price_oracle.read() {
+                Ok(oracle) => *oracle,
+                Err(err) => {
+                    return TransactionValidationOutcome::Error(
+                        *valid_tx.transaction().hash(),
+                        Box::new(err.clone()),
+                    )
+                }
+            };
+
+            let mut encoded = Vec::with_capacity(valid_tx.transaction().encoded_length());
+            let tx: TransactionSigned = valid_tx.transaction().clone().into_consensus().into();
+            tx.encode_2718(&mut encoded);
+
+            let cost_addition = oracle.l1_data_fee(&encoded, is_curie);
+            let cost = valid_tx.transaction().cost().saturating_add(cost_addition);
+
+            // Checks for max cost
+            if cost > balance {
+                return TransactionValidationOutcome::Invalid(
+                    valid_tx.into_transaction(),
+                    InvalidTransactionError::InsufficientFunds(
+                        GotExpected { got: balance, expected: cost }.into(),
+                    )
+                    .into(),
+                )
+            }
+
+            return TransactionValidationOutcome::Valid {
+                balance,
+                state_nonce,
+                transaction: valid_tx,
+                propagate,
+            }
+        }
+
+        outcome
+    }
+
+    /// Validates all given transactions.
+    ///
+    /// Returns all outcomes for the given transactions in the same order.
+    ///
+    /// See also [`Self::validate_one`]
+    pub fn validate_all(
+        &self,
+        transactions: Vec<(TransactionOrigin, Tx)>,
+    ) -> Vec<TransactionValidationOutcome<Tx>> {
+        transactions.into_iter().map(|(origin, tx)| self.validate_one(origin, tx)).collect()
+    }
+}
+
+impl<Client, Tx> TransactionValidator for ScrollTransactionValidator<Client, Tx>
+where
+    Client: StateProviderFactory + BlockReaderIdExt<Block = reth_primitives::Block>,
+    Tx: EthPoolTransaction,
+{
+    type Transaction = Tx;
+
+    async fn validate_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+    ) -> TransactionValidationOutcome<Self::Transaction> {
+        self.validate_one(origin, transaction)
+    }
+
+    async fn validate_transactions(
+        &self,
+        transactions: Vec<(TransactionOrigin, Self::Transaction)>,
+    ) -> Vec<TransactionValidationOutcome<Self::Transaction>> {
+        self.validate_all(transactions)
+    }
+
+    fn on_new_head_block(&self, new_tip_block: &SealedBlock) {
+        self.inner.on_new_head_block(new_tip_block);
+        self.update_l1_block_info(new_tip_block.number, new_tip_block.hash());
+    }
+}
+
+/// Tracks additional infos for the current block.
+#[derive(Debug)]
+pub struct ScrollL1BlockInfo {
+    /// The L1 gas price oracle values at the current block, or the error raised while loading
+    /// them.
+    l1_gas_price_oracle: RwLock<ProviderResult<L1GasPriceOracle>>,
+    /// Current block number.
+    number: AtomicU64,
+}
+
+impl Default for ScrollL1BlockInfo {
+    fn default() -> Self {
+        Self {
+            l1_gas_price_oracle: RwLock::new(Ok(L1GasPriceOracle::default())),
+            number: AtomicU64::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::TxLegacy;
+    use alloy_primitives::{Address, PrimitiveSignature as Signature, TxKind};
+    use reth_primitives::{Transaction, TransactionSignedEcRecovered};
+    use reth_provider::{test_utils::MockEthProvider, ProviderError};
+    use reth_scroll_chainspec::SCROLL_MAINNET;
+    use reth_scroll_primitives::TxL1Message;
+    use reth_transaction_pool::{
+        blobstore::InMemoryBlobStore, validate::EthTransactionValidatorBuilder,
+    };
+
+    fn validator() -> ScrollTransactionValidator<MockEthProvider, EthPooledTransaction> {
+        let client = MockEthProvider::default();
+        let validator = EthTransactionValidatorBuilder::new(Arc::new(SCROLL_MAINNET.inner.clone()))
+            .no_shanghai()
+            .no_cancun()
+            .build(client, InMemoryBlobStore::default());
+        ScrollTransactionValidator::new(validator, SCROLL_MAINNET.clone())
+    }
+
+    fn pooled(transaction: Transaction, signature: Signature) -> EthPooledTransaction {
+        let signer = Address::random();
+        let signed_tx = TransactionSigned::new_unhashed(transaction, signature);
+        let signed_recovered =
+            TransactionSignedEcRecovered::from_signed_transaction(signed_tx, signer);
+        let len = signed_recovered.encode_2718_len();
+        EthPooledTransaction::new(signed_recovered, len)
+    }
+
+    #[test]
+    fn validate_l1_message() {
+        let validator = validator();
+
+        let l1_message =
+            Transaction::L1Message(TxL1Message { sender: Address::random(), ..Default::default() });
+        let outcome = validator.validate_one(
+            TransactionOrigin::External,
+            pooled(l1_message, TxL1Message::signature()),
+        );
+
+        let err = match outcome {
+            TransactionValidationOutcome::Invalid(_, err) => err,
+            _ => panic!("Expected invalid transaction"),
+        };
+        assert_eq!(err.to_string(), "transaction type not supported");
+    }
+
+    #[test]
+    fn validate_eip1559_before_curie() {
+        let validator = validator();
+
+        let tx = Transaction::Eip1559(Default::default());
+        let outcome = validator
+            .validate_one(TransactionOrigin::External, pooled(tx, Signature::test_signature()));
+
+        let err = match outcome {
+            TransactionValidationOutcome::Invalid(_, err) => err,
+            _ => panic!("Expected invalid transaction"),
+        };
+        assert_eq!(err.to_string(), InvalidTransactionError::Eip1559Disabled.to_string());
+    }
+
+    #[test]
+    fn validate_with_unavailable_l1_gas_price_oracle() {
+        let validator = validator();
+        *validator.block_info.l1_gas_price_oracle.write() =
+            Err(ProviderError::StateForHashNotFound(B256::ZERO));
+
+        let tx = Transaction::Legacy(TxLegacy {
+            chain_id: Some(SCROLL_MAINNET.chain.id()),
+            gas_limit: 21_000,
+            to: TxKind::Call(Address::random()),
+            ..Default::default()
+        });
+        let outcome = validator
+            .validate_one(TransactionOrigin::External, pooled(tx, Signature::test_signature()));
+
+        let err = match outcome {
+            TransactionValidationOutcome::Error(_, err) => err,
+            _ => panic!("Expected validation error"),
+        };
+        assert_eq!(err.to_string(), ProviderError::StateForHashNotFound(B256::ZERO).to_string());
+    }
+}
@@ -1,7 +1,6 @@
-use crate::{storage::ScrollStorage, ScrollNode};
+use crate::{storage::ScrollStorage, ScrollNode, ScrollPrimitives};
 use reth_ethereum_engine_primitives::EthEngineTypes;
 use reth_node_types::{NodeTypes, NodeTypesWithEngine};
-use reth_primitives::EthPrimitives;
 use reth_scroll_chainspec::ScrollChainSpec;
 use reth_scroll_state_commitment::BinaryMerklePatriciaTrie;
 
@@ -10,8 +9,7 @@ impl NodeTypesWithEngine for ScrollNode {
 }
 
 impl NodeTypes for ScrollNode {
-    // TODO(scroll): update to scroll primitives when we introduce the revm SDK pattern.
-    type Primitives = EthPrimitives;
+    type Primitives = ScrollPrimitives;
     type ChainSpec = ScrollChainSpec;
     type StateCommitment = BinaryMerklePatriciaTrie;
     type Storage = ScrollStorage;
@@ -0,0 +1,95 @@
+use alloy_primitives::{Address, Bytes};
+use reth_e2e_test_utils::{
+    scroll::setup_scroll, transaction::TransactionTestContext, wallet::Wallet,
+};
+use reth_primitives::Transaction;
+use reth_scroll_chainspec::SCROLL_DEV;
+use reth_scroll_rpc::L1MessageRequest;
+
+#[tokio::test]
+async fn can_include_l1_messages_and_transactions() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let (mut nodes, _tasks, wallet) = setup_scroll(1, SCROLL_DEV.clone()).await?;
+    let mut node = nodes.pop().unwrap();
+
+    let message = L1MessageRequest {
+        sender: Address::with_last_byte(1),
+        to: Address::with_last_byte(2),
+        gas_limit: 100_000,
+        input: Bytes::from_static(&[1, 2, 3]),
+        ..Default::default()
+    };
+    let l1_message_hash = node.enqueue_l1_message(message)?;
+    let raw_tx = TransactionTestContext::transfer_tx_bytes(wallet.chain_id, wallet.inner).await;
+    let tx_hash = node.inject_tx(raw_tx).await?;
+
+    let block = node.advance_block().await?;
+    assert_eq!(block.number, 1);
+    let hashes = block.body.transactions.iter().map(|tx| tx.hash()).collect::<Vec<_>>();
+    assert_eq!(hashes, vec![l1_message_hash, tx_hash]);
+    assert!(matches!(block.body.transactions[0].transaction, Transaction::L1Message(_)));
+
+    node.assert_receipt(l1_message_hash)?;
+    node.assert_receipt(tx_hash)?;
+    node.assert_state_root(block.hash())?;
+
+    // the L1 message is only included once
+    let block = node.advance_block().await?;
+    assert!(block.body.transactions.is_empty());
+    node.assert_state_root(block.hash())?;
+
+    // the queue index of the next L1 message follows the included one
+    let wallet = Wallet::default();
+    let message = L1MessageRequest {
+        sender: wallet.inner.address(),
+        gas_limit: 21_000,
+        ..Default::default()
+    };
+    let l1_message_hash = node.enqueue_l1_message(message)?;
+    let block = node.advance_block().await?;
+    let Transaction::L1Message(l1_message) = &block.body.transactions[0].transaction else {
+        panic!("expected an L1 message")
+    };
+    assert_eq!(l1_message.queue_index, 1);
+    node.assert_receipt(l1_message_hash)?;
+
+    // L1 messages with a gas limit below their intrinsic gas are rejected
+    let message = L1MessageRequest {
+        sender: wallet.inner.address(),
+        gas_limit: 20_999,
+        ..Default::default()
+    };
+    assert!(node.enqueue_l1_message(message).is_err());
+
+    // an L1 message exceeding the block gas limit can't be included and is skipped
+    let message = L1MessageRequest {
+        sender: wallet.inner.address(),
+        gas_limit: block.gas_limit + 1,
+        ..Default::default()
+    };
+    node.enqueue_l1_message(message)?;
+    let block = node.advance_block().await?;
+    assert_eq!(block.number, 4);
+    assert!(block.body.transactions.is_empty());
+
+    // the chain keeps including the following L1 messages
+    let message = L1MessageRequest {
+        sender: wallet.inner.address(),
+        gas_limit: 21_000,
+        ..Default::default()
+    };
+    let l1_message_hash = node.enqueue_l1_message(message)?;
+    let block = node.advance_block().await?;
+    assert_eq!(block.number, 5);
+    let hashes = block.body.transactions.iter().map(|tx| tx.hash()).collect::<Vec<_>>();
+    assert_eq!(hashes, vec![l1_message_hash]);
+    let Transaction::L1Message(l1_message) = &block.body.transactions[0].transaction else {
+        panic!("expected an L1 message")
+    };
+    assert_eq!(l1_message.queue_index, 3);
+    node.assert_receipt(l1_message_hash)?;
+    node.assert_state_root(block.hash())?;
+
+    Ok(())
+}
@@ -0,0 +1,9 @@
+#![allow(missing_docs)]
+// Only use the crate if `scroll` feature is used.
+#![cfg_attr(not(feature = "scroll"), allow(unused_crate_dependencies))]
+#![cfg(all(feature = "scroll", not(feature = "optimism")))]
+
+mod l1_message;
+mod p2p;
+
+const fn main() {}
@@ -0,0 +1,29 @@
+use reth_e2e_test_utils::scroll::setup_scroll;
+use reth_scroll_chainspec::SCROLL_DEV;
+
+#[tokio::test]
+async fn can_sync() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let (mut nodes, _tasks, _wallet) = setup_scroll(2, SCROLL_DEV.clone()).await?;
+    let mut second_node = nodes.pop().unwrap();
+    let mut first_node = nodes.pop().unwrap();
+
+    // build a chain on the first node
+    let mut tip = first_node.head()?;
+    for _ in 0..3 {
+        tip = first_node.advance_block().await?.header;
+    }
+
+    // the second node syncs the chain from the first one
+    second_node.update_forkchoice(tip.hash()).await?;
+    second_node.wait_for_head(tip.hash()).await?;
+    second_node.assert_state_root(tip.hash())?;
+
+    // and extends it
+    let block = second_node.advance_block().await?;
+    assert_eq!(block.number, tip.number + 1);
+    assert_eq!(block.parent_hash, tip.hash());
+
+    Ok(())
+}
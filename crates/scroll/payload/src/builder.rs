@@ -0,0 +1,552 @@
+//! Scroll payload builder implementation.
+
+use crate::L1MessageProvider;
+use alloy_consensus::{Header, EMPTY_OMMER_ROOT_HASH};
+use alloy_eips::merge::BEACON_NONCE;
+use alloy_primitives::{B256, U256};
+use reth_basic_payload_builder::{
+    is_better_payload, BuildArguments, BuildOutcome, PayloadBuilder, PayloadConfig,
+};
+use reth_chain_state::ExecutedBlock;
+use reth_chainspec::EthereumHardforks;
+use reth_evm::{ConfigureEvm, NextBlockEnvAttributes};
+use reth_execution_types::ExecutionOutcome;
+use reth_payload_builder::{EthBuiltPayload, EthPayloadBuilderAttributes};
+use reth_payload_builder_primitives::PayloadBuilderError;
+use reth_payload_primitives::PayloadBuilderAttributes;
+use reth_primitives::{
+    proofs, Block, BlockBody, BlockExt, InvalidTransactionError, Receipt, SealedHeader,
+    Transaction, TransactionSigned, TransactionSignedEcRecovered, TxType,
+};
+use reth_provider::{
+    ChainSpecProvider, DBProvider, DatabaseProviderFactory, ProviderError, StateProvider,
+    StateProviderFactory, StateReader,
+};
+use reth_scroll_chainspec::ScrollChainSpec;
+use reth_scroll_consensus::{apply_curie_hard_fork, L1_GAS_PRICE_ORACLE_ADDRESS};
+use reth_scroll_evm::ScrollEvmConfig;
+use reth_scroll_execution::FinalizeExecution;
+use reth_scroll_forks::{ScrollHardfork, ScrollHardforks};
+use reth_scroll_primitives::TxL1Message;
+use reth_scroll_state_commitment::{
+    mpt::mpt_base_state, mpt_state_root, StateCommitmentScheme, StateCommitmentSchemeProvider,
+};
+use reth_scroll_storage::ScrollStateProviderDatabase;
+use reth_transaction_pool::{
+    error::InvalidPoolTransactionError, noop::NoopTransactionPool, BestTransactions,
+    BestTransactionsAttributes, TransactionPool, ValidPoolTransaction,
+};
+use reth_trie::{updates::TrieUpdates, HashedPostState, KeccakKeyHasher};
+use revm::{
+    db::{states::bundle_state::BundleRetention, State},
+    primitives::{
+        BlockEnv, CfgEnvWithHandlerCfg, EVMError, EnvWithHandlerCfg, ExecutionResult,
+        InvalidTransaction, ResultAndState, TxEnv,
+    },
+    DatabaseCommit,
+};
+use std::sync::Arc;
+use tracing::{debug, trace, warn};
+
+type BestTransactionsIter<Pool> = Box<
+    dyn BestTransactions<Item = Arc<ValidPoolTransaction<<Pool as TransactionPool>::Transaction>>>,
+>;
+
+/// Scroll payload builder.
+///
+/// Builds blocks which start with the pending L1 messages, followed by the best transactions of
+/// the pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScrollPayloadBuilder<EvmConfig = ScrollEvmConfig, L1Messages = ()> {
+    /// The type responsible for creating the evm.
+    evm_config: EvmConfig,
+    /// The type responsible for yielding the L1 messages pending inclusion.
+    l1_messages: L1Messages,
+}
+
+impl<EvmConfig> ScrollPayloadBuilder<EvmConfig> {
+    /// `ScrollPayloadBuilder` constructor.
+    pub const fn new(evm_config: EvmConfig) -> Self {
+        Self { evm_config, l1_messages: () }
+    }
+}
+
+impl<EvmConfig, L1Messages> ScrollPayloadBuilder<EvmConfig, L1Messages> {
+    /// Configures the type responsible for yielding the L1 messages pending inclusion.
+    pub fn with_l1_messages<T>(self, l1_messages: T) -> ScrollPayloadBuilder<EvmConfig, T> {
+        let Self { evm_config, .. } = self;
+        ScrollPayloadBuilder { evm_config, l1_messages }
+    }
+}
+
+impl<EvmConfig, L1Messages> ScrollPayloadBuilder<EvmConfig, L1Messages>
+where
+    EvmConfig: ConfigureEvm<Header = Header>,
+{
+    /// Returns the configured [`CfgEnvWithHandlerCfg`] and [`BlockEnv`] for the targeted payload
+    /// (that has the `parent` as its parent).
+    fn cfg_and_block_env(
+        &self,
+        config: &PayloadConfig<EthPayloadBuilderAttributes>,
+        parent: &SealedHeader,
+    ) -> Result<(CfgEnvWithHandlerCfg, BlockEnv), PayloadBuilderError> {
+        let next_attributes = NextBlockEnvAttributes {
+            timestamp: config.attributes.timestamp(),
+            suggested_fee_recipient: config.attributes.suggested_fee_recipient(),
+            prev_randao: config.attributes.prev_randao(),
+        };
+        self.evm_config
+            .next_cfg_and_block_env(parent, next_attributes)
+            .map_err(PayloadBuilderError::other)
+    }
+}
+
+impl<EvmConfig, L1Messages, Pool, Client> PayloadBuilder<Pool, Client>
+    for ScrollPayloadBuilder<EvmConfig, L1Messages>
+where
+    EvmConfig: ConfigureEvm<Header = Header>,
+    L1Messages: L1MessageProvider,
+    Client: StateProviderFactory
+        + ChainSpecProvider<ChainSpec = ScrollChainSpec>
+        + DatabaseProviderFactory
+        + StateReader,
+    Pool: TransactionPool,
+{
+    type Attributes = EthPayloadBuilderAttributes;
+    type BuiltPayload = EthBuiltPayload;
+
+    fn try_build(
+        &self,
+        args: BuildArguments<Pool, Client, EthPayloadBuilderAttributes, EthBuiltPayload>,
+    ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError> {
+        let (cfg_env, block_env) =
+            self.cfg_and_block_env(&args.config, &args.config.parent_header)?;
+
+        let pool = args.pool.clone();
+        default_scroll_payload(
+            self.evm_config.clone(),
+            &self.l1_messages,
+            args,
+            cfg_env,
+            block_env,
+            |attributes| pool.best_transactions_with_attributes(attributes),
+        )
+    }
+
+    fn build_empty_payload(
+        &self,
+        client: &Client,
+        config: PayloadConfig<Self::Attributes>,
+    ) -> Result<EthBuiltPayload, PayloadBuilderError> {
+        let args = BuildArguments::new(
+            client,
+            // we use defaults here because for the empty payload we don't need to execute anything
+            NoopTransactionPool::default(),
+            Default::default(),
+            config,
+            Default::default(),
+            None,
+        );
+
+        let (cfg_env, block_env) =
+            self.cfg_and_block_env(&args.config, &args.config.parent_header)?;
+
+        let pool = args.pool.clone();
+
+        // the empty payload doesn't include any L1 message
+        default_scroll_payload(
+            self.evm_config.clone(),
+            (),
+            args,
+            cfg_env,
+            block_env,
+            |attributes| pool.best_transactions_with_attributes(attributes),
+        )?
+        .into_payload()
+        .ok_or_else(|| PayloadBuilderError::MissingPayload)
+    }
+}
+
+/// Constructs a Scroll payload.
+///
+/// The block starts with the L1 messages yielded by the [`L1MessageProvider`], in strictly
+/// increasing queue index order and capped at
+/// [`num_l1_messages_per_block`](reth_scroll_chainspec::ScrollChainConfig), followed by the best
+/// transactions from the pool. Pool transactions are charged the L1 data fee by the evm and the
+/// fees are sent to the configured fee vault.
+#[inline]
+pub fn default_scroll_payload<EvmConfig, L1Messages, Pool, Client, F>(
+    evm_config: EvmConfig,
+    l1_messages: L1Messages,
+    args: BuildArguments<Pool, Client, EthPayloadBuilderAttributes, EthBuiltPayload>,
+    initialized_cfg: CfgEnvWithHandlerCfg,
+    initialized_block_env: BlockEnv,
+    best_txs: F,
+) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError>
+where
+    EvmConfig: ConfigureEvm<Header = Header>,
+    L1Messages: L1MessageProvider,
+    Client: StateProviderFactory
+        + ChainSpecProvider<ChainSpec = ScrollChainSpec>
+        + DatabaseProviderFactory
+        + StateReader,
+    Pool: TransactionPool,
+    F: FnOnce(BestTransactionsAttributes) -> BestTransactionsIter<Pool>,
+{
+    let BuildArguments { client, pool: _, mut cached_reads, config, cancel, best_payload } = args;
+
+    let chain_spec = client.chain_spec();
+    let state_provider = client.state_by_block_hash(config.parent_header.hash())?;
+    let state = ScrollStateProviderDatabase::new(state_provider);
+    let mut db =
+        State::builder().with_database(cached_reads.as_db_mut(state)).with_bundle_update().build();
+    let PayloadConfig { parent_header, extra_data, attributes } = config;
+
+    debug!(target: "payload_builder", id=%attributes.id, parent_header = ?parent_header.hash(), parent_number = parent_header.number, "building new payload");
+    let mut cumulative_gas_used = 0;
+    let block_gas_limit: u64 = initialized_block_env.gas_limit.to::<u64>();
+    let base_fee = initialized_block_env.basefee.to::<u64>();
+    let block_number = initialized_block_env.number.to::<u64>();
+    let is_curie = chain_spec.is_curie_active_at_block(block_number);
+
+    let mut executed_txs = Vec::new();
+    let mut executed_senders = Vec::new();
+    let mut receipts = Vec::new();
+    let mut total_fees = U256::ZERO;
+
+    // set state clear flag if the block is after the Spurious Dragon hardfork.
+    db.set_state_clear_flag(chain_spec.is_spurious_dragon_active_at_block(block_number));
+
+    // load the l1 gas oracle contract in cache
+    db.load_cache_account(L1_GAS_PRICE_ORACLE_ADDRESS)?;
+
+    if chain_spec.fork(ScrollHardfork::Curie).transitions_at_block(block_number) {
+        apply_curie_hard_fork(&mut db).map_err(|err| {
+            warn!(target: "payload_builder",
+                parent_hash=%parent_header.hash(),
+                %err,
+                "failed to apply curie hardfork for payload"
+            );
+            PayloadBuilderError::Internal(err.into())
+        })?;
+    }
+
+    let env = EnvWithHandlerCfg::new_with_cfg_env(
+        initialized_cfg.clone(),
+        initialized_block_env.clone(),
+        TxEnv::default(),
+    );
+    let mut evm = evm_config.evm_with_env(&mut db, env);
+
+    // include the pending L1 messages first
+    let l1_messages = l1_messages.pending_l1_messages(
+        parent_header.hash(),
+        chain_spec.config.l1_config.num_l1_messages_per_block,
+    )?;
+    let mut last_queue_index = None;
+    for l1_message in l1_messages {
+        // L1 messages must be included in strictly increasing queue index order
+        if last_queue_index.is_some_and(|index| l1_message.queue_index <= index) {
+            warn!(target: "payload_builder", queue_index = l1_message.queue_index, ?last_queue_index, "skipping out of order L1 message");
+            continue
+        }
+
+        // an L1 message exceeding the block gas limit can't be included in any block, it's skipped
+        // so that the following L1 messages can be included
+        if l1_message.gas_limit > block_gas_limit {
+            warn!(target: "payload_builder", queue_index = l1_message.queue_index, gas_limit = l1_message.gas_limit, "skipping L1 message exceeding the block gas limit");
+            continue
+        }
+
+        // stop including L1 messages once the block is full, the remaining ones will be included
+        // in the next block in order
+        if cumulative_gas_used + l1_message.gas_limit > block_gas_limit {
+            trace!(target: "payload_builder", queue_index = l1_message.queue_index, "L1 message exceeds the block gas limit");
+            break
+        }
+
+        // check if the job was cancelled, if so we can exit early
+        if cancel.is_cancelled() {
+            return Ok(BuildOutcome::Cancelled)
+        }
+
+        let queue_index = l1_message.queue_index;
+        let tx = l1_message_recovered(l1_message);
+
+        // Configure the environment for the tx and disable the base fee.
+        *evm.tx_mut() = evm_config.tx_env(tx.as_signed(), tx.signer());
+        evm.context.evm.env.cfg.disable_base_fee = true;
+
+        let result = evm.transact();
+        evm.context.evm.env.cfg.disable_base_fee = false;
+        let ResultAndState { result, state } = match result {
+            Ok(res) => res,
+            Err(EVMError::Transaction(err)) => {
+                // an invalid L1 message can't be included in any block, it's skipped as in l2geth
+                // so that the following L1 messages can be included
+                warn!(target: "payload_builder", %err, queue_index, "skipping invalid L1 message");
+                continue
+            }
+            // this is an error that we should treat as fatal for this attempt
+            Err(err) => return Err(PayloadBuilderError::EvmExecutionError(err)),
+        };
+
+        // commit changes
+        evm.db_mut().commit(state);
+
+        // l1 messages do not get any gas refunded
+        if let ExecutionResult::Success { gas_refunded, .. } = result {
+            cumulative_gas_used += gas_refunded
+        }
+        cumulative_gas_used += result.gas_used();
+
+        receipts.push(Some(Receipt {
+            tx_type: tx.tx_type(),
+            success: result.is_success(),
+            cumulative_gas_used,
+            logs: result.into_logs(),
+            l1_fee: U256::ZERO,
+        }));
+
+        last_queue_index = Some(queue_index);
+        executed_senders.push(tx.signer());
+        executed_txs.push(tx.into_signed());
+    }
+
+    // then fill the block with the best transactions from the pool
+    let mut best_txs = best_txs(BestTransactionsAttributes::new(base_fee, None));
+    while let Some(pool_tx) = best_txs.next() {
+        // ensure we still have capacity for this transaction
+        if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
+            // we can't fit this transaction into the block, so we need to mark it as invalid
+            // which also removes all dependent transaction from the iterator before we can
+            // continue
+            best_txs.mark_invalid(
+                &pool_tx,
+                InvalidPoolTransactionError::ExceedsGasLimit(pool_tx.gas_limit(), block_gas_limit),
+            );
+            continue
+        }
+
+        // check if the job was cancelled, if so we can exit early
+        if cancel.is_cancelled() {
+            return Ok(BuildOutcome::Cancelled)
+        }
+
+        // convert tx to a signed transaction
+        let tx = pool_tx.to_recovered_transaction();
+
+        // verify the transaction type is accepted by the current fork
+        let unsupported = match tx.tx_type() {
+            TxType::Eip2930 if !is_curie => Some(InvalidTransactionError::Eip2930Disabled),
+            TxType::Eip1559 if !is_curie => Some(InvalidTransactionError::Eip1559Disabled),
+            TxType::Eip4844 => Some(InvalidTransactionError::Eip4844Disabled),
+            TxType::Eip7702 => Some(InvalidTransactionError::Eip7702Disabled),
+            TxType::L1Message => Some(InvalidTransactionError::TxTypeNotSupported),
+            _ => None,
+        };
+        if let Some(err) = unsupported {
+            trace!(target: "payload_builder", %err, ?tx, "skipping unsupported transaction type");
+            best_txs.mark_invalid(&pool_tx, InvalidPoolTransactionError::Consensus(err));
+            continue
+        }
+
+        // Configure the environment for the tx.
+        *evm.tx_mut() = evm_config.tx_env(tx.as_signed(), tx.signer());
+
+        let ResultAndState { result, state } = match evm.transact() {
+            Ok(res) => res,
+            Err(err) => {
+                match err {
+                    EVMError::Transaction(err) => {
+                        if matches!(err, InvalidTransaction::NonceTooLow { .. }) {
+                            // if the nonce is too low, we can skip this transaction
+                            trace!(target: "payload_builder", %err, ?tx, "skipping nonce too low transaction");
+                        } else {
+                            // if the transaction is invalid, we can skip it and all of its
+                            // descendants
+                            trace!(target: "payload_builder", %err, ?tx, "skipping invalid transaction and its descendants");
+                            best_txs.mark_invalid(
+                                &pool_tx,
+                                InvalidPoolTransactionError::Consensus(
+                                    InvalidTransactionError::TxTypeNotSupported,
+                                ),
+                            );
+                        }
+
+                        continue
+                    }
+                    err => {
+                        // this is an error that we should treat as fatal for this attempt
+                        return Err(PayloadBuilderError::EvmExecutionError(err))
+                    }
+                }
+            }
+        };
+
+        // commit changes
+        evm.db_mut().commit(state);
+
+        // the l1 fee charged by the evm to the sender
+        let l1_fee = {
+            let l1_block_info =
+                evm.context.evm.inner.l1_block_info.as_ref().expect("l1_block_info loaded");
+            let transaction_rlp_bytes =
+                evm.context.evm.env.tx.scroll.rlp_bytes.as_ref().expect("rlp_bytes loaded");
+            l1_block_info.calculate_tx_l1_cost(transaction_rlp_bytes, evm.handler.cfg.spec_id)
+        };
+
+        let gas_used = result.gas_used();
+
+        // add gas used by the transaction to cumulative gas used, before creating the receipt
+        cumulative_gas_used += gas_used;
+
+        receipts.push(Some(Receipt {
+            tx_type: tx.tx_type(),
+            success: result.is_success(),
+            cumulative_gas_used,
+            logs: result.into_logs(),
+            l1_fee,
+        }));
+
+        // update add to total fees
+        let miner_fee = tx
+            .effective_tip_per_gas(Some(base_fee))
+            .expect("fee is always valid; execution succeeded");
+        total_fees += U256::from(miner_fee) * U256::from(gas_used) + l1_fee;
+
+        // append sender and transaction to the respective lists
+        executed_senders.push(tx.signer());
+        executed_txs.push(tx.into_signed());
+    }
+
+    // Release db
+    drop(evm);
+
+    // check if we have a better block
+    if !is_better_payload(best_payload.as_ref(), total_fees) {
+        // can skip building the block
+        return Ok(BuildOutcome::Aborted { fees: total_fees, cached_reads })
+    }
+
+    // merge all transitions into bundle state
+    db.merge_transitions(BundleRetention::Reverts);
+
+    let execution_outcome =
+        ExecutionOutcome::new(db.finalize(), vec![receipts].into(), block_number, Vec::new());
+    let receipts_root =
+        execution_outcome.receipts_root_slow(block_number).expect("Number is in range");
+    let logs_bloom = execution_outcome.block_logs_bloom(block_number).expect("Number is in range");
+
+    // calculate the state root, which is the root of the keccak Merkle Patricia Trie from Euclid
+    // on, the root of the zktrie not being computed anymore
+    let hashed_state = db.database.db.hashed_post_state(execution_outcome.state());
+    let (state_root, trie_output) = match client.state_commitment_scheme(attributes.timestamp) {
+        StateCommitmentScheme::ZkTrie => {
+            db.database.db.state_root_from_state_with_updates(hashed_state.clone()).inspect_err(
+                |err| {
+                    warn!(target: "payload_builder",
+                        parent_hash=%parent_header.hash(),
+                        %err,
+                        "failed to calculate state root for payload"
+                    );
+                },
+            )?
+        }
+        StateCommitmentScheme::Mpt => (
+            compute_mpt_state_root(&client, &parent_header, &execution_outcome)?,
+            TrieUpdates::default(),
+        ),
+    };
+
+    // create the block header
+    let transactions_root = proofs::calculate_transaction_root(&executed_txs);
+
+    let header = Header {
+        parent_hash: parent_header.hash(),
+        ommers_hash: EMPTY_OMMER_ROOT_HASH,
+        beneficiary: initialized_block_env.coinbase,
+        state_root,
+        transactions_root,
+        receipts_root,
+        withdrawals_root: None,
+        logs_bloom,
+        timestamp: attributes.timestamp,
+        mix_hash: attributes.prev_randao,
+        nonce: BEACON_NONCE.into(),
+        base_fee_per_gas: is_curie.then_some(base_fee),
+        number: parent_header.number + 1,
+        gas_limit: block_gas_limit,
+        difficulty: U256::ZERO,
+        gas_used: cumulative_gas_used,
+        extra_data,
+        parent_beacon_block_root: None,
+        blob_gas_used: None,
+        excess_blob_gas: None,
+        requests_hash: None,
+    };
+
+    // seal the block
+    let block = Block {
+        header,
+        body: BlockBody { transactions: executed_txs, ommers: vec![], withdrawals: None },
+    };
+
+    let sealed_block = Arc::new(block.seal_slow());
+    debug!(target: "payload_builder", id=%attributes.id, sealed_block_header = ?sealed_block.header, "sealed built block");
+
+    // create the executed block data
+    let executed = ExecutedBlock {
+        block: sealed_block.clone(),
+        senders: Arc::new(executed_senders),
+        execution_output: Arc::new(execution_outcome),
+        hashed_state: Arc::new(hashed_state),
+        trie: Arc::new(trie_output),
+    };
+
+    let payload =
+        EthBuiltPayload::new(attributes.id, sealed_block, total_fees, Some(executed), None);
+
+    Ok(BuildOutcome::Better { payload, cached_reads })
+}
+
+/// Computes the root of the keccak Merkle Patricia Trie committing to the state after the block
+/// built on top of the parent, overlaying the trie tables with the state changes of the blocks
+/// since the one they are at and with the ones of the built block.
+fn compute_mpt_state_root<Client>(
+    client: &Client,
+    parent: &SealedHeader,
+    execution_outcome: &ExecutionOutcome,
+) -> Result<B256, PayloadBuilderError>
+where
+    Client: DatabaseProviderFactory + StateReader,
+{
+    let provider = client.database_provider_ro()?;
+    let (block, mut state) = mpt_base_state(provider.tx_ref())?;
+    if block > parent.number {
+        return Err(ProviderError::StateForNumberNotFound(parent.number).into())
+    }
+
+    for number in block + 1..=parent.number {
+        let outcome =
+            client.get_state(number)?.ok_or(ProviderError::StateForNumberNotFound(number))?;
+        state.extend(HashedPostState::from_bundle_state::<KeccakKeyHasher>(&outcome.bundle.state));
+    }
+    state.extend(HashedPostState::from_bundle_state::<KeccakKeyHasher>(
+        &execution_outcome.bundle.state,
+    ));
+
+    let (root, _) = mpt_state_root(provider.tx_ref(), &state).map_err(ProviderError::from)?;
+    Ok(root)
+}
+
+/// Converts the [`TxL1Message`] into a [`TransactionSignedEcRecovered`], using the sender of the
+/// message as signer.
+fn l1_message_recovered(l1_message: TxL1Message) -> TransactionSignedEcRecovered {
+    let sender = l1_message.sender;
+    let tx = TransactionSigned::new_unhashed(
+        Transaction::L1Message(l1_message),
+        TxL1Message::signature(),
+    );
+    TransactionSignedEcRecovered::from_signed_transaction(tx, sender)
+}
@@ -0,0 +1,28 @@
+//! Source of the L1 messages included by the payload builder.
+
+use alloy_primitives::B256;
+use reth_provider::ProviderResult;
+use reth_scroll_primitives::TxL1Message;
+
+/// Provides the L1 messages pending inclusion in the L2 chain.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait L1MessageProvider: Send + Sync {
+    /// Returns at most `limit` L1 messages which follow the last L1 message included in the chain
+    /// up to the block with hash `parent_hash`, ordered by queue index.
+    fn pending_l1_messages(
+        &self,
+        parent_hash: B256,
+        limit: u64,
+    ) -> ProviderResult<Vec<TxL1Message>>;
+}
+
+/// The unit type doesn't provide any L1 message.
+impl L1MessageProvider for () {
+    fn pending_l1_messages(
+        &self,
+        _parent_hash: B256,
+        _limit: u64,
+    ) -> ProviderResult<Vec<TxL1Message>> {
+        Ok(Vec::new())
+    }
+}
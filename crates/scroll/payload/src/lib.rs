@@ -0,0 +1,8 @@
+//! Payload builder for Scroll.
+#![cfg(all(feature = "scroll", not(feature = "optimism")))]
+
+pub use builder::{default_scroll_payload, ScrollPayloadBuilder};
+mod builder;
+
+pub use l1_message::L1MessageProvider;
+mod l1_message;
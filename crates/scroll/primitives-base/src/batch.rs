@@ -0,0 +1,36 @@
+//! Scroll rollup batches.
+
+use alloy_primitives::B256;
+use serde::{Deserialize, Serialize};
+
+/// A batch of L2 blocks committed to the `ScrollChain` contract on L1.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "reth-codec", derive(reth_codecs::Compact))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+pub struct CommittedBatch {
+    /// The hash of the batch header.
+    pub hash: B256,
+    /// The codec version of the batch.
+    pub version: u8,
+    /// The number of the first L2 block of the batch.
+    pub first_block: u64,
+    /// The number of the last L2 block of the batch.
+    pub last_block: u64,
+    /// The number of the L1 block in which the batch was committed.
+    pub l1_block: u64,
+}
+
+/// The finalization of a batch on L1, which also finalizes all the previous batches.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "reth-codec", derive(reth_codecs::Compact))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+pub struct FinalizedBatch {
+    /// The hash of the batch header.
+    pub hash: B256,
+    /// The state root after the last L2 block of the batch.
+    pub state_root: B256,
+    /// The withdraw trie root after the last L2 block of the batch.
+    pub withdraw_root: B256,
+    /// The number of the L1 block in which the batch was finalized.
+    pub l1_block: u64,
+}
@@ -0,0 +1,23 @@
+//! Primitive types for the Scroll extension of `Reth`, used by the Ethereum primitive types of
+//! `reth-primitives` and `reth-primitives-traits` under the `scroll` feature.
+//!
+//! The types are re-exported by `reth-scroll-primitives`, which the other crates depend on.
+
+#![warn(unused_crate_dependencies)]
+
+pub use execution_context::ScrollPostExecutionContext;
+mod execution_context;
+
+pub use account_extension::AccountExtension;
+mod account_extension;
+
+pub use batch::{CommittedBatch, FinalizedBatch};
+mod batch;
+
+pub use l1_transaction::{
+    ScrollL1MessageTransactionFields, TxL1Message, L1_MESSAGE_TRANSACTION_TYPE,
+};
+pub mod l1_transaction;
+
+/// Poseidon hashing primitives.
+pub mod poseidon;
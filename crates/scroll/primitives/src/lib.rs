@@ -2,16 +2,36 @@
 
 #![warn(unused_crate_dependencies)]
 
-pub use execution_context::ScrollPostExecutionContext;
-mod execution_context;
+pub use reth_scroll_primitives_base::*;
 
-pub use account_extension::AccountExtension;
-mod account_extension;
+use reth_primitives::{Account, EthPrimitives, Receipt, TransactionSigned};
 
-pub use l1_transaction::{
-    ScrollL1MessageTransactionFields, TxL1Message, L1_MESSAGE_TRANSACTION_TYPE,
-};
-pub mod l1_transaction;
+/// Scroll primitive types.
+///
+/// The Scroll specific parts of the primitives (the L1 message transaction type, the L1 fee of
+/// the receipts and the account extension) are compiled into the Ethereum types of
+/// `reth-primitives` and `reth-primitives-traits` by the `scroll` feature. The node launcher and
+/// the persistence service only support nodes with the Ethereum primitives, so the Scroll node
+/// uses them as its [`NodePrimitives`](reth_primitives::NodePrimitives).
+///
+/// TODO(scroll): replace the aliases with dedicated types and remove the `scroll` feature from
+/// `reth-primitives` and `reth-primitives-traits`, along with `reth-scroll-primitives-base`. This
+/// is blocked on the following being generic over the
+/// [`NodePrimitives`](reth_primitives::NodePrimitives) of the node:
+/// - the engine launcher, the persistence service and the `ExecutedBlock` of `reth-chain-state`,
+///   which require `Primitives = EthPrimitives`;
+/// - the built payloads and the engine types, which convert the execution payloads into blocks of
+///   Ethereum transactions;
+/// - the `PlainAccountState` and `HashedAccounts` tables, which store the Ethereum [`Account`], and
+///   the `AccountInfo` of `reth-scroll-revm`, which carries the account extension under the
+///   `scroll` feature.
+pub type ScrollPrimitives = EthPrimitives;
 
-/// Poseidon hashing primitives.
-pub mod poseidon;
+/// Signed Scroll transaction.
+pub type ScrollTransactionSigned = TransactionSigned;
+
+/// Scroll receipt, carrying the L1 fee of the transaction.
+pub type ScrollReceipt = Receipt;
+
+/// Scroll account, carrying the code size and Poseidon code hash.
+pub type ScrollAccount = Account;
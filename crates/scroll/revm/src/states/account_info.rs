@@ -1,4 +1,4 @@
-use reth_scroll_primitives::{
+use reth_scroll_primitives_base::{
     poseidon::{hash_code, POSEIDON_EMPTY},
     ScrollPostExecutionContext,
 };
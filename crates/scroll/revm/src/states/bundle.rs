@@ -4,7 +4,7 @@ use crate::states::{
     reverts::ScrollReverts,
     ScrollAccountInfo, ScrollAccountInfoRevert, ScrollAccountRevert,
 };
-use reth_scroll_primitives::ScrollPostExecutionContext;
+use reth_scroll_primitives_base::ScrollPostExecutionContext;
 use revm::{
     db::{
         states::{PlainStorageChangeset, StorageSlot},
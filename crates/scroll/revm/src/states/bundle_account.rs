@@ -1,5 +1,5 @@
 use crate::states::{ScrollAccountInfo, ScrollAccountInfoRevert, ScrollAccountRevert};
-use reth_scroll_primitives::ScrollPostExecutionContext;
+use reth_scroll_primitives_base::ScrollPostExecutionContext;
 use revm::{
     db::{
         states::StorageSlot, AccountStatus, BundleAccount, RevertToSlot, StorageWithOriginalValues,
@@ -1,5 +1,5 @@
 use crate::states::ScrollAccountInfo;
-use reth_scroll_primitives::ScrollPostExecutionContext;
+use reth_scroll_primitives_base::ScrollPostExecutionContext;
 use revm::{
     db::states::{PlainStateReverts, PlainStorageChangeset, PlainStorageRevert, StateChangeset},
     primitives::{Address, Bytecode, B256},
@@ -1,5 +1,5 @@
 use crate::states::{changes::ScrollPlainStateReverts, ScrollAccountInfo};
-use reth_scroll_primitives::ScrollPostExecutionContext;
+use reth_scroll_primitives_base::ScrollPostExecutionContext;
 use revm::{
     db::{
         states::{reverts::AccountInfoRevert, PlainStorageRevert},
@@ -0,0 +1,204 @@
+//! Scroll-Reth `scrolldev_` endpoint implementation, only served in dev mode.
+
+use alloy_primitives::{keccak256, Address, Bytes, TxHash, B256, U256};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_db_api::transaction::DbTxMut;
+use reth_provider::{DBProvider, DatabaseProviderFactory, ProviderResult};
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+use reth_scroll_l1::{L1MessageReader, L1MessageWriter};
+use reth_scroll_primitives::TxL1Message;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+/// An L1 message to append to the local L1 message queue, as if it was sent on L1 through the
+/// `L1MessageQueue` contract.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1MessageRequest {
+    /// The L1 sender of the message.
+    pub sender: Address,
+    /// The L2 target of the message.
+    pub to: Address,
+    /// The value sent.
+    #[serde(default)]
+    pub value: U256,
+    /// The gas limit of the message.
+    #[serde(with = "alloy_serde::quantity", rename = "gas")]
+    pub gas_limit: u64,
+    /// The input of the message.
+    #[serde(default)]
+    pub input: Bytes,
+}
+
+/// The base intrinsic gas of an L1 message, charged by the `L1MessageQueue` contract.
+const INTRINSIC_GAS_TX: u64 = 21_000;
+
+/// The intrinsic gas of an L1 message per byte of input, charged by the `L1MessageQueue`
+/// contract regardless of the byte value.
+const INTRINSIC_GAS_PER_BYTE: u64 = 16;
+
+impl L1MessageRequest {
+    /// Returns the intrinsic gas of the message, the minimum gas limit accepted by the
+    /// `L1MessageQueue` contract.
+    pub fn intrinsic_gas(&self) -> u64 {
+        INTRINSIC_GAS_TX
+            .saturating_add((self.input.len() as u64).saturating_mul(INTRINSIC_GAS_PER_BYTE))
+    }
+}
+
+/// Scroll-Reth `scrolldev_` namespace.
+#[rpc(server, namespace = "scrolldev")]
+pub trait ScrollDevApi {
+    /// Appends the given message to the local L1 message queue and returns the hash of its L1
+    /// message transaction.
+    ///
+    /// The message gets the next queue index and is included by the payload builder in the
+    /// following blocks, along with the transactions of the pool. In instant mining mode, it
+    /// triggers a new block.
+    ///
+    /// As on L1, messages with a gas limit below their intrinsic gas are rejected.
+    #[method(name = "enqueueL1Message")]
+    fn enqueue_l1_message(&self, message: L1MessageRequest) -> RpcResult<B256>;
+}
+
+/// Implementation of the `scrolldev_` namespace, writing to the local L1 message queue otherwise
+/// indexed by the [`L1MessageQueueIndexer`](reth_scroll_l1::L1MessageQueueIndexer).
+///
+/// The local L1 message queue must not be indexed from L1 at the same time.
+#[derive(Debug, Clone)]
+pub struct ScrollDevApi<Provider> {
+    /// The provider of the local chain.
+    provider: Provider,
+    /// The sender of the hashes of the enqueued L1 messages to the local miner.
+    mining_triggers: Option<mpsc::Sender<TxHash>>,
+}
+
+impl<Provider> ScrollDevApi<Provider>
+where
+    Provider: DatabaseProviderFactory<ProviderRW: DBProvider<Tx: DbTxMut>>,
+{
+    /// Returns a new [`ScrollDevApi`].
+    pub const fn new(provider: Provider) -> Self {
+        Self { provider, mining_triggers: None }
+    }
+
+    /// Sends the hashes of the enqueued L1 messages to the given trigger of the local miner, so
+    /// that they are included right away in instant mining mode.
+    pub fn with_mining_triggers(mut self, mining_triggers: mpsc::Sender<TxHash>) -> Self {
+        self.mining_triggers = Some(mining_triggers);
+        self
+    }
+
+    /// Appends the L1 message to the local L1 message queue, enabling the queue if it was never
+    /// indexed.
+    fn enqueue(&self, message: L1MessageRequest) -> ProviderResult<TxL1Message> {
+        let provider = self.provider.database_provider_rw()?;
+        let message = TxL1Message {
+            queue_index: provider.next_l1_message_queue_index()?,
+            gas_limit: message.gas_limit,
+            to: message.to,
+            value: message.value,
+            sender: message.sender,
+            input: message.input,
+        };
+        provider.insert_l1_messages([message.clone()])?;
+        if provider.last_l1_message_queue_block()?.is_none() {
+            provider.save_last_l1_message_queue_block(0)?;
+        }
+        provider.commit()?;
+        Ok(message)
+    }
+}
+
+impl<Provider> ScrollDevApiServer for ScrollDevApi<Provider>
+where
+    Provider: DatabaseProviderFactory<ProviderRW: DBProvider<Tx: DbTxMut>> + 'static,
+{
+    fn enqueue_l1_message(&self, message: L1MessageRequest) -> RpcResult<B256> {
+        let intrinsic_gas = message.intrinsic_gas();
+        if message.gas_limit < intrinsic_gas {
+            return Err(invalid_params_rpc_err(format!(
+                "gas limit {} below intrinsic gas {intrinsic_gas}",
+                message.gas_limit
+            )))
+        }
+
+        let message = self.enqueue(message).map_err(|err| internal_rpc_err(err.to_string()))?;
+        let mut encoded = Vec::with_capacity(message.eip2718_encoded_length());
+        message.eip2718_encode(&mut encoded);
+        let hash = keccak256(encoded);
+
+        // a full channel means a block including the message is already pending
+        if let Some(mining_triggers) = &self.mining_triggers {
+            let _ = mining_triggers.try_send(hash);
+        }
+        Ok(hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_provider::test_utils::create_test_provider_factory;
+
+    #[test]
+    fn test_enqueue_l1_message() -> ProviderResult<()> {
+        let factory = create_test_provider_factory();
+        let api = ScrollDevApi::new(factory.clone());
+        assert_eq!(factory.database_provider_ro()?.last_l1_message_queue_block()?, None);
+
+        let request = L1MessageRequest {
+            sender: Address::with_last_byte(1),
+            to: Address::with_last_byte(2),
+            gas_limit: 100_000,
+            ..Default::default()
+        };
+        assert_eq!(api.enqueue(request.clone())?.queue_index, 0);
+        assert_eq!(api.enqueue(request)?.queue_index, 1);
+
+        let provider = factory.database_provider_ro()?;
+        assert_eq!(provider.last_l1_message_queue_block()?, Some(0));
+        assert_eq!(provider.l1_messages(..)?.len(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_enqueue_l1_message_triggers_mining() -> RpcResult<()> {
+        let (tx, mut rx) = mpsc::channel(1);
+        let api = ScrollDevApi::new(create_test_provider_factory()).with_mining_triggers(tx);
+
+        let request = L1MessageRequest { gas_limit: 100_000, ..Default::default() };
+        let hash = api.enqueue_l1_message(request.clone())?;
+        assert_eq!(rx.try_recv(), Ok(hash));
+
+        // the pending trigger isn't duplicated
+        api.enqueue_l1_message(request.clone())?;
+        api.enqueue_l1_message(request)?;
+        assert!(rx.try_recv().is_ok());
+        assert!(rx.try_recv().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_enqueue_l1_message_below_intrinsic_gas() -> ProviderResult<()> {
+        let factory = create_test_provider_factory();
+        let api = ScrollDevApi::new(factory.clone());
+
+        let request = L1MessageRequest { input: Bytes::from([0, 1]), ..Default::default() };
+        assert_eq!(request.intrinsic_gas(), 21_032);
+        for gas_limit in [0, 21_000, 21_031] {
+            let request = L1MessageRequest { gas_limit, ..request.clone() };
+            let err = api.enqueue_l1_message(request).unwrap_err();
+            assert_eq!(err.code(), jsonrpsee_types::error::INVALID_PARAMS_CODE);
+        }
+        assert!(factory.database_provider_ro()?.l1_messages(..)?.is_empty());
+
+        let request = L1MessageRequest { gas_limit: 21_032, ..request };
+        assert!(api.enqueue_l1_message(request).is_ok());
+        assert_eq!(factory.database_provider_ro()?.l1_messages(..)?.len(), 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+//! RPC errors specific to Scroll.
+
+use alloy_rpc_types_eth::BlockError;
+use reth_rpc_eth_api::AsEthApiError;
+use reth_rpc_eth_types::EthApiError;
+use reth_rpc_server_types::result::internal_rpc_err;
+
+/// Scroll specific errors, that extend [`EthApiError`].
+#[derive(Debug, thiserror::Error)]
+pub enum ScrollEthApiError {
+    /// L1 ethereum error.
+    #[error(transparent)]
+    Eth(#[from] EthApiError),
+    /// Thrown when an L1 message can't be converted into its RPC representation.
+    #[error("failed to convert L1 message: {0}")]
+    L1MessageConversion(#[from] serde_json::Error),
+}
+
+impl AsEthApiError for ScrollEthApiError {
+    fn as_err(&self) -> Option<&EthApiError> {
+        match self {
+            Self::Eth(err) => Some(err),
+            Self::L1MessageConversion(_) => None,
+        }
+    }
+}
+
+impl From<ScrollEthApiError> for jsonrpsee_types::error::ErrorObject<'static> {
+    fn from(err: ScrollEthApiError) -> Self {
+        match err {
+            ScrollEthApiError::Eth(err) => err.into(),
+            ScrollEthApiError::L1MessageConversion(_) => internal_rpc_err(err.to_string()),
+        }
+    }
+}
+
+impl From<BlockError> for ScrollEthApiError {
+    fn from(error: BlockError) -> Self {
+        Self::Eth(error.into())
+    }
+}
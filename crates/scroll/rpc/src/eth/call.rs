@@ -0,0 +1,148 @@
+//! Contains RPC handler implementations specific to endpoints that call/execute within evm.
+
+use crate::ScrollEthApi;
+use alloy_consensus::{Header, TxEip1559, TxEip2930, TxLegacy};
+use alloy_eips::{eip2718::Encodable2718, eip2930::AccessList};
+use alloy_primitives::{Bytes, Signature, U256};
+use alloy_rpc_types_eth::TransactionRequest;
+use reth_evm::ConfigureEvm;
+use reth_primitives::{Transaction, TransactionSigned};
+use reth_revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, TxEnv};
+use reth_rpc_eth_api::{
+    helpers::{estimate::EstimateCall, Call, EthCall, LoadPendingBlock, LoadState, SpawnBlocking},
+    RpcNodeCore,
+};
+
+impl<N> EthCall for ScrollEthApi<N>
+where
+    Self: EstimateCall + LoadPendingBlock,
+    N: RpcNodeCore,
+{
+}
+
+impl<N> EstimateCall for ScrollEthApi<N>
+where
+    Self: Call,
+    N: RpcNodeCore,
+{
+}
+
+impl<N> Call for ScrollEthApi<N>
+where
+    Self: LoadState<Evm: ConfigureEvm<Header = Header>> + SpawnBlocking,
+    N: RpcNodeCore,
+{
+    #[inline]
+    fn call_gas_limit(&self) -> u64 {
+        self.inner.gas_cap()
+    }
+
+    #[inline]
+    fn max_simulate_blocks(&self) -> u64 {
+        self.inner.max_simulate_blocks()
+    }
+
+    /// Creates a new [`EnvWithHandlerCfg`] to be used for executing the [`TransactionRequest`] in
+    /// `eth_call` and `eth_estimateGas`.
+    ///
+    /// As in l2geth, the L1 data fee of the transaction is charged to the caller, as if the
+    /// transaction was signed and included in the block.
+    fn build_call_evm_env(
+        &self,
+        cfg: CfgEnvWithHandlerCfg,
+        block: BlockEnv,
+        request: TransactionRequest,
+    ) -> Result<EnvWithHandlerCfg, Self::Error> {
+        let mut tx = self.create_txn_env(&block, request)?;
+        tx.scroll.is_l1_msg = false;
+        tx.scroll.rlp_bytes = Some(l1_data_fee_envelope(&tx));
+        Ok(EnvWithHandlerCfg::new_with_cfg_env(cfg, block, tx))
+    }
+}
+
+/// Returns the EIP-2718 encoding of the transaction executed with the [`TxEnv`], which the L1 data
+/// fee is computed from.
+///
+/// The transaction is signed with the placeholder signature used by l2geth to estimate the L1 data
+/// fee of unsigned transactions.
+fn l1_data_fee_envelope(tx: &TxEnv) -> Bytes {
+    let nonce = tx.nonce.unwrap_or_default();
+    let transaction = if let Some(max_priority_fee_per_gas) = tx.gas_priority_fee {
+        Transaction::Eip1559(TxEip1559 {
+            chain_id: tx.chain_id.unwrap_or_default(),
+            nonce,
+            gas_limit: tx.gas_limit,
+            max_fee_per_gas: tx.gas_price.saturating_to(),
+            max_priority_fee_per_gas: max_priority_fee_per_gas.saturating_to(),
+            to: tx.transact_to,
+            value: tx.value,
+            access_list: AccessList(tx.access_list.clone()),
+            input: tx.data.clone(),
+        })
+    } else if !tx.access_list.is_empty() {
+        Transaction::Eip2930(TxEip2930 {
+            chain_id: tx.chain_id.unwrap_or_default(),
+            nonce,
+            gas_price: tx.gas_price.saturating_to(),
+            gas_limit: tx.gas_limit,
+            to: tx.transact_to,
+            value: tx.value,
+            access_list: AccessList(tx.access_list.clone()),
+            input: tx.data.clone(),
+        })
+    } else {
+        Transaction::Legacy(TxLegacy {
+            chain_id: tx.chain_id,
+            nonce,
+            gas_price: tx.gas_price.saturating_to(),
+            gas_limit: tx.gas_limit,
+            to: tx.transact_to,
+            value: tx.value,
+            input: tx.data.clone(),
+        })
+    };
+
+    let signature = Signature::new(U256::MAX, U256::MAX, true);
+    TransactionSigned::new_unhashed(transaction, signature).encoded_2718().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::eip2718::Decodable2718;
+    use alloy_primitives::{Address, TxKind};
+    use reth_scroll_evm::L1GasPriceOracle;
+
+    #[test]
+    fn l1_data_fee_envelope_matches_signed_transaction() {
+        let tx = TxEnv {
+            caller: Address::random(),
+            gas_limit: 21_000,
+            gas_price: U256::from(2_000_000_000u64),
+            gas_priority_fee: Some(U256::from(1_000_000_000u64)),
+            transact_to: TxKind::Call(Address::random()),
+            value: U256::from(1),
+            data: Bytes::from_static(&[1, 2, 3]),
+            nonce: Some(7),
+            chain_id: Some(534352),
+            ..Default::default()
+        };
+
+        let envelope = l1_data_fee_envelope(&tx);
+        let decoded = TransactionSigned::decode_2718(&mut envelope.as_ref()).unwrap();
+        let Transaction::Eip1559(decoded) = decoded.transaction else {
+            panic!("expected an EIP-1559 transaction")
+        };
+        assert_eq!(decoded.nonce, 7);
+        assert_eq!(decoded.chain_id, 534352);
+        assert_eq!(decoded.input, tx.data);
+
+        // the L1 data fee is charged for the size of the signed transaction
+        let oracle = L1GasPriceOracle {
+            l1_blob_base_fee: U256::from(1),
+            blob_scalar: U256::from(1_000_000_000u64),
+            ..Default::default()
+        };
+        assert_eq!(oracle.l1_data_fee(&envelope, true), U256::from(envelope.len()));
+    }
+}
@@ -0,0 +1,467 @@
+//! Scroll-Reth `eth_` endpoint implementation.
+
+pub mod receipt;
+pub mod transaction;
+
+mod block;
+mod call;
+mod pending_block;
+
+pub use receipt::ScrollReceiptBuilder;
+
+use std::{fmt, future::Future, sync::Arc};
+
+use alloy_consensus::Header;
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::{B256, U256};
+use alloy_rpc_types_eth::FeeHistory;
+use derive_more::Deref;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_evm::ConfigureEvm;
+use reth_network_api::NetworkInfo;
+use reth_node_builder::{rpc::EthApiBuilder, EthApiBuilderCtx, FullNodeComponents};
+use reth_primitives::EthPrimitives;
+use reth_provider::{
+    BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt, CanonStateSubscriptions,
+    ChainSpecProvider, EvmEnvProvider, HeaderProvider, StageCheckpointReader, StateProviderFactory,
+};
+use reth_rpc::eth::{core::EthApiInner, DevSigner};
+use reth_rpc_eth_api::{
+    helpers::{
+        AddDevSigners, EthApiSpec, EthFees, EthSigner, EthState, LoadBlock, LoadFee, LoadState,
+        SpawnBlocking, Trace,
+    },
+    EthApiTypes, FromEthApiError, RpcNodeCore, RpcNodeCoreExt,
+};
+use reth_rpc_eth_types::{
+    fee_history::calculate_reward_percentiles_for_block, EthApiError, EthStateCache,
+    FeeHistoryCache, GasPriceOracle,
+};
+use reth_scroll_chainspec::ScrollChainSpec;
+use reth_scroll_evm::L1BaseFeeProvider;
+use reth_tasks::{
+    pool::{BlockingTaskGuard, BlockingTaskPool},
+    TaskSpawner,
+};
+use reth_transaction_pool::TransactionPool;
+
+use crate::{Scroll, ScrollEthApiError};
+
+/// Adapter for [`EthApiInner`], which holds all the data required to serve core `eth_` API.
+pub type EthApiNodeBackend<N> = EthApiInner<
+    <N as RpcNodeCore>::Provider,
+    <N as RpcNodeCore>::Pool,
+    <N as RpcNodeCore>::Network,
+    <N as RpcNodeCore>::Evm,
+>;
+
+/// Scroll-Reth `Eth` API implementation.
+///
+/// This type provides the functionality for handling `eth_` related requests.
+///
+/// This wraps a default `Eth` implementation, and provides additional functionality where the
+/// scroll spec deviates from the default (ethereum) spec, e.g. L1 message transactions and the L1
+/// fee field of transaction receipts.
+///
+/// This type implements the [`FullEthApi`](reth_rpc_eth_api::helpers::FullEthApi) by implemented
+/// all the `Eth` helper traits and prerequisite traits.
+#[derive(Deref, Clone)]
+pub struct ScrollEthApi<N: RpcNodeCore> {
+    /// Gateway to node's core components.
+    #[deref]
+    inner: Arc<EthApiNodeBackend<N>>,
+}
+
+impl<N> ScrollEthApi<N>
+where
+    N: RpcNodeCore<
+        Provider: BlockReaderIdExt
+                      + ChainSpecProvider
+                      + CanonStateSubscriptions<Primitives = EthPrimitives>
+                      + Clone
+                      + 'static,
+    >,
+{
+    /// Creates a new instance for given context.
+    pub fn new(ctx: &EthApiBuilderCtx<N>) -> Self {
+        let blocking_task_pool =
+            BlockingTaskPool::build().expect("failed to build blocking task pool");
+
+        let inner = EthApiInner::new(
+            ctx.provider.clone(),
+            ctx.pool.clone(),
+            ctx.network.clone(),
+            ctx.cache.clone(),
+            ctx.new_gas_price_oracle(),
+            ctx.config.rpc_gas_cap,
+            ctx.config.rpc_max_simulate_blocks,
+            ctx.config.eth_proof_window,
+            blocking_task_pool,
+            ctx.new_fee_history_cache(),
+            ctx.evm_config.clone(),
+            ctx.executor.clone(),
+            ctx.config.proof_permits,
+        );
+
+        Self { inner: Arc::new(inner) }
+    }
+}
+
+impl<N> EthApiBuilder<N> for ScrollEthApi<N>
+where
+    N: FullNodeComponents<
+        Provider: BlockReaderIdExt
+                      + ChainSpecProvider
+                      + CanonStateSubscriptions<Primitives = EthPrimitives>
+                      + Clone
+                      + 'static,
+    >,
+{
+    fn build(ctx: &EthApiBuilderCtx<N>) -> Self {
+        Self::new(ctx)
+    }
+}
+
+impl<N> EthApiTypes for ScrollEthApi<N>
+where
+    Self: Send + Sync,
+    N: RpcNodeCore,
+{
+    type Error = ScrollEthApiError;
+    type NetworkTypes = Scroll;
+    type TransactionCompat = Self;
+
+    fn tx_resp_builder(&self) -> &Self::TransactionCompat {
+        self
+    }
+}
+
+impl<N> RpcNodeCore for ScrollEthApi<N>
+where
+    N: RpcNodeCore,
+{
+    type Provider = N::Provider;
+    type Pool = N::Pool;
+    type Evm = <N as RpcNodeCore>::Evm;
+    type Network = <N as RpcNodeCore>::Network;
+    type PayloadBuilder = ();
+
+    #[inline]
+    fn pool(&self) -> &Self::Pool {
+        self.inner.pool()
+    }
+
+    #[inline]
+    fn evm_config(&self) -> &Self::Evm {
+        self.inner.evm_config()
+    }
+
+    #[inline]
+    fn network(&self) -> &Self::Network {
+        self.inner.network()
+    }
+
+    #[inline]
+    fn payload_builder(&self) -> &Self::PayloadBuilder {
+        &()
+    }
+
+    #[inline]
+    fn provider(&self) -> &Self::Provider {
+        self.inner.provider()
+    }
+}
+
+impl<N> RpcNodeCoreExt for ScrollEthApi<N>
+where
+    N: RpcNodeCore,
+{
+    #[inline]
+    fn cache(&self) -> &EthStateCache {
+        self.inner.cache()
+    }
+}
+
+impl<N> EthApiSpec for ScrollEthApi<N>
+where
+    N: RpcNodeCore<
+        Provider: ChainSpecProvider<ChainSpec: EthereumHardforks>
+                      + BlockNumReader
+                      + StageCheckpointReader,
+        Network: NetworkInfo,
+    >,
+{
+    #[inline]
+    fn starting_block(&self) -> U256 {
+        self.inner.starting_block()
+    }
+
+    #[inline]
+    fn signers(&self) -> &parking_lot::RwLock<Vec<Box<dyn EthSigner>>> {
+        self.inner.signers()
+    }
+}
+
+impl<N> SpawnBlocking for ScrollEthApi<N>
+where
+    Self: Send + Sync + Clone + 'static,
+    N: RpcNodeCore,
+{
+    #[inline]
+    fn io_task_spawner(&self) -> impl TaskSpawner {
+        self.inner.task_spawner()
+    }
+
+    #[inline]
+    fn tracing_task_pool(&self) -> &BlockingTaskPool {
+        self.inner.blocking_task_pool()
+    }
+
+    #[inline]
+    fn tracing_task_guard(&self) -> &BlockingTaskGuard {
+        self.inner.blocking_task_guard()
+    }
+}
+
+impl<N> LoadFee for ScrollEthApi<N>
+where
+    Self: LoadBlock<Provider = N::Provider>,
+    N: RpcNodeCore<
+        Provider: BlockReaderIdExt
+                      + EvmEnvProvider
+                      + ChainSpecProvider<ChainSpec: EthChainSpec + EthereumHardforks>
+                      + StateProviderFactory,
+    >,
+{
+    #[inline]
+    fn gas_oracle(&self) -> &GasPriceOracle<Self::Provider> {
+        self.inner.gas_oracle()
+    }
+
+    #[inline]
+    fn fee_history_cache(&self) -> &FeeHistoryCache {
+        self.inner.fee_history_cache()
+    }
+}
+
+impl<N> LoadState for ScrollEthApi<N> where
+    N: RpcNodeCore<
+        Provider: StateProviderFactory + ChainSpecProvider<ChainSpec: EthereumHardforks>,
+        Pool: TransactionPool,
+    >
+{
+}
+
+impl<N> EthState for ScrollEthApi<N>
+where
+    Self: LoadState + SpawnBlocking,
+    N: RpcNodeCore,
+{
+    #[inline]
+    fn max_proof_window(&self) -> u64 {
+        self.inner.eth_proof_window()
+    }
+}
+
+impl<N> ScrollEthApi<N>
+where
+    N: RpcNodeCore<
+        Provider: HeaderProvider
+                      + ChainSpecProvider<ChainSpec = ScrollChainSpec>
+                      + StateProviderFactory,
+    >,
+{
+    /// Returns the L2 base fee of the block following the block with the given hash, derived from
+    /// the L1 base fee stored in the L1 gas price oracle at the state of the block.
+    fn next_block_base_fee(&self, block_hash: B256) -> Result<Option<u64>, ScrollEthApiError> {
+        let Some(header) =
+            self.provider().header(&block_hash).map_err(ScrollEthApiError::from_eth_err)?
+        else {
+            return Ok(None)
+        };
+        let l1_base_fee =
+            self.provider().l1_base_fee(block_hash).map_err(ScrollEthApiError::from_eth_err)?;
+        Ok(self.provider().chain_spec().next_block_base_fee(&header, l1_base_fee))
+    }
+}
+
+impl<N> EthFees for ScrollEthApi<N>
+where
+    Self: LoadFee,
+    N: RpcNodeCore<
+        Provider: BlockReaderIdExt
+                      + ChainSpecProvider<ChainSpec = ScrollChainSpec>
+                      + StateProviderFactory,
+    >,
+{
+    /// Returns the suggested tip added to the base fee of the next block, which is derived from the
+    /// L1 base fee from Curie onward.
+    fn gas_price(&self) -> impl Future<Output = Result<U256, Self::Error>> + Send
+    where
+        Self: LoadBlock,
+    {
+        async move {
+            let header = self
+                .provider()
+                .sealed_header_by_number_or_tag(BlockNumberOrTag::Latest)
+                .map_err(Self::Error::from_eth_err)?;
+            let suggested_tip =
+                self.gas_oracle().suggest_tip_cap().await.map_err(Self::Error::from_eth_err)?;
+            let base_fee = match header {
+                Some(header) => self
+                    .next_block_base_fee(header.hash())?
+                    .or(header.base_fee_per_gas)
+                    .unwrap_or_default(),
+                None => 0,
+            };
+            Ok(suggested_tip + U256::from(base_fee))
+        }
+    }
+
+    /// Reports the fee history, for the given amount of blocks, up until the given newest block.
+    ///
+    /// Unlike Ethereum, the base fee of the block following the newest block is derived from the
+    /// L1 base fee at the state of the newest block. Scroll has no blob transactions, so the blob
+    /// fees and ratios are always zero.
+    fn fee_history(
+        &self,
+        mut block_count: u64,
+        mut newest_block: BlockNumberOrTag,
+        reward_percentiles: Option<Vec<f64>>,
+    ) -> impl Future<Output = Result<FeeHistory, Self::Error>> + Send {
+        async move {
+            if block_count == 0 {
+                return Ok(FeeHistory::default())
+            }
+
+            let max_fee_history = if reward_percentiles.is_none() {
+                self.gas_oracle().config().max_header_history
+            } else {
+                self.gas_oracle().config().max_block_history
+            };
+            block_count = block_count.min(max_fee_history);
+
+            if newest_block.is_pending() {
+                // cap the target block since we don't have fee history for the pending block
+                newest_block = BlockNumberOrTag::Latest;
+                // account for missing pending block
+                block_count = block_count.saturating_sub(1);
+            }
+
+            let end_block = self
+                .provider()
+                .block_number_for_id(newest_block.into())
+                .map_err(Self::Error::from_eth_err)?
+                .ok_or(EthApiError::HeaderNotFound(newest_block.into()))?;
+
+            // need to add 1 to the end block to get the correct (inclusive) range, without
+            // querying outside of genesis
+            let end_block_plus = end_block + 1;
+            block_count = block_count.min(end_block_plus);
+
+            // percentiles must be monotonically increasing and 0 <= p <= 100
+            if let Some(percentiles) = &reward_percentiles {
+                if percentiles.windows(2).any(|w| w[0] > w[1] || w[0] > 100.) {
+                    return Err(EthApiError::InvalidRewardPercentiles.into())
+                }
+            }
+
+            let start_block = end_block_plus - block_count;
+
+            let mut base_fee_per_gas: Vec<u128> = Vec::new();
+            let mut gas_used_ratio: Vec<f64> = Vec::new();
+            let mut rewards: Vec<Vec<u128>> = Vec::new();
+
+            let newest_hash = if let Some(fee_entries) =
+                self.fee_history_cache().get_history(start_block, end_block).await
+            {
+                if fee_entries.len() != block_count as usize {
+                    return Err(EthApiError::InvalidBlockRange.into())
+                }
+
+                for entry in &fee_entries {
+                    base_fee_per_gas.push(entry.base_fee_per_gas as u128);
+                    gas_used_ratio.push(entry.gas_used_ratio);
+
+                    if let Some(percentiles) = &reward_percentiles {
+                        rewards.push(
+                            percentiles
+                                .iter()
+                                .map(|&percentile| self.approximate_percentile(entry, percentile))
+                                .collect(),
+                        );
+                    }
+                }
+                fee_entries.last().expect("is not empty").header_hash
+            } else {
+                let headers = self
+                    .provider()
+                    .sealed_headers_range(start_block..=end_block)
+                    .map_err(Self::Error::from_eth_err)?;
+                if headers.len() != block_count as usize {
+                    return Err(EthApiError::InvalidBlockRange.into())
+                }
+
+                for header in &headers {
+                    base_fee_per_gas.push(header.base_fee_per_gas.unwrap_or_default() as u128);
+                    gas_used_ratio.push(header.gas_used as f64 / header.gas_limit as f64);
+
+                    if let Some(percentiles) = &reward_percentiles {
+                        let (block, receipts) = self
+                            .cache()
+                            .get_block_and_receipts(header.hash())
+                            .await
+                            .map_err(Self::Error::from_eth_err)?
+                            .ok_or(EthApiError::InvalidBlockRange)?;
+                        rewards.push(
+                            calculate_reward_percentiles_for_block(
+                                percentiles,
+                                header.gas_used,
+                                header.base_fee_per_gas.unwrap_or_default(),
+                                &block.body.transactions,
+                                &receipts,
+                            )
+                            .unwrap_or_default(),
+                        );
+                    }
+                }
+                headers.last().expect("is present").hash()
+            };
+
+            // the base fees include the block following the newest block of the range
+            let next_block_base_fee = self.next_block_base_fee(newest_hash)?.unwrap_or_default();
+            base_fee_per_gas.push(next_block_base_fee as u128);
+
+            Ok(FeeHistory {
+                base_fee_per_blob_gas: vec![0; base_fee_per_gas.len()],
+                blob_gas_used_ratio: vec![0.; gas_used_ratio.len()],
+                base_fee_per_gas,
+                gas_used_ratio,
+                oldest_block: start_block,
+                reward: reward_percentiles.map(|_| rewards),
+            })
+        }
+    }
+}
+
+impl<N> Trace for ScrollEthApi<N>
+where
+    Self: RpcNodeCore<Provider: BlockReader> + LoadState<Evm: ConfigureEvm<Header = Header>>,
+    N: RpcNodeCore,
+{
+}
+
+impl<N> AddDevSigners for ScrollEthApi<N>
+where
+    N: RpcNodeCore,
+{
+    fn with_dev_accounts(&self) {
+        *self.inner.signers().write() = DevSigner::random_signers(20)
+    }
+}
+
+impl<N: RpcNodeCore> fmt::Debug for ScrollEthApi<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScrollEthApi").finish_non_exhaustive()
+    }
+}
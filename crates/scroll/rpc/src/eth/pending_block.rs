@@ -0,0 +1,98 @@
+//! Loads Scroll pending block for a RPC response.
+
+use crate::ScrollEthApi;
+use alloy_consensus::Header;
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::B256;
+use reth_evm::ConfigureEvm;
+use reth_primitives::SealedHeader;
+use reth_provider::{
+    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProvider, StateProviderFactory,
+};
+use reth_revm::primitives::{BlockEnv, CfgEnv, CfgEnvWithHandlerCfg, SpecId};
+use reth_rpc_eth_api::{
+    helpers::{LoadPendingBlock, SpawnBlocking},
+    FromEthApiError, RpcNodeCore,
+};
+use reth_rpc_eth_types::{EthApiError, PendingBlock, PendingBlockEnv, PendingBlockEnvOrigin};
+use reth_scroll_chainspec::ScrollChainSpec;
+use reth_scroll_consensus::{L1_BASE_FEE_SLOT, L1_GAS_PRICE_ORACLE_ADDRESS};
+use reth_transaction_pool::TransactionPool;
+
+/// The assumed interval between Scroll blocks, in seconds.
+const BLOCK_TIME: u64 = 3;
+
+impl<N> LoadPendingBlock for ScrollEthApi<N>
+where
+    Self: SpawnBlocking,
+    N: RpcNodeCore<
+        Provider: BlockReaderIdExt<
+            Block = reth_primitives::Block,
+            Receipt = reth_primitives::Receipt,
+        > + EvmEnvProvider
+                      + ChainSpecProvider<ChainSpec = ScrollChainSpec>
+                      + StateProviderFactory,
+        Pool: TransactionPool,
+        Evm: ConfigureEvm<Header = Header>,
+    >,
+{
+    #[inline]
+    fn pending_block(&self) -> &tokio::sync::Mutex<Option<PendingBlock>> {
+        self.inner.pending_block()
+    }
+
+    /// Configures the [`CfgEnvWithHandlerCfg`] and [`BlockEnv`] for the pending block.
+    ///
+    /// If no pending block is available, it is derived from the `latest` block. From Curie, the
+    /// base fee of the derived block is computed from the L1 base fee stored in the L1 gas price
+    /// oracle at the `latest` state, see [`ScrollChainSpec::next_block_base_fee`].
+    fn pending_block_env_and_cfg(&self) -> Result<PendingBlockEnv, Self::Error> {
+        let origin = if let Some(pending) =
+            self.provider().pending_block_with_senders().map_err(Self::Error::from_eth_err)?
+        {
+            PendingBlockEnvOrigin::ActualPending(pending)
+        } else {
+            let latest = self
+                .provider()
+                .latest_header()
+                .map_err(Self::Error::from_eth_err)?
+                .ok_or(EthApiError::HeaderNotFound(BlockNumberOrTag::Latest.into()))?;
+
+            let l1_base_fee = self
+                .provider()
+                .state_by_block_hash(latest.hash())
+                .and_then(|state| {
+                    state.storage(L1_GAS_PRICE_ORACLE_ADDRESS, B256::from(L1_BASE_FEE_SLOT))
+                })
+                .map_err(Self::Error::from_eth_err)?
+                .unwrap_or_default();
+            let base_fee_per_gas =
+                self.provider().chain_spec().next_block_base_fee(latest.header(), l1_base_fee);
+
+            let (mut latest_header, block_hash) = latest.split();
+            // child block
+            latest_header.number += 1;
+            latest_header.timestamp += BLOCK_TIME;
+            latest_header.base_fee_per_gas = base_fee_per_gas;
+
+            // we're reusing the same block hash because we need this to lookup the block's state
+            let latest = SealedHeader::new(latest_header, block_hash);
+
+            PendingBlockEnvOrigin::DerivedFromLatest(latest)
+        };
+
+        let mut cfg = CfgEnvWithHandlerCfg::new_with_spec_id(CfgEnv::default(), SpecId::LATEST);
+
+        let mut block_env = BlockEnv::default();
+        self.provider()
+            .fill_env_with_header(
+                &mut cfg,
+                &mut block_env,
+                origin.header(),
+                self.evm_config().clone(),
+            )
+            .map_err(Self::Error::from_eth_err)?;
+
+        Ok(PendingBlockEnv::new(cfg, block_env, origin))
+    }
+}
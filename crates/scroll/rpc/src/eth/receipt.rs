@@ -0,0 +1,116 @@
+//! Loads and formats Scroll receipt RPC response.
+
+use alloy_network::AnyReceiptEnvelope;
+use alloy_primitives::U256;
+use alloy_rpc_types_eth::{Log, TransactionReceipt};
+use alloy_serde::{OtherFields, WithOtherFields};
+use reth_primitives::{Receipt, TransactionMeta, TransactionSigned};
+use reth_provider::{ReceiptProvider, TransactionsProvider};
+use reth_rpc_eth_api::{
+    helpers::LoadReceipt, FromEthApiError, RpcNodeCore, RpcNodeCoreExt, RpcReceipt,
+};
+use reth_rpc_eth_types::{receipt::build_receipt, EthApiError};
+
+use crate::{ScrollEthApi, ScrollEthApiError, ScrollTransactionReceipt};
+
+impl<N> LoadReceipt for ScrollEthApi<N>
+where
+    Self: RpcNodeCoreExt<
+        Provider: TransactionsProvider<Transaction = TransactionSigned>
+                      + ReceiptProvider<Receipt = Receipt>,
+    >,
+    N: RpcNodeCore,
+{
+    async fn build_transaction_receipt(
+        &self,
+        tx: TransactionSigned,
+        meta: TransactionMeta,
+        receipt: Receipt,
+    ) -> Result<RpcReceipt<Self::NetworkTypes>, Self::Error> {
+        let hash = meta.block_hash;
+        // get all receipts for the block
+        let all_receipts = self
+            .cache()
+            .get_receipts(hash)
+            .await
+            .map_err(Self::Error::from_eth_err)?
+            .ok_or(Self::Error::from_eth_err(EthApiError::HeaderNotFound(hash.into())))?;
+
+        Ok(ScrollReceiptBuilder::new(&tx, meta, &receipt, &all_receipts)?.build())
+    }
+}
+
+/// Builds a [`ScrollTransactionReceipt`].
+#[derive(Debug)]
+pub struct ScrollReceiptBuilder {
+    /// Core receipt, has all the fields of an L1 receipt and is the basis for the Scroll receipt.
+    pub core_receipt: TransactionReceipt<AnyReceiptEnvelope<Log>>,
+    /// The L1 fee paid by the transaction, zero for L1 messages.
+    pub l1_fee: U256,
+}
+
+impl ScrollReceiptBuilder {
+    /// Returns a new builder.
+    ///
+    /// Note: This requires _all_ block receipts because we need to calculate the gas used by the
+    /// transaction.
+    pub fn new(
+        transaction: &TransactionSigned,
+        meta: TransactionMeta,
+        receipt: &Receipt,
+        all_receipts: &[Receipt],
+    ) -> Result<Self, ScrollEthApiError> {
+        let core_receipt =
+            build_receipt(transaction, meta, receipt, all_receipts, |receipt_with_bloom| {
+                AnyReceiptEnvelope { inner: receipt_with_bloom, r#type: receipt.tx_type.into() }
+            })?;
+
+        Ok(Self { core_receipt, l1_fee: receipt.l1_fee })
+    }
+
+    /// Builds [`ScrollTransactionReceipt`] by combing core (l1) receipt fields and the `l1Fee`.
+    pub fn build(self) -> ScrollTransactionReceipt {
+        let Self { core_receipt, l1_fee } = self;
+
+        let mut other = OtherFields::default();
+        other.insert("l1Fee".to_string(), serde_json::json!(l1_fee));
+
+        WithOtherFields { inner: core_receipt, other }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::b256;
+    use reth_primitives::{Transaction, TxType};
+    use reth_scroll_primitives::TxL1Message;
+
+    #[test]
+    fn test_l1_fee_field() {
+        let tx = TransactionSigned::new_unhashed(
+            Transaction::L1Message(TxL1Message { gas_limit: 21_000, ..Default::default() }),
+            TxL1Message::signature(),
+        );
+        let meta = TransactionMeta {
+            tx_hash: tx.hash(),
+            block_hash: b256!("0000000000000000000000000000000000000000000000000000000000000001"),
+            ..Default::default()
+        };
+        let receipt = Receipt {
+            tx_type: TxType::L1Message,
+            success: true,
+            cumulative_gas_used: 21_000,
+            l1_fee: U256::from(1_000),
+            ..Default::default()
+        };
+
+        let receipt =
+            ScrollReceiptBuilder::new(&tx, meta, &receipt, &[receipt.clone()]).unwrap().build();
+        let json = serde_json::to_value(&receipt).unwrap();
+
+        assert_eq!(json["type"], "0x7e");
+        assert_eq!(json["l1Fee"], "0x3e8");
+        assert_eq!(json["gasUsed"], "0x5208");
+    }
+}
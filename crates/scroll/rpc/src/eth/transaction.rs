@@ -0,0 +1,202 @@
+//! Loads and formats Scroll transaction RPC response.
+
+use alloy_consensus::{Signed, Transaction as _, TxEip4844Variant, TxEnvelope};
+use alloy_network::{AnyTxEnvelope, UnknownTxEnvelope};
+use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_rpc_types_eth::TransactionInfo;
+use alloy_serde::WithOtherFields;
+use reth_primitives::{TransactionSigned, TransactionSignedEcRecovered};
+use reth_provider::{BlockReaderIdExt, TransactionsProvider};
+use reth_rpc_eth_api::{
+    helpers::{EthSigner, EthTransactions, LoadTransaction, SpawnBlocking},
+    FullEthApiTypes, RpcNodeCore, TransactionCompat,
+};
+use reth_scroll_primitives::{
+    ScrollL1MessageTransactionFields, TxL1Message, L1_MESSAGE_TRANSACTION_TYPE,
+};
+use reth_transaction_pool::TransactionPool;
+use serde::Serialize;
+
+use crate::{ScrollEthApi, ScrollEthApiError, ScrollTransaction};
+
+impl<N> EthTransactions for ScrollEthApi<N>
+where
+    Self: LoadTransaction<Provider: BlockReaderIdExt>,
+    N: RpcNodeCore,
+{
+    fn signers(&self) -> &parking_lot::RwLock<Vec<Box<dyn EthSigner>>> {
+        self.inner.signers()
+    }
+}
+
+impl<N> LoadTransaction for ScrollEthApi<N>
+where
+    Self: SpawnBlocking + FullEthApiTypes,
+    N: RpcNodeCore<Provider: TransactionsProvider, Pool: TransactionPool>,
+    Self::Pool: TransactionPool,
+{
+}
+
+impl<N> TransactionCompat for ScrollEthApi<N>
+where
+    N: RpcNodeCore,
+{
+    type Transaction = ScrollTransaction;
+    type Error = ScrollEthApiError;
+
+    fn fill(
+        &self,
+        tx: TransactionSignedEcRecovered,
+        tx_info: TransactionInfo,
+    ) -> Result<Self::Transaction, Self::Error> {
+        let from = tx.signer();
+        let hash = tx.hash();
+        let TransactionSigned { transaction, signature, .. } = tx.into_signed();
+
+        let TransactionInfo {
+            block_hash, block_number, index: transaction_index, base_fee, ..
+        } = tx_info;
+
+        // L1 messages don't pay any L2 gas
+        let effective_gas_price = if transaction.is_l1_message() {
+            0
+        } else {
+            base_fee
+                .map(|base_fee| {
+                    transaction.effective_tip_per_gas(Some(base_fee as u64)).unwrap_or_default() +
+                        base_fee
+                })
+                .unwrap_or_else(|| transaction.max_fee_per_gas())
+        };
+
+        let inner = match transaction {
+            reth_primitives::Transaction::Legacy(tx) => {
+                AnyTxEnvelope::Ethereum(Signed::new_unchecked(tx, signature, hash).into())
+            }
+            reth_primitives::Transaction::Eip2930(tx) => {
+                AnyTxEnvelope::Ethereum(Signed::new_unchecked(tx, signature, hash).into())
+            }
+            reth_primitives::Transaction::Eip1559(tx) => {
+                AnyTxEnvelope::Ethereum(Signed::new_unchecked(tx, signature, hash).into())
+            }
+            reth_primitives::Transaction::Eip4844(tx) => {
+                AnyTxEnvelope::Ethereum(Signed::new_unchecked(tx, signature, hash).into())
+            }
+            reth_primitives::Transaction::Eip7702(tx) => {
+                AnyTxEnvelope::Ethereum(Signed::new_unchecked(tx, signature, hash).into())
+            }
+            reth_primitives::Transaction::L1Message(tx) => {
+                AnyTxEnvelope::Unknown(l1_message_envelope(tx, hash)?)
+            }
+        };
+
+        Ok(WithOtherFields::new(alloy_rpc_types_eth::Transaction {
+            inner,
+            block_hash,
+            block_number,
+            transaction_index,
+            from,
+            effective_gas_price: Some(effective_gas_price),
+        }))
+    }
+
+    fn otterscan_api_truncate_input(tx: &mut Self::Transaction) {
+        let input = match &mut tx.inner.inner {
+            AnyTxEnvelope::Ethereum(TxEnvelope::Eip1559(tx)) => &mut tx.tx_mut().input,
+            AnyTxEnvelope::Ethereum(TxEnvelope::Eip2930(tx)) => &mut tx.tx_mut().input,
+            AnyTxEnvelope::Ethereum(TxEnvelope::Legacy(tx)) => &mut tx.tx_mut().input,
+            AnyTxEnvelope::Ethereum(TxEnvelope::Eip4844(tx)) => match tx.tx_mut() {
+                TxEip4844Variant::TxEip4844(tx) => &mut tx.input,
+                TxEip4844Variant::TxEip4844WithSidecar(tx) => &mut tx.tx.input,
+            },
+            AnyTxEnvelope::Ethereum(TxEnvelope::Eip7702(tx)) => &mut tx.tx_mut().input,
+            _ => return,
+        };
+        *input = input.slice(..4);
+    }
+}
+
+/// The JSON representation of an L1 message transaction, as returned by l2geth.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcL1Message {
+    hash: B256,
+    #[serde(rename = "type", with = "alloy_serde::quantity")]
+    ty: u8,
+    /// The nonce of an L1 message is its queue index.
+    #[serde(with = "alloy_serde::quantity")]
+    nonce: u64,
+    #[serde(with = "alloy_serde::quantity")]
+    gas: u64,
+    to: Address,
+    value: U256,
+    input: Bytes,
+    v: U256,
+    r: U256,
+    s: U256,
+    #[serde(flatten)]
+    fields: ScrollL1MessageTransactionFields,
+}
+
+/// Converts the L1 message into an [`UnknownTxEnvelope`], with the
+/// [`ScrollL1MessageTransactionFields`] in its fields.
+fn l1_message_envelope(
+    tx: TxL1Message,
+    hash: B256,
+) -> Result<UnknownTxEnvelope, ScrollEthApiError> {
+    let TxL1Message { queue_index, gas_limit, to, value, sender, input } = tx;
+    let message = RpcL1Message {
+        hash,
+        ty: L1_MESSAGE_TRANSACTION_TYPE,
+        nonce: queue_index,
+        gas: gas_limit,
+        to,
+        value,
+        input,
+        v: U256::ZERO,
+        r: U256::ZERO,
+        s: U256::ZERO,
+        fields: ScrollL1MessageTransactionFields { queue_index, sender },
+    };
+
+    Ok(serde_json::from_value(serde_json::to_value(message)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, bytes};
+
+    #[test]
+    fn test_l1_message_envelope() {
+        let tx = TxL1Message {
+            queue_index: 947883,
+            gas_limit: 2000000,
+            to: address!("781e90f1c8fc4611c9b7497c3b47f99ef6969cbc"),
+            value: U256::ZERO,
+            sender: address!("7885bcbd5cecef1336b5300fb5186a12ddd8c478"),
+            input: bytes!("8ef1332e"),
+        };
+        let hash = b256!("1e4c8d4f4f5d2c6ef5b7b0b61c7e5a4c2b1d4f0f6f5b6d0c2c1a2a2e8e3d1f0a");
+
+        let envelope = l1_message_envelope(tx, hash).unwrap();
+        let json = serde_json::to_value(&envelope).unwrap();
+
+        assert_eq!(json["type"], "0x7e");
+        assert_eq!(json["hash"], hash.to_string());
+        assert_eq!(json["queueIndex"], "0xe76ab");
+        assert_eq!(json["nonce"], "0xe76ab");
+        assert_eq!(json["sender"], "0x7885bcbd5cecef1336b5300fb5186a12ddd8c478");
+        assert_eq!(json["gas"], "0x1e8480");
+
+        let fields =
+            envelope.inner.fields.deserialize_into::<ScrollL1MessageTransactionFields>().unwrap();
+        assert_eq!(
+            fields,
+            ScrollL1MessageTransactionFields {
+                queue_index: 947883,
+                sender: address!("7885bcbd5cecef1336b5300fb5186a12ddd8c478"),
+            }
+        );
+    }
+}
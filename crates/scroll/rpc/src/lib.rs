@@ -0,0 +1,14 @@
+//! Scroll-Reth RPC support.
+#![cfg(all(feature = "scroll", not(feature = "optimism")))]
+
+pub mod dev;
+pub mod error;
+pub mod eth;
+pub mod network;
+pub mod scroll;
+
+pub use dev::{L1MessageRequest, ScrollDevApi, ScrollDevApiServer};
+pub use error::ScrollEthApiError;
+pub use eth::{ScrollEthApi, ScrollReceiptBuilder};
+pub use network::{Scroll, ScrollTransaction, ScrollTransactionReceipt};
+pub use scroll::{ScrollApi, ScrollApiServer, WithdrawalProof};
@@ -0,0 +1,208 @@
+//! Scroll [`Network`] definition and RPC response types.
+//!
+//! Scroll responses follow the JSON layout of l2geth: L1 message transactions are returned with
+//! their `queueIndex` and `sender` and receipts carry the `l1Fee` paid by the transaction.
+
+use alloy_network::{
+    AnyNetwork, AnyReceiptEnvelope, AnyRpcTransaction, AnyTxEnvelope, AnyTxType,
+    AnyTypedTransaction, BuildResult, Network, NetworkWallet, TransactionBuilder,
+    TransactionBuilderError, UnbuiltTransactionBuilder,
+};
+use alloy_primitives::{Address, Bytes, ChainId, TxKind, U256};
+use alloy_rpc_types_eth::{AccessList, Header, Log, TransactionReceipt, TransactionRequest};
+use alloy_serde::WithOtherFields;
+
+/// RPC transaction response for Scroll.
+///
+/// L1 message transactions are represented by an unknown typed envelope holding the
+/// [`ScrollL1MessageTransactionFields`](reth_scroll_primitives::ScrollL1MessageTransactionFields).
+pub type ScrollTransaction = AnyRpcTransaction;
+
+/// RPC transaction receipt response for Scroll, with the `l1Fee` stored in the other fields.
+pub type ScrollTransactionReceipt = WithOtherFields<TransactionReceipt<AnyReceiptEnvelope<Log>>>;
+
+/// Types for the Scroll network.
+///
+/// This reuses the types of the [`AnyNetwork`], in order to support the L1 message transaction
+/// type, but returns the regular Ethereum header in RPC responses.
+#[derive(Clone, Copy, Debug)]
+pub struct Scroll {
+    _private: (),
+}
+
+impl Network for Scroll {
+    type TxType = AnyTxType;
+
+    type TxEnvelope = AnyTxEnvelope;
+
+    type UnsignedTx = AnyTypedTransaction;
+
+    type ReceiptEnvelope = AnyReceiptEnvelope;
+
+    type Header = alloy_consensus::Header;
+
+    type TransactionRequest = WithOtherFields<TransactionRequest>;
+
+    type TransactionResponse = ScrollTransaction;
+
+    type ReceiptResponse = ScrollTransactionReceipt;
+
+    type HeaderResponse = Header;
+
+    type BlockResponse =
+        alloy_rpc_types_eth::Block<Self::TransactionResponse, Self::HeaderResponse>;
+}
+
+/// Maps the builder error of the [`AnyNetwork`] to the [`Scroll`] network, which shares the same
+/// transaction types.
+fn into_scroll_builder_error(
+    error: TransactionBuilderError<AnyNetwork>,
+) -> TransactionBuilderError<Scroll> {
+    match error {
+        TransactionBuilderError::InvalidTransactionRequest(ty, missing) => {
+            TransactionBuilderError::InvalidTransactionRequest(ty, missing)
+        }
+        TransactionBuilderError::UnsupportedSignatureType => {
+            TransactionBuilderError::UnsupportedSignatureType
+        }
+        TransactionBuilderError::Signer(err) => TransactionBuilderError::Signer(err),
+        TransactionBuilderError::Custom(err) => TransactionBuilderError::Custom(err),
+    }
+}
+
+impl TransactionBuilder<Scroll> for WithOtherFields<TransactionRequest> {
+    fn chain_id(&self) -> Option<ChainId> {
+        TransactionBuilder::<AnyNetwork>::chain_id(self)
+    }
+
+    fn set_chain_id(&mut self, chain_id: ChainId) {
+        TransactionBuilder::<AnyNetwork>::set_chain_id(self, chain_id)
+    }
+
+    fn nonce(&self) -> Option<u64> {
+        TransactionBuilder::<AnyNetwork>::nonce(self)
+    }
+
+    fn set_nonce(&mut self, nonce: u64) {
+        TransactionBuilder::<AnyNetwork>::set_nonce(self, nonce)
+    }
+
+    fn input(&self) -> Option<&Bytes> {
+        TransactionBuilder::<AnyNetwork>::input(self)
+    }
+
+    fn set_input<T: Into<Bytes>>(&mut self, input: T) {
+        TransactionBuilder::<AnyNetwork>::set_input(self, input)
+    }
+
+    fn from(&self) -> Option<Address> {
+        TransactionBuilder::<AnyNetwork>::from(self)
+    }
+
+    fn set_from(&mut self, from: Address) {
+        TransactionBuilder::<AnyNetwork>::set_from(self, from)
+    }
+
+    fn kind(&self) -> Option<TxKind> {
+        TransactionBuilder::<AnyNetwork>::kind(self)
+    }
+
+    fn clear_kind(&mut self) {
+        TransactionBuilder::<AnyNetwork>::clear_kind(self)
+    }
+
+    fn set_kind(&mut self, kind: TxKind) {
+        TransactionBuilder::<AnyNetwork>::set_kind(self, kind)
+    }
+
+    fn value(&self) -> Option<U256> {
+        TransactionBuilder::<AnyNetwork>::value(self)
+    }
+
+    fn set_value(&mut self, value: U256) {
+        TransactionBuilder::<AnyNetwork>::set_value(self, value)
+    }
+
+    fn gas_price(&self) -> Option<u128> {
+        TransactionBuilder::<AnyNetwork>::gas_price(self)
+    }
+
+    fn set_gas_price(&mut self, gas_price: u128) {
+        TransactionBuilder::<AnyNetwork>::set_gas_price(self, gas_price)
+    }
+
+    fn max_fee_per_gas(&self) -> Option<u128> {
+        TransactionBuilder::<AnyNetwork>::max_fee_per_gas(self)
+    }
+
+    fn set_max_fee_per_gas(&mut self, max_fee_per_gas: u128) {
+        TransactionBuilder::<AnyNetwork>::set_max_fee_per_gas(self, max_fee_per_gas)
+    }
+
+    fn max_priority_fee_per_gas(&self) -> Option<u128> {
+        TransactionBuilder::<AnyNetwork>::max_priority_fee_per_gas(self)
+    }
+
+    fn set_max_priority_fee_per_gas(&mut self, max_priority_fee_per_gas: u128) {
+        TransactionBuilder::<AnyNetwork>::set_max_priority_fee_per_gas(
+            self,
+            max_priority_fee_per_gas,
+        )
+    }
+
+    fn gas_limit(&self) -> Option<u64> {
+        TransactionBuilder::<AnyNetwork>::gas_limit(self)
+    }
+
+    fn set_gas_limit(&mut self, gas_limit: u64) {
+        TransactionBuilder::<AnyNetwork>::set_gas_limit(self, gas_limit)
+    }
+
+    fn access_list(&self) -> Option<&AccessList> {
+        TransactionBuilder::<AnyNetwork>::access_list(self)
+    }
+
+    fn set_access_list(&mut self, access_list: AccessList) {
+        TransactionBuilder::<AnyNetwork>::set_access_list(self, access_list)
+    }
+
+    fn complete_type(&self, ty: AnyTxType) -> Result<(), Vec<&'static str>> {
+        TransactionBuilder::<AnyNetwork>::complete_type(self, ty)
+    }
+
+    fn can_submit(&self) -> bool {
+        TransactionBuilder::<AnyNetwork>::can_submit(self)
+    }
+
+    fn can_build(&self) -> bool {
+        TransactionBuilder::<AnyNetwork>::can_build(self)
+    }
+
+    fn output_tx_type(&self) -> AnyTxType {
+        TransactionBuilder::<AnyNetwork>::output_tx_type(self)
+    }
+
+    fn output_tx_type_checked(&self) -> Option<AnyTxType> {
+        TransactionBuilder::<AnyNetwork>::output_tx_type_checked(self)
+    }
+
+    fn prep_for_submission(&mut self) {
+        TransactionBuilder::<AnyNetwork>::prep_for_submission(self)
+    }
+
+    fn build_unsigned(self) -> BuildResult<AnyTypedTransaction, Scroll> {
+        TransactionBuilder::<AnyNetwork>::build_unsigned(self).map_err(
+            |UnbuiltTransactionBuilder { error, tx }| UnbuiltTransactionBuilder {
+                error: into_scroll_builder_error(error),
+                tx,
+            },
+        )
+    }
+
+    async fn build<W: NetworkWallet<Scroll>>(
+        self,
+        wallet: &W,
+    ) -> Result<AnyTxEnvelope, TransactionBuilderError<Scroll>> {
+        Ok(wallet.sign_request(self).await?)
+    }
+}
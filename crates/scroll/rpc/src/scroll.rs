@@ -0,0 +1,174 @@
+//! Scroll-Reth `scroll_` endpoint implementation.
+
+use alloy_eips::BlockId;
+use alloy_primitives::{Bytes, B256, U256};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_chainspec::ChainSpecProvider;
+use reth_provider::{BlockReaderIdExt, DatabaseProviderFactory, StateProviderFactory};
+use reth_rpc_eth_types::EthApiError;
+use reth_rpc_server_types::result::internal_rpc_err;
+use reth_scroll_evm::L1GasPriceOracle;
+use reth_scroll_forks::ScrollHardforks;
+use reth_scroll_l1::BatchReader;
+use reth_scroll_withdraw_trie::{
+    withdraw_proof, withdraw_root, WithdrawTrieError, WithdrawTrieReader,
+};
+use serde::{Deserialize, Serialize};
+
+/// The Merkle proof of an L2 to L1 message in the withdraw trie, as expected by the
+/// `relayMessageWithProof` method of the `L1ScrollMessenger` contract.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalProof {
+    /// The hash of the message.
+    pub message_hash: B256,
+    /// The index of the message in the withdraw trie, which is also its nonce.
+    #[serde(with = "alloy_serde::quantity")]
+    pub index: u64,
+    /// The index of the finalized batch whose withdraw root the proof is against, or `None` if
+    /// the message wasn't finalized on L1 yet.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub batch_index: Option<u64>,
+    /// The L2 block after which the withdraw trie has the root the proof is against.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: u64,
+    /// The root of the withdraw trie the proof is against.
+    pub withdraw_root: B256,
+    /// The siblings of the nodes on the path from the message to the root, leaf first,
+    /// concatenated.
+    pub proof: Bytes,
+}
+
+/// Scroll-Reth `scroll_` namespace.
+#[rpc(server, namespace = "scroll")]
+pub trait ScrollApi {
+    /// Returns the Merkle proof of the message with the given hash in the withdraw trie, against
+    /// the withdraw root of the last finalized batch if the message was finalized on L1, or the
+    /// withdraw root after the last indexed L2 block otherwise.
+    ///
+    /// Returns an error if the node doesn't index the withdraw trie.
+    #[method(name = "getWithdrawalProof")]
+    fn withdrawal_proof(&self, message_hash: B256) -> RpcResult<Option<WithdrawalProof>>;
+
+    /// Returns the L1 data fee of the given EIP-2718 encoded transaction if it were included in
+    /// the block following the given block, the latest block by default.
+    #[method(name = "estimateL1DataFee")]
+    fn estimate_l1_data_fee(&self, tx: Bytes, block_id: Option<BlockId>) -> RpcResult<U256>;
+
+    /// Returns the values of the L1 gas price oracle at the given block, the latest block by
+    /// default.
+    #[method(name = "getL1GasOracleParams")]
+    fn l1_gas_oracle_params(&self, block_id: Option<BlockId>) -> RpcResult<L1GasPriceOracle>;
+}
+
+/// Implementation of the `scroll_` namespace, reading the withdraw trie indexed by the
+/// [`WithdrawTrieIndexer`](reth_scroll_withdraw_trie::WithdrawTrieIndexer), the batches indexed
+/// by the [`BatchIndexer`](reth_scroll_l1::BatchIndexer) and the L1 gas price oracle state.
+#[derive(Debug, Clone)]
+pub struct ScrollApi<Provider> {
+    /// The provider of the local chain.
+    provider: Provider,
+    /// Whether the withdraw trie is indexed.
+    withdraw_trie: bool,
+}
+
+impl<Provider> ScrollApi<Provider>
+where
+    Provider: DatabaseProviderFactory,
+{
+    /// Returns a new [`ScrollApi`].
+    ///
+    /// The withdraw trie isn't indexed by default, see [`ScrollApi::with_withdraw_trie`].
+    pub const fn new(provider: Provider) -> Self {
+        Self { provider, withdraw_trie: false }
+    }
+
+    /// Sets whether the withdraw trie is indexed. Otherwise, `scroll_getWithdrawalProof` returns
+    /// [`WithdrawTrieError::NotIndexed`].
+    pub const fn with_withdraw_trie(mut self, withdraw_trie: bool) -> Self {
+        self.withdraw_trie = withdraw_trie;
+        self
+    }
+
+    /// Returns the Merkle proof of the message with the given hash in the withdraw trie.
+    fn withdrawal_proof_of(
+        &self,
+        message_hash: B256,
+    ) -> Result<Option<WithdrawalProof>, WithdrawTrieError> {
+        if !self.withdraw_trie {
+            return Err(WithdrawTrieError::NotIndexed)
+        }
+
+        let provider = self.provider.database_provider_ro()?;
+        let Some(index) = provider.withdraw_message_index(message_hash)? else { return Ok(None) };
+        let last_block = provider.last_withdraw_trie_block()?.unwrap_or_default();
+
+        // prove the message against the last finalized batch if it includes the message
+        let mut batch_index = None;
+        let mut block_number = last_block;
+        if let Some((finalized_index, _)) = provider.last_finalized_batch()? {
+            if let Some(batch) = provider.committed_batch(finalized_index)? {
+                if batch.last_block <= last_block &&
+                    provider.withdraw_trie_size(batch.last_block)? > index
+                {
+                    batch_index = Some(finalized_index);
+                    block_number = batch.last_block;
+                }
+            }
+        }
+
+        let size = provider.withdraw_trie_size(block_number)?;
+        let proof = withdraw_proof(&provider, index, size)?;
+        Ok(Some(WithdrawalProof {
+            message_hash,
+            index,
+            batch_index,
+            block_number,
+            withdraw_root: withdraw_root(&provider, size)?,
+            proof: proof.iter().flat_map(|node| node.0).collect(),
+        }))
+    }
+}
+
+impl<Provider> ScrollApi<Provider>
+where
+    Provider:
+        StateProviderFactory + BlockReaderIdExt + ChainSpecProvider<ChainSpec: ScrollHardforks>,
+{
+    /// Returns the values of the L1 gas price oracle at the given block, along with whether Curie
+    /// is active for the following block.
+    fn l1_gas_price_oracle_at(
+        &self,
+        block_id: BlockId,
+    ) -> Result<(L1GasPriceOracle, bool), EthApiError> {
+        let number = self
+            .provider
+            .block_number_for_id(block_id)?
+            .ok_or(EthApiError::HeaderNotFound(block_id))?;
+        let state = self.provider.state_by_block_id(block_id)?;
+        let is_curie = self.provider.chain_spec().is_curie_active_at_block(number + 1);
+        Ok((L1GasPriceOracle::try_from_state(&state)?, is_curie))
+    }
+}
+
+impl<Provider> ScrollApiServer for ScrollApi<Provider>
+where
+    Provider: DatabaseProviderFactory
+        + StateProviderFactory
+        + BlockReaderIdExt
+        + ChainSpecProvider<ChainSpec: ScrollHardforks>
+        + 'static,
+{
+    fn withdrawal_proof(&self, message_hash: B256) -> RpcResult<Option<WithdrawalProof>> {
+        self.withdrawal_proof_of(message_hash).map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    fn estimate_l1_data_fee(&self, tx: Bytes, block_id: Option<BlockId>) -> RpcResult<U256> {
+        let (oracle, is_curie) = self.l1_gas_price_oracle_at(block_id.unwrap_or_default())?;
+        Ok(oracle.l1_data_fee(&tx, is_curie))
+    }
+
+    fn l1_gas_oracle_params(&self, block_id: Option<BlockId>) -> RpcResult<L1GasPriceOracle> {
+        Ok(self.l1_gas_price_oracle_at(block_id.unwrap_or_default())?.0)
+    }
+}
@@ -0,0 +1,136 @@
+#![allow(missing_docs, unreachable_pub)]
+use alloy_primitives::{B256, U256};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use proptest::{prelude::*, strategy::ValueTree, test_runner::TestRunner};
+use reth_primitives::Account;
+use reth_provider::{
+    providers::ConsistentDbView, test_utils::create_test_provider_factory, StateWriter, TrieWriter,
+};
+use reth_scroll_state_commitment::{
+    test_utils::{b256_clear_last_byte, u256_clear_msb},
+    ParallelStateRoot, StateRoot,
+};
+use reth_trie::{
+    hashed_cursor::HashedPostStateCursorFactory, HashedPostState, HashedStorage, TrieInput,
+};
+use reth_trie_db::{DatabaseHashedCursorFactory, DatabaseStateRoot};
+use std::collections::HashMap;
+
+pub fn calculate_state_root(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Calculate Scroll State Root");
+    group.sample_size(10);
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    for size in [100, 500, 1_000] {
+        let (db_state, updated_state) = generate_test_data(size);
+        let provider_factory = create_test_provider_factory();
+        {
+            let provider_rw = provider_factory.provider_rw().unwrap();
+            provider_rw.write_hashed_state(&db_state.into_sorted()).unwrap();
+            let (_, updates) =
+                StateRoot::from_tx(provider_rw.tx_ref()).root_with_updates().unwrap();
+            provider_rw.write_trie_updates(&updates).unwrap();
+            provider_rw.commit().unwrap();
+        }
+
+        let view = ConsistentDbView::new(provider_factory.clone(), None);
+
+        // state root
+        group.bench_function(BenchmarkId::new("sync root", size), |b| {
+            b.to_async(&runtime).iter_with_setup(
+                || {
+                    let sorted_state = updated_state.clone().into_sorted();
+                    let prefix_sets = updated_state.construct_prefix_sets().freeze();
+                    let provider = provider_factory.provider().unwrap();
+                    (provider, sorted_state, prefix_sets)
+                },
+                |(provider, sorted_state, prefix_sets)| async move {
+                    let hashed_cursor_factory = HashedPostStateCursorFactory::new(
+                        DatabaseHashedCursorFactory::new(provider.tx_ref()),
+                        &sorted_state,
+                    );
+                    StateRoot::from_tx(provider.tx_ref())
+                        .with_hashed_cursor_factory(hashed_cursor_factory)
+                        .with_prefix_sets(prefix_sets)
+                        .root()
+                },
+            )
+        });
+
+        // parallel root
+        group.bench_function(BenchmarkId::new("parallel root", size), |b| {
+            b.to_async(&runtime).iter_with_setup(
+                || {
+                    ParallelStateRoot::new(
+                        view.clone(),
+                        TrieInput::from_state(updated_state.clone()),
+                    )
+                },
+                |calculator| async { calculator.incremental_root() },
+            );
+        });
+    }
+}
+
+fn generate_test_data(size: usize) -> (HashedPostState, HashedPostState) {
+    let storage_size = 100;
+    let mut runner = TestRunner::new(ProptestConfig::default());
+
+    // keys and values must be valid field elements
+    let key = any::<B256>().prop_map(b256_clear_last_byte);
+    let value =
+        any::<U256>().prop_map(u256_clear_msb).prop_filter("non zero value", |v| !v.is_zero());
+    let account = (any::<u64>(), any::<U256>()).prop_map(|(nonce, balance)| Account {
+        nonce,
+        balance: u256_clear_msb(balance),
+        #[cfg(feature = "scroll")]
+        account_extension: Some(Default::default()),
+        ..Default::default()
+    });
+
+    use proptest::{collection::hash_map, sample::subsequence};
+    let db_state =
+        hash_map(key.clone(), (account, hash_map(key, value.clone(), storage_size)), size)
+            .new_tree(&mut runner)
+            .unwrap()
+            .current();
+
+    let keys = db_state.keys().copied().collect::<Vec<_>>();
+    let keys_to_update = subsequence(keys, size / 2).new_tree(&mut runner).unwrap().current();
+
+    let updated_storages = keys_to_update
+        .into_iter()
+        .map(|address| {
+            let (_, storage) = db_state.get(&address).unwrap();
+            let slots = storage.keys().copied().collect::<Vec<_>>();
+            let slots_to_update =
+                subsequence(slots, storage_size / 2).new_tree(&mut runner).unwrap().current();
+            (
+                address,
+                slots_to_update
+                    .into_iter()
+                    .map(|slot| (slot, value.new_tree(&mut runner).unwrap().current()))
+                    .collect::<HashMap<_, _>>(),
+            )
+        })
+        .collect::<HashMap<_, _>>();
+
+    (
+        HashedPostState::default()
+            .with_accounts(
+                db_state.iter().map(|(address, (account, _))| (*address, Some(*account))),
+            )
+            .with_storages(db_state.into_iter().map(|(address, (_, storage))| {
+                (address, HashedStorage::from_iter(false, storage))
+            })),
+        HashedPostState::default().with_storages(
+            updated_storages
+                .into_iter()
+                .map(|(address, storage)| (address, HashedStorage::from_iter(false, storage))),
+        ),
+    )
+}
+
+criterion_group!(state_root, calculate_state_root);
+criterion_main!(state_root);
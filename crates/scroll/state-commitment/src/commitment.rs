@@ -1,4 +1,6 @@
-use super::{PoseidonKeyHasher, StateRoot, StorageRoot};
+use super::{
+    proof::Proof, witness::TrieWitness, PoseidonKeyHasher, SparseStateTrie, StateRoot, StorageRoot,
+};
 use reth_db::transaction::DbTx;
 use reth_trie_db::{DatabaseHashedCursorFactory, DatabaseTrieCursorFactory, StateCommitment};
 
@@ -13,14 +15,9 @@ impl StateCommitment for BinaryMerklePatriciaTrie {
         StateRoot<DatabaseTrieCursorFactory<'a, TX>, DatabaseHashedCursorFactory<'a, TX>>;
     type StorageRoot<'a, TX: DbTx + 'a> =
         StorageRoot<DatabaseTrieCursorFactory<'a, TX>, DatabaseHashedCursorFactory<'a, TX>>;
-    // TODO(scroll): replace with scroll proof type
-    type StateProof<'a, TX: DbTx + 'a> = reth_trie::proof::Proof<
-        DatabaseTrieCursorFactory<'a, TX>,
-        DatabaseHashedCursorFactory<'a, TX>,
-    >;
-    // TODO(scroll): replace with scroll witness type
-    type StateWitness<'a, TX: DbTx + 'a> = reth_trie::witness::TrieWitness<
-        DatabaseTrieCursorFactory<'a, TX>,
-        DatabaseHashedCursorFactory<'a, TX>,
-    >;
+    type StateProof<'a, TX: DbTx + 'a> =
+        Proof<DatabaseTrieCursorFactory<'a, TX>, DatabaseHashedCursorFactory<'a, TX>>;
+    type StateWitness<'a, TX: DbTx + 'a> =
+        TrieWitness<DatabaseTrieCursorFactory<'a, TX>, DatabaseHashedCursorFactory<'a, TX>>;
+    type SparseStateTrie = SparseStateTrie;
 }
@@ -9,12 +9,23 @@ pub use commitment::BinaryMerklePatriciaTrie;
 mod root;
 pub use root::*;
 
+pub mod proof;
+pub use proof::{verify_account_proof, verify_storage_proof};
+
+pub mod witness;
+
+mod sparse;
+pub use sparse::SparseStateTrie;
+
 mod key;
 pub use key::PoseidonKeyHasher;
 
 mod value;
 pub use value::PoseidonValueHasher;
 
+pub mod mpt;
+pub use mpt::{mpt_state_root, StateCommitmentScheme, StateCommitmentSchemeProvider, MPT_STAGE_ID};
+
 /// test utils for the state commitment
 #[cfg(feature = "test-utils")]
 pub mod test_utils;
@@ -0,0 +1,723 @@
+//! The keccak Merkle Patricia Trie committing to the Scroll state from the
+//! [`Euclid`](ScrollHardfork::Euclid) hardfork on.
+//!
+//! The trie is computed from the keccak hashed state stored in the
+//! [`MptHashedAccounts`](tables::MptHashedAccounts) and
+//! [`MptHashedStorages`](tables::MptHashedStorages) tables, kept apart from the Poseidon hashed
+//! state of the zktrie. Its branch nodes are stored in the
+//! [`MptAccountsTrie`](tables::MptAccountsTrie) and [`MptStoragesTrie`](tables::MptStoragesTrie)
+//! tables, so that updating the root only walks the paths of the changed leaves. The root is
+//! computed by [`StateRoot`] over cursors on these tables.
+//!
+//! The tables are kept up to date as soon as Euclid is scheduled, so that the roots of the first
+//! Euclid blocks can be computed before these blocks are persisted.
+
+use alloy_primitives::{keccak256, BlockNumber, B256, U256};
+use itertools::Itertools;
+use reth_chainspec::Hardforks;
+#[cfg(feature = "scroll")]
+use reth_chainspec::{ChainSpecProvider, ForkCondition};
+use reth_db::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW},
+    tables,
+    transaction::{DbTx, DbTxMut},
+    DatabaseError,
+};
+use reth_primitives::{Account, Header, StorageEntry};
+use reth_provider::{AccountExtReader, DBProvider, ProviderError, ProviderResult, StorageReader};
+use reth_scroll_forks::ScrollHardfork;
+use reth_stages_types::StageId;
+use reth_trie::{
+    hashed_cursor::{
+        HashedCursor, HashedCursorFactory, HashedPostStateCursorFactory, HashedStorageCursor,
+    },
+    trie_cursor::{TrieCursor, TrieCursorFactory},
+    updates::TrieUpdates,
+    BranchNodeCompact, HashedPostState, KeccakKeyHasher, KeyEncoding, Nibbles, StateRoot,
+    StorageTrieEntry, StoredNibbles, StoredNibblesSubKey,
+};
+use reth_trie_db::DatabaseHashedPostState;
+use tracing::info;
+
+/// The id of the stage keeping the keccak Merkle Patricia Trie up to date with the plain state.
+pub const MPT_STAGE_ID: StageId = StageId::Other("ScrollMptMigration");
+
+/// The scheme of the state commitment of a Scroll block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StateCommitmentScheme {
+    /// The Poseidon binary zktrie, before Euclid.
+    ZkTrie,
+    /// The keccak Merkle Patricia Trie, from Euclid on.
+    Mpt,
+}
+
+impl StateCommitmentScheme {
+    /// Returns the state commitment scheme of the block with the given timestamp.
+    pub fn at_timestamp(chain_spec: &impl Hardforks, timestamp: u64) -> Self {
+        if chain_spec.is_fork_active_at_timestamp(ScrollHardfork::Euclid, timestamp) {
+            Self::Mpt
+        } else {
+            Self::ZkTrie
+        }
+    }
+}
+
+/// A provider of the [`StateCommitmentScheme`] of the blocks of its chain.
+///
+/// Without the `scroll` feature, the state of every block is committed to by the trie built from
+/// the hashed state tables, and the keccak Merkle Patricia Trie is never scheduled.
+pub trait StateCommitmentSchemeProvider {
+    /// Returns the state commitment scheme of the block with the given timestamp.
+    fn state_commitment_scheme(&self, timestamp: u64) -> StateCommitmentScheme;
+
+    /// Returns whether the chain switches to the keccak Merkle Patricia Trie at some point, the
+    /// trie being kept up to date from then on so that it's ready when the switch happens.
+    fn is_mpt_scheduled(&self) -> bool;
+}
+
+#[cfg(not(feature = "scroll"))]
+impl<T> StateCommitmentSchemeProvider for T {
+    fn state_commitment_scheme(&self, _timestamp: u64) -> StateCommitmentScheme {
+        StateCommitmentScheme::ZkTrie
+    }
+
+    fn is_mpt_scheduled(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(feature = "scroll")]
+impl<T> StateCommitmentSchemeProvider for T
+where
+    T: ChainSpecProvider<ChainSpec: Hardforks>,
+{
+    fn state_commitment_scheme(&self, timestamp: u64) -> StateCommitmentScheme {
+        StateCommitmentScheme::at_timestamp(&*self.chain_spec(), timestamp)
+    }
+
+    fn is_mpt_scheduled(&self) -> bool {
+        self.chain_spec().fork(ScrollHardfork::Euclid) != ForkCondition::Never
+    }
+}
+
+/// Returns the block whose state the keccak hashed state and trie tables hold, or `None` if the
+/// state isn't migrated to them yet.
+pub fn mpt_checkpoint<TX: DbTx>(tx: &TX) -> Result<Option<BlockNumber>, DatabaseError> {
+    if tx.cursor_read::<tables::MptHashedAccounts>()?.first()?.is_none() {
+        return Ok(None)
+    }
+    Ok(tx.get::<tables::StageCheckpoints>(MPT_STAGE_ID.to_string())?.map(|c| c.block_number))
+}
+
+/// Returns the last persisted block, along with the keccak hashed post state to overlay the keccak
+/// hashed state and trie tables with to get the state after that block.
+///
+/// The roots of the blocks that aren't persisted yet are computed by extending the returned state
+/// with their changes. The state is migrated to the tables by the `ScrollMptMigrationStage` before
+/// the blocks are validated, and the tables are updated as the blocks are persisted, so an error is
+/// returned if they are behind the persisted state. The only exception is the genesis state of a
+/// new chain, which is migrated with the first persisted blocks and is hashed in memory until then.
+pub fn mpt_base_state<TX: DbTx>(tx: &TX) -> ProviderResult<(BlockNumber, HashedPostState)> {
+    let block = tx
+        .get::<tables::StageCheckpoints>(StageId::Execution.to_string())?
+        .unwrap_or_default()
+        .block_number;
+    match mpt_checkpoint(tx)? {
+        Some(checkpoint) if checkpoint == block => Ok((block, HashedPostState::default())),
+        None if block == 0 => Ok((block, hashed_plain_state(tx)?)),
+        _ => Err(ProviderError::StateForNumberNotFound(block)),
+    }
+}
+
+/// Returns the whole plain state as a keccak hashed post state.
+fn hashed_plain_state<TX: DbTx>(tx: &TX) -> Result<HashedPostState, DatabaseError> {
+    let mut state = HashedPostState::default();
+    for entry in tx.cursor_read::<tables::PlainAccountState>()?.walk(None)? {
+        let (address, account) = entry?;
+        state.accounts.insert(keccak256(address), Some(account));
+    }
+    for entry in tx.cursor_read::<tables::PlainStorageState>()?.walk(None)? {
+        let (address, StorageEntry { key, value }) = entry?;
+        state.storages.entry(keccak256(address)).or_default().storage.insert(keccak256(key), value);
+    }
+    Ok(state)
+}
+
+/// Hashes the whole plain state into the keccak hashed state tables, replacing their content and
+/// removing the stored branch nodes of the trie.
+pub fn hash_plain_state_for_mpt<TX: DbTx + DbTxMut>(tx: &TX) -> Result<(), DatabaseError> {
+    clear_mpt_tables(tx)?;
+
+    let mut hashed_accounts = tx.cursor_write::<tables::MptHashedAccounts>()?;
+    for entry in tx.cursor_read::<tables::PlainAccountState>()?.walk(None)? {
+        let (address, account) = entry?;
+        hashed_accounts.upsert(keccak256(address), account)?;
+    }
+
+    let mut hashed_storages = tx.cursor_dup_write::<tables::MptHashedStorages>()?;
+    for entry in tx.cursor_read::<tables::PlainStorageState>()?.walk(None)? {
+        let (address, StorageEntry { key, value }) = entry?;
+        hashed_storages.upsert(keccak256(address), StorageEntry { key: keccak256(key), value })?;
+    }
+
+    Ok(())
+}
+
+/// Clears the keccak hashed state and trie tables.
+fn clear_mpt_tables<TX: DbTx + DbTxMut>(tx: &TX) -> Result<(), DatabaseError> {
+    tx.clear::<tables::MptHashedAccounts>()?;
+    tx.clear::<tables::MptHashedStorages>()?;
+    tx.clear::<tables::MptAccountsTrie>()?;
+    tx.clear::<tables::MptStoragesTrie>()?;
+    Ok(())
+}
+
+/// Writes the keccak hashed post state to the keccak hashed state tables.
+pub fn write_mpt_hashed_state<TX: DbTx + DbTxMut>(
+    tx: &TX,
+    state: &HashedPostState,
+) -> Result<(), DatabaseError> {
+    let mut accounts = tx.cursor_write::<tables::MptHashedAccounts>()?;
+    for (hashed_address, account) in state.accounts.iter().sorted_by_key(|(key, _)| *key) {
+        if let Some(account) = account {
+            accounts.upsert(*hashed_address, *account)?;
+        } else if accounts.seek_exact(*hashed_address)?.is_some() {
+            accounts.delete_current()?;
+        }
+    }
+
+    let mut storages = tx.cursor_dup_write::<tables::MptHashedStorages>()?;
+    for (hashed_address, storage) in state.storages.iter().sorted_by_key(|(key, _)| *key) {
+        if storage.wiped && storages.seek_exact(*hashed_address)?.is_some() {
+            storages.delete_current_duplicates()?;
+        }
+        for (key, value) in storage.storage.iter().sorted_by_key(|(key, _)| *key) {
+            if storages
+                .seek_by_key_subkey(*hashed_address, *key)?
+                .filter(|entry| entry.key == *key)
+                .is_some()
+            {
+                storages.delete_current()?;
+            }
+            if !value.is_zero() {
+                storages.upsert(*hashed_address, StorageEntry { key: *key, value: *value })?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the changes to the stored branch nodes of the keccak Merkle Patricia Trie.
+pub fn write_mpt_trie_updates<TX: DbTx + DbTxMut>(
+    tx: &TX,
+    updates: &TrieUpdates,
+) -> Result<(), DatabaseError> {
+    // the updated nodes take precedence over the removed ones
+    let removed = updates
+        .removed_nodes_ref()
+        .iter()
+        .filter(|path| !updates.account_nodes_ref().contains_key(*path))
+        .map(|path| (path, None));
+    let updated = updates.account_nodes_ref().iter().map(|(path, node)| (path, Some(node)));
+    let mut accounts = tx.cursor_write::<tables::MptAccountsTrie>()?;
+    for (path, node) in removed.chain(updated).sorted_unstable_by_key(|(path, _)| *path) {
+        let path = StoredNibbles(path.clone());
+        match node {
+            Some(node) if !path.0.is_empty() => accounts.upsert(path, node.clone())?,
+            Some(_) => {}
+            None => {
+                if accounts.seek_exact(path)?.is_some() {
+                    accounts.delete_current()?;
+                }
+            }
+        }
+    }
+
+    let mut storages = tx.cursor_dup_write::<tables::MptStoragesTrie>()?;
+    for (hashed_address, storage) in
+        updates.storage_tries_ref().iter().sorted_unstable_by_key(|(key, _)| *key)
+    {
+        if storage.is_deleted() && storages.seek_exact(*hashed_address)?.is_some() {
+            storages.delete_current_duplicates()?;
+        }
+
+        let removed = storage
+            .removed_nodes_ref()
+            .iter()
+            .filter(|path| !storage.storage_nodes_ref().contains_key(*path))
+            .map(|path| (path, None));
+        let updated = storage.storage_nodes_ref().iter().map(|(path, node)| (path, Some(node)));
+        for (path, node) in removed
+            .chain(updated)
+            .filter(|(path, _)| !path.is_empty())
+            .sorted_unstable_by_key(|(path, _)| *path)
+        {
+            let nibbles = StoredNibblesSubKey(path.clone());
+            if storages
+                .seek_by_key_subkey(*hashed_address, nibbles.clone())?
+                .filter(|entry| entry.nibbles == nibbles)
+                .is_some()
+            {
+                storages.delete_current()?;
+            }
+            if let Some(node) = node {
+                storages
+                    .upsert(*hashed_address, StorageTrieEntry { nibbles, node: node.clone() })?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Computes the root of the keccak Merkle Patricia Trie of the keccak hashed state overlaid with
+/// the given keccak hashed post state, along with the changes to the stored branch nodes.
+///
+/// Only the paths of the changed accounts and storage slots are walked, the subtries of the others
+/// being taken from the stored branch nodes.
+pub fn mpt_state_root<TX: DbTx>(
+    tx: &TX,
+    state: &HashedPostState,
+) -> Result<(B256, TrieUpdates), DatabaseError> {
+    let prefix_sets = state.construct_prefix_sets_with_key_encoding(KeyEncoding::Nibbles).freeze();
+    let state = state.clone().into_sorted();
+    Ok(StateRoot::new(
+        MptTrieCursorFactory::new(tx),
+        HashedPostStateCursorFactory::new(MptHashedCursorFactory::new(tx), &state),
+    )
+    .with_prefix_sets(prefix_sets)
+    .root_with_updates()?)
+}
+
+/// Updates the keccak hashed state and trie to the state after the block of the header `to`,
+/// returning the root of the trie, or `None` if the state after `to` isn't committed to by the
+/// trie.
+///
+/// The tables are kept up to date as soon as Euclid is scheduled, and left empty otherwise. They
+/// are updated from the changesets if they hold the state after a previous block, and the whole
+/// plain state is migrated to them otherwise, the genesis state not being part of the changesets.
+pub fn update_mpt_state<Provider>(provider: &Provider, to: &Header) -> ProviderResult<Option<B256>>
+where
+    Provider:
+        DBProvider<Tx: DbTxMut> + AccountExtReader + StorageReader + StateCommitmentSchemeProvider,
+{
+    if !provider.is_mpt_scheduled() {
+        return Ok(None)
+    }
+
+    let tx = provider.tx_ref();
+    let state = match mpt_checkpoint(tx)?.filter(|from| *from <= to.number) {
+        Some(from) => {
+            let mut state = HashedPostState::default();
+            let accounts = provider.changed_accounts_with_range(from + 1..=to.number)?;
+            for (address, account) in provider.basic_accounts(accounts)? {
+                state.accounts.insert(keccak256(address), account);
+            }
+            let storages = provider.changed_storages_with_range(from + 1..=to.number)?;
+            for (address, slots) in provider.plain_state_storages(storages)? {
+                state
+                    .storages
+                    .entry(keccak256(address))
+                    .or_default()
+                    .storage
+                    .extend(slots.into_iter().map(|slot| (keccak256(slot.key), slot.value)));
+            }
+            state
+        }
+        None => {
+            info!(target: "trie::mpt", block = to.number, "Migrating the state to the MPT");
+            hash_plain_state_for_mpt(tx)?;
+            HashedPostState::default()
+        }
+    };
+
+    let (root, updates) = mpt_state_root(tx, &state)?;
+    write_mpt_hashed_state(tx, &state)?;
+    write_mpt_trie_updates(tx, &updates)?;
+    Ok(is_mpt(provider, to).then_some(root))
+}
+
+/// Unwinds the keccak hashed state and trie to the state after the block of the header `to`,
+/// returning the root of the trie, or `None` if the state after the block isn't committed to by the
+/// trie. The tables are left untouched if they are empty, Euclid not being scheduled.
+///
+/// The changesets of the unwound blocks must still be in the database.
+pub fn unwind_mpt_state<Provider>(provider: &Provider, to: &Header) -> ProviderResult<Option<B256>>
+where
+    Provider: DBProvider<Tx: DbTxMut> + StateCommitmentSchemeProvider,
+{
+    let tx = provider.tx_ref();
+    if tx.cursor_read::<tables::MptHashedAccounts>()?.first()?.is_none() {
+        return Ok(None)
+    }
+
+    let state = HashedPostState::from_reverts::<KeccakKeyHasher>(tx, to.number + 1)?;
+    let (root, updates) = mpt_state_root(tx, &state)?;
+    write_mpt_hashed_state(tx, &state)?;
+    write_mpt_trie_updates(tx, &updates)?;
+    Ok(is_mpt(provider, to).then_some(root))
+}
+
+/// Returns whether the state after the block of the header is committed to by the trie.
+fn is_mpt(provider: &impl StateCommitmentSchemeProvider, header: &Header) -> bool {
+    provider.state_commitment_scheme(header.timestamp) == StateCommitmentScheme::Mpt
+}
+
+/// The factory of the cursors over the branch nodes of the keccak Merkle Patricia Trie, stored in
+/// the [`MptAccountsTrie`](tables::MptAccountsTrie) and
+/// [`MptStoragesTrie`](tables::MptStoragesTrie) tables.
+#[derive(Debug)]
+pub struct MptTrieCursorFactory<'a, TX>(&'a TX);
+
+impl<TX> Clone for MptTrieCursorFactory<'_, TX> {
+    fn clone(&self) -> Self {
+        Self(self.0)
+    }
+}
+
+impl<'a, TX> MptTrieCursorFactory<'a, TX> {
+    /// Returns a new [`MptTrieCursorFactory`] over the tables of the transaction.
+    pub const fn new(tx: &'a TX) -> Self {
+        Self(tx)
+    }
+}
+
+impl<TX: DbTx> TrieCursorFactory for MptTrieCursorFactory<'_, TX> {
+    type AccountTrieCursor = MptAccountTrieCursor<<TX as DbTx>::Cursor<tables::MptAccountsTrie>>;
+    type StorageTrieCursor = MptStorageTrieCursor<<TX as DbTx>::DupCursor<tables::MptStoragesTrie>>;
+
+    fn account_trie_cursor(&self) -> Result<Self::AccountTrieCursor, DatabaseError> {
+        Ok(MptAccountTrieCursor(self.0.cursor_read::<tables::MptAccountsTrie>()?))
+    }
+
+    fn storage_trie_cursor(
+        &self,
+        hashed_address: B256,
+    ) -> Result<Self::StorageTrieCursor, DatabaseError> {
+        Ok(MptStorageTrieCursor {
+            cursor: self.0.cursor_dup_read::<tables::MptStoragesTrie>()?,
+            hashed_address,
+        })
+    }
+}
+
+/// A cursor over the branch nodes of the keccak Merkle Patricia Trie of the accounts.
+#[derive(Debug)]
+pub struct MptAccountTrieCursor<C>(C);
+
+impl<C> TrieCursor for MptAccountTrieCursor<C>
+where
+    C: DbCursorRO<tables::MptAccountsTrie> + Send + Sync,
+{
+    fn seek_exact(
+        &mut self,
+        key: Nibbles,
+    ) -> Result<Option<(Nibbles, BranchNodeCompact)>, DatabaseError> {
+        Ok(self.0.seek_exact(StoredNibbles(key))?.map(|(key, node)| (key.0, node)))
+    }
+
+    fn seek(
+        &mut self,
+        key: Nibbles,
+    ) -> Result<Option<(Nibbles, BranchNodeCompact)>, DatabaseError> {
+        Ok(self.0.seek(StoredNibbles(key))?.map(|(key, node)| (key.0, node)))
+    }
+
+    fn next(&mut self) -> Result<Option<(Nibbles, BranchNodeCompact)>, DatabaseError> {
+        Ok(self.0.next()?.map(|(key, node)| (key.0, node)))
+    }
+
+    fn current(&mut self) -> Result<Option<Nibbles>, DatabaseError> {
+        Ok(self.0.current()?.map(|(key, _)| key.0))
+    }
+}
+
+/// A cursor over the branch nodes of the keccak Merkle Patricia Trie of the storage of an account.
+#[derive(Debug)]
+pub struct MptStorageTrieCursor<C> {
+    cursor: C,
+    hashed_address: B256,
+}
+
+impl<C> TrieCursor for MptStorageTrieCursor<C>
+where
+    C: DbCursorRO<tables::MptStoragesTrie> + DbDupCursorRO<tables::MptStoragesTrie> + Send + Sync,
+{
+    fn seek_exact(
+        &mut self,
+        key: Nibbles,
+    ) -> Result<Option<(Nibbles, BranchNodeCompact)>, DatabaseError> {
+        let nibbles = StoredNibblesSubKey(key);
+        Ok(self
+            .cursor
+            .seek_by_key_subkey(self.hashed_address, nibbles.clone())?
+            .filter(|entry| entry.nibbles == nibbles)
+            .map(|entry| (entry.nibbles.0, entry.node)))
+    }
+
+    fn seek(
+        &mut self,
+        key: Nibbles,
+    ) -> Result<Option<(Nibbles, BranchNodeCompact)>, DatabaseError> {
+        Ok(self
+            .cursor
+            .seek_by_key_subkey(self.hashed_address, StoredNibblesSubKey(key))?
+            .map(|entry| (entry.nibbles.0, entry.node)))
+    }
+
+    fn next(&mut self) -> Result<Option<(Nibbles, BranchNodeCompact)>, DatabaseError> {
+        Ok(self.cursor.next_dup()?.map(|(_, entry)| (entry.nibbles.0, entry.node)))
+    }
+
+    fn current(&mut self) -> Result<Option<Nibbles>, DatabaseError> {
+        Ok(self.cursor.current()?.map(|(_, entry)| entry.nibbles.0))
+    }
+}
+
+/// The factory of the cursors over the keccak hashed state, stored in the
+/// [`MptHashedAccounts`](tables::MptHashedAccounts) and
+/// [`MptHashedStorages`](tables::MptHashedStorages) tables.
+#[derive(Debug)]
+pub struct MptHashedCursorFactory<'a, TX>(&'a TX);
+
+impl<TX> Clone for MptHashedCursorFactory<'_, TX> {
+    fn clone(&self) -> Self {
+        Self(self.0)
+    }
+}
+
+impl<'a, TX> MptHashedCursorFactory<'a, TX> {
+    /// Returns a new [`MptHashedCursorFactory`] over the tables of the transaction.
+    pub const fn new(tx: &'a TX) -> Self {
+        Self(tx)
+    }
+}
+
+impl<TX: DbTx> HashedCursorFactory for MptHashedCursorFactory<'_, TX> {
+    type AccountCursor = MptHashedAccountCursor<<TX as DbTx>::Cursor<tables::MptHashedAccounts>>;
+    type StorageCursor = MptHashedStorageCursor<<TX as DbTx>::DupCursor<tables::MptHashedStorages>>;
+
+    fn hashed_account_cursor(&self) -> Result<Self::AccountCursor, DatabaseError> {
+        Ok(MptHashedAccountCursor(self.0.cursor_read::<tables::MptHashedAccounts>()?))
+    }
+
+    fn hashed_storage_cursor(
+        &self,
+        hashed_address: B256,
+    ) -> Result<Self::StorageCursor, DatabaseError> {
+        Ok(MptHashedStorageCursor {
+            cursor: self.0.cursor_dup_read::<tables::MptHashedStorages>()?,
+            hashed_address,
+        })
+    }
+}
+
+/// A cursor over the keccak hashed accounts.
+#[derive(Debug)]
+pub struct MptHashedAccountCursor<C>(C);
+
+impl<C: DbCursorRO<tables::MptHashedAccounts>> HashedCursor for MptHashedAccountCursor<C> {
+    type Value = Account;
+
+    fn seek(&mut self, key: B256) -> Result<Option<(B256, Account)>, DatabaseError> {
+        self.0.seek(key)
+    }
+
+    fn next(&mut self) -> Result<Option<(B256, Account)>, DatabaseError> {
+        self.0.next()
+    }
+}
+
+/// A cursor over the keccak hashed storage of an account.
+#[derive(Debug)]
+pub struct MptHashedStorageCursor<C> {
+    cursor: C,
+    hashed_address: B256,
+}
+
+impl<C> HashedCursor for MptHashedStorageCursor<C>
+where
+    C: DbCursorRO<tables::MptHashedStorages> + DbDupCursorRO<tables::MptHashedStorages>,
+{
+    type Value = U256;
+
+    fn seek(&mut self, key: B256) -> Result<Option<(B256, U256)>, DatabaseError> {
+        Ok(self
+            .cursor
+            .seek_by_key_subkey(self.hashed_address, key)?
+            .map(|entry| (entry.key, entry.value)))
+    }
+
+    fn next(&mut self) -> Result<Option<(B256, U256)>, DatabaseError> {
+        Ok(self.cursor.next_dup_val()?.map(|entry| (entry.key, entry.value)))
+    }
+}
+
+impl<C> HashedStorageCursor for MptHashedStorageCursor<C>
+where
+    C: DbCursorRO<tables::MptHashedStorages> + DbDupCursorRO<tables::MptHashedStorages>,
+{
+    fn is_storage_empty(&mut self) -> Result<bool, DatabaseError> {
+        Ok(self.cursor.seek_exact(self.hashed_address)?.is_none())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Address;
+    use alloy_trie::HashBuilder;
+    use reth_provider::test_utils::create_test_provider_factory;
+    use reth_stages_types::StageCheckpoint;
+    use reth_trie::{HashedStorage, TrieAccount, EMPTY_ROOT_HASH};
+    use std::collections::BTreeMap;
+
+    /// The accounts and storages of the keccak hashed state.
+    type State = BTreeMap<B256, (Account, BTreeMap<B256, U256>)>;
+
+    /// Returns a pseudo-random number derived from the seed.
+    fn random(seed: &[u64]) -> u64 {
+        let hash = keccak256(seed.iter().flat_map(|n| n.to_be_bytes()).collect::<Vec<_>>());
+        u64::from_be_bytes(hash[..8].try_into().unwrap())
+    }
+
+    /// Returns pseudo-random changes to the state, applying them to it.
+    fn random_changes(round: u64, state: &mut State) -> HashedPostState {
+        let mut changes = HashedPostState::default();
+        for index in 0..300 {
+            let hashed_address = keccak256(U256::from(index).to_be_bytes::<32>());
+            let seed = random(&[round, index]);
+            let exists = state.contains_key(&hashed_address);
+            let action = if round == 0 { 1 } else { seed % 8 };
+
+            if action == 0 && exists {
+                state.remove(&hashed_address);
+                changes.accounts.insert(hashed_address, None);
+                changes.storages.insert(hashed_address, HashedStorage::new(true));
+                continue
+            }
+            if action > 3 || (action == 3 && !exists) {
+                continue
+            }
+
+            let (account, storage) = state.entry(hashed_address).or_default();
+            if action < 3 {
+                *account = Account {
+                    nonce: round,
+                    balance: U256::from(seed % 1000),
+                    bytecode_hash: (seed % 3 == 0).then(|| keccak256(seed.to_be_bytes())),
+                    ..Default::default()
+                };
+                changes.accounts.insert(hashed_address, Some(*account));
+            }
+
+            let hashed_storage = changes.storages.entry(hashed_address).or_default();
+            if action == 2 {
+                storage.clear();
+                hashed_storage.wiped = true;
+            }
+            for slot in 0..random(&[round, index, 0]) % 12 {
+                let key = U256::from(random(&[round, index, slot]) % 40);
+                let key = keccak256(key.to_be_bytes::<32>());
+                let value = U256::from(random(&[round, index, slot, 0]) % 3);
+                if value.is_zero() {
+                    storage.remove(&key);
+                } else {
+                    storage.insert(key, value);
+                }
+                hashed_storage.storage.insert(key, value);
+            }
+        }
+        changes
+    }
+
+    /// Computes the root of the state with a [`HashBuilder`].
+    fn expected_root(state: &State) -> B256 {
+        let mut builder = HashBuilder::default();
+        for (hashed_address, (account, storage)) in state {
+            let mut storage_builder = HashBuilder::default();
+            for (key, value) in storage {
+                storage_builder.add_leaf(Nibbles::unpack(key), &alloy_rlp::encode(value));
+            }
+            let account = TrieAccount::from((*account, storage_builder.root()));
+            builder.add_leaf(Nibbles::unpack(hashed_address), &alloy_rlp::encode(account));
+        }
+        builder.root()
+    }
+
+    /// The content of the trie tables.
+    type StoredNodes = (Vec<(StoredNibbles, BranchNodeCompact)>, Vec<(B256, StorageTrieEntry)>);
+
+    /// Returns the content of the trie tables.
+    fn stored_nodes<TX: DbTx>(tx: &TX) -> Result<StoredNodes, DatabaseError> {
+        Ok((
+            tx.cursor_read::<tables::MptAccountsTrie>()?.walk(None)?.collect::<Result<_, _>>()?,
+            tx.cursor_read::<tables::MptStoragesTrie>()?.walk(None)?.collect::<Result<_, _>>()?,
+        ))
+    }
+
+    #[test]
+    fn test_mpt_state_root_incremental() -> Result<(), DatabaseError> {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+        assert_eq!(mpt_state_root(tx, &HashedPostState::default())?.0, EMPTY_ROOT_HASH);
+
+        let mut state = State::new();
+        for round in 0..8 {
+            let changes = random_changes(round, &mut state);
+            let (root, updates) = mpt_state_root(tx, &changes)?;
+            assert_eq!(root, expected_root(&state), "round {round}");
+            write_mpt_hashed_state(tx, &changes)?;
+            write_mpt_trie_updates(tx, &updates)?;
+
+            // the stored branch nodes are the ones of the trie rebuilt from scratch
+            let nodes = stored_nodes(tx)?;
+            tx.clear::<tables::MptAccountsTrie>()?;
+            tx.clear::<tables::MptStoragesTrie>()?;
+            let (rebuilt_root, rebuilt_updates) = mpt_state_root(tx, &HashedPostState::default())?;
+            write_mpt_trie_updates(tx, &rebuilt_updates)?;
+            assert_eq!(rebuilt_root, root, "round {round}");
+            assert_eq!(stored_nodes(tx)?, nodes, "round {round}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mpt_base_state() -> ProviderResult<()> {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+        let address = Address::with_last_byte(1);
+        let account = Account { nonce: 1, ..Default::default() };
+        let checkpoint = |id: StageId, block| {
+            tx.put::<tables::StageCheckpoints>(id.to_string(), StageCheckpoint::new(block))
+        };
+        tx.put::<tables::PlainAccountState>(address, account)?;
+
+        // before the first blocks are persisted, the genesis state is overlaid on the empty tables
+        let (block, state) = mpt_base_state(tx)?;
+        assert_eq!(block, 0);
+        assert_eq!(state.accounts, [(keccak256(address), Some(account))].into_iter().collect());
+        let (root, _) = mpt_state_root(tx, &state)?;
+
+        // the state isn't migrated to the tables
+        checkpoint(StageId::Execution, 2)?;
+        assert!(matches!(mpt_base_state(tx), Err(ProviderError::StateForNumberNotFound(2))));
+
+        // the tables are behind the persisted state
+        hash_plain_state_for_mpt(tx)?;
+        checkpoint(MPT_STAGE_ID, 1)?;
+        assert_eq!(mpt_checkpoint(tx)?, Some(1));
+        assert!(matches!(mpt_base_state(tx), Err(ProviderError::StateForNumberNotFound(2))));
+
+        // the tables are at the persisted state
+        checkpoint(MPT_STAGE_ID, 2)?;
+        assert_eq!(mpt_base_state(tx)?, (2, HashedPostState::default()));
+        assert_eq!(mpt_state_root(tx, &HashedPostState::default())?.0, root);
+
+        Ok(())
+    }
+}
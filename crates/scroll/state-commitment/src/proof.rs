@@ -0,0 +1,487 @@
+use super::{PoseidonKeyHasher, PoseidonValueHasher, ScrollTrieAccount};
+use alloy_consensus::constants::KECCAK_EMPTY;
+use alloy_primitives::{
+    map::{HashMap, HashSet},
+    Address, Bytes, B256, U256,
+};
+use alloy_trie::proof::{ProofNodes, ProofRetainer};
+use reth_db::transaction::DbTx;
+use reth_execution_errors::StateProofError;
+use reth_primitives_traits::Account;
+use reth_scroll_primitives::poseidon::EMPTY_ROOT_HASH;
+use reth_scroll_trie::{
+    proof_for_key, verify_proof, HashBuilder, LeafNode, ZkTrieNode, ZkTrieProofError,
+};
+use reth_trie::{
+    hashed_cursor::{HashedCursorFactory, HashedPostStateCursorFactory, HashedStorageCursor},
+    node_iter::{TrieElement, TrieNodeIter},
+    prefix_set::{PrefixSetMut, TriePrefixSetsMut},
+    trie_cursor::{InMemoryTrieCursorFactory, TrieCursorFactory},
+    walker::TrieWalker,
+    AccountProof, BitsCompatibility, KeyHasher, MultiProof, Nibbles, StorageMultiProof, TrieInput,
+};
+use reth_trie_db::{DatabaseHashedCursorFactory, DatabaseProof, DatabaseTrieCursorFactory};
+
+/// The compressed flags of an account leaf, the keccak code hash is the only value which is not a
+/// field element.
+const ACCOUNT_COMPRESSED_FLAGS: u32 = 0b1000;
+
+/// The compressed flags of a storage leaf.
+const STORAGE_COMPRESSED_FLAGS: u32 = 0b1;
+
+/// A struct for generating zktrie proofs.
+///
+/// Proof generator adds the target address and slots to the prefix set, enables the proof retainer
+/// on the hash builder and follows the same algorithm as the state root calculator.
+/// See [`StateRoot::root`](crate::StateRoot::root) for more info.
+///
+/// The proofs follow the format of the proofs returned by l2geth, see
+/// [`ZkTrieNode`] for the encoding of the nodes.
+#[derive(Debug)]
+pub struct Proof<T, H> {
+    /// The factory for traversing trie nodes.
+    trie_cursor_factory: T,
+    /// The factory for hashed cursors.
+    hashed_cursor_factory: H,
+    /// A set of prefix sets that have changes.
+    prefix_sets: TriePrefixSetsMut,
+}
+
+impl<T, H> Proof<T, H> {
+    /// Create a new [`Proof`] instance.
+    pub fn new(t: T, h: H) -> Self {
+        Self {
+            trie_cursor_factory: t,
+            hashed_cursor_factory: h,
+            prefix_sets: TriePrefixSetsMut::default(),
+        }
+    }
+
+    /// Set the trie cursor factory.
+    pub fn with_trie_cursor_factory<TF>(self, trie_cursor_factory: TF) -> Proof<TF, H> {
+        Proof {
+            trie_cursor_factory,
+            hashed_cursor_factory: self.hashed_cursor_factory,
+            prefix_sets: self.prefix_sets,
+        }
+    }
+
+    /// Set the hashed cursor factory.
+    pub fn with_hashed_cursor_factory<HF>(self, hashed_cursor_factory: HF) -> Proof<T, HF> {
+        Proof {
+            trie_cursor_factory: self.trie_cursor_factory,
+            hashed_cursor_factory,
+            prefix_sets: self.prefix_sets,
+        }
+    }
+
+    /// Set the prefix sets. They have to be mutable in order to allow extension with proof target.
+    pub fn with_prefix_sets_mut(mut self, prefix_sets: TriePrefixSetsMut) -> Self {
+        self.prefix_sets = prefix_sets;
+        self
+    }
+}
+
+impl<T, H> Proof<T, H>
+where
+    T: TrieCursorFactory + Clone,
+    H: HashedCursorFactory + Clone,
+{
+    /// Generate an account proof from intermediate nodes.
+    pub fn account_proof(
+        self,
+        address: Address,
+        slots: &[B256],
+    ) -> Result<AccountProof, StateProofError> {
+        let multiproof = self.multiproof(HashMap::from_iter([(
+            PoseidonKeyHasher::hash_key(address),
+            slots.iter().map(PoseidonKeyHasher::hash_key).collect(),
+        )]))?;
+        Ok(account_proof(&multiproof, address, slots))
+    }
+
+    /// Generate a state multiproof according to specified targets.
+    ///
+    /// The leaf nodes of the target accounts and slots hold the value preimage, all other leaf
+    /// nodes only commit to the hash of their value.
+    pub fn multiproof(
+        mut self,
+        mut targets: HashMap<B256, HashSet<B256>>,
+    ) -> Result<MultiProof, StateProofError> {
+        let hashed_account_cursor = self.hashed_cursor_factory.hashed_account_cursor()?;
+        let trie_cursor = self.trie_cursor_factory.account_trie_cursor()?;
+
+        // Create the walker.
+        let mut prefix_set = self.prefix_sets.account_prefix_set.clone();
+        prefix_set.extend_keys(targets.keys().map(Nibbles::unpack_bits));
+        let walker = TrieWalker::new(trie_cursor, prefix_set.freeze());
+
+        // Create a hash builder to rebuild the root node since it is not available in the database.
+        let retainer = targets.keys().map(Nibbles::unpack_bits).collect();
+        let mut hash_builder = HashBuilder::default().with_proof_retainer(retainer);
+
+        // Initialize all storage multiproofs as empty.
+        // Storage multiproofs for non empty tries will be overwritten if necessary.
+        let mut storages: HashMap<_, _> =
+            targets.keys().map(|key| (*key, empty_storage_multiproof())).collect();
+        let mut target_leaves = Vec::with_capacity(targets.len());
+        let mut account_node_iter = TrieNodeIter::new(walker, hashed_account_cursor);
+        while let Some(account_node) = account_node_iter.try_next()? {
+            match account_node {
+                TrieElement::Branch(node) => {
+                    hash_builder.add_branch(node.key, node.value, node.children_are_in_trie);
+                }
+                TrieElement::Leaf(hashed_address, account) => {
+                    let storage_prefix_set = self
+                        .prefix_sets
+                        .storage_prefix_sets
+                        .remove(&hashed_address)
+                        .unwrap_or_default();
+                    let proof_targets = targets.remove(&hashed_address);
+                    let is_target = proof_targets.is_some();
+                    let storage_multiproof = StorageProof::new_hashed(
+                        self.trie_cursor_factory.clone(),
+                        self.hashed_cursor_factory.clone(),
+                        hashed_address,
+                    )
+                    .with_prefix_set_mut(storage_prefix_set)
+                    .storage_multiproof(proof_targets.unwrap_or_default())?;
+
+                    let account = ScrollTrieAccount::from((account, storage_multiproof.root));
+                    let key = Nibbles::unpack_bits(hashed_address);
+                    if is_target {
+                        let values = account_leaf_values(&account);
+                        let leaf = LeafNode::new(&key, ACCOUNT_COMPRESSED_FLAGS, values);
+                        target_leaves.push((key.clone(), leaf));
+                    }
+                    let account_hash = PoseidonValueHasher::hash_account(account);
+                    hash_builder.add_leaf(key, account_hash.as_slice());
+
+                    // Overwrite storage multiproof.
+                    storages.insert(hashed_address, storage_multiproof);
+                }
+            }
+        }
+        let _ = hash_builder.root();
+
+        let mut account_subtree = hash_builder.take_proof_nodes();
+        for (key, leaf) in target_leaves {
+            insert_leaf_preimage(&mut account_subtree, &key, leaf);
+        }
+        Ok(MultiProof { account_subtree, storages })
+    }
+}
+
+/// Generates storage zktrie proofs.
+#[derive(Debug)]
+pub struct StorageProof<T, H> {
+    /// The factory for traversing trie nodes.
+    trie_cursor_factory: T,
+    /// The factory for hashed cursors.
+    hashed_cursor_factory: H,
+    /// The hashed address of an account.
+    hashed_address: B256,
+    /// The set of storage slot prefixes that have changed.
+    prefix_set: PrefixSetMut,
+}
+
+impl<T, H> StorageProof<T, H> {
+    /// Create a new [`StorageProof`] instance.
+    pub fn new(t: T, h: H, address: Address) -> Self {
+        Self::new_hashed(t, h, PoseidonKeyHasher::hash_key(address))
+    }
+
+    /// Create a new [`StorageProof`] instance with hashed address.
+    pub fn new_hashed(t: T, h: H, hashed_address: B256) -> Self {
+        Self {
+            trie_cursor_factory: t,
+            hashed_cursor_factory: h,
+            hashed_address,
+            prefix_set: PrefixSetMut::default(),
+        }
+    }
+
+    /// Set the trie cursor factory.
+    pub fn with_trie_cursor_factory<TF>(self, trie_cursor_factory: TF) -> StorageProof<TF, H> {
+        StorageProof {
+            trie_cursor_factory,
+            hashed_cursor_factory: self.hashed_cursor_factory,
+            hashed_address: self.hashed_address,
+            prefix_set: self.prefix_set,
+        }
+    }
+
+    /// Set the hashed cursor factory.
+    pub fn with_hashed_cursor_factory<HF>(self, hashed_cursor_factory: HF) -> StorageProof<T, HF> {
+        StorageProof {
+            trie_cursor_factory: self.trie_cursor_factory,
+            hashed_cursor_factory,
+            hashed_address: self.hashed_address,
+            prefix_set: self.prefix_set,
+        }
+    }
+
+    /// Set the changed prefixes.
+    pub fn with_prefix_set_mut(mut self, prefix_set: PrefixSetMut) -> Self {
+        self.prefix_set = prefix_set;
+        self
+    }
+}
+
+impl<T, H> StorageProof<T, H>
+where
+    T: TrieCursorFactory,
+    H: HashedCursorFactory,
+{
+    /// Generate a storage proof for the slot.
+    pub fn storage_proof(self, slot: B256) -> Result<reth_trie::StorageProof, StateProofError> {
+        let targets = HashSet::from_iter([PoseidonKeyHasher::hash_key(slot)]);
+        Ok(storage_proof(&self.storage_multiproof(targets)?, slot))
+    }
+
+    /// Generate storage proof.
+    pub fn storage_multiproof(
+        mut self,
+        targets: HashSet<B256>,
+    ) -> Result<StorageMultiProof, StateProofError> {
+        let mut hashed_storage_cursor =
+            self.hashed_cursor_factory.hashed_storage_cursor(self.hashed_address)?;
+
+        // short circuit on empty storage
+        if hashed_storage_cursor.is_storage_empty()? {
+            return Ok(empty_storage_multiproof())
+        }
+
+        let target_nibbles = targets.iter().map(Nibbles::unpack_bits).collect::<Vec<_>>();
+        self.prefix_set.extend_keys(target_nibbles.clone());
+
+        let trie_cursor = self.trie_cursor_factory.storage_trie_cursor(self.hashed_address)?;
+        let walker = TrieWalker::new(trie_cursor, self.prefix_set.freeze());
+
+        let retainer = ProofRetainer::from_iter(target_nibbles);
+        let mut hash_builder = HashBuilder::default().with_proof_retainer(retainer);
+        let mut target_leaves = Vec::with_capacity(targets.len());
+        let mut storage_node_iter = TrieNodeIter::new(walker, hashed_storage_cursor);
+        while let Some(node) = storage_node_iter.try_next()? {
+            match node {
+                TrieElement::Branch(node) => {
+                    hash_builder.add_branch(node.key, node.value, node.children_are_in_trie);
+                }
+                TrieElement::Leaf(hashed_slot, value) => {
+                    let key = Nibbles::unpack_bits(hashed_slot);
+                    if targets.contains(&hashed_slot) {
+                        let values = vec![value.into()];
+                        let leaf = LeafNode::new(&key, STORAGE_COMPRESSED_FLAGS, values);
+                        target_leaves.push((key.clone(), leaf));
+                    }
+                    let hashed_value = PoseidonValueHasher::hash_storage(value);
+                    hash_builder.add_leaf(key, hashed_value.as_slice());
+                }
+            }
+        }
+
+        let root = hash_builder.root();
+        let mut subtree = hash_builder.take_proof_nodes();
+        for (key, leaf) in target_leaves {
+            insert_leaf_preimage(&mut subtree, &key, leaf);
+        }
+        Ok(StorageMultiProof { root, subtree })
+    }
+}
+
+/// Verifies the zktrie account proof and the storage proofs it contains against the state root.
+pub fn verify_account_proof(root: B256, proof: &AccountProof) -> Result<(), ZkTrieProofError> {
+    // Verify storage proofs.
+    for storage_proof in &proof.storage_proofs {
+        verify_storage_proof(proof.storage_root, storage_proof)?;
+    }
+
+    // Verify the account proof.
+    let expected = if proof.info.is_none() && proof.storage_root == EMPTY_ROOT_HASH {
+        None
+    } else {
+        Some(PoseidonValueHasher::hash_account(ScrollTrieAccount::from((
+            proof.info.unwrap_or_default(),
+            proof.storage_root,
+        ))))
+    };
+    let key = Nibbles::unpack_bits(PoseidonKeyHasher::hash_key(proof.address));
+    verify_proof(root, &key, expected, &proof.proof)
+}
+
+/// Verifies the zktrie storage proof against the storage root.
+pub fn verify_storage_proof(
+    root: B256,
+    proof: &reth_trie::StorageProof,
+) -> Result<(), ZkTrieProofError> {
+    let expected = (!proof.value.is_zero()).then(|| PoseidonValueHasher::hash_storage(proof.value));
+    verify_proof(root, &proof.nibbles, expected, &proof.proof)
+}
+
+/// Constructs the account proof for the address and slots from the multiproof.
+fn account_proof(multiproof: &MultiProof, address: Address, slots: &[B256]) -> AccountProof {
+    let hashed_address = PoseidonKeyHasher::hash_key(address);
+    let key = Nibbles::unpack_bits(hashed_address);
+    let (proof, leaf) =
+        proof_with_key_preimage(&multiproof.account_subtree, &key, address.into_word());
+    let info = leaf.as_ref().and_then(account_from_leaf);
+
+    // Retrieve proofs for requested storage slots.
+    let storage_multiproof = multiproof.storages.get(&hashed_address);
+    let storage_root = storage_multiproof.map(|m| m.root).unwrap_or(EMPTY_ROOT_HASH);
+    let storage_proofs = slots
+        .iter()
+        .map(|slot| match storage_multiproof {
+            Some(storage_multiproof) => storage_proof(storage_multiproof, *slot),
+            None => reth_trie::StorageProof::new_with_nibbles(
+                *slot,
+                Nibbles::unpack_bits(PoseidonKeyHasher::hash_key(slot)),
+            ),
+        })
+        .collect();
+
+    AccountProof { address, info, proof, storage_root, storage_proofs }
+}
+
+/// Constructs the storage proof for the slot from the storage multiproof.
+fn storage_proof(multiproof: &StorageMultiProof, slot: B256) -> reth_trie::StorageProof {
+    let key = Nibbles::unpack_bits(PoseidonKeyHasher::hash_key(slot));
+    let (proof, leaf) = proof_with_key_preimage(&multiproof.subtree, &key, slot);
+    let value = leaf
+        .filter(|leaf| leaf.compressed_flags == STORAGE_COMPRESSED_FLAGS)
+        .and_then(|leaf| leaf.values.first().map(|value| U256::from_be_bytes(value.0)))
+        .unwrap_or_default();
+
+    reth_trie::StorageProof { key: slot, nibbles: key, value, proof }
+}
+
+/// Returns the proof for the key, setting the key preimage of the leaf node of the key.
+///
+/// Returns the leaf node of the key if the key is part of the trie.
+fn proof_with_key_preimage(
+    nodes: &ProofNodes,
+    key: &Nibbles,
+    key_preimage: B256,
+) -> (Vec<Bytes>, Option<LeafNode>) {
+    let mut proof = proof_for_key(nodes, key);
+
+    // The last node is followed by the magic bytes.
+    let leaf =
+        proof.len().checked_sub(2).and_then(|index| match ZkTrieNode::decode(&proof[index]) {
+            Ok(ZkTrieNode::Leaf(leaf)) if leaf.matches_key(key) => {
+                let leaf = leaf.with_key_preimage(key_preimage);
+                proof[index] = leaf.encode().into();
+                Some(leaf)
+            }
+            _ => None,
+        });
+
+    (proof, leaf)
+}
+
+/// Replaces the retained leaf node, which only commits to the hash of the value, with the leaf
+/// node holding the value preimage.
+fn insert_leaf_preimage(nodes: &mut ProofNodes, key: &Nibbles, leaf: LeafNode) {
+    let Some((path, node)) = nodes.matching_nodes_sorted(key).pop() else { return };
+    if let Ok(ZkTrieNode::Leaf(retained)) = ZkTrieNode::decode(&node) {
+        if retained.matches_key(key) {
+            nodes.insert(path, leaf.encode().into());
+        }
+    }
+}
+
+/// Returns the value preimage of the account leaf.
+fn account_leaf_values(account: &ScrollTrieAccount) -> Vec<B256> {
+    vec![
+        U256::from_limbs([account.nonce, account.code_size, 0, 0]).into(),
+        account.balance.into(),
+        account.storage_root,
+        account.code_hash,
+        account.poseidon_code_hash,
+    ]
+}
+
+/// Decodes the account from the value preimage of the account leaf.
+fn account_from_leaf(leaf: &LeafNode) -> Option<Account> {
+    let [nonce_code_size, balance, _storage_root, code_hash, poseidon_code_hash] =
+        leaf.values.as_slice()
+    else {
+        return None
+    };
+    if leaf.compressed_flags != ACCOUNT_COMPRESSED_FLAGS {
+        return None
+    }
+
+    #[cfg(not(feature = "scroll"))]
+    let _ = poseidon_code_hash;
+
+    let nonce_code_size = U256::from_be_bytes(nonce_code_size.0);
+    Some(Account {
+        nonce: nonce_code_size.as_limbs()[0],
+        balance: U256::from_be_bytes(balance.0),
+        bytecode_hash: (*code_hash != KECCAK_EMPTY).then_some(*code_hash),
+        #[cfg(feature = "scroll")]
+        account_extension: Some(reth_scroll_primitives::AccountExtension::from((
+            nonce_code_size.as_limbs()[1],
+            *poseidon_code_hash,
+        ))),
+    })
+}
+
+/// Returns the multiproof of an empty storage trie.
+fn empty_storage_multiproof() -> StorageMultiProof {
+    StorageMultiProof {
+        root: EMPTY_ROOT_HASH,
+        subtree: ProofNodes::from_iter([(Nibbles::default(), ZkTrieNode::Empty.encode().into())]),
+    }
+}
+
+impl<'a, TX: DbTx> DatabaseProof<'a, TX>
+    for Proof<DatabaseTrieCursorFactory<'a, TX>, DatabaseHashedCursorFactory<'a, TX>>
+{
+    /// Create a new [`Proof`] instance from database transaction.
+    fn from_tx(tx: &'a TX) -> Self {
+        Self::new(DatabaseTrieCursorFactory::new(tx), DatabaseHashedCursorFactory::new(tx))
+    }
+
+    fn overlay_account_proof(
+        tx: &'a TX,
+        input: TrieInput,
+        address: Address,
+        slots: &[B256],
+    ) -> Result<AccountProof, StateProofError> {
+        let nodes_sorted = input.nodes.into_sorted();
+        let state_sorted = input.state.into_sorted();
+        Self::from_tx(tx)
+            .with_trie_cursor_factory(InMemoryTrieCursorFactory::new(
+                DatabaseTrieCursorFactory::new(tx),
+                &nodes_sorted,
+            ))
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(
+                DatabaseHashedCursorFactory::new(tx),
+                &state_sorted,
+            ))
+            .with_prefix_sets_mut(input.prefix_sets)
+            .account_proof(address, slots)
+    }
+
+    fn overlay_multiproof(
+        tx: &'a TX,
+        input: TrieInput,
+        targets: HashMap<B256, HashSet<B256>>,
+    ) -> Result<MultiProof, StateProofError> {
+        let nodes_sorted = input.nodes.into_sorted();
+        let state_sorted = input.state.into_sorted();
+        Self::from_tx(tx)
+            .with_trie_cursor_factory(InMemoryTrieCursorFactory::new(
+                DatabaseTrieCursorFactory::new(tx),
+                &nodes_sorted,
+            ))
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(
+                DatabaseHashedCursorFactory::new(tx),
+                &state_sorted,
+            ))
+            .with_prefix_sets_mut(input.prefix_sets)
+            .multiproof(targets)
+    }
+}
@@ -23,6 +23,9 @@ use reth_trie::metrics::{StateRootMetrics, TrieRootMetrics, TrieType};
 mod utils;
 pub use utils::*;
 
+mod parallel;
+pub use parallel::ParallelStateRoot;
+
 // TODO(scroll): Instead of introducing this new type we should make StateRoot generic over
 // the [`HashBuilder`] and key traversal types
 
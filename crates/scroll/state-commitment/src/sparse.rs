@@ -0,0 +1,174 @@
+use super::{PoseidonValueHasher, ScrollTrieAccount};
+use alloy_primitives::{
+    map::{HashMap, HashSet},
+    Bytes, B256, U256,
+};
+use reth_primitives_traits::Account;
+use reth_scroll_primitives::poseidon::EMPTY_ROOT_HASH;
+use reth_scroll_trie::{SparseTrie, ZkTrieNode};
+use reth_trie::{
+    updates::{StorageTrieUpdates, TrieUpdates},
+    BitsCompatibility, MultiProof, Nibbles,
+};
+use reth_trie_sparse::{
+    SparseStateTrieError, SparseStateTrieInterface, SparseStateTrieResult, SparseTrieError,
+};
+
+/// Sparse state trie representing the lazy-loaded binary Poseidon state trie.
+///
+/// The tries are revealed from the zktrie multiproofs generated by [`Proof`](crate::proof::Proof)
+/// and the account leaves are hashed as [`ScrollTrieAccount`]s, committing to the code size and
+/// Poseidon code hash of the accounts.
+///
+/// See [`reth_trie_sparse::SparseStateTrie`] for the Merkle Patricia Trie counterpart.
+#[derive(Debug, Default)]
+pub struct SparseStateTrie {
+    /// Sparse account trie.
+    state: SparseTrie,
+    /// Sparse storage tries.
+    storages: HashMap<B256, SparseTrie>,
+    /// Collection of revealed account and storage keys.
+    revealed: HashMap<B256, HashSet<B256>>,
+    /// Flag indicating whether trie updates should be retained.
+    retain_updates: bool,
+}
+
+impl SparseStateTrie {
+    /// Returns `true` if account was already revealed.
+    pub fn is_account_revealed(&self, account: &B256) -> bool {
+        self.revealed.contains_key(account)
+    }
+
+    /// Returns `true` if storage slot for account was already revealed.
+    pub fn is_storage_slot_revealed(&self, account: &B256, slot: &B256) -> bool {
+        self.revealed.get(account).is_some_and(|slots| slots.contains(slot))
+    }
+
+    /// Returns mutable reference to storage sparse trie if it was revealed.
+    pub fn storage_trie_mut(&mut self, account: &B256) -> Option<&mut SparseTrie> {
+        self.storages.get_mut(account).filter(|trie| !trie.is_blind())
+    }
+}
+
+impl SparseStateTrieInterface for SparseStateTrie {
+    fn with_updates(mut self, retain_updates: bool) -> Self {
+        self.state = self.state.with_updates(retain_updates);
+        self.retain_updates = retain_updates;
+        self
+    }
+
+    fn reveal_multiproof(
+        &mut self,
+        targets: HashMap<B256, HashSet<B256>>,
+        multiproof: MultiProof,
+    ) -> SparseStateTrieResult<()> {
+        reveal_nodes(&mut self.state, multiproof.account_subtree.into_nodes_sorted())?;
+
+        for (account, storage_subtree) in multiproof.storages {
+            let retain_updates = self.retain_updates;
+            let trie = self
+                .storages
+                .entry(account)
+                .or_insert_with(|| SparseTrie::default().with_updates(retain_updates));
+            reveal_nodes(trie, storage_subtree.subtree.into_nodes_sorted())?;
+            // No nodes are retained for storage tries without target slots, the root is revealed
+            // from the hash such that the account leaf can still be updated.
+            trie.reveal_root_hash(storage_subtree.root);
+        }
+
+        for (account, slots) in targets {
+            self.revealed.entry(account).or_default().extend(slots);
+        }
+
+        Ok(())
+    }
+
+    fn wipe_storage(&mut self, address: B256) -> SparseStateTrieResult<()> {
+        self.storage_trie_mut(&address).ok_or(SparseTrieError::Blind)?.wipe();
+        Ok(())
+    }
+
+    fn update_storage_slot(
+        &mut self,
+        address: B256,
+        slot: B256,
+        value: U256,
+    ) -> SparseStateTrieResult<()> {
+        let storage_trie = self.storage_trie_mut(&address).ok_or(SparseTrieError::Blind)?;
+        let slot_nibbles = Nibbles::unpack_bits(slot);
+        if value.is_zero() {
+            storage_trie.remove_leaf(&slot_nibbles)?;
+        } else {
+            storage_trie.update_leaf(slot_nibbles, PoseidonValueHasher::hash_storage(value))?;
+        }
+        Ok(())
+    }
+
+    fn storage_root(&mut self, address: B256) -> Option<B256> {
+        self.storages.get_mut(&address).and_then(SparseTrie::root)
+    }
+
+    /// Update or remove trie account based on new account info. The storage root is taken from
+    /// the storage trie, which is revealed by the multiproof of every target account.
+    fn update_account(&mut self, address: B256, account: Account) -> SparseStateTrieResult<()> {
+        let storage_root = self.storage_root(address).ok_or(SparseTrieError::Blind)?;
+        let nibbles = Nibbles::unpack_bits(address);
+
+        if account.is_empty() && storage_root == EMPTY_ROOT_HASH {
+            self.state.remove_leaf(&nibbles)?;
+        } else {
+            let account = ScrollTrieAccount::from((account, storage_root));
+            self.state.update_leaf(nibbles, PoseidonValueHasher::hash_account(account))?;
+        }
+        Ok(())
+    }
+
+    fn calculate_below_level(&mut self, level: usize) {
+        self.state.calculate_below_level(level);
+    }
+
+    fn root(&mut self) -> Option<B256> {
+        self.state.root()
+    }
+
+    fn take_trie_updates(&mut self) -> Option<TrieUpdates> {
+        if self.state.is_blind() {
+            return None
+        }
+
+        let updates = self.state.take_updates();
+        Some(TrieUpdates {
+            account_nodes: updates.updated_nodes,
+            removed_nodes: updates.removed_nodes,
+            storage_tries: self
+                .storages
+                .iter_mut()
+                .filter(|(_, trie)| !trie.is_blind())
+                .map(|(address, trie)| {
+                    let updates = trie.take_updates();
+                    let updates = StorageTrieUpdates {
+                        is_deleted: updates.wiped,
+                        storage_nodes: updates.updated_nodes,
+                        removed_nodes: updates.removed_nodes,
+                    };
+                    (*address, updates)
+                })
+                .filter(|(_, updates)| !updates.is_empty())
+                .collect(),
+        })
+    }
+}
+
+/// Reveals the zktrie proof nodes in the sparse trie.
+/// NOTE: This method does not extensively validate the proof.
+fn reveal_nodes(
+    trie: &mut SparseTrie,
+    nodes: impl IntoIterator<Item = (Nibbles, Bytes)>,
+) -> SparseStateTrieResult<()> {
+    for (path, encoded) in nodes {
+        let node =
+            ZkTrieNode::decode(&encoded).map_err(|err| SparseStateTrieError::Other(err.into()))?;
+        trie.reveal_node(path, node).map_err(|err| SparseStateTrieError::Other(err.into()))?;
+    }
+    Ok(())
+}
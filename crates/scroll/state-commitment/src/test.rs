@@ -9,17 +9,28 @@ use reth_db::{
     transaction::DbTxMut,
 };
 use reth_primitives::{Account, StorageEntry};
-use reth_provider::{test_utils::create_test_provider_factory, StorageTrieWriter, TrieWriter};
-use reth_scroll_state_commitment::{test_utils::*, PoseidonKeyHasher, StateRoot, StorageRoot};
+use reth_provider::{
+    providers::ConsistentDbView, test_utils::create_test_provider_factory, StorageTrieWriter,
+    TrieWriter,
+};
+use reth_scroll_state_commitment::{
+    proof::Proof, test_utils::*, verify_account_proof, witness::TrieWitness, ParallelStateRoot,
+    PoseidonKeyHasher, SparseStateTrie, StateRoot, StorageRoot,
+};
+use reth_scroll_trie::ZkTrieNode;
 
 use reth_scroll_state_commitment::state_root_unsorted;
 use reth_trie::{
     prefix_set::{PrefixSetMut, TriePrefixSets},
     trie_cursor::InMemoryTrieCursorFactory,
     updates::TrieUpdates,
-    BitsCompatibility, HashedPostState, HashedStorage, KeyHasher, Nibbles,
+    BitsCompatibility, HashedPostState, HashedStorage, KeyHasher, Nibbles, TrieInput,
 };
-use reth_trie_db::{DatabaseStateRoot, DatabaseStorageRoot, DatabaseTrieCursorFactory};
+use reth_trie_db::{
+    DatabaseProof, DatabaseStateRoot, DatabaseStorageRoot, DatabaseTrieCursorFactory,
+    DatabaseTrieWitness,
+};
+use reth_trie_sparse::SparseStateTrieInterface;
 use std::collections::BTreeMap;
 
 proptest! {
@@ -280,6 +291,239 @@ fn test_basic_state_root_with_updates_succeeds() {
     let (_root, _updates) = StateRoot::from_tx(tx.tx_ref()).root_with_updates().unwrap();
 }
 
+#[test]
+fn test_account_proof_verifies() {
+    let address_1 = Address::with_last_byte(1);
+    let address_2 = Address::with_last_byte(2);
+    let missing_address = Address::with_last_byte(3);
+    let account = Account {
+        nonce: 1,
+        balance: Uint::from(10),
+        #[cfg(feature = "scroll")]
+        account_extension: Some(Default::default()),
+        ..Default::default()
+    };
+    let slot = B256::with_last_byte(1);
+    let missing_slot = B256::with_last_byte(2);
+    let storage =
+        BTreeMap::from([(slot, U256::from(42)), (B256::with_last_byte(3), U256::from(1))]);
+
+    let factory = create_test_provider_factory();
+    let tx = factory.provider_rw().unwrap();
+    insert_account(tx.tx_ref(), address_1, account, &storage);
+    insert_account(tx.tx_ref(), address_2, account, &Default::default());
+    tx.commit().unwrap();
+
+    let tx = factory.provider_rw().unwrap();
+    let root = StateRoot::from_tx(tx.tx_ref()).root().unwrap();
+
+    // inclusion proof with existing and missing slots
+    let proof =
+        Proof::from_tx(tx.tx_ref()).account_proof(address_1, &[slot, missing_slot]).unwrap();
+    assert_eq!(proof.info, Some(account));
+    assert_eq!(proof.storage_proofs[0].value, U256::from(42));
+    assert_eq!(proof.storage_proofs[1].value, U256::ZERO);
+    verify_account_proof(root, &proof).unwrap();
+
+    // the proof does not verify against another root
+    assert!(verify_account_proof(B256::with_last_byte(1), &proof).is_err());
+
+    // exclusion proof
+    let proof = Proof::from_tx(tx.tx_ref()).account_proof(missing_address, &[slot]).unwrap();
+    assert_eq!(proof.info, None);
+    verify_account_proof(root, &proof).unwrap();
+}
+
+#[test]
+fn test_witness_contains_touched_nodes() {
+    let address = Address::with_last_byte(1);
+    let account = Account {
+        balance: Uint::from(10),
+        #[cfg(feature = "scroll")]
+        account_extension: Some(Default::default()),
+        ..Default::default()
+    };
+    let slot = B256::with_last_byte(1);
+    let storage =
+        BTreeMap::from([(slot, U256::from(42)), (B256::with_last_byte(2), U256::from(1))]);
+
+    let factory = create_test_provider_factory();
+    let tx = factory.provider_rw().unwrap();
+    insert_account(tx.tx_ref(), address, account, &storage);
+    insert_account(tx.tx_ref(), Address::with_last_byte(2), account, &Default::default());
+    tx.commit().unwrap();
+
+    let tx = factory.provider_rw().unwrap();
+    let root = StateRoot::from_tx(tx.tx_ref()).root().unwrap();
+
+    let hashed_address = PoseidonKeyHasher::hash_key(address);
+    let hashed_slot = PoseidonKeyHasher::hash_key(slot);
+    let mut state = HashedPostState::default();
+    state.accounts.insert(hashed_address, Some(account));
+    state
+        .storages
+        .insert(hashed_address, HashedStorage::from_iter(false, [(hashed_slot, U256::from(43))]));
+    let witness = TrieWitness::from_tx(tx.tx_ref()).compute(state).unwrap();
+
+    // the witness contains the root nodes of the account and storage tries
+    let storage_root =
+        Proof::from_tx(tx.tx_ref()).account_proof(address, &[]).unwrap().storage_root;
+    assert!(witness.contains_key(&root));
+    assert!(witness.contains_key(&storage_root));
+
+    // the leaves of the touched account and slot hold the value preimage
+    let leaves = witness
+        .values()
+        .filter_map(|node| match ZkTrieNode::decode(node).unwrap() {
+            ZkTrieNode::Leaf(leaf) => Some(leaf),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert!(leaves.iter().any(|leaf| {
+        leaf.matches_key(&Nibbles::unpack_bits(hashed_address)) && leaf.values.len() == 5
+    }));
+    assert!(leaves.iter().any(|leaf| {
+        leaf.matches_key(&Nibbles::unpack_bits(hashed_slot)) &&
+            leaf.values == vec![B256::with_last_byte(42)]
+    }));
+}
+
+#[test]
+fn test_parallel_state_root_matches_serial() {
+    let factory = create_test_provider_factory();
+    let tx = factory.provider_rw().unwrap();
+    let mut hashed_state = HashedPostState::default();
+    for i in 0..50u8 {
+        let address = Address::with_last_byte(i);
+        let account = Account {
+            nonce: i as u64,
+            balance: U256::from(i),
+            #[cfg(feature = "scroll")]
+            account_extension: Some(Default::default()),
+            ..Default::default()
+        };
+        let storage = (0..i % 5)
+            .map(|slot| (B256::with_last_byte(slot), U256::from(slot as u64 + 1)))
+            .collect::<BTreeMap<_, _>>();
+        insert_account(tx.tx_ref(), address, account, &storage);
+
+        // update the storage of every third account
+        if i % 3 == 0 {
+            hashed_state.storages.insert(
+                PoseidonKeyHasher::hash_key(address),
+                HashedStorage::from_iter(
+                    false,
+                    [(PoseidonKeyHasher::hash_key(B256::with_last_byte(1)), U256::from(i))],
+                ),
+            );
+        }
+    }
+    tx.commit().unwrap();
+
+    let view = ConsistentDbView::new(factory.clone(), None);
+    let tx = factory.provider().unwrap();
+    assert_eq!(
+        ParallelStateRoot::new(view.clone(), Default::default()).incremental_root().unwrap(),
+        StateRoot::from_tx(tx.tx_ref()).root().unwrap()
+    );
+    assert_eq!(
+        ParallelStateRoot::new(view, TrieInput::from_state(hashed_state.clone()))
+            .incremental_root()
+            .unwrap(),
+        StateRoot::overlay_root(tx.tx_ref(), hashed_state).unwrap()
+    );
+}
+
+#[test]
+fn test_sparse_state_trie_matches_state_root() {
+    let factory = create_test_provider_factory();
+    let tx = factory.provider_rw().unwrap();
+    let mut hashed_state = HashedPostState::default();
+    for i in 0..30u8 {
+        let address = Address::with_last_byte(i);
+        let account = Account {
+            nonce: i as u64,
+            balance: U256::from(i),
+            #[cfg(feature = "scroll")]
+            account_extension: Some(Default::default()),
+            ..Default::default()
+        };
+        let storage = (0..i % 4)
+            .map(|slot| (B256::with_last_byte(slot), U256::from(slot as u64 + 1)))
+            .collect::<BTreeMap<_, _>>();
+        insert_account(tx.tx_ref(), address, account, &storage);
+
+        let hashed_address = PoseidonKeyHasher::hash_key(address);
+        match i % 5 {
+            // update the balance and storage of the account, removing the first slot
+            0 => {
+                hashed_state
+                    .accounts
+                    .insert(hashed_address, Some(Account { balance: U256::from(1000), ..account }));
+                hashed_state.storages.insert(
+                    hashed_address,
+                    HashedStorage::from_iter(
+                        false,
+                        [
+                            (PoseidonKeyHasher::hash_key(B256::with_last_byte(0)), U256::ZERO),
+                            (PoseidonKeyHasher::hash_key(B256::with_last_byte(10)), U256::from(i)),
+                        ],
+                    ),
+                );
+            }
+            // destroy the account
+            1 if storage.is_empty() => {
+                hashed_state.accounts.insert(hashed_address, None);
+            }
+            _ => {}
+        }
+    }
+    tx.commit().unwrap();
+
+    // create a new account
+    let hashed_address = PoseidonKeyHasher::hash_key(Address::with_last_byte(100));
+    hashed_state.accounts.insert(
+        hashed_address,
+        Some(Account {
+            nonce: 1,
+            #[cfg(feature = "scroll")]
+            account_extension: Some(Default::default()),
+            ..Default::default()
+        }),
+    );
+
+    let tx = factory.provider().unwrap();
+    let targets = hashed_state
+        .accounts
+        .keys()
+        .map(|hashed_address| {
+            let slots = hashed_state
+                .storages
+                .get(hashed_address)
+                .map(|storage| storage.storage.keys().copied().collect())
+                .unwrap_or_default();
+            (*hashed_address, slots)
+        })
+        .collect();
+    let multiproof = Proof::from_tx(tx.tx_ref()).multiproof(targets).unwrap();
+
+    let mut sparse = SparseStateTrie::default().with_updates(true);
+    sparse.reveal_multiproof(Default::default(), multiproof).unwrap();
+    assert_eq!(sparse.root(), Some(StateRoot::from_tx(tx.tx_ref()).root().unwrap()));
+
+    for (hashed_address, storage) in &hashed_state.storages {
+        for (hashed_slot, value) in &storage.storage {
+            sparse.update_storage_slot(*hashed_address, *hashed_slot, *value).unwrap();
+        }
+    }
+    for (hashed_address, account) in &hashed_state.accounts {
+        sparse.update_account(*hashed_address, account.unwrap_or_default()).unwrap();
+    }
+
+    assert_eq!(sparse.root(), Some(StateRoot::overlay_root(tx.tx_ref(), hashed_state).unwrap()));
+    assert!(sparse.take_trie_updates().is_some());
+}
+
 fn insert_account(
     tx: &impl DbTxMut,
     address: Address,
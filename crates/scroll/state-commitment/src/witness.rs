@@ -0,0 +1,187 @@
+use super::proof::Proof;
+use alloy_primitives::{
+    map::{HashMap, HashSet},
+    Bytes, B256,
+};
+use reth_db::transaction::DbTx;
+use reth_execution_errors::{StateProofError, TrieWitnessError};
+use reth_scroll_primitives::poseidon::PrimeField;
+use reth_scroll_trie::ZkTrieNode;
+use reth_trie::{
+    hashed_cursor::{HashedCursor, HashedCursorFactory, HashedPostStateCursorFactory},
+    prefix_set::TriePrefixSetsMut,
+    trie_cursor::{InMemoryTrieCursorFactory, TrieCursorFactory},
+    HashedPostState, Nibbles, TrieInput,
+};
+use reth_trie_db::{DatabaseHashedCursorFactory, DatabaseTrieCursorFactory, DatabaseTrieWitness};
+
+/// State transition witness for the zktrie.
+///
+/// The witness maps the Poseidon hash of every zktrie node, in big endian representation, to the
+/// node encoded in the l2geth format, see [`ZkTrieNode`]. It contains all the nodes on the paths
+/// of the accounts and storage slots touched by the state transition. The leaf nodes of the
+/// touched accounts and slots hold the value preimage, which includes the Poseidon code hash of
+/// the accounts.
+///
+/// The branch node types encode whether the children are terminal nodes, which allows to recompute
+/// the root after insertions and deletions without any additional sibling nodes.
+#[derive(Debug)]
+pub struct TrieWitness<T, H> {
+    /// The cursor factory for traversing trie nodes.
+    trie_cursor_factory: T,
+    /// The factory for hashed cursors.
+    hashed_cursor_factory: H,
+    /// A set of prefix sets that have changes.
+    prefix_sets: TriePrefixSetsMut,
+    /// Recorded witness.
+    witness: HashMap<B256, Bytes>,
+}
+
+impl<T, H> TrieWitness<T, H> {
+    /// Creates a new witness generator.
+    pub fn new(trie_cursor_factory: T, hashed_cursor_factory: H) -> Self {
+        Self {
+            trie_cursor_factory,
+            hashed_cursor_factory,
+            prefix_sets: TriePrefixSetsMut::default(),
+            witness: HashMap::default(),
+        }
+    }
+
+    /// Set the trie cursor factory.
+    pub fn with_trie_cursor_factory<TF>(self, trie_cursor_factory: TF) -> TrieWitness<TF, H> {
+        TrieWitness {
+            trie_cursor_factory,
+            hashed_cursor_factory: self.hashed_cursor_factory,
+            prefix_sets: self.prefix_sets,
+            witness: self.witness,
+        }
+    }
+
+    /// Set the hashed cursor factory.
+    pub fn with_hashed_cursor_factory<HF>(self, hashed_cursor_factory: HF) -> TrieWitness<T, HF> {
+        TrieWitness {
+            trie_cursor_factory: self.trie_cursor_factory,
+            hashed_cursor_factory,
+            prefix_sets: self.prefix_sets,
+            witness: self.witness,
+        }
+    }
+
+    /// Set the prefix sets. They have to be mutable in order to allow extension with proof target.
+    pub fn with_prefix_sets_mut(mut self, prefix_sets: TriePrefixSetsMut) -> Self {
+        self.prefix_sets = prefix_sets;
+        self
+    }
+}
+
+impl<T, H> TrieWitness<T, H>
+where
+    T: TrieCursorFactory + Clone,
+    H: HashedCursorFactory + Clone,
+{
+    /// Compute the state transition witness for the trie. Gather all required nodes
+    /// to apply `state` on top of the current trie state.
+    ///
+    /// # Arguments
+    ///
+    /// `state` - state transition containing both modified and touched accounts and storage slots.
+    pub fn compute(
+        mut self,
+        state: HashedPostState,
+    ) -> Result<HashMap<B256, Bytes>, TrieWitnessError> {
+        if state.is_empty() {
+            return Ok(self.witness)
+        }
+
+        let proof_targets = self.get_proof_targets(&state)?;
+        let multiproof =
+            Proof::new(self.trie_cursor_factory.clone(), self.hashed_cursor_factory.clone())
+                .with_prefix_sets_mut(self.prefix_sets.clone())
+                .multiproof(proof_targets)?;
+
+        record_nodes(&mut self.witness, multiproof.account_subtree.into_inner())?;
+        for storage_multiproof in multiproof.storages.into_values() {
+            record_nodes(&mut self.witness, storage_multiproof.subtree.into_inner())?;
+        }
+
+        Ok(self.witness)
+    }
+
+    /// Retrieve proof targets for incoming hashed state.
+    /// This method will aggregate all accounts and slots present in the hash state as well as
+    /// select all existing slots from the database for the accounts that have been destroyed.
+    fn get_proof_targets(
+        &self,
+        state: &HashedPostState,
+    ) -> Result<HashMap<B256, HashSet<B256>>, StateProofError> {
+        let mut proof_targets = HashMap::default();
+        for hashed_address in state.accounts.keys() {
+            proof_targets.insert(*hashed_address, HashSet::default());
+        }
+        for (hashed_address, storage) in &state.storages {
+            let mut storage_keys = storage.storage.keys().copied().collect::<HashSet<_>>();
+            if storage.wiped {
+                // storage for this account was destroyed, gather all slots from the current state
+                let mut storage_cursor =
+                    self.hashed_cursor_factory.hashed_storage_cursor(*hashed_address)?;
+                // position cursor at the start
+                let mut current_entry = storage_cursor.seek(B256::ZERO)?;
+                while let Some((hashed_slot, _)) = current_entry {
+                    storage_keys.insert(hashed_slot);
+                    current_entry = storage_cursor.next()?;
+                }
+            }
+            proof_targets.insert(*hashed_address, storage_keys);
+        }
+        Ok(proof_targets)
+    }
+}
+
+/// Records the proof nodes in the witness, keyed by their big endian Poseidon hash. Empty nodes
+/// are skipped.
+fn record_nodes(
+    witness: &mut HashMap<B256, Bytes>,
+    nodes: impl IntoIterator<Item = (Nibbles, Bytes)>,
+) -> Result<(), TrieWitnessError> {
+    for (path, encoded) in nodes {
+        let node = ZkTrieNode::decode(&encoded)
+            .map_err(|_| TrieWitnessError::InvalidNode(path.clone()))?;
+        if matches!(node, ZkTrieNode::Empty) {
+            continue
+        }
+
+        let mut hash = node.hash().map_err(|_| TrieWitnessError::InvalidNode(path))?.to_repr();
+        hash.reverse();
+        witness.insert(B256::from(hash), encoded);
+    }
+    Ok(())
+}
+
+impl<'a, TX: DbTx> DatabaseTrieWitness<'a, TX>
+    for TrieWitness<DatabaseTrieCursorFactory<'a, TX>, DatabaseHashedCursorFactory<'a, TX>>
+{
+    fn from_tx(tx: &'a TX) -> Self {
+        Self::new(DatabaseTrieCursorFactory::new(tx), DatabaseHashedCursorFactory::new(tx))
+    }
+
+    fn overlay_witness(
+        tx: &'a TX,
+        input: TrieInput,
+        target: HashedPostState,
+    ) -> Result<HashMap<B256, Bytes>, TrieWitnessError> {
+        let nodes_sorted = input.nodes.into_sorted();
+        let state_sorted = input.state.into_sorted();
+        Self::from_tx(tx)
+            .with_trie_cursor_factory(InMemoryTrieCursorFactory::new(
+                DatabaseTrieCursorFactory::new(tx),
+                &nodes_sorted,
+            ))
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(
+                DatabaseHashedCursorFactory::new(tx),
+                &state_sorted,
+            ))
+            .with_prefix_sets_mut(input.prefix_sets)
+            .compute(target)
+    }
+}
@@ -1,4 +1,8 @@
 use super::{
+    node::{
+        BranchNode, BRANCH_NODE_LBRB_TYPE, BRANCH_NODE_LBRT_TYPE, BRANCH_NODE_LTRB_TYPE,
+        BRANCH_NODE_LTRT_TYPE,
+    },
     BRANCH_NODE_LBRB_DOMAIN, BRANCH_NODE_LBRT_DOMAIN, BRANCH_NODE_LTRB_DOMAIN,
     BRANCH_NODE_LTRT_DOMAIN,
 };
@@ -95,6 +99,29 @@ impl<'a> BranchNodeRef<'a> {
         hash_with_domain(&[left_child, right_child], self.hashing_domain()).to_repr().into()
     }
 
+    /// Returns the branch node in the format used by zktrie proofs.
+    pub(crate) fn proof_node(&self) -> BranchNode {
+        let mut children_iter = self.children();
+        let mut next_child = || {
+            children_iter
+                .next()
+                .map(|(_, c)| *c.unwrap_or_default())
+                .expect("branch node has two children")
+        };
+        let (left_child, right_child) = (next_child(), next_child());
+        BranchNode::from_repr(self.node_type(), &left_child, &right_child)
+    }
+
+    fn node_type(&self) -> u8 {
+        match *self.state_mask {
+            0b1011 => BRANCH_NODE_LBRT_TYPE,
+            0b1111 => BRANCH_NODE_LTRT_TYPE,
+            0b0111 => BRANCH_NODE_LTRB_TYPE,
+            0b0011 => BRANCH_NODE_LBRB_TYPE,
+            _ => unreachable!("invalid branch node state mask"),
+        }
+    }
+
     fn hashing_domain(&self) -> Fr {
         match *self.state_mask {
             0b1011 => BRANCH_NODE_LBRT_DOMAIN,
@@ -1,6 +1,7 @@
 use crate::{
     branch::{BranchNodeRef, CHILD_INDEX_MASK},
     leaf::HashLeaf,
+    node::{BranchNode, LeafNode, ZkTrieNode},
     sub_tree::SubTreeRef,
 };
 use alloy_primitives::{map::HashMap, B256};
@@ -11,7 +12,7 @@ use alloy_trie::{
     BranchNodeCompact, Nibbles, TrieMask,
 };
 use core::cmp;
-use reth_scroll_primitives::poseidon::EMPTY_ROOT_HASH;
+use reth_scroll_primitives::poseidon::{PrimeField, EMPTY_ROOT_HASH};
 use tracing::trace;
 
 #[derive(Debug, Default)]
@@ -119,7 +120,7 @@ impl HashBuilder {
         let root = self.current_root();
         if root == EMPTY_ROOT_HASH {
             if let Some(proof_retainer) = self.proof_retainer.as_mut() {
-                proof_retainer.retain(&Nibbles::default(), &[])
+                proof_retainer.retain(&Nibbles::default(), &ZkTrieNode::Empty.encode())
             }
         }
         root
@@ -228,7 +229,13 @@ impl HashBuilder {
                             "pushing leaf node",
                         );
                         self.stack.push(leaf_hash);
-                        // self.retain_proof_from_stack(&current.slice(..len_from));
+                        if let Some(proof_retainer) = self.proof_retainer.as_mut() {
+                            // The leaf only commits to the hash of the value, the full preimage
+                            // is not known to the hash builder.
+                            let leaf_node =
+                                LeafNode::hashed(&current, &B256::from_slice(leaf_value));
+                            proof_retainer.retain(&current.slice(..len_from), &leaf_node.encode());
+                        }
                     }
                     HashBuilderValueRef::Hash(hash) => {
                         trace!(target: "trie::hash_builder", ?hash, "pushing branch node hash");
@@ -256,7 +263,13 @@ impl HashBuilder {
                 self.update_masks(&current, len_from);
                 let stack_last = self.stack.pop().expect("there should be at least one stack item");
                 let sub_tree = SubTreeRef::new(&short_node_key, &stack_last);
-                let sub_tree_root = sub_tree.root();
+                let sub_tree_root = match self.proof_retainer.as_mut() {
+                    Some(proof_retainer) => sub_tree.root_with_nodes(|depth, bit, child| {
+                        let node = BranchNode::with_single_child(bit, &B256::from(child.to_repr()));
+                        proof_retainer.retain(&current.slice(..len_from + depth), &node.encode());
+                    }),
+                    None => sub_tree.root(),
+                };
 
                 trace!(
                     target: "trie::hash_builder",
@@ -265,7 +278,6 @@ impl HashBuilder {
                     "pushing subtree root",
                 );
                 self.stack.push(sub_tree_root);
-                // self.retain_proof_from_stack(&current.slice(..len_from));
                 self.resize_masks(len_from);
             }
 
@@ -310,7 +322,7 @@ impl HashBuilder {
     ///
     /// Returns the hashes of the children of the branch node, only if `updated_branch_nodes` is
     /// enabled.
-    fn push_branch_node(&mut self, _current: &Nibbles, len: usize) -> Vec<B256> {
+    fn push_branch_node(&mut self, current: &Nibbles, len: usize) -> Vec<B256> {
         let state_mask = self.state_masks[len];
         let hash_mask = self.hash_masks[len];
         let branch_node = BranchNodeRef::new(&self.stack, state_mask);
@@ -323,8 +335,9 @@ impl HashBuilder {
 
         let branch_hash = branch_node.hash();
 
-        // TODO: enable proof retention
-        // self.retain_proof_from_stack(&current.slice(..len));
+        if let Some(proof_retainer) = self.proof_retainer.as_mut() {
+            proof_retainer.retain(&current.slice(..len), &branch_node.proof_node().encode());
+        }
 
         // Clears the stack from the branch node elements
         let first_child_idx = branch_node.first_child_index();
@@ -379,16 +392,6 @@ impl HashBuilder {
         }
     }
 
-    // TODO(scroll): Enable proof retention
-    // fn retain_proof_from_stack(&mut self, prefix: &Nibbles) {
-    //     if let Some(proof_retainer) = self.proof_retainer.as_mut() {
-    //         proof_retainer.retain(
-    //             prefix,
-    //             self.stack.last().expect("there should be at least one stack item").as_ref(),
-    //         );
-    //     }
-    // }
-
     fn update_masks(&mut self, current: &Nibbles, len_from: usize) {
         if len_from > 0 {
             let flag = TrieMask::from_nibble(current[len_from - 1]);
@@ -582,4 +585,68 @@ mod test {
 
         assert_eq!(expected, root);
     }
+
+    #[test]
+    fn test_proof_retention() {
+        let leaf_keys = [[0u8, 0, 0, 0], [0, 0, 0, 1], [0, 0, 1, 0], [1, 1, 1, 0], [1, 1, 1, 1]]
+            .map(|prefix| {
+                let mut key = [0u8; 32];
+                key[..4].copy_from_slice(&prefix);
+                Nibbles::from_nibbles_unchecked(key)
+            });
+        let leaf_value = |i: usize| {
+            let mut value = [0u8; 32];
+            value[0] = i as u8 + 1;
+            B256::from(value)
+        };
+
+        // an existing key and two keys which are absent from the trie, one of them ending in an
+        // empty node and the other one in the leaf node of another key.
+        let mut absent_in_empty = [0u8; 32];
+        absent_in_empty[..2].copy_from_slice(&[0, 1]);
+        let absent_in_empty = Nibbles::from_nibbles_unchecked(absent_in_empty);
+        let mut absent_in_leaf = [0u8; 32];
+        absent_in_leaf[..4].copy_from_slice(&[0, 0, 1, 1]);
+        let absent_in_leaf = Nibbles::from_nibbles_unchecked(absent_in_leaf);
+        let targets = vec![leaf_keys[0].clone(), absent_in_empty.clone(), absent_in_leaf.clone()];
+
+        let mut hb = HashBuilder::default().with_proof_retainer(ProofRetainer::new(targets));
+        for (i, key) in leaf_keys.iter().enumerate() {
+            hb.add_leaf(key.clone(), leaf_value(i).as_slice());
+        }
+        let root = hb.root();
+        let proof_nodes = hb.take_proof_nodes();
+
+        let proof = crate::proof_for_key(&proof_nodes, &leaf_keys[0]);
+        assert_eq!(proof.len(), 6);
+        crate::verify_proof(root, &leaf_keys[0], Some(leaf_value(0)), &proof).unwrap();
+        assert!(crate::verify_proof(root, &leaf_keys[0], Some(leaf_value(1)), &proof).is_err());
+        assert!(crate::verify_proof(root, &leaf_keys[0], None, &proof).is_err());
+
+        let proof = crate::proof_for_key(&proof_nodes, &absent_in_empty);
+        assert_eq!(
+            crate::ZkTrieNode::decode(&proof[proof.len() - 2]).unwrap(),
+            crate::ZkTrieNode::Empty
+        );
+        crate::verify_proof(root, &absent_in_empty, None, &proof).unwrap();
+
+        let proof = crate::proof_for_key(&proof_nodes, &absent_in_leaf);
+        assert!(matches!(
+            crate::ZkTrieNode::decode(&proof[proof.len() - 2]).unwrap(),
+            crate::ZkTrieNode::Leaf(_)
+        ));
+        crate::verify_proof(root, &absent_in_leaf, None, &proof).unwrap();
+        assert!(crate::verify_proof(root, &absent_in_leaf, Some(leaf_value(2)), &proof).is_err());
+    }
+
+    #[test]
+    fn test_empty_trie_proof() {
+        let key = Nibbles::from_nibbles_unchecked([0u8; 32]);
+        let mut hb =
+            HashBuilder::default().with_proof_retainer(ProofRetainer::new(vec![key.clone()]));
+        let root = hb.root();
+        let proof = crate::proof_for_key(&hb.take_proof_nodes(), &key);
+        assert_eq!(proof.len(), 2);
+        crate::verify_proof(root, &key, None, &proof).unwrap();
+    }
 }
@@ -10,9 +10,19 @@ extern crate alloc;
 mod branch;
 mod hash_builder;
 mod leaf;
+mod node;
+mod proof;
+mod sparse;
 mod sub_tree;
 
 pub use hash_builder::HashBuilder;
+pub use node::{
+    BranchNode, LeafNode, ZkTrieNode, ZkTrieNodeError, BRANCH_NODE_LBRB_TYPE,
+    BRANCH_NODE_LBRT_TYPE, BRANCH_NODE_LTRB_TYPE, BRANCH_NODE_LTRT_TYPE, EMPTY_NODE_TYPE,
+    LEAF_NODE_TYPE,
+};
+pub use proof::{proof_for_key, verify_proof, ZkTrieProofError, ZKTRIE_PROOF_MAGIC_BYTES};
+pub use sparse::{SparseNode, SparseTrie, SparseTrieUpdates};
 
 use reth_scroll_primitives::poseidon::Fr;
 
@@ -0,0 +1,361 @@
+use super::LEAF_NODE_DOMAIN;
+use alloy_primitives::B256;
+use alloy_trie::Nibbles;
+use reth_scroll_primitives::poseidon::{
+    hash_with_domain, split_and_hash_be_bytes, Fr, PrimeField, DOMAIN_MULTIPLIER_PER_FIELD_ELEMENT,
+};
+use reth_trie::BitsCompatibility;
+
+#[allow(unused_imports)]
+use alloc::vec::Vec;
+
+/// The node type of a leaf node.
+pub const LEAF_NODE_TYPE: u8 = 4;
+
+/// The node type of an empty node.
+pub const EMPTY_NODE_TYPE: u8 = 5;
+
+/// The node type of a branch node with two terminal children.
+pub const BRANCH_NODE_LTRT_TYPE: u8 = 6;
+
+/// The node type of a branch node with a left terminal child and a right branch child.
+pub const BRANCH_NODE_LTRB_TYPE: u8 = 7;
+
+/// The node type of a branch node with a left branch child and a right terminal child.
+pub const BRANCH_NODE_LBRT_TYPE: u8 = 8;
+
+/// The node type of a branch node with two branch children.
+pub const BRANCH_NODE_LBRB_TYPE: u8 = 9;
+
+/// The length of the encoded compressed flags of a leaf node.
+const COMPRESSED_FLAGS_LEN: usize = 4;
+
+/// An error that can occur when decoding or hashing a [`ZkTrieNode`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ZkTrieNodeError {
+    /// The encoded node is empty.
+    #[error("empty node encoding")]
+    EmptyEncoding,
+    /// The node type is not known.
+    #[error("unknown node type {0}")]
+    UnknownNodeType(u8),
+    /// The encoded node has an unexpected length.
+    #[error("invalid encoding length {0} for node type {1}")]
+    InvalidLength(usize, u8),
+    /// The key preimage of a leaf node has an unexpected length.
+    #[error("invalid key preimage length {0}")]
+    InvalidKeyPreimageLength(usize),
+    /// The leaf node does not hold any value.
+    #[error("leaf node has no value")]
+    EmptyLeafValue,
+    /// The bytes do not represent a valid field element.
+    #[error("invalid field element {0}")]
+    InvalidFieldElement(B256),
+}
+
+/// A node of the zktrie in the format used by l2geth proofs.
+///
+/// All hashes are represented in big endian.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ZkTrieNode {
+    /// An empty node.
+    Empty,
+    /// A branch node.
+    Branch(BranchNode),
+    /// A leaf node.
+    Leaf(LeafNode),
+}
+
+impl ZkTrieNode {
+    /// Decodes a node from its l2geth encoding.
+    pub fn decode(bytes: &[u8]) -> Result<Self, ZkTrieNodeError> {
+        let (&node_type, data) = bytes.split_first().ok_or(ZkTrieNodeError::EmptyEncoding)?;
+        match node_type {
+            EMPTY_NODE_TYPE => {
+                if !data.is_empty() {
+                    return Err(ZkTrieNodeError::InvalidLength(bytes.len(), node_type));
+                }
+                Ok(Self::Empty)
+            }
+            BRANCH_NODE_LTRT_TYPE..=BRANCH_NODE_LBRB_TYPE => {
+                if data.len() != 2 * B256::len_bytes() {
+                    return Err(ZkTrieNodeError::InvalidLength(bytes.len(), node_type));
+                }
+                let (left, right) = data.split_at(B256::len_bytes());
+                Ok(Self::Branch(BranchNode {
+                    node_type,
+                    left: B256::from_slice(left),
+                    right: B256::from_slice(right),
+                }))
+            }
+            LEAF_NODE_TYPE => LeafNode::decode(data)
+                .ok_or(ZkTrieNodeError::InvalidLength(bytes.len(), node_type))?
+                .map(Self::Leaf),
+            _ => Err(ZkTrieNodeError::UnknownNodeType(node_type)),
+        }
+    }
+
+    /// Encodes the node into its l2geth encoding.
+    pub fn encode(&self) -> Vec<u8> {
+        match self {
+            Self::Empty => vec![EMPTY_NODE_TYPE],
+            Self::Branch(branch) => branch.encode(),
+            Self::Leaf(leaf) => leaf.encode(),
+        }
+    }
+
+    /// Returns the hash of the node.
+    pub fn hash(&self) -> Result<Fr, ZkTrieNodeError> {
+        match self {
+            Self::Empty => Ok(Fr::zero()),
+            Self::Branch(branch) => branch.hash(),
+            Self::Leaf(leaf) => leaf.hash(),
+        }
+    }
+}
+
+/// A branch node of the zktrie, committing to the hashes of its two children.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchNode {
+    /// The node type, which encodes whether the children are terminal nodes.
+    pub node_type: u8,
+    /// The hash of the left child.
+    pub left: B256,
+    /// The hash of the right child.
+    pub right: B256,
+}
+
+impl BranchNode {
+    /// Creates a new branch node from the node type and the hashes of its children, in the
+    /// little endian representation used by the [`HashBuilder`](crate::HashBuilder).
+    pub(crate) fn from_repr(node_type: u8, left: &B256, right: &B256) -> Self {
+        Self { node_type, left: reverse(left), right: reverse(right) }
+    }
+
+    /// Creates a new branch node with a single branch child at the provided bit and an empty
+    /// sibling.
+    pub(crate) fn with_single_child(bit: u8, child: &B256) -> Self {
+        if bit == 0 {
+            Self::from_repr(BRANCH_NODE_LBRT_TYPE, child, &B256::ZERO)
+        } else {
+            Self::from_repr(BRANCH_NODE_LTRB_TYPE, &B256::ZERO, child)
+        }
+    }
+
+    /// Returns the hash of the child at the provided bit.
+    pub fn child(&self, bit: u8) -> B256 {
+        if bit == 0 {
+            self.left
+        } else {
+            self.right
+        }
+    }
+
+    /// Returns the hash of the branch node.
+    pub fn hash(&self) -> Result<Fr, ZkTrieNodeError> {
+        let domain = Fr::from_raw([self.node_type as u64, 0, 0, 0]);
+        Ok(hash_with_domain(
+            &[field_element_from_be_bytes(&self.left)?, field_element_from_be_bytes(&self.right)?],
+            domain,
+        ))
+    }
+
+    /// Encodes the branch node into its l2geth encoding.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut encoded = Vec::with_capacity(1 + 2 * B256::len_bytes());
+        encoded.push(self.node_type);
+        encoded.extend_from_slice(self.left.as_slice());
+        encoded.extend_from_slice(self.right.as_slice());
+        encoded
+    }
+}
+
+/// A leaf node of the zktrie.
+///
+/// The leaf commits to the key and the hash of the value preimage. The value preimage consists of
+/// 32 byte words, each of which is either interpreted directly as a field element or, if the
+/// respective bit in the compressed flags is set, hashed into a field element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeafNode {
+    /// The key of the leaf, which is the field element encoded by the path to the leaf.
+    pub node_key: B256,
+    /// The flags indicating which words of the value preimage are compressed.
+    pub compressed_flags: u32,
+    /// The value preimage of the leaf.
+    pub values: Vec<B256>,
+    /// The preimage of the node key, if known.
+    pub key_preimage: Option<B256>,
+}
+
+impl LeafNode {
+    /// Creates a new leaf node for the provided key bits and value preimage.
+    pub fn new(key: &Nibbles, compressed_flags: u32, values: Vec<B256>) -> Self {
+        let mut node_key = B256::from(key.encode_leaf_key());
+        node_key.reverse();
+        Self { node_key, compressed_flags, values, key_preimage: None }
+    }
+
+    /// Creates a new leaf node which only commits to the hash of the value, in the little endian
+    /// representation used by the [`HashBuilder`](crate::HashBuilder).
+    ///
+    /// The value hash is used as the single uncompressed word of the value preimage, which results
+    /// in the same leaf hash as the leaf holding the full value preimage.
+    pub fn hashed(key: &Nibbles, value_hash: &B256) -> Self {
+        Self::new(key, 0, vec![reverse(value_hash)])
+    }
+
+    /// Sets the key preimage of the leaf.
+    pub const fn with_key_preimage(mut self, key_preimage: B256) -> Self {
+        self.key_preimage = Some(key_preimage);
+        self
+    }
+
+    /// Returns true if the node key of the leaf matches the provided key bits.
+    pub fn matches_key(&self, key: &Nibbles) -> bool {
+        let mut node_key = B256::from(key.encode_leaf_key());
+        node_key.reverse();
+        self.node_key == node_key
+    }
+
+    /// Returns the bit of the node key at the provided depth.
+    pub fn key_bit(&self, depth: usize) -> u8 {
+        // The node key is big endian, the path is derived from the least significant bit first.
+        let byte = self.node_key[B256::len_bytes() - 1 - depth / 8];
+        (byte >> (depth % 8)) & 1
+    }
+
+    /// Returns the hash of the value preimage.
+    pub fn value_hash(&self) -> Result<Fr, ZkTrieNodeError> {
+        let elements = self
+            .values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| {
+                if i < 32 && self.compressed_flags & (1 << i) != 0 {
+                    Ok(split_and_hash_be_bytes(value))
+                } else {
+                    field_element_from_be_bytes(value)
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        match elements.as_slice() {
+            [] => Err(ZkTrieNodeError::EmptyLeafValue),
+            [element] => Ok(*element),
+            [first, second, rest @ ..] => {
+                let domain = Fr::from_raw([
+                    elements.len() as u64 * DOMAIN_MULTIPLIER_PER_FIELD_ELEMENT,
+                    0,
+                    0,
+                    0,
+                ]);
+                Ok(hash_elements(*first, *second, rest.to_vec(), domain))
+            }
+        }
+    }
+
+    /// Returns the hash of the leaf node.
+    pub fn hash(&self) -> Result<Fr, ZkTrieNodeError> {
+        Ok(hash_with_domain(
+            &[field_element_from_be_bytes(&self.node_key)?, self.value_hash()?],
+            LEAF_NODE_DOMAIN,
+        ))
+    }
+
+    fn decode(data: &[u8]) -> Option<Result<Self, ZkTrieNodeError>> {
+        let (node_key, data) = split_at_checked(data, B256::len_bytes())?;
+        let (flags, data) = split_at_checked(data, COMPRESSED_FLAGS_LEN)?;
+        let flags = u32::from_le_bytes(flags.try_into().ok()?);
+        let (num_values, compressed_flags) = ((flags & 0xff) as usize, flags >> 8);
+
+        let (values, data) = split_at_checked(data, num_values * B256::len_bytes())?;
+        let values = values.chunks_exact(B256::len_bytes()).map(B256::from_slice).collect();
+
+        let (&preimage_len, preimage) = data.split_first()?;
+        let key_preimage = match (preimage_len as usize, preimage.len()) {
+            (0, 0) => None,
+            (32, 32) => Some(B256::from_slice(preimage)),
+            (len, _) => return Some(Err(ZkTrieNodeError::InvalidKeyPreimageLength(len))),
+        };
+
+        Some(Ok(Self {
+            node_key: B256::from_slice(node_key),
+            compressed_flags,
+            values,
+            key_preimage,
+        }))
+    }
+
+    /// Encodes the leaf node into its l2geth encoding.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut encoded = Vec::with_capacity(
+            2 + B256::len_bytes() * (2 + self.values.len()) + COMPRESSED_FLAGS_LEN,
+        );
+        encoded.push(LEAF_NODE_TYPE);
+        encoded.extend_from_slice(self.node_key.as_slice());
+        let flags = (self.compressed_flags << 8) + self.values.len() as u32;
+        encoded.extend_from_slice(&flags.to_le_bytes());
+        for value in &self.values {
+            encoded.extend_from_slice(value.as_slice());
+        }
+        match &self.key_preimage {
+            Some(preimage) => {
+                encoded.push(B256::len_bytes() as u8);
+                encoded.extend_from_slice(preimage.as_slice());
+            }
+            None => encoded.push(0),
+        }
+        encoded
+    }
+}
+
+/// Hashes the elements pairwise, level by level, in the same way as zktrie's `HashElems`.
+fn hash_elements(first: Fr, second: Fr, mut rest: Vec<Fr>, domain: Fr) -> Fr {
+    let mut base = hash_with_domain(&[first, second], domain);
+    loop {
+        match rest.as_slice() {
+            [] => return base,
+            [last] => return hash_with_domain(&[base, *last], domain),
+            _ => {
+                rest = rest
+                    .chunks(2)
+                    .map(|chunk| match chunk {
+                        [left, right] => hash_with_domain(&[*left, *right], domain),
+                        [single] => *single,
+                        _ => unreachable!("chunks of two elements"),
+                    })
+                    .collect();
+                base = hash_with_domain(&[base, rest.remove(0)], domain);
+            }
+        }
+    }
+}
+
+/// Parses a field element from big endian bytes.
+pub(crate) fn field_element_from_be_bytes(bytes: &B256) -> Result<Fr, ZkTrieNodeError> {
+    Fr::from_repr_vartime(reverse(bytes).0).ok_or(ZkTrieNodeError::InvalidFieldElement(*bytes))
+}
+
+/// Reverses the byte order of the hash.
+fn reverse(hash: &B256) -> B256 {
+    let mut hash = *hash;
+    hash.reverse();
+    hash
+}
+
+/// Splits the slice at the provided index, returning `None` if the slice is too short.
+fn split_at_checked(data: &[u8], mid: usize) -> Option<(&[u8], &[u8])> {
+    (mid <= data.len()).then(|| data.split_at(mid))
+}
+
+impl From<BranchNode> for ZkTrieNode {
+    fn from(node: BranchNode) -> Self {
+        Self::Branch(node)
+    }
+}
+
+impl From<LeafNode> for ZkTrieNode {
+    fn from(node: LeafNode) -> Self {
+        Self::Leaf(node)
+    }
+}
@@ -0,0 +1,126 @@
+use crate::node::{field_element_from_be_bytes, ZkTrieNode, ZkTrieNodeError};
+use alloy_primitives::{Bytes, B256};
+use alloy_trie::{proof::ProofNodes, Nibbles};
+use reth_scroll_primitives::poseidon::PrimeField;
+
+#[allow(unused_imports)]
+use alloc::vec::Vec;
+
+/// The magic bytes l2geth appends to every zktrie proof.
+pub const ZKTRIE_PROOF_MAGIC_BYTES: &[u8] = b"THIS IS SOME MAGIC BYTES FOR SMT m1rRXgP2xpDI";
+
+/// An error that can occur when verifying a zktrie proof.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ZkTrieProofError {
+    /// A proof node could not be decoded or hashed.
+    #[error(transparent)]
+    Node(#[from] ZkTrieNodeError),
+    /// The proof does not contain any nodes.
+    #[error("proof is empty")]
+    EmptyProof,
+    /// The hash of a node does not match the hash committed to by its parent, or the root.
+    #[error("hash mismatch for proof node at depth {0}")]
+    HashMismatch(usize),
+    /// The proof ends in a branch node.
+    #[error("proof ends in a branch node at depth {0}")]
+    IncompleteProof(usize),
+    /// The proof contains nodes after a leaf or empty node.
+    #[error("unexpected proof node after terminal node at depth {0}")]
+    UnexpectedNode(usize),
+    /// The leaf node proving the absence of the key does not lie on the path of the key.
+    #[error("leaf node at depth {0} is not on the path of the key")]
+    LeafNotOnPath(usize),
+    /// The proven value does not match the expected value.
+    #[error("proof value mismatch, expected {expected:?}, got {got:?}")]
+    ValueMismatch {
+        /// The hash of the proven value.
+        got: Option<B256>,
+        /// The hash of the expected value.
+        expected: Option<B256>,
+    },
+}
+
+/// Returns the zktrie proof for the provided key from the retained proof nodes.
+///
+/// The proof is ordered from the root to the leaf, ends with an empty node if the key is not part
+/// of the trie and no leaf lies on its path, and is terminated with the
+/// [`ZKTRIE_PROOF_MAGIC_BYTES`], mirroring the proofs returned by l2geth.
+pub fn proof_for_key(nodes: &ProofNodes, key: &Nibbles) -> Vec<Bytes> {
+    let nodes = nodes.matching_nodes_sorted(key);
+    let ends_in_empty_child = nodes.last().is_some_and(|(path, node)| {
+        matches!(
+            ZkTrieNode::decode(node),
+            Ok(ZkTrieNode::Branch(branch))
+                if key.get(path.len()).is_some_and(|bit| branch.child(*bit) == B256::ZERO)
+        )
+    });
+
+    let mut proof = nodes.into_iter().map(|(_, node)| node).collect::<Vec<_>>();
+    if ends_in_empty_child {
+        proof.push(ZkTrieNode::Empty.encode().into());
+    }
+    proof.push(Bytes::from_static(ZKTRIE_PROOF_MAGIC_BYTES));
+    proof
+}
+
+/// Verifies the zktrie proof for the provided key against the root.
+///
+/// The `expected_value` is the hash of the value stored at the key, in the little endian
+/// representation used by the [`HashBuilder`](crate::HashBuilder), or `None` if the proof is
+/// expected to prove the absence of the key. Leaf nodes may either hold the full value preimage or
+/// only the hash of the value.
+pub fn verify_proof(
+    root: B256,
+    key: &Nibbles,
+    expected_value: Option<B256>,
+    proof: &[Bytes],
+) -> Result<(), ZkTrieProofError> {
+    let proof = match proof.split_last() {
+        Some((last, proof)) if last.as_ref() == ZKTRIE_PROOF_MAGIC_BYTES => proof,
+        _ => proof,
+    };
+    if proof.is_empty() {
+        return Err(ZkTrieProofError::EmptyProof);
+    }
+
+    let mut expected_hash = field_element_from_be_bytes(&root)?;
+    let mut proven_value = None;
+    for (depth, node) in proof.iter().enumerate() {
+        let node = ZkTrieNode::decode(node)?;
+        if node.hash()? != expected_hash {
+            return Err(ZkTrieProofError::HashMismatch(depth));
+        }
+
+        let is_last = depth == proof.len() - 1;
+        match node {
+            ZkTrieNode::Branch(branch) => {
+                let bit = match key.get(depth) {
+                    Some(bit) if !is_last => *bit,
+                    _ => return Err(ZkTrieProofError::IncompleteProof(depth)),
+                };
+                expected_hash = field_element_from_be_bytes(&branch.child(bit))?;
+            }
+            ZkTrieNode::Leaf(leaf) => {
+                if !is_last {
+                    return Err(ZkTrieProofError::UnexpectedNode(depth));
+                }
+                if leaf.matches_key(key) {
+                    proven_value = Some(B256::from(leaf.value_hash()?.to_repr()));
+                } else if (0..depth).any(|i| key.get(i) != Some(&leaf.key_bit(i))) {
+                    return Err(ZkTrieProofError::LeafNotOnPath(depth));
+                }
+            }
+            ZkTrieNode::Empty => {
+                if !is_last {
+                    return Err(ZkTrieProofError::UnexpectedNode(depth));
+                }
+            }
+        }
+    }
+
+    if proven_value != expected_value {
+        return Err(ZkTrieProofError::ValueMismatch { got: proven_value, expected: expected_value });
+    }
+
+    Ok(())
+}
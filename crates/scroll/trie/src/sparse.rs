@@ -0,0 +1,563 @@
+use crate::{
+    leaf::HashLeaf,
+    node::{ZkTrieNode, ZkTrieNodeError},
+    BRANCH_NODE_LBRB_DOMAIN, BRANCH_NODE_LBRB_TYPE, BRANCH_NODE_LBRT_DOMAIN, BRANCH_NODE_LBRT_TYPE,
+    BRANCH_NODE_LTRB_DOMAIN, BRANCH_NODE_LTRB_TYPE, BRANCH_NODE_LTRT_DOMAIN, BRANCH_NODE_LTRT_TYPE,
+};
+use alloy_primitives::{
+    map::{HashMap, HashSet},
+    B256,
+};
+use alloy_trie::{nodes::LeafNodeRef, BranchNodeCompact, Nibbles, TrieMask};
+use reth_scroll_primitives::poseidon::{hash_with_domain, Fr, PrimeField};
+use reth_trie_sparse::{SparseTrieError, SparseTrieResult};
+
+#[allow(unused_imports)]
+use alloc::vec::Vec;
+
+/// The number of bits of the keys of the trie, see
+/// [`BitsCompatibility::unpack_bits`](reth_trie::BitsCompatibility::unpack_bits).
+const KEY_BITS: usize = 254;
+
+/// The state mask of a branch node with two children.
+const BRANCH_NODE_STATE_MASK: TrieMask = TrieMask::new(0b11);
+
+/// A sparse binary Merkle Patricia Trie (zktrie), revealed from zktrie proof nodes.
+///
+/// The nodes are keyed by their path in bits. Leaf nodes are placed at the shortest unique prefix
+/// of their key and branch nodes always have both of their children present in the trie, empty
+/// children are represented by [`SparseNode::Empty`].
+///
+/// All hashes are kept in the little endian representation used by the
+/// [`HashBuilder`](crate::HashBuilder), the root is returned in big endian.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SparseTrie {
+    /// Map from a path (bits) to its revealed node.
+    nodes: HashMap<Nibbles, SparseNode>,
+    /// Retained trie updates.
+    updates: Option<SparseTrieUpdates>,
+}
+
+impl SparseTrie {
+    /// Creates new revealed empty trie.
+    pub fn revealed_empty() -> Self {
+        Self { nodes: HashMap::from_iter([(Nibbles::default(), SparseNode::Empty)]), updates: None }
+    }
+
+    /// Set the retention of branch node updates and deletions.
+    pub fn with_updates(mut self, retain_updates: bool) -> Self {
+        if retain_updates {
+            self.updates = Some(SparseTrieUpdates::default());
+        }
+        self
+    }
+
+    /// Returns `true` if the root node of the trie has not been revealed.
+    pub fn is_blind(&self) -> bool {
+        !self.nodes.contains_key(&Nibbles::default())
+    }
+
+    /// Takes and returns the retained sparse node updates.
+    pub fn take_updates(&mut self) -> SparseTrieUpdates {
+        self.updates.take().unwrap_or_default()
+    }
+
+    /// Reveal the proof node at the provided path.
+    ///
+    /// Only the root node and nodes which are blinded in the trie are revealed, all other nodes
+    /// are either already known or not part of the trie anymore.
+    pub fn reveal_node(&mut self, path: Nibbles, node: ZkTrieNode) -> Result<(), ZkTrieNodeError> {
+        match self.nodes.get(&path) {
+            Some(SparseNode::Hash { .. }) => {}
+            None if path.is_empty() => {}
+            _ => return Ok(()),
+        }
+
+        let node = match node {
+            ZkTrieNode::Empty => SparseNode::Empty,
+            ZkTrieNode::Leaf(leaf) => SparseNode::Leaf {
+                key: Nibbles::from_vec_unchecked(
+                    (0..KEY_BITS).map(|depth| leaf.key_bit(depth)).collect(),
+                ),
+                value_hash: B256::from(leaf.value_hash()?.to_repr()),
+            },
+            ZkTrieNode::Branch(branch) => {
+                let hash = B256::from(branch.hash()?.to_repr());
+                let terminal_children = terminal_children(branch.node_type);
+                for (bit, is_terminal) in [0, 1].into_iter().zip(terminal_children) {
+                    let mut child_hash = branch.child(bit);
+                    let child = if child_hash == B256::ZERO {
+                        SparseNode::Empty
+                    } else {
+                        child_hash.reverse();
+                        SparseNode::Hash { hash: child_hash, is_terminal }
+                    };
+                    self.nodes.insert(child_path(&path, bit), child);
+                }
+                SparseNode::Branch { hash: Some(hash), store_in_db_trie: None }
+            }
+        };
+        self.nodes.insert(path, node);
+
+        Ok(())
+    }
+
+    /// Reveal the root of the trie as a blinded node with the provided hash, in big endian
+    /// representation. This allows to compute the root of a trie without revealing any of its
+    /// nodes. The root is left untouched if it has already been revealed.
+    pub fn reveal_root_hash(&mut self, root: B256) {
+        if !self.is_blind() {
+            return
+        }
+        let node = if root == B256::ZERO {
+            SparseNode::Empty
+        } else {
+            let mut hash = root;
+            hash.reverse();
+            // The terminal flag of the root node is not relevant, the root has no parent.
+            SparseNode::Hash { hash, is_terminal: false }
+        };
+        self.nodes.insert(Nibbles::default(), node);
+    }
+
+    /// Update the leaf node with the provided value hash, in little endian representation.
+    pub fn update_leaf(&mut self, key: Nibbles, value_hash: B256) -> SparseTrieResult<()> {
+        let mut path = Nibbles::default();
+        loop {
+            let node = self.nodes.get_mut(&path).ok_or(SparseTrieError::Blind)?;
+            match node {
+                SparseNode::Empty => {
+                    *node = SparseNode::Leaf { key, value_hash };
+                    return Ok(())
+                }
+                SparseNode::Hash { hash, .. } => {
+                    return Err(SparseTrieError::BlindedNode { path, hash: *hash })
+                }
+                SparseNode::Leaf { key: leaf_key, value_hash: leaf_value_hash }
+                    if *leaf_key == key =>
+                {
+                    *leaf_value_hash = value_hash;
+                    return Ok(())
+                }
+                SparseNode::Leaf { .. } => {
+                    let SparseNode::Leaf { key: leaf_key, value_hash: leaf_value_hash } =
+                        core::mem::replace(node, SparseNode::new_branch())
+                    else {
+                        unreachable!("node is a leaf")
+                    };
+
+                    // Extend the path with single child branch nodes until the keys diverge.
+                    let common_prefix_len = key.common_prefix_length(&leaf_key);
+                    while path.len() < common_prefix_len {
+                        let bit = key[path.len()];
+                        self.nodes.insert(child_path(&path, bit ^ 1), SparseNode::Empty);
+                        path.push(bit);
+                        self.nodes.insert(path.clone(), SparseNode::new_branch());
+                    }
+
+                    self.nodes.insert(
+                        child_path(&path, leaf_key[common_prefix_len]),
+                        SparseNode::Leaf { key: leaf_key, value_hash: leaf_value_hash },
+                    );
+                    self.nodes.insert(
+                        child_path(&path, key[common_prefix_len]),
+                        SparseNode::Leaf { key, value_hash },
+                    );
+                    return Ok(())
+                }
+                SparseNode::Branch { hash, .. } => {
+                    *hash = None;
+                    path.push(key[path.len()]);
+                }
+            }
+        }
+    }
+
+    /// Remove the leaf node with the provided key. Removing a key which is not part of the trie
+    /// is a no-op.
+    ///
+    /// Terminal nodes which are left without a sibling are moved up the trie, since the hash of
+    /// a leaf does not depend on its position.
+    pub fn remove_leaf(&mut self, key: &Nibbles) -> SparseTrieResult<()> {
+        let mut path = Nibbles::default();
+        loop {
+            match self.nodes.get_mut(&path).ok_or(SparseTrieError::Blind)? {
+                SparseNode::Empty => return Ok(()),
+                SparseNode::Hash { hash, .. } => {
+                    return Err(SparseTrieError::BlindedNode { path, hash: *hash })
+                }
+                SparseNode::Leaf { key: leaf_key, .. } => {
+                    if leaf_key != key {
+                        return Ok(())
+                    }
+                    break
+                }
+                SparseNode::Branch { hash, .. } => {
+                    *hash = None;
+                    path.push(key[path.len()]);
+                }
+            }
+        }
+
+        self.nodes.insert(path.clone(), SparseNode::Empty);
+        while let Some(bit) = path.last() {
+            let sibling_path = child_path(&path.slice(..path.len() - 1), bit ^ 1);
+            let node = self.nodes.get(&path).ok_or(SparseTrieError::Blind)?;
+            let sibling = self.nodes.get(&sibling_path).ok_or(SparseTrieError::Blind)?;
+            let replacement = match (node, sibling) {
+                (SparseNode::Empty, sibling) if sibling.is_terminal() => sibling.clone(),
+                (node, SparseNode::Empty) if node.is_terminal() => node.clone(),
+                _ => break,
+            };
+
+            self.nodes.remove(&path);
+            self.nodes.remove(&sibling_path);
+            path.pop();
+            self.nodes.insert(path.clone(), replacement);
+
+            // The parent branch node has been replaced by the remaining terminal node.
+            if let Some(updates) = self.updates.as_mut() {
+                updates.updated_nodes.remove(&path);
+                updates.removed_nodes.insert(path.clone());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Wipe the trie, removing all values and nodes, and replacing the root with an empty node.
+    pub fn wipe(&mut self) {
+        let updates_retained = self.updates.is_some();
+        *self = Self::revealed_empty();
+        self.updates = updates_retained.then(SparseTrieUpdates::wiped);
+    }
+
+    /// Calculates and returns the trie root, in big endian representation, if the trie has been
+    /// revealed.
+    pub fn root(&mut self) -> Option<B256> {
+        if self.is_blind() {
+            return None
+        }
+        let mut root = self.hash_node(&Nibbles::default());
+        root.reverse();
+        Some(root)
+    }
+
+    /// Calculates the hashes of the branch nodes at the provided depth and all nodes below them.
+    pub fn calculate_below_level(&mut self, depth: usize) {
+        let paths = self
+            .nodes
+            .iter()
+            .filter(|(path, node)| {
+                path.len() == depth && matches!(node, SparseNode::Branch { hash: None, .. })
+            })
+            .map(|(path, _)| path.clone())
+            .collect::<Vec<_>>();
+        for path in paths {
+            self.hash_node(&path);
+        }
+    }
+
+    /// Returns the hash of the node at the provided path, calculating and caching the hashes of
+    /// all the changed branch nodes below it.
+    fn hash_node(&mut self, path: &Nibbles) -> B256 {
+        match self.nodes.get(path) {
+            None | Some(SparseNode::Empty) => return B256::ZERO,
+            Some(SparseNode::Hash { hash, .. } | SparseNode::Branch { hash: Some(hash), .. }) => {
+                return *hash
+            }
+            Some(SparseNode::Leaf { key, value_hash }) => {
+                return LeafNodeRef::new(key, value_hash.as_slice()).hash_leaf()
+            }
+            Some(SparseNode::Branch { hash: None, .. }) => {}
+        }
+
+        let children = [child_path(path, 0), child_path(path, 1)];
+        let child_hashes = [self.hash_node(&children[0]), self.hash_node(&children[1])];
+        let domain = match children.each_ref().map(|child| self.is_terminal(child)) {
+            [true, true] => BRANCH_NODE_LTRT_DOMAIN,
+            [true, false] => BRANCH_NODE_LTRB_DOMAIN,
+            [false, true] => BRANCH_NODE_LBRT_DOMAIN,
+            [false, false] => BRANCH_NODE_LBRB_DOMAIN,
+        };
+        let hash: B256 = hash_with_domain(
+            &child_hashes.map(|hash| {
+                Fr::from_repr_vartime(hash.0).expect("child hash is a valid field element")
+            }),
+            domain,
+        )
+        .to_repr()
+        .into();
+
+        let store_in_db_trie = self
+            .updates
+            .is_some()
+            .then(|| self.update_branch_node(path, &hash, &children, &child_hashes));
+        self.nodes.insert(path.clone(), SparseNode::Branch { hash: Some(hash), store_in_db_trie });
+        hash
+    }
+
+    /// Records the update of the branch node at the provided path, following the same rules as
+    /// the [`HashBuilder`](crate::HashBuilder), and returns whether the node is stored in the
+    /// database trie.
+    ///
+    /// Single child branch nodes are never stored, they inherit the flag of the branch node below
+    /// them such that their parent can set its tree mask.
+    fn update_branch_node(
+        &mut self,
+        path: &Nibbles,
+        hash: &B256,
+        children: &[Nibbles; 2],
+        child_hashes: &[B256; 2],
+    ) -> bool {
+        let is_single_child = children.iter().any(|child| self.is_empty(child));
+        let mut tree_mask = TrieMask::default();
+        let mut hash_mask = TrieMask::default();
+        let mut hashes = Vec::new();
+        for (bit, (child, child_hash)) in children.iter().zip(child_hashes).enumerate() {
+            match self.nodes.get(child) {
+                Some(SparseNode::Branch { store_in_db_trie, .. }) => {
+                    if !is_single_child && !self.is_single_child_branch(child) {
+                        hash_mask.set_bit(bit as u8);
+                        hashes.push(*child_hash);
+                    }
+                    // Branch nodes with unknown flags were revealed from the database.
+                    if store_in_db_trie.unwrap_or(true) {
+                        tree_mask.set_bit(bit as u8);
+                    }
+                }
+                // Blinded branch nodes are assumed to be stored in the database.
+                Some(SparseNode::Hash { is_terminal: false, .. }) => tree_mask.set_bit(bit as u8),
+                _ => {}
+            }
+        }
+
+        let updates = self.updates.as_mut().expect("updates are retained");
+        if is_single_child {
+            updates.updated_nodes.remove(path);
+            updates.removed_nodes.insert(path.clone());
+            return !tree_mask.is_empty()
+        }
+
+        let store_in_db_trie = !tree_mask.is_empty() || !hash_mask.is_empty();
+        if store_in_db_trie {
+            let root_hash = path.is_empty().then(|| {
+                let mut root = *hash;
+                root.reverse();
+                root
+            });
+            let node = BranchNodeCompact::new(
+                BRANCH_NODE_STATE_MASK,
+                tree_mask,
+                hash_mask,
+                hashes,
+                root_hash,
+            );
+            updates.removed_nodes.remove(path);
+            updates.updated_nodes.insert(path.clone(), node);
+        } else {
+            updates.updated_nodes.remove(path);
+            updates.removed_nodes.insert(path.clone());
+        }
+        store_in_db_trie
+    }
+
+    /// Returns `true` if the node at the provided path is a terminal node.
+    fn is_terminal(&self, path: &Nibbles) -> bool {
+        self.nodes.get(path).is_none_or(SparseNode::is_terminal)
+    }
+
+    /// Returns `true` if the node at the provided path is empty.
+    fn is_empty(&self, path: &Nibbles) -> bool {
+        matches!(self.nodes.get(path), None | Some(SparseNode::Empty))
+    }
+
+    /// Returns `true` if the node at the provided path is a branch node with an empty child.
+    fn is_single_child_branch(&self, path: &Nibbles) -> bool {
+        self.is_empty(&child_path(path, 0)) || self.is_empty(&child_path(path, 1))
+    }
+}
+
+/// A node of the [`SparseTrie`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SparseNode {
+    /// Empty node.
+    Empty,
+    /// A blinded node, of which only the hash is known.
+    Hash {
+        /// The hash of the node, in little endian representation.
+        hash: B256,
+        /// Whether the node is a terminal node, as encoded by the node type of its parent.
+        is_terminal: bool,
+    },
+    /// A leaf node.
+    Leaf {
+        /// The full key of the leaf.
+        key: Nibbles,
+        /// The hash of the value of the leaf, in little endian representation.
+        value_hash: B256,
+    },
+    /// A branch node. The children are stored at the path extended by the child bit.
+    Branch {
+        /// The cached hash of the node, `None` if the node or any of its descendants changed.
+        hash: Option<B256>,
+        /// Whether the node is stored in the database trie, calculated along with the hash if the
+        /// trie updates are retained.
+        store_in_db_trie: Option<bool>,
+    },
+}
+
+impl SparseNode {
+    /// Creates a new branch node without a cached hash.
+    pub const fn new_branch() -> Self {
+        Self::Branch { hash: None, store_in_db_trie: None }
+    }
+
+    /// Returns `true` if the node is a terminal node, i.e. an empty node or a leaf.
+    pub const fn is_terminal(&self) -> bool {
+        match self {
+            Self::Empty | Self::Leaf { .. } => true,
+            Self::Hash { is_terminal, .. } => *is_terminal,
+            Self::Branch { .. } => false,
+        }
+    }
+}
+
+/// The aggregation of sparse trie updates.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SparseTrieUpdates {
+    /// Updated branch nodes, indexed by their path.
+    pub updated_nodes: HashMap<Nibbles, BranchNodeCompact>,
+    /// Paths of the removed branch nodes.
+    pub removed_nodes: HashSet<Nibbles>,
+    /// Whether the trie has been wiped.
+    pub wiped: bool,
+}
+
+impl SparseTrieUpdates {
+    /// Create new wiped sparse trie updates.
+    pub fn wiped() -> Self {
+        Self { wiped: true, ..Default::default() }
+    }
+}
+
+/// Returns whether the left and right children of a branch node with the provided type are
+/// terminal nodes.
+const fn terminal_children(node_type: u8) -> [bool; 2] {
+    match node_type {
+        BRANCH_NODE_LTRT_TYPE => [true, true],
+        BRANCH_NODE_LTRB_TYPE => [true, false],
+        BRANCH_NODE_LBRT_TYPE => [false, true],
+        BRANCH_NODE_LBRB_TYPE => [false, false],
+        _ => unreachable!("invalid branch node type"),
+    }
+}
+
+/// Returns the path of the child at the provided bit.
+fn child_path(path: &Nibbles, bit: u8) -> Nibbles {
+    let mut child = path.clone();
+    child.push(bit);
+    child
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::HashBuilder;
+    use alloc::collections::BTreeMap;
+    use alloy_primitives::keccak256;
+    use alloy_trie::proof::ProofRetainer;
+    use reth_trie::BitsCompatibility;
+
+    fn leaf_key(i: u64) -> Nibbles {
+        Nibbles::unpack_bits(keccak256(i.to_be_bytes()))
+    }
+
+    fn leaf_value(i: u64) -> B256 {
+        B256::from(Fr::from(i + 1).to_repr())
+    }
+
+    /// Returns the root of the trie consisting of the provided leaves, along with the proof nodes
+    /// for the targets.
+    fn hash_builder_root(
+        leaves: &BTreeMap<Nibbles, B256>,
+        targets: Vec<Nibbles>,
+    ) -> (B256, Vec<(Nibbles, ZkTrieNode)>) {
+        let mut hb = HashBuilder::default().with_proof_retainer(ProofRetainer::new(targets));
+        for (key, value) in leaves {
+            hb.add_leaf(key.clone(), value.as_slice());
+        }
+        let root = hb.root();
+        let nodes = hb
+            .take_proof_nodes()
+            .into_nodes_sorted()
+            .into_iter()
+            .map(|(path, node)| (path, ZkTrieNode::decode(&node).unwrap()))
+            .collect();
+        (root, nodes)
+    }
+
+    #[test]
+    fn test_sparse_trie_matches_hash_builder() {
+        let mut leaves = (0..32).map(|i| (leaf_key(i), leaf_value(i))).collect::<BTreeMap<_, _>>();
+
+        // reveal a subset of the existing keys and a new key
+        let targets = (0..8).chain([100]).map(leaf_key).collect::<Vec<_>>();
+        let (root, nodes) = hash_builder_root(&leaves, targets);
+        let mut sparse = SparseTrie::default().with_updates(true);
+        assert_eq!(sparse.root(), None);
+        for (path, node) in nodes {
+            sparse.reveal_node(path, node).unwrap();
+        }
+        assert_eq!(sparse.root(), Some(root));
+
+        // update, insert and remove leaves
+        sparse.update_leaf(leaf_key(0), leaf_value(1000)).unwrap();
+        leaves.insert(leaf_key(0), leaf_value(1000));
+        sparse.update_leaf(leaf_key(100), leaf_value(100)).unwrap();
+        leaves.insert(leaf_key(100), leaf_value(100));
+        for i in 1..7 {
+            sparse.remove_leaf(&leaf_key(i)).unwrap();
+            leaves.remove(&leaf_key(i));
+        }
+
+        let (root, _) = hash_builder_root(&leaves, Vec::new());
+        sparse.calculate_below_level(2);
+        assert_eq!(sparse.root(), Some(root));
+
+        // removing an absent key is a no-op
+        sparse.remove_leaf(&leaf_key(1)).unwrap();
+        assert_eq!(sparse.root(), Some(root));
+    }
+
+    #[test]
+    fn test_sparse_trie_blinded_node() {
+        let leaves = (0..32).map(|i| (leaf_key(i), leaf_value(i))).collect::<BTreeMap<_, _>>();
+        let (_, nodes) = hash_builder_root(&leaves, vec![leaf_key(0)]);
+        let mut sparse = SparseTrie::default();
+        for (path, node) in nodes {
+            sparse.reveal_node(path, node).unwrap();
+        }
+
+        // the path of another key is blinded
+        assert!(matches!(
+            sparse.update_leaf(leaf_key(1), leaf_value(1)),
+            Err(SparseTrieError::BlindedNode { .. })
+        ));
+    }
+
+    #[test]
+    fn test_sparse_trie_remove_all() {
+        let leaves = (0..4).map(|i| (leaf_key(i), leaf_value(i))).collect::<BTreeMap<_, _>>();
+        let (_, nodes) = hash_builder_root(&leaves, leaves.keys().cloned().collect());
+        let mut sparse = SparseTrie::default();
+        for (path, node) in nodes {
+            sparse.reveal_node(path, node).unwrap();
+        }
+        for key in leaves.keys() {
+            sparse.remove_leaf(key).unwrap();
+        }
+        assert_eq!(sparse.root(), Some(B256::ZERO));
+        assert_eq!(sparse, SparseTrie::revealed_empty());
+    }
+}
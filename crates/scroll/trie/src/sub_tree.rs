@@ -21,9 +21,17 @@ impl<'a> SubTreeRef<'a> {
     }
 
     pub(crate) fn root(&self) -> B256 {
+        self.root_with_nodes(|_, _, _| {})
+    }
+
+    /// Returns the root of the sub tree, calling `on_node` for each of the branch nodes on the
+    /// path to the child with the depth of the node relative to the root of the sub tree, the bit
+    /// of its only child and the hash of that child.
+    pub(crate) fn root_with_nodes(&self, mut on_node: impl FnMut(usize, u8, Fr)) -> B256 {
         let mut tree_root =
             Fr::from_repr_vartime(self.child.0).expect("child is a valid field element");
-        for bit in self.key.as_slice().iter().rev() {
+        for (depth, bit) in self.key.as_slice().iter().enumerate().rev() {
+            on_node(depth, *bit, tree_root);
             tree_root = if *bit == 0 {
                 hash_with_domain(&[tree_root, Fr::zero()], BRANCH_NODE_LBRT_DOMAIN)
             } else {
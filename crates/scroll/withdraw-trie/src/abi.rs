@@ -0,0 +1,15 @@
+//! ABI of the Scroll `L2MessageQueue` predeploy.
+
+use alloy_primitives::{address, Address};
+use alloy_sol_types::sol;
+
+/// The address of the `L2MessageQueue` predeploy.
+/// <https://scrollscan.com/address/0x5300000000000000000000000000000000000000>
+pub const L2_MESSAGE_QUEUE_ADDRESS: Address = address!("5300000000000000000000000000000000000000");
+
+sol! {
+    /// Emitted by the `L2MessageQueue` contract when a message is appended to the withdraw trie.
+    /// <https://github.com/scroll-tech/scroll-contracts/blob/main/src/L2/predeploys/L2MessageQueue.sol>
+    #[derive(Debug, PartialEq, Eq)]
+    event AppendMessage(uint256 index, bytes32 messageHash);
+}
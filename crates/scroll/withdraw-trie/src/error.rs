@@ -0,0 +1,40 @@
+use reth_storage_errors::provider::ProviderError;
+
+/// Errors returned by the [`WithdrawTrieIndexer`](crate::WithdrawTrieIndexer) and the withdraw
+/// trie proofs.
+#[derive(Debug, thiserror::Error)]
+pub enum WithdrawTrieError {
+    /// An error occurred while reading or writing the local copy of the withdraw trie.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    /// The log could not be decoded into an `AppendMessage` event.
+    #[error("failed to decode AppendMessage log: {0}")]
+    Decode(#[from] alloy_sol_types::Error),
+    /// The index of the message doesn't follow the last indexed message.
+    #[error("withdraw trie index gap: expected {expected}, got {got}")]
+    IndexGap {
+        /// The expected index.
+        expected: u64,
+        /// The index of the message appended to the trie.
+        got: u64,
+    },
+    /// The message isn't in the withdraw trie the proof is requested for.
+    #[error("message {index} not in the withdraw trie of size {size}")]
+    IndexOutOfRange {
+        /// The index of the message.
+        index: u64,
+        /// The number of messages in the withdraw trie.
+        size: u64,
+    },
+    /// A node of a complete subtree of the withdraw trie is missing from the local copy.
+    #[error("withdraw trie node {index} at height {height} not found")]
+    NodeNotFound {
+        /// The height of the node.
+        height: u8,
+        /// The index of the node at its height.
+        index: u64,
+    },
+    /// The withdraw trie isn't indexed by the node.
+    #[error("withdraw trie not indexed, the node must be started with --scroll.withdraw-trie")]
+    NotIndexed,
+}
@@ -0,0 +1,172 @@
+//! Indexer of the Scroll withdraw trie.
+
+use crate::{
+    abi::{AppendMessage, L2_MESSAGE_QUEUE_ADDRESS},
+    trie::append_message,
+    WithdrawTrieError, WithdrawTrieReader, WithdrawTrieWriter,
+};
+use alloy_primitives::Log;
+use alloy_sol_types::SolEvent;
+use reth_db_api::transaction::DbTxMut;
+use reth_storage_api::{DBProvider, DatabaseProviderFactory};
+
+/// Replays the `AppendMessage` events of the `L2MessageQueue` contract into a local copy of the
+/// withdraw trie, stored in the [`WithdrawTrieNodes`](reth_db::tables::WithdrawTrieNodes) table.
+///
+/// Only the nodes of complete subtrees are stored, which never change once the trie grows, so the
+/// root and the Merkle proofs of the trie after any indexed L2 block can be computed from the
+/// local copy, see [`withdraw_root`](crate::withdraw_root) and
+/// [`withdraw_proof`](crate::withdraw_proof).
+#[derive(Debug)]
+pub struct WithdrawTrieIndexer<F> {
+    /// The factory of the database providers storing the withdraw trie.
+    factory: F,
+}
+
+impl<F> WithdrawTrieIndexer<F>
+where
+    F: DatabaseProviderFactory<ProviderRW: DBProvider<Tx: DbTxMut>>,
+{
+    /// Returns a new [`WithdrawTrieIndexer`].
+    pub const fn new(factory: F) -> Self {
+        Self { factory }
+    }
+
+    /// Returns the number of the last indexed L2 block.
+    pub fn last_block(&self) -> Result<Option<u64>, WithdrawTrieError> {
+        Ok(self.factory.database_provider_ro()?.last_withdraw_trie_block()?)
+    }
+
+    /// Indexes the L2 blocks, given as their numbers along with the logs of their receipts, in
+    /// order. The blocks which were already indexed are skipped. Returns the number of messages
+    /// appended to the withdraw trie.
+    pub fn index_blocks<'a, I, L>(&self, blocks: I) -> Result<u64, WithdrawTrieError>
+    where
+        I: IntoIterator<Item = (u64, L)>,
+        L: IntoIterator<Item = &'a Log>,
+    {
+        let provider = self.factory.database_provider_rw()?;
+        let last_block = provider.last_withdraw_trie_block()?;
+        let first_index = provider.next_withdraw_message_index()?;
+
+        let mut next_index = first_index;
+        for (number, logs) in blocks {
+            if last_block.is_some_and(|last_block| number <= last_block) {
+                continue
+            }
+
+            let size = next_index;
+            for log in logs {
+                if log.address != L2_MESSAGE_QUEUE_ADDRESS ||
+                    log.topics().first() != Some(&AppendMessage::SIGNATURE_HASH)
+                {
+                    continue
+                }
+                let event = AppendMessage::decode_log_data(&log.data, true)?;
+                let index = event.index.saturating_to::<u64>();
+                if index != next_index {
+                    return Err(WithdrawTrieError::IndexGap { expected: next_index, got: index })
+                }
+                append_message(&provider, index, event.messageHash)?;
+                next_index += 1;
+            }
+
+            if next_index != size {
+                provider.save_withdraw_trie_size(number, next_index)?;
+            }
+            provider.save_last_withdraw_trie_block(number)?;
+        }
+        provider.commit()?;
+
+        Ok(next_index - first_index)
+    }
+
+    /// Unwinds the withdraw trie to the given L2 block, removing the messages appended after it.
+    /// Returns the number of removed messages.
+    pub fn unwind(&self, block_number: u64) -> Result<usize, WithdrawTrieError> {
+        let provider = self.factory.database_provider_rw()?;
+        let Some(last_block) = provider.last_withdraw_trie_block()? else { return Ok(0) };
+        if last_block <= block_number {
+            return Ok(0)
+        }
+
+        let removed = provider.remove_withdraw_messages_after(block_number)?;
+        provider.save_last_withdraw_trie_block(block_number)?;
+        provider.commit()?;
+
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{verify_withdraw_proof, withdraw_proof, withdraw_root};
+    use alloy_primitives::{keccak256, Address, B256, U256};
+    use reth_provider::test_utils::create_test_provider_factory;
+
+    fn append_message_log(index: u64) -> Log {
+        let event = AppendMessage { index: U256::from(index), messageHash: message_hash(index) };
+        Log { address: L2_MESSAGE_QUEUE_ADDRESS, data: event.encode_log_data() }
+    }
+
+    fn message_hash(index: u64) -> B256 {
+        keccak256(index.to_be_bytes())
+    }
+
+    #[test]
+    fn test_index_withdraw_trie() -> Result<(), WithdrawTrieError> {
+        let factory = create_test_provider_factory();
+        let indexer = WithdrawTrieIndexer::new(factory.clone());
+
+        let mut other = append_message_log(10);
+        other.address = Address::random();
+        let blocks = [
+            (1, vec![append_message_log(0), append_message_log(1)]),
+            (2, vec![other]),
+            (3, vec![append_message_log(2)]),
+        ];
+        let blocks = blocks.iter().map(|(number, logs)| (*number, logs));
+        assert_eq!(indexer.index_blocks(blocks.clone())?, 3);
+        // the blocks are only indexed once
+        assert_eq!(indexer.index_blocks(blocks)?, 0);
+        assert_eq!(indexer.last_block()?, Some(3));
+
+        let provider = factory.database_provider_ro()?;
+        assert_eq!(provider.withdraw_trie_size(0)?, 0);
+        assert_eq!(provider.withdraw_trie_size(2)?, 2);
+        assert_eq!(provider.next_withdraw_message_index()?, 3);
+        assert_eq!(provider.withdraw_message_index(message_hash(1))?, Some(1));
+
+        let root = withdraw_root(&provider, 2)?;
+        let proof = withdraw_proof(&provider, 1, 2)?;
+        assert!(verify_withdraw_proof(root, message_hash(1), 1, &proof));
+        drop(provider);
+
+        // the messages of the unwound blocks are removed and indexed again
+        assert_eq!(indexer.unwind(2)?, 1);
+        assert_eq!(indexer.last_block()?, Some(2));
+        let provider = factory.database_provider_ro()?;
+        assert_eq!(provider.next_withdraw_message_index()?, 2);
+        assert_eq!(provider.withdraw_message_index(message_hash(2))?, None);
+        assert_eq!(withdraw_root(&provider, 2)?, root);
+        drop(provider);
+
+        let blocks = [(3, vec![append_message_log(2), append_message_log(3)])];
+        assert_eq!(indexer.index_blocks(blocks.iter().map(|(number, logs)| (*number, logs)))?, 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_withdraw_trie_gap() {
+        let factory = create_test_provider_factory();
+        let indexer = WithdrawTrieIndexer::new(factory);
+
+        let blocks = [(1, vec![append_message_log(0), append_message_log(2)])];
+        assert!(matches!(
+            indexer.index_blocks(blocks.iter().map(|(number, logs)| (*number, logs))),
+            Err(WithdrawTrieError::IndexGap { expected: 1, got: 2 })
+        ));
+    }
+}
@@ -0,0 +1,21 @@
+//! Local copy of the Scroll withdraw trie.
+//!
+//! The L2 to L1 messages are committed to the append-only withdraw trie of the `L2MessageQueue`
+//! predeploy, whose root is finalized on L1 along with each batch. The [`WithdrawTrieIndexer`]
+//! replays the `AppendMessage` events of the contract into a local copy of the trie, read through
+//! the [`WithdrawTrieReader`], from which the Merkle proofs expected by the L1 bridge are computed
+//! with [`withdraw_proof`].
+
+pub mod abi;
+
+mod error;
+pub use error::WithdrawTrieError;
+
+mod indexer;
+pub use indexer::WithdrawTrieIndexer;
+
+mod storage;
+pub use storage::{WithdrawTrieReader, WithdrawTrieWriter};
+
+mod trie;
+pub use trie::{verify_withdraw_proof, withdraw_proof, withdraw_root};
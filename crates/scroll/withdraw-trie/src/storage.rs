@@ -0,0 +1,122 @@
+use alloy_primitives::B256;
+use reth_db::tables::{self, ChainStateKey};
+use reth_db_api::{
+    cursor::DbCursorRO,
+    models::WithdrawTrieNodeKey,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_storage_api::DBProvider;
+use reth_storage_errors::provider::ProviderResult;
+
+/// Reads the local copy of the withdraw trie.
+pub trait WithdrawTrieReader {
+    /// Returns the node of a complete subtree of the withdraw trie at the given position.
+    fn withdraw_trie_node(&self, key: WithdrawTrieNodeKey) -> ProviderResult<Option<B256>>;
+
+    /// Returns the index in the withdraw trie of the message with the given hash.
+    fn withdraw_message_index(&self, message_hash: B256) -> ProviderResult<Option<u64>>;
+
+    /// Returns the number of messages in the withdraw trie after the given L2 block.
+    fn withdraw_trie_size(&self, block_number: u64) -> ProviderResult<u64>;
+
+    /// Returns the index following the last indexed message.
+    fn next_withdraw_message_index(&self) -> ProviderResult<u64> {
+        self.withdraw_trie_size(u64::MAX)
+    }
+
+    /// Returns the number of the last L2 block indexed for the withdraw trie.
+    fn last_withdraw_trie_block(&self) -> ProviderResult<Option<u64>>;
+}
+
+/// Writes the local copy of the withdraw trie.
+pub trait WithdrawTrieWriter {
+    /// Inserts the message with the given hash as the leaf at the given index.
+    fn insert_withdraw_message(&self, index: u64, message_hash: B256) -> ProviderResult<()>;
+
+    /// Inserts the node of a complete subtree of the withdraw trie.
+    fn insert_withdraw_trie_node(&self, key: WithdrawTrieNodeKey, node: B256)
+        -> ProviderResult<()>;
+
+    /// Saves the number of messages in the withdraw trie after the given L2 block.
+    fn save_withdraw_trie_size(&self, block_number: u64, size: u64) -> ProviderResult<()>;
+
+    /// Removes the messages appended after the given L2 block, along with the nodes of the
+    /// subtrees they complete. Returns the number of removed messages.
+    fn remove_withdraw_messages_after(&self, block_number: u64) -> ProviderResult<usize>;
+
+    /// Saves the number of the last L2 block indexed for the withdraw trie.
+    fn save_last_withdraw_trie_block(&self, block_number: u64) -> ProviderResult<()>;
+}
+
+impl<P: DBProvider> WithdrawTrieReader for P {
+    fn withdraw_trie_node(&self, key: WithdrawTrieNodeKey) -> ProviderResult<Option<B256>> {
+        Ok(self.tx_ref().get::<tables::WithdrawTrieNodes>(key)?)
+    }
+
+    fn withdraw_message_index(&self, message_hash: B256) -> ProviderResult<Option<u64>> {
+        Ok(self.tx_ref().get::<tables::WithdrawMessageIndices>(message_hash)?)
+    }
+
+    fn withdraw_trie_size(&self, block_number: u64) -> ProviderResult<u64> {
+        // the sizes are only recorded for the blocks appending messages
+        let mut cursor = self.tx_ref().cursor_read::<tables::WithdrawTrieSizes>()?;
+        let entry = match cursor.seek(block_number.saturating_add(1))? {
+            Some(_) => cursor.prev()?,
+            None => cursor.last()?,
+        };
+        Ok(entry.map_or(0, |(_, size)| size))
+    }
+
+    fn last_withdraw_trie_block(&self) -> ProviderResult<Option<u64>> {
+        Ok(self.tx_ref().get::<tables::ChainState>(ChainStateKey::LastWithdrawTrieBlock)?)
+    }
+}
+
+impl<P> WithdrawTrieWriter for P
+where
+    P: DBProvider<Tx: DbTxMut>,
+{
+    fn insert_withdraw_message(&self, index: u64, message_hash: B256) -> ProviderResult<()> {
+        self.insert_withdraw_trie_node(WithdrawTrieNodeKey::new(0, index), message_hash)?;
+        Ok(self.tx_ref().put::<tables::WithdrawMessageIndices>(message_hash, index)?)
+    }
+
+    fn insert_withdraw_trie_node(
+        &self,
+        key: WithdrawTrieNodeKey,
+        node: B256,
+    ) -> ProviderResult<()> {
+        Ok(self.tx_ref().put::<tables::WithdrawTrieNodes>(key, node)?)
+    }
+
+    fn save_withdraw_trie_size(&self, block_number: u64, size: u64) -> ProviderResult<()> {
+        Ok(self.tx_ref().put::<tables::WithdrawTrieSizes>(block_number, size)?)
+    }
+
+    fn remove_withdraw_messages_after(&self, block_number: u64) -> ProviderResult<usize> {
+        let size = self.withdraw_trie_size(block_number)?;
+
+        let leaves = self.take::<tables::WithdrawTrieNodes>(
+            WithdrawTrieNodeKey::new(0, size)..=WithdrawTrieNodeKey::new(0, u64::MAX),
+        )?;
+        for (_, message_hash) in &leaves {
+            self.tx_ref().delete::<tables::WithdrawMessageIndices>(*message_hash, None)?;
+        }
+        // the subtree of the node at index `i` and height `h` is complete once the message at
+        // index `(i + 1) * 2^h - 1` is appended
+        for height in 1..u64::BITS as u8 {
+            let start = WithdrawTrieNodeKey::new(height, size >> height);
+            let end = WithdrawTrieNodeKey::new(height, u64::MAX);
+            self.remove::<tables::WithdrawTrieNodes>(start..=end)?;
+        }
+        self.remove::<tables::WithdrawTrieSizes>(block_number + 1..)?;
+
+        Ok(leaves.len())
+    }
+
+    fn save_last_withdraw_trie_block(&self, block_number: u64) -> ProviderResult<()> {
+        Ok(self
+            .tx_ref()
+            .put::<tables::ChainState>(ChainStateKey::LastWithdrawTrieBlock, block_number)?)
+    }
+}
@@ -0,0 +1,196 @@
+//! Roots and Merkle proofs of the withdraw trie.
+//!
+//! The withdraw trie is the append-only Merkle tree of the `L2MessageQueue` contract, whose leaves
+//! are the hashes of the L2 to L1 messages. The trie holding `n` messages has the smallest height
+//! `h` such that `2^h >= n`, the missing leaves being zero, and a node is the keccak hash of the
+//! concatenation of its children.
+
+use crate::{WithdrawTrieError, WithdrawTrieReader, WithdrawTrieWriter};
+use alloy_primitives::{keccak256, B256};
+use reth_db_api::models::WithdrawTrieNodeKey;
+
+/// Returns the root of the withdraw trie holding the first `size` messages.
+pub fn withdraw_root<R: WithdrawTrieReader>(
+    reader: &R,
+    size: u64,
+) -> Result<B256, WithdrawTrieError> {
+    if size == 0 {
+        return Ok(B256::ZERO)
+    }
+    let height = trie_height(size);
+    let zero_hashes = zero_hashes(height);
+    node(reader, height, 0, size, &zero_hashes)
+}
+
+/// Returns the Merkle proof of the message at `index` in the withdraw trie holding the first
+/// `size` messages: the siblings of the nodes on the path from the leaf to the root, leaf first.
+///
+/// The proof is checked on L1 by the `WithdrawTrieVerifier` library, see
+/// [`verify_withdraw_proof`].
+pub fn withdraw_proof<R: WithdrawTrieReader>(
+    reader: &R,
+    index: u64,
+    size: u64,
+) -> Result<Vec<B256>, WithdrawTrieError> {
+    if index >= size {
+        return Err(WithdrawTrieError::IndexOutOfRange { index, size })
+    }
+    let height = trie_height(size);
+    let zero_hashes = zero_hashes(height);
+    (0..height).map(|h| node(reader, h, (index >> h) ^ 1, size, &zero_hashes)).collect()
+}
+
+/// Returns whether the Merkle proof of the message at `index` matches the withdraw trie root.
+pub fn verify_withdraw_proof(root: B256, message_hash: B256, index: u64, proof: &[B256]) -> bool {
+    let mut hash = message_hash;
+    for (height, sibling) in proof.iter().enumerate() {
+        hash = if (index >> height) % 2 == 0 {
+            hash_pair(hash, *sibling)
+        } else {
+            hash_pair(*sibling, hash)
+        };
+    }
+    hash == root
+}
+
+/// Appends the message at `index` to the withdraw trie, storing the nodes of the subtrees it
+/// completes.
+pub(crate) fn append_message<P: WithdrawTrieReader + WithdrawTrieWriter>(
+    provider: &P,
+    index: u64,
+    message_hash: B256,
+) -> Result<(), WithdrawTrieError> {
+    provider.insert_withdraw_message(index, message_hash)?;
+
+    let (mut height, mut index, mut node) = (0, index, message_hash);
+    while index % 2 == 1 {
+        node = hash_pair(stored_node(provider, height, index - 1)?, node);
+        height += 1;
+        index /= 2;
+        provider.insert_withdraw_trie_node(WithdrawTrieNodeKey::new(height, index), node)?;
+    }
+
+    Ok(())
+}
+
+/// Returns the node at the given position in the withdraw trie holding the first `size`
+/// messages, reading the nodes of complete subtrees from the local copy.
+fn node<R: WithdrawTrieReader>(
+    reader: &R,
+    height: u8,
+    index: u64,
+    size: u64,
+    zero_hashes: &[B256],
+) -> Result<B256, WithdrawTrieError> {
+    let first_leaf = index << height;
+    if first_leaf >= size {
+        return Ok(zero_hashes[height as usize])
+    }
+    if first_leaf + (1 << height) <= size {
+        return stored_node(reader, height, index)
+    }
+
+    // the subtree is partially filled
+    let left = node(reader, height - 1, index * 2, size, zero_hashes)?;
+    let right = node(reader, height - 1, index * 2 + 1, size, zero_hashes)?;
+    Ok(hash_pair(left, right))
+}
+
+/// Returns the stored node of a complete subtree.
+fn stored_node<R: WithdrawTrieReader>(
+    reader: &R,
+    height: u8,
+    index: u64,
+) -> Result<B256, WithdrawTrieError> {
+    reader
+        .withdraw_trie_node(WithdrawTrieNodeKey::new(height, index))?
+        .ok_or(WithdrawTrieError::NodeNotFound { height, index })
+}
+
+/// Returns the height of the withdraw trie holding `size` messages.
+const fn trie_height(size: u64) -> u8 {
+    (u64::BITS - size.saturating_sub(1).leading_zeros()) as u8
+}
+
+/// Returns the roots of the empty subtrees up to the given height.
+fn zero_hashes(height: u8) -> Vec<B256> {
+    let mut zero_hashes = vec![B256::ZERO];
+    for h in 0..height as usize {
+        zero_hashes.push(hash_pair(zero_hashes[h], zero_hashes[h]));
+    }
+    zero_hashes
+}
+
+/// Returns the keccak hash of the concatenation of the two nodes.
+fn hash_pair(left: B256, right: B256) -> B256 {
+    let mut buf = [0u8; 64];
+    buf[..32].copy_from_slice(left.as_slice());
+    buf[32..].copy_from_slice(right.as_slice());
+    keccak256(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_provider::test_utils::create_test_provider_factory;
+    use reth_storage_api::DatabaseProviderFactory;
+
+    /// Port of the `AppendOnlyMerkleTree` contract, returning the root after each message.
+    fn contract_roots(messages: &[B256]) -> Vec<B256> {
+        let zero_hashes = zero_hashes(64);
+        let mut branches = [B256::ZERO; 64];
+        let mut roots = Vec::new();
+        for (index, message) in messages.iter().enumerate() {
+            let (mut index, mut hash, mut height) = (index, *message, 0);
+            while index != 0 {
+                if index % 2 == 0 {
+                    branches[height] = hash;
+                    hash = hash_pair(hash, zero_hashes[height]);
+                } else {
+                    hash = hash_pair(branches[height], hash);
+                }
+                height += 1;
+                index >>= 1;
+            }
+            branches[height] = hash;
+            roots.push(hash);
+        }
+        roots
+    }
+
+    #[test]
+    fn test_withdraw_trie_proofs() -> Result<(), WithdrawTrieError> {
+        let factory = create_test_provider_factory();
+        let provider = factory.database_provider_rw()?;
+        let messages = (0..19u64).map(|i| keccak256(i.to_be_bytes())).collect::<Vec<_>>();
+        let roots = contract_roots(&messages);
+
+        assert_eq!(withdraw_root(&provider, 0)?, B256::ZERO);
+        for (index, message) in messages.iter().enumerate() {
+            append_message(&provider, index as u64, *message)?;
+            let size = index as u64 + 1;
+            let root = withdraw_root(&provider, size)?;
+            assert_eq!(root, roots[index]);
+
+            // every message of the trie can be proven against its root
+            for (index, message) in messages[..size as usize].iter().enumerate() {
+                let proof = withdraw_proof(&provider, index as u64, size)?;
+                assert_eq!(proof.len(), trie_height(size) as usize);
+                assert!(verify_withdraw_proof(root, *message, index as u64, &proof));
+                assert!(!verify_withdraw_proof(root, B256::ZERO, index as u64, &proof));
+            }
+        }
+
+        // messages outside of the trie can't be proven
+        assert!(matches!(
+            withdraw_proof(&provider, 19, 19),
+            Err(WithdrawTrieError::IndexOutOfRange { index: 19, size: 19 })
+        ));
+        assert!(matches!(
+            withdraw_proof(&provider, 0, 0),
+            Err(WithdrawTrieError::IndexOutOfRange { index: 0, size: 0 })
+        ));
+
+        Ok(())
+    }
+}
@@ -33,6 +33,8 @@
 //!
 //! # }
 //! ```
+#[cfg(feature = "scroll")]
+use crate::stages::ScrollMptMigrationStage;
 use crate::{
     stages::{
         AccountHashingStage, BodyStage, ExecutionStage, FinishStage, HeaderStage,
@@ -382,6 +384,8 @@ where
 }
 
 /// A set containing all stages that hash account state.
+///
+/// The state commitment stages are run after the [`MerkleStage`], see [`StateCommitmentStages`].
 #[derive(Debug, Default)]
 #[non_exhaustive]
 pub struct HashingStages {
@@ -394,6 +398,7 @@ where
     MerkleStage: Stage<Provider>,
     AccountHashingStage: Stage<Provider>,
     StorageHashingStage: Stage<Provider>,
+    StateCommitmentStages: StageSet<Provider>,
 {
     fn builder(self) -> StageSetBuilder<Provider> {
         StageSetBuilder::default()
@@ -407,6 +412,33 @@ where
                 self.stages_config.etl.clone(),
             ))
             .add_stage(MerkleStage::new_execution(self.stages_config.merkle.clean_threshold))
+            .add_set(StateCommitmentStages)
+    }
+}
+
+/// A set containing the stages committing to the state with a scheme other than the trie built by
+/// the [`MerkleStage`].
+///
+/// With the `scroll` feature, this is the `ScrollMptMigrationStage` computing the keccak Merkle
+/// Patricia Trie committing to the state from the Euclid hardfork on. Otherwise, the set is empty.
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct StateCommitmentStages;
+
+#[cfg(feature = "scroll")]
+impl<Provider> StageSet<Provider> for StateCommitmentStages
+where
+    ScrollMptMigrationStage: Stage<Provider>,
+{
+    fn builder(self) -> StageSetBuilder<Provider> {
+        StageSetBuilder::default().add_stage(ScrollMptMigrationStage)
+    }
+}
+
+#[cfg(not(feature = "scroll"))]
+impl<Provider> StageSet<Provider> for StateCommitmentStages {
+    fn builder(self) -> StageSetBuilder<Provider> {
+        StageSetBuilder::default()
     }
 }
 
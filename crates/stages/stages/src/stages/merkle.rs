@@ -8,6 +8,7 @@ use reth_provider::{
     DBProvider, HeaderProvider, LatestStateProviderRef, ProviderError, StageCheckpointReader,
     StageCheckpointWriter, StateCommitmentProvider, StateRootProviderExt, StatsReader, TrieWriter,
 };
+use reth_scroll_state_commitment::{StateCommitmentScheme, StateCommitmentSchemeProvider};
 use reth_stages_api::{
     BlockErrorKind, EntitiesCheckpoint, ExecInput, ExecOutput, MerkleCheckpoint, Stage,
     StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
@@ -137,7 +138,8 @@ where
         + HeaderProvider
         + StageCheckpointReader
         + StageCheckpointWriter
-        + StateCommitmentProvider,
+        + StateCommitmentProvider
+        + StateCommitmentSchemeProvider,
 {
     /// Return the id of the stage
     fn id(&self) -> StageId {
@@ -274,7 +276,9 @@ where
         // Reset the checkpoint
         self.save_execution_checkpoint(provider, None)?;
 
-        validate_state_root(trie_root, SealedHeader::seal(target_block), to_block)?;
+        if commits_to_trie(provider, target_block.timestamp) {
+            validate_state_root(trie_root, SealedHeader::seal(target_block), to_block)?;
+        }
 
         Ok(ExecOutput {
             checkpoint: StageCheckpoint::new(to_block)
@@ -328,7 +332,9 @@ where
                 .header_by_number(input.unwind_to)?
                 .ok_or_else(|| ProviderError::HeaderNotFound(input.unwind_to.into()))?;
 
-            validate_state_root(block_root, SealedHeader::seal(target), input.unwind_to)?;
+            if commits_to_trie(provider, target.timestamp) {
+                validate_state_root(block_root, SealedHeader::seal(target), input.unwind_to)?;
+            }
 
             // Validation passed, apply unwind changes to the database.
             provider.write_trie_updates(&updates)?;
@@ -340,9 +346,18 @@ where
     }
 }
 
+/// Returns whether the state of the block with the given timestamp is committed to by the trie
+/// built by the [`MerkleStage`].
+///
+/// From the Euclid hardfork on, the Scroll state is committed to by the keccak Merkle Patricia
+/// Trie, whose root is checked by the `ScrollMptMigrationStage` instead.
+fn commits_to_trie(provider: &impl StateCommitmentSchemeProvider, timestamp: u64) -> bool {
+    provider.state_commitment_scheme(timestamp) == StateCommitmentScheme::ZkTrie
+}
+
 /// Check that the computed state root matches the root in the expected header.
 #[inline]
-fn validate_state_root(
+pub(crate) fn validate_state_root(
     got: B256,
     expected: SealedHeader,
     target_block: BlockNumber,
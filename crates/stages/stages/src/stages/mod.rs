@@ -16,6 +16,9 @@ mod index_account_history;
 mod index_storage_history;
 /// Stage for computing state root.
 mod merkle;
+/// Stage for computing the keccak Merkle Patricia Trie of the Scroll state.
+#[cfg(feature = "scroll")]
+mod mpt;
 mod prune;
 /// The sender recovery stage.
 mod sender_recovery;
@@ -31,6 +34,8 @@ pub use headers::*;
 pub use index_account_history::*;
 pub use index_storage_history::*;
 pub use merkle::*;
+#[cfg(feature = "scroll")]
+pub use mpt::*;
 pub use prune::*;
 pub use sender_recovery::*;
 pub use tx_lookup::*;
@@ -0,0 +1,247 @@
+use super::merkle::validate_state_root;
+use reth_db_api::transaction::DbTxMut;
+use reth_primitives::SealedHeader;
+use reth_provider::{AccountExtReader, DBProvider, HeaderProvider, ProviderError, StorageReader};
+use reth_scroll_state_commitment::{
+    mpt::{unwind_mpt_state, update_mpt_state},
+    StateCommitmentSchemeProvider, MPT_STAGE_ID,
+};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+
+/// The stage keeping the keccak Merkle Patricia Trie committing to the Scroll state from the Euclid
+/// hardfork on up to date.
+///
+/// When the stage first runs with Euclid scheduled, it hashes the whole plain state into the keccak
+/// hashed state tables and builds the trie from them. After that, the tables are updated from the
+/// changesets of the executed blocks. The node runs the stage before validating any block if the
+/// tables are behind the persisted state, since the roots of the blocks from Euclid on are computed
+/// on top of them. From Euclid on, the root of the trie is checked against the
+/// state root of the target block, which the [`MerkleStage`](crate::stages::MerkleStage) only
+/// checks before Euclid.
+///
+/// The stage is a no-op if Euclid isn't scheduled.
+#[derive(Debug, Default, Clone, Copy)]
+#[non_exhaustive]
+pub struct ScrollMptMigrationStage;
+
+impl<Provider> Stage<Provider> for ScrollMptMigrationStage
+where
+    Provider: DBProvider<Tx: DbTxMut>
+        + HeaderProvider
+        + AccountExtReader
+        + StorageReader
+        + StateCommitmentSchemeProvider,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        MPT_STAGE_ID
+    }
+
+    /// Execute the stage.
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let target = input.target();
+        let header = provider
+            .header_by_number(target)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(target.into()))?;
+        if let Some(root) = update_mpt_state(provider, &header)? {
+            validate_state_root(root, SealedHeader::seal(header), target)?;
+        }
+
+        Ok(ExecOutput::done(StageCheckpoint::new(target)))
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let header = provider
+            .header_by_number(input.unwind_to)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(input.unwind_to.into()))?;
+        if let Some(root) = unwind_mpt_state(provider, &header)? {
+            validate_state_root(root, SealedHeader::seal(header), input.unwind_to)?;
+        }
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestStageDB;
+    use alloy_primitives::{keccak256, Address, BlockNumber, B256, U256};
+    use reth_chainspec::{ChainSpecBuilder, ForkCondition};
+    use reth_db::tables;
+    use reth_db_api::{
+        cursor::{DbCursorRO, DbDupCursorRO, DbDupCursorRW},
+        models::AccountBeforeTx,
+        transaction::DbTx,
+    };
+    use reth_primitives::{Account, Header, StorageEntry};
+    use reth_provider::{
+        test_utils::{create_test_provider_factory, create_test_provider_factory_with_chain_spec},
+        DatabaseProviderFactory, StageCheckpointWriter,
+    };
+    use reth_scroll_forks::ScrollHardfork;
+    use reth_scroll_state_commitment::mpt_state_root;
+    use reth_trie::HashedPostState;
+    use std::sync::Arc;
+
+    /// The timestamp of the Euclid hardfork, the blocks being 10 seconds apart.
+    const EUCLID_TIME: u64 = 20;
+
+    /// The changed accounts and storage slots of a block.
+    type Changes = Vec<(Address, Option<Account>, Vec<(B256, U256)>)>;
+
+    /// Applies the changes of the block to the plain state, recording the previous values in the
+    /// changesets.
+    fn apply_block<TX: DbTx + DbTxMut>(tx: &TX, block: BlockNumber, changes: Changes) {
+        let mut storages = tx.cursor_dup_write::<tables::PlainStorageState>().unwrap();
+        for (address, account, slots) in changes {
+            let info = tx.get::<tables::PlainAccountState>(address).unwrap();
+            tx.put::<tables::AccountChangeSets>(block, AccountBeforeTx { address, info }).unwrap();
+            match account {
+                Some(account) => tx.put::<tables::PlainAccountState>(address, account).unwrap(),
+                None => {
+                    tx.delete::<tables::PlainAccountState>(address, None).unwrap();
+                }
+            }
+
+            for (key, value) in slots {
+                let previous = storages
+                    .seek_by_key_subkey(address, key)
+                    .unwrap()
+                    .filter(|entry| entry.key == key);
+                if previous.is_some() {
+                    storages.delete_current().unwrap();
+                }
+                let previous = previous.map(|entry| entry.value).unwrap_or_default();
+                tx.put::<tables::StorageChangeSets>(
+                    (block, address).into(),
+                    StorageEntry { key, value: previous },
+                )
+                .unwrap();
+                if !value.is_zero() {
+                    storages.upsert(address, StorageEntry { key, value }).unwrap();
+                }
+            }
+        }
+    }
+
+    /// Returns the root of the keccak Merkle Patricia Trie of the plain state, built from scratch.
+    fn expected_root<TX: DbTx>(tx: &TX) -> B256 {
+        let mut state = HashedPostState::default();
+        for entry in tx.cursor_read::<tables::PlainAccountState>().unwrap().walk(None).unwrap() {
+            let (address, account) = entry.unwrap();
+            state.accounts.insert(keccak256(address), Some(account));
+        }
+        for entry in tx.cursor_read::<tables::PlainStorageState>().unwrap().walk(None).unwrap() {
+            let (address, StorageEntry { key, value }) = entry.unwrap();
+            let storage = state.storages.entry(keccak256(address)).or_default();
+            storage.storage.insert(keccak256(key), value);
+        }
+
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        mpt_state_root(provider.tx_ref(), &state).unwrap().0
+    }
+
+    #[test]
+    fn migrate_across_euclid() {
+        let chain_spec = ChainSpecBuilder::mainnet()
+            .with_fork(ScrollHardfork::Euclid, ForkCondition::Timestamp(EUCLID_TIME))
+            .build();
+        let factory = create_test_provider_factory_with_chain_spec(Arc::new(chain_spec));
+        let provider = factory.database_provider_rw().unwrap();
+        let tx = provider.tx_ref();
+        let mut stage = ScrollMptMigrationStage;
+
+        let (alice, bob, carol) =
+            (Address::with_last_byte(1), Address::with_last_byte(2), Address::with_last_byte(3));
+        let account = |nonce: u64| {
+            Some(Account { nonce, balance: U256::from(nonce * 100), ..Default::default() })
+        };
+        let slot = B256::with_last_byte;
+        let blocks: Vec<Changes> = vec![
+            vec![(alice, account(1), vec![(slot(1), U256::from(1)), (slot(2), U256::from(2))])],
+            vec![(bob, account(1), vec![(slot(1), U256::from(3))])],
+            vec![
+                (alice, account(2), vec![(slot(1), U256::ZERO), (slot(3), U256::from(4))]),
+                (carol, account(1), vec![]),
+            ],
+            vec![(bob, None, vec![(slot(1), U256::ZERO)]), (carol, account(2), vec![])],
+        ];
+
+        for (block, changes) in (0..).zip(blocks) {
+            apply_block(tx, block, changes);
+            let header = Header {
+                number: block,
+                timestamp: block * 10,
+                state_root: expected_root(tx),
+                ..Default::default()
+            };
+            TestStageDB::insert_header(None, tx, &SealedHeader::seal(header), U256::ZERO).unwrap();
+            if block == 0 {
+                continue
+            }
+
+            let input = ExecInput {
+                target: Some(block),
+                checkpoint: Some(StageCheckpoint::new(block - 1)),
+            };
+            assert_eq!(
+                stage.execute(&provider, input).unwrap(),
+                ExecOutput::done(StageCheckpoint::new(block))
+            );
+            provider.save_stage_checkpoint(MPT_STAGE_ID, StageCheckpoint::new(block)).unwrap();
+            // the state is migrated before Euclid is active, so that the trie is ready for it
+            let (root, _) = mpt_state_root(tx, &HashedPostState::default()).unwrap();
+            assert_eq!(root, expected_root(tx));
+        }
+
+        // the state after block 2 is committed to by the trie, rolled back from the changesets
+        let input =
+            UnwindInput { checkpoint: StageCheckpoint::new(3), unwind_to: 2, bad_block: None };
+        assert_eq!(
+            stage.unwind(&provider, input).unwrap(),
+            UnwindOutput { checkpoint: StageCheckpoint::new(2) }
+        );
+        assert_eq!(tx.entries::<tables::MptHashedAccounts>().unwrap(), 3);
+
+        // the state after block 1 isn't, but the trie is still kept up to date
+        let input =
+            UnwindInput { checkpoint: StageCheckpoint::new(2), unwind_to: 1, bad_block: None };
+        assert_eq!(
+            stage.unwind(&provider, input).unwrap(),
+            UnwindOutput { checkpoint: StageCheckpoint::new(1) }
+        );
+        assert_eq!(tx.entries::<tables::MptHashedAccounts>().unwrap(), 2);
+    }
+
+    #[test]
+    fn noop_without_euclid() {
+        let factory = create_test_provider_factory();
+        let provider = factory.database_provider_rw().unwrap();
+        let tx = provider.tx_ref();
+
+        let changes = vec![(Address::with_last_byte(1), Some(Account::default()), vec![])];
+        apply_block(tx, 1, changes);
+        let header = Header { number: 1, ..Default::default() };
+        TestStageDB::insert_header(None, tx, &SealedHeader::seal(header), U256::ZERO).unwrap();
+
+        let input = ExecInput { target: Some(1), checkpoint: Some(StageCheckpoint::new(0)) };
+        assert_eq!(
+            ScrollMptMigrationStage.execute(&provider, input).unwrap(),
+            ExecOutput::done(StageCheckpoint::new(1))
+        );
+        assert_eq!(tx.entries::<tables::MptHashedAccounts>().unwrap(), 0);
+    }
+}
@@ -12,6 +12,7 @@ use reth_primitives::{
     Account, Bytecode, Receipt, StorageEntry, TransactionSigned, TransactionSignedNoHash, TxType,
 };
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
+use reth_scroll_primitives::{CommittedBatch, FinalizedBatch, TxL1Message};
 use reth_stages_types::StageCheckpoint;
 use reth_trie_common::{StoredNibbles, StoredNibblesSubKey, *};
 use serde::{Deserialize, Serialize};
@@ -21,6 +22,7 @@ pub mod blocks;
 pub mod integer_list;
 pub mod sharded_key;
 pub mod storage_sharded_key;
+pub mod withdraw_trie;
 
 pub use accounts::*;
 pub use blocks::*;
@@ -28,6 +30,7 @@ pub use reth_db_models::{
     AccountBeforeTx, ClientVersion, StoredBlockBodyIndices, StoredBlockWithdrawals,
 };
 pub use sharded_key::ShardedKey;
+pub use withdraw_trie::WithdrawTrieNodeKey;
 
 /// Macro that implements [`Encode`] and [`Decode`] for uint types.
 macro_rules! impl_uints {
@@ -232,6 +235,9 @@ impl_compression_for_compact!(
     StageCheckpoint,
     PruneCheckpoint,
     ClientVersion,
+    TxL1Message,
+    CommittedBatch,
+    FinalizedBatch,
     // Non-DB
     GenesisAccount
 );
@@ -0,0 +1,65 @@
+//! Scroll withdraw trie related models and types.
+
+use crate::{
+    impl_fixed_arbitrary,
+    table::{Decode, Encode},
+    DatabaseError,
+};
+use serde::{Deserialize, Serialize};
+
+/// The position of a node in the Scroll withdraw trie: its height above the leaves, concatenated
+/// with its index at that height.
+///
+/// Since it's used as a key, it isn't compressed when encoding it.
+#[derive(
+    Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Ord, PartialOrd, Hash,
+)]
+pub struct WithdrawTrieNodeKey {
+    /// The height of the node, leaves being at height 0.
+    pub height: u8,
+    /// The index of the node at its height.
+    pub index: u64,
+}
+
+impl WithdrawTrieNodeKey {
+    /// Returns a new [`WithdrawTrieNodeKey`].
+    pub const fn new(height: u8, index: u64) -> Self {
+        Self { height, index }
+    }
+}
+
+impl Encode for WithdrawTrieNodeKey {
+    type Encoded = [u8; 9];
+
+    fn encode(self) -> Self::Encoded {
+        let mut buf = [0u8; 9];
+        buf[0] = self.height;
+        buf[1..].copy_from_slice(&self.index.to_be_bytes());
+        buf
+    }
+}
+
+impl Decode for WithdrawTrieNodeKey {
+    fn decode(value: &[u8]) -> Result<Self, DatabaseError> {
+        let (&height, index) = value.split_first().ok_or(DatabaseError::Decode)?;
+        let index = u64::from_be_bytes(index.try_into().map_err(|_| DatabaseError::Decode)?);
+        Ok(Self { height, index })
+    }
+}
+
+impl_fixed_arbitrary!((WithdrawTrieNodeKey, 9));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_withdraw_trie_node_key() {
+        let key = WithdrawTrieNodeKey::new(3, 0x0102);
+        let encoded = Encode::encode(key);
+        assert_eq!(encoded, [3, 0, 0, 0, 0, 0, 0, 1, 2]);
+
+        let decoded: WithdrawTrieNodeKey = Decode::decode(&encoded).unwrap();
+        assert_eq!(decoded, key);
+    }
+}
@@ -13,12 +13,12 @@ use reth_provider::{
     errors::provider::ProviderResult, providers::StaticFileWriter, writer::UnifiedStorageWriter,
     BlockHashReader, BlockNumReader, BundleStateInit, ChainSpecProvider, DBProvider,
     DatabaseProviderFactory, ExecutionOutcome, HashingWriter, HeaderProvider, HistoryWriter,
-    OriginalValuesKnown, ProviderError, RevertsInit, StageCheckpointWriter, StateWriter,
-    StaticFileProviderFactory, StorageLocation, TrieWriter,
+    OriginalValuesKnown, ProviderError, RevertsInit, StageCheckpointWriter,
+    StateCommitmentProvider, StateWriter, StaticFileProviderFactory, StorageLocation, TrieWriter,
 };
 use reth_stages_types::{StageCheckpoint, StageId};
-use reth_trie::{IntermediateStateRootState, StateRoot as StateRootComputer, StateRootProgress};
-use reth_trie_db::DatabaseStateRoot;
+use reth_trie::{IntermediateStateRootState, StateRootProgress};
+use reth_trie_db::{DatabaseStateRoot, StateCommitment};
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, io::BufRead};
 use tracing::{debug, error, info, trace};
@@ -58,6 +58,18 @@ pub enum InitDatabaseError {
     /// State root doesn't match the expected one.
     #[error("state root mismatch: {_0}")]
     StateRootMismatch(GotExpected<B256>),
+    /// Account extension from the state dump doesn't match the one computed from the account's
+    /// bytecode.
+    #[cfg(feature = "scroll")]
+    #[error("account extension mismatch for {address}: got {got:?}, expected {expected:?}")]
+    AccountExtensionMismatch {
+        /// The account's address.
+        address: Address,
+        /// Account extension from the state dump.
+        got: reth_scroll_primitives::AccountExtension,
+        /// Account extension computed from the account's bytecode.
+        expected: reth_scroll_primitives::AccountExtension,
+    },
 }
 
 impl From<DatabaseError> for InitDatabaseError {
@@ -357,6 +369,7 @@ where
         + HashingWriter
         + TrieWriter
         + StateWriter
+        + StateCommitmentProvider
         + AsRef<Provider>,
 {
     let block = provider_rw.last_block_number()?;
@@ -448,7 +461,11 @@ fn parse_accounts(
             break
         }
 
-        let GenesisAccountWithAddress { genesis_account, address } = serde_json::from_str(&line)?;
+        let account: GenesisAccountWithAddress = serde_json::from_str(&line)?;
+        #[cfg(feature = "scroll")]
+        account.verify_account_extension()?;
+
+        let GenesisAccountWithAddress { genesis_account, address, .. } = account;
         collector.insert(address, genesis_account)?;
 
         if !collector.is_empty() && collector.len() % AVERAGE_COUNT_ACCOUNTS_PER_GB_STATE_DUMP == 0
@@ -529,7 +546,7 @@ where
 /// database.
 fn compute_state_root<Provider>(provider: &Provider) -> eyre::Result<B256>
 where
-    Provider: DBProvider<Tx: DbTxMut> + TrieWriter,
+    Provider: DBProvider<Tx: DbTxMut> + TrieWriter + StateCommitmentProvider,
 {
     trace!(target: "reth::cli", "Computing state root");
 
@@ -538,10 +555,10 @@ where
     let mut total_flushed_updates = 0;
 
     loop {
-        match StateRootComputer::from_tx(tx)
-            .with_intermediate_state(intermediate_state)
-            .root_with_progress()?
-        {
+        match <Provider::StateCommitment as StateCommitment>::StateRoot::root_with_progress(
+            tx,
+            intermediate_state,
+        )? {
             StateRootProgress::Progress(state, _, updates) => {
                 let updated_len = provider.write_trie_updates(&updates)?;
                 total_flushed_updates += updated_len;
@@ -594,6 +611,46 @@ struct GenesisAccountWithAddress {
     genesis_account: GenesisAccount,
     /// The account's address.
     address: Address,
+    /// The size of the account's bytecode, as found in l2geth state dumps.
+    #[cfg(feature = "scroll")]
+    #[serde(default, rename = "codeSize", skip_serializing_if = "Option::is_none")]
+    code_size: Option<u64>,
+    /// The Poseidon hash of the account's bytecode, as found in l2geth state dumps.
+    #[cfg(feature = "scroll")]
+    #[serde(default, rename = "poseidonCodeHash", skip_serializing_if = "Option::is_none")]
+    poseidon_code_hash: Option<B256>,
+}
+
+#[cfg(feature = "scroll")]
+impl GenesisAccountWithAddress {
+    /// Checks the code size and Poseidon code hash of the account, when present in the state dump,
+    /// against the ones computed from the account's bytecode.
+    fn verify_account_extension(&self) -> Result<(), InitDatabaseError> {
+        use reth_scroll_primitives::{poseidon::POSEIDON_EMPTY, AccountExtension};
+
+        let code = self.genesis_account.code.clone().unwrap_or_default();
+        let expected = AccountExtension::from_bytecode(&code);
+        let got = AccountExtension::from((
+            self.code_size.unwrap_or(expected.code_size),
+            self.poseidon_code_hash.or(expected.poseidon_code_hash).unwrap_or(POSEIDON_EMPTY),
+        ));
+
+        if got != expected {
+            error!(target: "reth::cli",
+                address = %self.address,
+                ?got,
+                ?expected,
+                "Account extension from state dump does not match the account's bytecode"
+            );
+            return Err(InitDatabaseError::AccountExtensionMismatch {
+                address: self.address,
+                got,
+                expected,
+            })
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -733,4 +790,55 @@ mod tests {
             )],
         );
     }
+
+    #[cfg(feature = "scroll")]
+    #[test]
+    fn verify_l2geth_account_extension() {
+        use alloy_primitives::Bytes;
+        use reth_scroll_primitives::{poseidon::POSEIDON_EMPTY, AccountExtension};
+
+        // accounts without bytecode are dumped with the Poseidon hash of the empty code
+        let account: GenesisAccountWithAddress = serde_json::from_str(
+            r#"{
+                "balance": "1000",
+                "nonce": 1,
+                "poseidonCodeHash": "0x2098f5fb9e239eab3ceac3f27b81e481dc3124d55ffed523a839ee8446b64864",
+                "codeSize": 0,
+                "address": "0x0000000000000000000000000000000000000001"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(account.poseidon_code_hash, Some(POSEIDON_EMPTY));
+        assert_eq!(account.verify_account_extension(), Ok(()));
+
+        // accounts with bytecode are dumped with its size and Poseidon hash
+        let code = Bytes::from_static(&[0x60, 0x00, 0x60, 0x00, 0xf3]);
+        let extension = AccountExtension::from_bytecode(&code);
+        let account = |code_size: u64, poseidon_code_hash: B256| {
+            serde_json::from_str::<GenesisAccountWithAddress>(&format!(
+                r#"{{
+                    "balance": "0",
+                    "code": "{code}",
+                    "poseidonCodeHash": "{poseidon_code_hash}",
+                    "codeSize": {code_size},
+                    "address": "0x0000000000000000000000000000000000000001"
+                }}"#
+            ))
+            .unwrap()
+        };
+
+        let dumped = account(extension.code_size, extension.poseidon_code_hash.unwrap());
+        assert_eq!(dumped.genesis_account.code, Some(code.clone()));
+        assert_eq!(dumped.code_size, Some(extension.code_size));
+        assert_eq!(dumped.verify_account_extension(), Ok(()));
+
+        assert_eq!(
+            account(extension.code_size, POSEIDON_EMPTY).verify_account_extension(),
+            Err(InitDatabaseError::AccountExtensionMismatch {
+                address: Address::with_last_byte(1),
+                got: AccountExtension { code_size: extension.code_size, poseidon_code_hash: None },
+                expected: extension,
+            })
+        );
+    }
 }
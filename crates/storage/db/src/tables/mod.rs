@@ -27,13 +27,14 @@ use reth_db_api::{
         blocks::{HeaderHash, StoredBlockOmmers},
         storage_sharded_key::StorageShardedKey,
         AccountBeforeTx, ClientVersion, CompactU256, ShardedKey, StoredBlockBodyIndices,
-        StoredBlockWithdrawals,
+        StoredBlockWithdrawals, WithdrawTrieNodeKey,
     },
     table::{Decode, DupSort, Encode, Table},
 };
 use reth_primitives::{Account, Bytecode, Receipt, StorageEntry, TransactionSignedNoHash};
 use reth_primitives_traits::IntegerList;
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
+use reth_scroll_primitives::{CommittedBatch, FinalizedBatch, TxL1Message};
 use reth_stages_types::StageCheckpoint;
 use reth_trie_common::{BranchNodeCompact, StorageTrieEntry, StoredNibbles, StoredNibblesSubKey};
 use serde::{Deserialize, Serialize};
@@ -506,6 +507,83 @@ tables! {
         type Key = ChainStateKey;
         type Value = BlockNumber;
     }
+
+    /// Stores the L1 messages emitted by the Scroll `L1MessageQueue` contract, indexed by queue
+    /// index.
+    table L1Messages {
+        type Key = u64;
+        type Value = TxL1Message;
+    }
+
+    /// Stores the queue index following the last L1 message included in the chain up to each
+    /// Scroll block, by block number.
+    table NextL1MessageQueueIndices {
+        type Key = BlockNumber;
+        type Value = u64;
+    }
+
+    /// Stores the batches committed to the Scroll `ScrollChain` contract, indexed by batch index.
+    table CommittedBatches {
+        type Key = u64;
+        type Value = CommittedBatch;
+    }
+
+    /// Stores the batch finalizations of the Scroll `ScrollChain` contract, indexed by batch index.
+    table FinalizedBatches {
+        type Key = u64;
+        type Value = FinalizedBatch;
+    }
+
+    /// Stores the nodes of the complete subtrees of the Scroll withdraw trie, the leaves being the
+    /// hashes of the messages appended to the `L2MessageQueue` contract.
+    table WithdrawTrieNodes {
+        type Key = WithdrawTrieNodeKey;
+        type Value = B256;
+    }
+
+    /// Stores the index in the Scroll withdraw trie of each appended message, by message hash.
+    table WithdrawMessageIndices {
+        type Key = B256;
+        type Value = u64;
+    }
+
+    /// Stores the number of messages in the Scroll withdraw trie after each L2 block appending
+    /// messages to the `L2MessageQueue` contract.
+    table WithdrawTrieSizes {
+        type Key = BlockNumber;
+        type Value = u64;
+    }
+
+    /// Stores the current state of an [`Account`] indexed with `keccak256Address`, the keys of the
+    /// keccak Merkle Patricia Trie committing to the Scroll state from the Euclid hardfork on.
+    table MptHashedAccounts {
+        type Key = B256;
+        type Value = Account;
+    }
+
+    /// Stores the current storage values indexed with `keccak256Address` and hash of storage key
+    /// `keccak256key`, the keys of the keccak Merkle Patricia Trie committing to the Scroll state
+    /// from the Euclid hardfork on.
+    table MptHashedStorages {
+        type Key = B256;
+        type Value = StorageEntry;
+        type SubKey = B256;
+    }
+
+    /// Stores the branch nodes of the keccak Merkle Patricia Trie of the accounts, committing to
+    /// the Scroll state from the Euclid hardfork on.
+    table MptAccountsTrie {
+        type Key = StoredNibbles;
+        type Value = BranchNodeCompact;
+    }
+
+    /// From `keccak256Address` => NibblesSubKey => branch node of the keccak Merkle Patricia Trie
+    /// of the account's storage.
+    table MptStoragesTrie {
+        type Key = B256;
+        type Value = StorageTrieEntry;
+        type SubKey = StoredNibblesSubKey;
+    }
 }
 
 /// Keys for the `ChainState` table.
@@ -515,6 +593,12 @@ pub enum ChainStateKey {
     LastFinalizedBlock,
     /// Last finalized block key
     LastSafeBlockBlock,
+    /// Last L1 block indexed for the Scroll L1 message queue key
+    LastL1MessageQueueBlock,
+    /// Last L1 block indexed for the Scroll `ScrollChain` contract key
+    LastScrollChainBlock,
+    /// Last L2 block indexed for the Scroll withdraw trie key
+    LastWithdrawTrieBlock,
 }
 
 impl Encode for ChainStateKey {
@@ -524,6 +608,9 @@ impl Encode for ChainStateKey {
         match self {
             Self::LastFinalizedBlock => [0],
             Self::LastSafeBlockBlock => [1],
+            Self::LastL1MessageQueueBlock => [2],
+            Self::LastScrollChainBlock => [3],
+            Self::LastWithdrawTrieBlock => [4],
         }
     }
 }
@@ -533,6 +620,9 @@ impl Decode for ChainStateKey {
         match value {
             [0] => Ok(Self::LastFinalizedBlock),
             [1] => Ok(Self::LastSafeBlockBlock),
+            [2] => Ok(Self::LastL1MessageQueueBlock),
+            [3] => Ok(Self::LastScrollChainBlock),
+            [4] => Ok(Self::LastWithdrawTrieBlock),
             _ => Err(reth_db_api::DatabaseError::Decode),
         }
     }
@@ -21,11 +21,8 @@ use reth_storage_api::{
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
-    proof::{Proof, StorageProof},
-    updates::TrieUpdates,
-    witness::TrieWitness,
-    AccountProof, HashedPostState, HashedStorage, KeyHasher, MultiProof, StorageMultiProof,
-    StorageRoot, TrieInput,
+    proof::StorageProof, updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage,
+    KeyHasher, MultiProof, StorageMultiProof, StorageRoot, TrieInput,
 };
 use reth_trie_db::{
     DatabaseHashedPostState, DatabaseHashedStorage, DatabaseProof, DatabaseStateRoot,
@@ -391,8 +388,13 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateProof
         slots: &[B256],
     ) -> ProviderResult<AccountProof> {
         input.prepend(self.revert_state()?);
-        Proof::overlay_account_proof(self.tx(), input, address, slots)
-            .map_err(Into::<ProviderError>::into)
+        <Provider::StateCommitment as StateCommitment>::StateProof::overlay_account_proof(
+            self.tx(),
+            input,
+            address,
+            slots,
+        )
+        .map_err(Into::<ProviderError>::into)
     }
 
     fn multiproof(
@@ -401,7 +403,12 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateProof
         targets: HashMap<B256, HashSet<B256>>,
     ) -> ProviderResult<MultiProof> {
         input.prepend(self.revert_state()?);
-        Proof::overlay_multiproof(self.tx(), input, targets).map_err(Into::<ProviderError>::into)
+        <Provider::StateCommitment as StateCommitment>::StateProof::overlay_multiproof(
+            self.tx(),
+            input,
+            targets,
+        )
+        .map_err(Into::<ProviderError>::into)
     }
 
     fn witness(
@@ -410,7 +417,12 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateProof
         target: HashedPostState,
     ) -> ProviderResult<HashMap<B256, Bytes>> {
         input.prepend(self.revert_state()?);
-        TrieWitness::overlay_witness(self.tx(), input, target).map_err(Into::<ProviderError>::into)
+        <Provider::StateCommitment as StateCommitment>::StateWitness::overlay_witness(
+            self.tx(),
+            input,
+            target,
+        )
+        .map_err(Into::<ProviderError>::into)
     }
 }
 
@@ -15,11 +15,8 @@ use reth_storage_api::{
 };
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie::{
-    proof::{Proof, StorageProof},
-    updates::TrieUpdates,
-    witness::TrieWitness,
-    AccountProof, HashedPostState, HashedStorage, KeyHasher, MultiProof, StorageMultiProof,
-    StorageRoot, TrieInput,
+    proof::StorageProof, updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage,
+    KeyHasher, MultiProof, StorageMultiProof, StorageRoot, TrieInput,
 };
 use reth_trie_db::{
     DatabaseProof, DatabaseStateRoot, DatabaseStorageProof, DatabaseStorageRoot,
@@ -206,8 +203,13 @@ impl<Provider: DBProvider + StateCommitmentProvider> StateProofProvider
         address: Address,
         slots: &[B256],
     ) -> ProviderResult<AccountProof> {
-        Proof::overlay_account_proof(self.tx(), input, address, slots)
-            .map_err(Into::<ProviderError>::into)
+        <Provider::StateCommitment as StateCommitment>::StateProof::overlay_account_proof(
+            self.tx(),
+            input,
+            address,
+            slots,
+        )
+        .map_err(Into::<ProviderError>::into)
     }
 
     fn multiproof(
@@ -215,7 +217,12 @@ impl<Provider: DBProvider + StateCommitmentProvider> StateProofProvider
         input: TrieInput,
         targets: HashMap<B256, HashSet<B256>>,
     ) -> ProviderResult<MultiProof> {
-        Proof::overlay_multiproof(self.tx(), input, targets).map_err(Into::<ProviderError>::into)
+        <Provider::StateCommitment as StateCommitment>::StateProof::overlay_multiproof(
+            self.tx(),
+            input,
+            targets,
+        )
+        .map_err(Into::<ProviderError>::into)
     }
 
     fn witness(
@@ -223,7 +230,12 @@ impl<Provider: DBProvider + StateCommitmentProvider> StateProofProvider
         input: TrieInput,
         target: HashedPostState,
     ) -> ProviderResult<HashMap<B256, Bytes>> {
-        TrieWitness::overlay_witness(self.tx(), input, target).map_err(Into::<ProviderError>::into)
+        <Provider::StateCommitment as StateCommitment>::StateWitness::overlay_witness(
+            self.tx(),
+            input,
+            target,
+        )
+        .map_err(Into::<ProviderError>::into)
     }
 }
 
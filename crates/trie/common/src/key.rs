@@ -102,27 +102,66 @@ impl BitsCompatibility for Nibbles {
     }
 }
 
-/// Helper method to unpack into [`Nibbles`] from a byte slice.
+/// The encoding of the hashed keys of a trie into the paths of its nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyEncoding {
+    /// A nibble per path element, as in the keccak Merkle Patricia Trie.
+    Nibbles,
+    /// A bit per path element, as in the Scroll binary zktrie.
+    Bits,
+}
+
+impl KeyEncoding {
+    /// Unpacks the key into the path of its leaf.
+    pub fn unpack<T: AsRef<[u8]>>(self, data: T) -> Nibbles {
+        match self {
+            Self::Nibbles => Nibbles::unpack(data),
+            Self::Bits => Nibbles::unpack_bits(data),
+        }
+    }
+
+    /// Packs the path into a byte representation.
+    pub fn pack(self, path: &Nibbles) -> SmallVec<[u8; 32]> {
+        match self {
+            Self::Nibbles => path.pack(),
+            Self::Bits => path.pack_bits(),
+        }
+    }
+
+    /// Returns the path following the given one at the same depth, or `None` if it's the last.
+    pub fn increment(self, path: &Nibbles) -> Option<Nibbles> {
+        match self {
+            Self::Nibbles => path.increment(),
+            Self::Bits => path.increment_bit(),
+        }
+    }
+}
+
+impl Default for KeyEncoding {
+    /// Returns [`KeyEncoding::Bits`] for the `scroll` feature, and [`KeyEncoding::Nibbles`]
+    /// otherwise.
+    fn default() -> Self {
+        if cfg!(feature = "scroll") {
+            Self::Bits
+        } else {
+            Self::Nibbles
+        }
+    }
+}
+
+/// Helper method to unpack into [`Nibbles`] from a byte slice with the default [`KeyEncoding`].
 ///
 /// For the `scroll` feature, this method will unpack the bits from the provided bytes such that
 /// there is a byte for each bit in the input. The representation is big-endian with respect to the
 /// input. When the `scroll` feature is not enabled, this method will unpack the bytes into nibbles.
 pub fn unpack_nibbles<T: AsRef<[u8]>>(data: T) -> Nibbles {
-    #[cfg(feature = "scroll")]
-    let nibbles = Nibbles::unpack_bits(data);
-    #[cfg(not(feature = "scroll"))]
-    let nibbles = Nibbles::unpack(data);
-    nibbles
+    KeyEncoding::default().unpack(data)
 }
 
-/// Helper method to pack into a byte slice from [`Nibbles`].
+/// Helper method to pack into a byte slice from [`Nibbles`] with the default [`KeyEncoding`].
 ///
 /// For the `scroll` feature, this method will pack the bits into a byte representation. When the
 /// `scroll` feature is not enabled, this method will pack the nibbles into bytes.
 pub fn pack_nibbles(nibbles: &Nibbles) -> SmallVec<[u8; 32]> {
-    #[cfg(feature = "scroll")]
-    let packed = nibbles.pack_bits();
-    #[cfg(not(feature = "scroll"))]
-    let packed = nibbles.pack();
-    packed
+    KeyEncoding::default().pack(nibbles)
 }
@@ -19,7 +19,9 @@ mod account;
 pub use account::TrieAccount;
 
 mod key;
-pub use key::{pack_nibbles, unpack_nibbles, BitsCompatibility, KeccakKeyHasher, KeyHasher};
+pub use key::{
+    pack_nibbles, unpack_nibbles, BitsCompatibility, KeccakKeyHasher, KeyEncoding, KeyHasher,
+};
 
 mod nibbles;
 pub use nibbles::{Nibbles, StoredNibbles, StoredNibblesSubKey};
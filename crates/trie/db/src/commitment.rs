@@ -6,6 +6,7 @@ use reth_db::transaction::DbTx;
 use reth_trie::{
     proof::Proof, witness::TrieWitness, KeccakKeyHasher, KeyHasher, StateRoot, StorageRoot,
 };
+use reth_trie_sparse::{SparseStateTrie, SparseStateTrieInterface};
 
 /// The `StateCommitment` trait provides associated types for state commitment operations.
 pub trait StateCommitment: std::fmt::Debug + Send + Sync + Unpin + 'static {
@@ -19,6 +20,8 @@ pub trait StateCommitment: std::fmt::Debug + Send + Sync + Unpin + 'static {
     type StateWitness<'a, TX: DbTx + 'a>: DatabaseTrieWitness<'a, TX>;
     /// The key hasher type.
     type KeyHasher: KeyHasher;
+    /// The sparse state trie type.
+    type SparseStateTrie: SparseStateTrieInterface;
 }
 
 /// The state commitment type for Ethereum's Merkle Patricia Trie.
@@ -36,4 +39,5 @@ impl StateCommitment for MerklePatriciaTrie {
     type StateWitness<'a, TX: DbTx + 'a> =
         TrieWitness<DatabaseTrieCursorFactory<'a, TX>, DatabaseHashedCursorFactory<'a, TX>>;
     type KeyHasher = KeccakKeyHasher;
+    type SparseStateTrie = SparseStateTrie;
 }
@@ -26,6 +26,9 @@ pub enum SparseStateTrieError {
     /// RLP error.
     #[error(transparent)]
     Rlp(#[from] alloy_rlp::Error),
+    /// Other error, e.g. failure to decode the nodes of a trie with a different node encoding.
+    #[error(transparent)]
+    Other(Box<dyn std::error::Error + Send + Sync>),
 }
 
 /// Result type with [`SparseTrieError`] as error.
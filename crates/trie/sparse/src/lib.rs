@@ -6,5 +6,8 @@ pub use state::*;
 mod trie;
 pub use trie::*;
 
+mod traits;
+pub use traits::*;
+
 mod errors;
 pub use errors::*;
@@ -0,0 +1,114 @@
+use crate::{SparseStateTrie, SparseStateTrieResult, SparseTrieError};
+use alloy_primitives::{
+    map::{HashMap, HashSet},
+    B256, U256,
+};
+use reth_primitives_traits::Account;
+use reth_trie_common::{updates::TrieUpdates, MultiProof, Nibbles};
+use std::fmt::Debug;
+
+/// A sparse state trie which can be revealed from multiproofs and updated with the post state of
+/// the executed transactions.
+///
+/// The trait abstracts over the key hashing as well as the node encoding and hashing scheme of the
+/// state trie, allowing the state root task to operate on any state commitment. The keys passed
+/// to the trie are already hashed with the key hasher of the state commitment.
+pub trait SparseStateTrieInterface: Debug + Default + Send + 'static {
+    /// Set the retention of branch node updates and deletions.
+    fn with_updates(self, retain_updates: bool) -> Self;
+
+    /// Reveal unknown trie paths from multiproof and the list of included accounts and slots.
+    fn reveal_multiproof(
+        &mut self,
+        targets: HashMap<B256, HashSet<B256>>,
+        multiproof: MultiProof,
+    ) -> SparseStateTrieResult<()>;
+
+    /// Wipe the revealed storage trie at the provided hashed address.
+    fn wipe_storage(&mut self, address: B256) -> SparseStateTrieResult<()>;
+
+    /// Update the value of the storage slot in the revealed storage trie at the provided hashed
+    /// address. Zero values remove the slot from the trie.
+    fn update_storage_slot(
+        &mut self,
+        address: B256,
+        slot: B256,
+        value: U256,
+    ) -> SparseStateTrieResult<()>;
+
+    /// Returns storage sparse trie root if the trie has been revealed.
+    fn storage_root(&mut self, address: B256) -> Option<B256>;
+
+    /// Update or remove trie account based on new account info.
+    ///
+    /// If the new account info and storage trie are empty, the account leaf will be removed.
+    fn update_account(&mut self, address: B256, account: Account) -> SparseStateTrieResult<()>;
+
+    /// Calculates the hashes of the nodes below the provided level.
+    fn calculate_below_level(&mut self, level: usize);
+
+    /// Returns sparse trie root if the trie has been revealed.
+    fn root(&mut self) -> Option<B256>;
+
+    /// Returns [`TrieUpdates`] by taking the updates from the revealed sparse tries.
+    ///
+    /// Returns `None` if the accounts trie is not revealed.
+    fn take_trie_updates(&mut self) -> Option<TrieUpdates>;
+}
+
+impl SparseStateTrieInterface for SparseStateTrie {
+    fn with_updates(self, retain_updates: bool) -> Self {
+        Self::with_updates(self, retain_updates)
+    }
+
+    fn reveal_multiproof(
+        &mut self,
+        targets: HashMap<B256, HashSet<B256>>,
+        multiproof: MultiProof,
+    ) -> SparseStateTrieResult<()> {
+        Self::reveal_multiproof(self, targets, multiproof)
+    }
+
+    fn wipe_storage(&mut self, address: B256) -> SparseStateTrieResult<()> {
+        self.storage_trie_mut(&address).ok_or(SparseTrieError::Blind)?.wipe();
+        Ok(())
+    }
+
+    fn update_storage_slot(
+        &mut self,
+        address: B256,
+        slot: B256,
+        value: U256,
+    ) -> SparseStateTrieResult<()> {
+        let storage_trie = self.storage_trie_mut(&address).ok_or(SparseTrieError::Blind)?;
+        let slot_nibbles = Nibbles::unpack(slot);
+        if value.is_zero() {
+            // TODO: handle blinded node error
+            storage_trie.remove_leaf(&slot_nibbles)?;
+        } else {
+            storage_trie
+                .update_leaf(slot_nibbles, alloy_rlp::encode_fixed_size(&value).to_vec())?;
+        }
+        Ok(())
+    }
+
+    fn storage_root(&mut self, address: B256) -> Option<B256> {
+        Self::storage_root(self, address)
+    }
+
+    fn update_account(&mut self, address: B256, account: Account) -> SparseStateTrieResult<()> {
+        Self::update_account(self, address, account)
+    }
+
+    fn calculate_below_level(&mut self, level: usize) {
+        Self::calculate_below_level(self, level)
+    }
+
+    fn root(&mut self) -> Option<B256> {
+        Self::root(self)
+    }
+
+    fn take_trie_updates(&mut self) -> Option<TrieUpdates> {
+        Self::take_trie_updates(self)
+    }
+}
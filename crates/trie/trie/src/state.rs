@@ -6,7 +6,7 @@ use alloy_primitives::{
 use itertools::Itertools;
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 use reth_primitives::Account;
-use reth_trie_common::{unpack_nibbles, KeyHasher};
+use reth_trie_common::{KeyEncoding, KeyHasher};
 use revm::db::{states::CacheAccount, AccountStatus, BundleAccount};
 use std::borrow::Cow;
 
@@ -111,12 +111,20 @@ impl HashedPostState {
     /// The prefix sets contain the hashed account and storage keys that have been changed in the
     /// post state.
     pub fn construct_prefix_sets(&self) -> TriePrefixSetsMut {
+        self.construct_prefix_sets_with_key_encoding(KeyEncoding::default())
+    }
+
+    /// Construct [`TriePrefixSetsMut`] from hashed post state, the keys being encoded into paths
+    /// with the given [`KeyEncoding`].
+    pub fn construct_prefix_sets_with_key_encoding(
+        &self,
+        key_encoding: KeyEncoding,
+    ) -> TriePrefixSetsMut {
         // Populate account prefix set.
         let mut account_prefix_set = PrefixSetMut::with_capacity(self.accounts.len());
         let mut destroyed_accounts = HashSet::default();
         for (hashed_address, account) in &self.accounts {
-            // TODO(scroll): replace this with abstraction.
-            account_prefix_set.insert(unpack_nibbles(hashed_address));
+            account_prefix_set.insert(key_encoding.unpack(hashed_address));
 
             if account.is_none() {
                 destroyed_accounts.insert(*hashed_address);
@@ -127,9 +135,11 @@ impl HashedPostState {
         let mut storage_prefix_sets =
             HashMap::with_capacity_and_hasher(self.storages.len(), Default::default());
         for (hashed_address, hashed_storage) in &self.storages {
-            // TODO(scroll): replace this with abstraction.
-            account_prefix_set.insert(unpack_nibbles(hashed_address));
-            storage_prefix_sets.insert(*hashed_address, hashed_storage.construct_prefix_set());
+            account_prefix_set.insert(key_encoding.unpack(hashed_address));
+            storage_prefix_sets.insert(
+                *hashed_address,
+                hashed_storage.construct_prefix_set_with_key_encoding(key_encoding),
+            );
         }
 
         TriePrefixSetsMut { account_prefix_set, storage_prefix_sets, destroyed_accounts }
@@ -247,12 +257,21 @@ impl HashedStorage {
 
     /// Construct [`PrefixSetMut`] from hashed storage.
     pub fn construct_prefix_set(&self) -> PrefixSetMut {
+        self.construct_prefix_set_with_key_encoding(KeyEncoding::default())
+    }
+
+    /// Construct [`PrefixSetMut`] from hashed storage, the keys being encoded into paths with the
+    /// given [`KeyEncoding`].
+    pub fn construct_prefix_set_with_key_encoding(
+        &self,
+        key_encoding: KeyEncoding,
+    ) -> PrefixSetMut {
         if self.wiped {
             PrefixSetMut::all()
         } else {
             let mut prefix_set = PrefixSetMut::with_capacity(self.storage.len());
             for hashed_slot in self.storage.keys() {
-                prefix_set.insert(unpack_nibbles(hashed_slot));
+                prefix_set.insert(key_encoding.unpack(hashed_slot));
             }
             prefix_set
         }
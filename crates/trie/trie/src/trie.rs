@@ -7,7 +7,7 @@ use crate::{
     trie_cursor::TrieCursorFactory,
     updates::{StorageTrieUpdates, TrieUpdates},
     walker::TrieWalker,
-    HashBuilder, Nibbles, TrieAccount, TRIE_ACCOUNT_RLP_MAX_SIZE,
+    HashBuilder, KeyEncoding, Nibbles, TrieAccount, TRIE_ACCOUNT_RLP_MAX_SIZE,
 };
 use alloy_consensus::EMPTY_ROOT_HASH;
 use alloy_primitives::{keccak256, Address, B256};
@@ -19,6 +19,9 @@ use tracing::trace;
 use crate::metrics::{StateRootMetrics, TrieRootMetrics};
 
 /// `StateRoot` is used to compute the root node of a state trie.
+///
+/// The trie is a keccak Merkle Patricia Trie whatever the features, its paths being the nibbles of
+/// the hashed keys, so the prefix sets must be built with [`KeyEncoding::Nibbles`].
 #[derive(Debug)]
 pub struct StateRoot<T, H> {
     /// The factory for trie cursors.
@@ -164,6 +167,7 @@ where
                     state.walker_stack,
                     self.prefix_sets.account_prefix_set,
                 )
+                .with_key_encoding(KeyEncoding::Nibbles)
                 .with_deletions_retained(retain_updates);
                 let node_iter = TrieNodeIter::new(walker, hashed_account_cursor)
                     .with_last_hashed_key(state.last_account_key);
@@ -172,6 +176,7 @@ where
             None => {
                 let hash_builder = HashBuilder::default().with_updates(retain_updates);
                 let walker = TrieWalker::new(trie_cursor, self.prefix_sets.account_prefix_set)
+                    .with_key_encoding(KeyEncoding::Nibbles)
                     .with_deletions_retained(retain_updates);
                 let node_iter = TrieNodeIter::new(walker, hashed_account_cursor);
                 (hash_builder, node_iter)
@@ -406,8 +411,9 @@ where
 
         let mut tracker = TrieTracker::default();
         let trie_cursor = self.trie_cursor_factory.storage_trie_cursor(self.hashed_address)?;
-        let walker =
-            TrieWalker::new(trie_cursor, self.prefix_set).with_deletions_retained(retain_updates);
+        let walker = TrieWalker::new(trie_cursor, self.prefix_set)
+            .with_key_encoding(KeyEncoding::Nibbles)
+            .with_deletions_retained(retain_updates);
 
         let mut hash_builder = HashBuilder::default().with_updates(retain_updates);
 
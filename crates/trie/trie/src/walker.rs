@@ -5,14 +5,11 @@ use crate::{
 };
 use alloy_primitives::{map::HashSet, B256};
 use reth_storage_errors::db::DatabaseError;
-use reth_trie_common::pack_nibbles;
+use reth_trie_common::KeyEncoding;
 
 #[cfg(feature = "metrics")]
 use crate::metrics::WalkerMetrics;
 
-#[cfg(feature = "scroll")]
-use crate::BitsCompatibility;
-
 /// `TrieWalker` is a structure that enables traversal of a Merkle trie.
 /// It allows moving through the trie in a depth-first manner, skipping certain branches
 /// if they have not changed.
@@ -30,6 +27,8 @@ pub struct TrieWalker<C> {
     pub changes: PrefixSet,
     /// The retained trie node keys that need to be removed.
     removed_keys: Option<HashSet<Nibbles>>,
+    /// The encoding of the hashed keys into the paths of the trie nodes.
+    key_encoding: KeyEncoding,
     #[cfg(feature = "metrics")]
     /// Walker metrics.
     metrics: WalkerMetrics,
@@ -44,6 +43,7 @@ impl<C> TrieWalker<C> {
             stack,
             can_skip_current_node: false,
             removed_keys: None,
+            key_encoding: KeyEncoding::default(),
             #[cfg(feature = "metrics")]
             metrics: WalkerMetrics::default(),
         };
@@ -59,6 +59,18 @@ impl<C> TrieWalker<C> {
         self
     }
 
+    /// Sets the encoding of the hashed keys into the paths of the trie nodes, which defaults to
+    /// [`KeyEncoding::default`].
+    pub const fn with_key_encoding(mut self, key_encoding: KeyEncoding) -> Self {
+        self.key_encoding = key_encoding;
+        self
+    }
+
+    /// Returns the encoding of the hashed keys into the paths of the trie nodes.
+    pub const fn key_encoding(&self) -> KeyEncoding {
+        self.key_encoding
+    }
+
     /// Split the walker into stack and trie updates.
     pub fn split(mut self) -> (Vec<CursorSubNode>, HashSet<Nibbles>) {
         let keys = self.take_removed_keys();
@@ -104,14 +116,9 @@ impl<C> TrieWalker<C> {
         self.key()
             .and_then(|key| {
                 if self.can_skip_current_node {
-                    // TODO(scroll): replace this with key abstraction.
-                    #[cfg(not(feature = "scroll"))]
-                    let key = key.increment().map(|inc| inc.pack());
-                    #[cfg(feature = "scroll")]
-                    let key = key.increment_bit().map(|inc| inc.pack_bits());
-                    key
+                    self.key_encoding.increment(key).map(|inc| self.key_encoding.pack(&inc))
                 } else {
-                    Some(pack_nibbles(key))
+                    Some(self.key_encoding.pack(key))
                 }
             })
             .map(|mut key| {
@@ -139,6 +146,7 @@ impl<C: TrieCursor> TrieWalker<C> {
             stack: vec![CursorSubNode::default()],
             can_skip_current_node: false,
             removed_keys: None,
+            key_encoding: KeyEncoding::default(),
             #[cfg(feature = "metrics")]
             metrics: WalkerMetrics::default(),
         };
@@ -7,6 +7,8 @@ use crate::{
 use alloy_rlp::Decodable;
 use rayon::iter::{ParallelBridge, ParallelIterator};
 use reth_chainspec::ChainSpec;
+use reth_evm::execute::BlockExecutorProvider;
+use reth_evm_ethereum::execute::EthExecutorProvider;
 use reth_primitives::{BlockBody, SealedBlock, StaticFileSegment};
 use reth_provider::{
     providers::StaticFileWriter, test_utils::create_test_provider_factory_with_chain_spec,
@@ -83,86 +85,97 @@ impl Case for BlockchainTestCase {
             })
             .par_bridge()
             .try_for_each(|case| {
-                // Create a new test database and initialize a provider for the test case.
                 let chain_spec: Arc<ChainSpec> = Arc::new(case.network.into());
-                let provider = create_test_provider_factory_with_chain_spec(chain_spec.clone())
-                    .database_provider_rw()
-                    .unwrap();
-
-                // Insert initial test state into the provider.
-                provider.insert_historical_block(
-                    SealedBlock::new(
-                        case.genesis_block_header.clone().into(),
-                        BlockBody::default(),
-                    )
-                    .try_seal_with_senders()
-                    .unwrap(),
-                )?;
-                case.pre.write_to_db(provider.tx_ref())?;
-
-                // Initialize receipts static file with genesis
-                {
-                    let static_file_provider = provider.static_file_provider();
-                    let mut receipts_writer =
-                        static_file_provider.latest_writer(StaticFileSegment::Receipts).unwrap();
-                    receipts_writer.increment_block(0).unwrap();
-                    receipts_writer.commit_without_sync_all().unwrap();
-                }
-
-                // Decode and insert blocks, creating a chain of blocks for the test case.
-                let last_block = case.blocks.iter().try_fold(None, |_, block| {
-                    let decoded = SealedBlock::decode(&mut block.rlp.as_ref())?;
-                    provider.insert_historical_block(
-                        decoded.clone().try_seal_with_senders().unwrap(),
-                    )?;
-                    Ok::<Option<SealedBlock>, Error>(Some(decoded))
-                })?;
-                provider
-                    .static_file_provider()
-                    .latest_writer(StaticFileSegment::Headers)
-                    .unwrap()
-                    .commit_without_sync_all()
-                    .unwrap();
-
-                // Execute the execution stage using the EVM processor factory for the test case
-                // network.
-                let _ = ExecutionStage::new_with_executor(
-                    reth_evm_ethereum::execute::EthExecutorProvider::ethereum(chain_spec),
-                )
-                .execute(
-                    &provider,
-                    ExecInput { target: last_block.as_ref().map(|b| b.number), checkpoint: None },
-                );
-
-                // Validate the post-state for the test case.
-                match (&case.post_state, &case.post_state_hash) {
-                    (Some(state), None) => {
-                        // Validate accounts in the state against the provider's database.
-                        for (&address, account) in state {
-                            account.assert_db(address, provider.tx_ref())?;
-                        }
-                    }
-                    (None, Some(expected_state_root)) => {
-                        // Insert state hashes into the provider based on the expected state root.
-                        let last_block = last_block.unwrap_or_default();
-                        provider.insert_hashes(
-                            0..=last_block.number,
-                            last_block.hash(),
-                            *expected_state_root,
-                        )?;
-                    }
-                    _ => return Err(Error::MissingPostState),
-                }
-
-                // Drop the provider without committing to the database.
-                drop(provider);
-                Ok(())
+                run_test(case, chain_spec.clone(), EthExecutorProvider::ethereum(chain_spec))
             })?;
 
         Ok(())
     }
 }
 
+/// Runs a single blockchain test with the given chain spec and executor provider, and validates
+/// its post state.
+pub(crate) fn run_test<E>(
+    case: &BlockchainTest,
+    chain_spec: Arc<ChainSpec>,
+    executor_provider: E,
+) -> Result<(), Error>
+where
+    E: BlockExecutorProvider,
+{
+    // Create a new test database and initialize a provider for the test case.
+    let provider =
+        create_test_provider_factory_with_chain_spec(chain_spec).database_provider_rw().unwrap();
+
+    // Insert initial test state into the provider.
+    provider.insert_historical_block(
+        SealedBlock::new(case.genesis_block_header.clone().into(), BlockBody::default())
+            .try_seal_with_senders()
+            .unwrap(),
+    )?;
+    case.pre.write_to_db(provider.tx_ref())?;
+
+    // Initialize receipts static file with genesis
+    {
+        let static_file_provider = provider.static_file_provider();
+        let mut receipts_writer =
+            static_file_provider.latest_writer(StaticFileSegment::Receipts).unwrap();
+        receipts_writer.increment_block(0).unwrap();
+        receipts_writer.commit_without_sync_all().unwrap();
+    }
+
+    // Decode and insert blocks, creating a chain of blocks for the test case.
+    let last_block = case.blocks.iter().try_fold(None, |_, block| {
+        let decoded = SealedBlock::decode(&mut block.rlp.as_ref())?;
+        provider.insert_historical_block(decoded.clone().try_seal_with_senders().unwrap())?;
+        Ok::<Option<SealedBlock>, Error>(Some(decoded))
+    })?;
+    provider
+        .static_file_provider()
+        .latest_writer(StaticFileSegment::Headers)
+        .unwrap()
+        .commit_without_sync_all()
+        .unwrap();
+
+    // Execute the execution stage using the given executor provider for the test case network.
+    let result = ExecutionStage::new_with_executor(executor_provider).execute(
+        &provider,
+        ExecInput { target: last_block.as_ref().map(|b| b.number), checkpoint: None },
+    );
+
+    // Only a test with a block expected to be rejected may fail execution. Such a test may also
+    // succeed, as the execution stage doesn't check everything the block may be rejected for.
+    if let Err(error) = result {
+        if case.blocks.iter().all(|block| block.expect_exception.is_none()) {
+            return Err(Error::Assertion(format!("unexpected execution error: {error}")))
+        }
+    }
+
+    // Validate the post-state for the test case.
+    match (&case.post_state, &case.post_state_hash) {
+        (Some(state), None) => {
+            // Validate accounts in the state against the provider's database.
+            for (&address, account) in state {
+                account.assert_db(address, provider.tx_ref())?;
+            }
+        }
+        (None, Some(expected_state_root)) => {
+            // Insert state hashes into the provider based on the expected state root.
+            let last_block = last_block.unwrap_or_default();
+            provider.insert_hashes(
+                0..=last_block.number,
+                last_block.hash(),
+                *expected_state_root,
+            )?;
+        }
+        _ => return Err(Error::MissingPostState),
+    }
+
+    // Drop the provider without committing to the database.
+    drop(provider);
+    Ok(())
+}
+
 /// Returns whether the test at the given path should be skipped.
 ///
 /// Some tests are edge cases that cannot happen on mainnet, while others are skipped for
@@ -1,3 +1,7 @@
 //! Specific test case handler implementations.
 
 pub mod blockchain_test;
+#[cfg(feature = "scroll")]
+pub mod scroll_block_test;
+#[cfg(feature = "scroll")]
+pub mod scroll_state_test;
@@ -0,0 +1,431 @@
+//! Test runners for ranges of Scroll blocks recorded from l2geth.
+//!
+//! Each test replays the blocks on top of the recorded pre-state with the
+//! [`ScrollExecutionStrategy`](reth_scroll_evm::ScrollExecutionStrategy) and compares the roots of
+//! the [`BinaryMerklePatriciaTrie`] and the receipts roots against the ones reported by l2geth. The
+//! first diverging block fails the test, along with a per-account diff of its state changes.
+//!
+//! A test file maps the names of its tests to their fields, which can be recorded from l2geth:
+//!
+//! - `pre`: the full state before the first block, as dumped by l2geth.
+//! - `blocks[].rlp`: the RLP encoded block, as returned by `debug_getRawBlock`.
+//! - `blocks[].stateRoot` and `blocks[].receiptsRoot`: the roots returned by
+//!   `eth_getBlockByNumber`.
+//! - `blocks[].post`: optionally, the `post` state returned by `debug_traceBlockByNumber` with the
+//!   `prestateTracer` in diff mode.
+
+use crate::{Case, Error, Suite};
+use alloy_genesis::{Genesis, GenesisAccount};
+use alloy_primitives::{keccak256, map::HashMap, Address, Bytes, B256, U256};
+use alloy_rlp::Decodable;
+use reth_chainspec::Hardforks;
+use reth_db::test_utils::create_test_rw_db;
+use reth_db_api::database::Database;
+use reth_evm::execute::{BlockExecutorProvider, Executor};
+use reth_primitives::{
+    proofs::calculate_receipt_root_ref, Account, Receipt, SealedBlock, SealedBlockWithSenders,
+};
+use reth_revm::{
+    db::{states::StorageSlot, BundleAccount, BundleState},
+    primitives::AccountInfo,
+    test_utils::StateProviderTest,
+};
+use reth_scroll_chainspec::{ScrollChainSpec, SCROLL_DEV, SCROLL_MAINNET, SCROLL_SEPOLIA};
+use reth_scroll_evm::ScrollExecutorProvider;
+use reth_scroll_forks::{ScrollHardfork, ScrollHardforks};
+use reth_scroll_state_commitment::{BinaryMerklePatriciaTrie, PoseidonKeyHasher};
+use reth_scroll_storage::ScrollStateProviderDatabase;
+use reth_trie::{HashedPostState, HashedStorage, KeyHasher};
+use reth_trie_db::{DatabaseStateRoot, StateCommitment};
+use serde::Deserialize;
+use std::{collections::BTreeMap, fmt::Write, fs, path::Path, sync::Arc};
+
+/// The state root of the [`BinaryMerklePatriciaTrie`] over a database transaction.
+type StateRoot<'a, TX> = <BinaryMerklePatriciaTrie as StateCommitment>::StateRoot<'a, TX>;
+
+/// A handler for the Scroll block test suite.
+#[derive(Debug)]
+pub struct ScrollBlockTests {
+    suite: String,
+}
+
+impl ScrollBlockTests {
+    /// Create a new handler for a subset of the Scroll block test suite.
+    pub const fn new(suite: String) -> Self {
+        Self { suite }
+    }
+}
+
+impl Suite for ScrollBlockTests {
+    type Case = ScrollBlockTestCase;
+
+    fn tests_dir(&self) -> &'static str {
+        "scroll-tests"
+    }
+
+    fn suite_name(&self) -> String {
+        format!("ScrollBlockTests/{}", self.suite)
+    }
+}
+
+/// A range of Scroll blocks recorded from l2geth.
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScrollBlockTest {
+    /// The chain of the blocks.
+    pub chain: ScrollChain,
+    /// The full state before the first block.
+    pub pre: BTreeMap<Address, GenesisAccount>,
+    /// The blocks, in order.
+    pub blocks: Vec<ScrollBlock>,
+}
+
+/// The chain of a [`ScrollBlockTest`].
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+pub enum ScrollChain {
+    /// Scroll mainnet.
+    #[serde(rename = "scroll-mainnet")]
+    Mainnet,
+    /// Scroll Sepolia.
+    #[serde(rename = "scroll-sepolia")]
+    Sepolia,
+    /// The Scroll dev chain.
+    #[serde(rename = "dev")]
+    Dev,
+    /// A custom chain described by its genesis, e.g. an l2geth devnet.
+    #[serde(rename = "genesis")]
+    Genesis(Box<Genesis>),
+}
+
+impl From<&ScrollChain> for Arc<ScrollChainSpec> {
+    fn from(chain: &ScrollChain) -> Self {
+        match chain {
+            ScrollChain::Mainnet => SCROLL_MAINNET.clone(),
+            ScrollChain::Sepolia => SCROLL_SEPOLIA.clone(),
+            ScrollChain::Dev => SCROLL_DEV.clone(),
+            ScrollChain::Genesis(genesis) => Arc::new((**genesis).clone().into()),
+        }
+    }
+}
+
+/// A block of a [`ScrollBlockTest`], along with the roots reported by l2geth.
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScrollBlock {
+    /// RLP encoded block bytes.
+    pub rlp: Bytes,
+    /// The state root reported by l2geth.
+    pub state_root: B256,
+    /// The receipts root reported by l2geth.
+    pub receipts_root: B256,
+    /// The accounts changed by the block, with their changed fields after the block.
+    pub post: Option<BTreeMap<Address, ScrollPostAccount>>,
+}
+
+/// The changed fields of an account after a block, as reported by the l2geth `prestateTracer`.
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+pub struct ScrollPostAccount {
+    /// Balance.
+    pub balance: Option<U256>,
+    /// Nonce.
+    pub nonce: Option<u64>,
+    /// Code.
+    pub code: Option<Bytes>,
+    /// Storage.
+    #[serde(default)]
+    pub storage: BTreeMap<B256, B256>,
+}
+
+/// A Scroll block test.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ScrollBlockTestCase {
+    tests: BTreeMap<String, ScrollBlockTest>,
+}
+
+impl Case for ScrollBlockTestCase {
+    fn load(path: &Path) -> Result<Self, Error> {
+        Ok(Self {
+            tests: {
+                let s = fs::read_to_string(path)
+                    .map_err(|error| Error::Io { path: path.into(), error })?;
+                serde_json::from_str(&s)
+                    .map_err(|error| Error::CouldNotDeserialize { path: path.into(), error })?
+            },
+        })
+    }
+
+    /// Executes the blocks of each test, failing at the first block whose state root or receipts
+    /// root diverges from l2geth.
+    fn run(&self) -> Result<(), Error> {
+        self.tests.iter().try_for_each(|(name, test)| {
+            test.run().map_err(|err| match err {
+                Error::Assertion(msg) => Error::Assertion(format!("{name}: {msg}")),
+                err => err,
+            })
+        })
+    }
+}
+
+impl ScrollBlockTest {
+    /// Executes the blocks on top of the pre-state, comparing the roots after each block.
+    fn run(&self) -> Result<(), Error> {
+        let chain_spec: Arc<ScrollChainSpec> = (&self.chain).into();
+        let executor_provider = ScrollExecutorProvider::scroll(chain_spec.clone());
+        // the trie is computed from the hashed state only, on top of an empty database
+        let db = create_test_rw_db();
+        let mut state = ChainState::new(&self.pre);
+
+        for expected in &self.blocks {
+            let block = SealedBlock::decode(&mut expected.rlp.as_ref())?;
+            let number = block.number;
+            if chain_spec.is_euclid_active_at_timestamp(block.timestamp) {
+                return Err(Error::Assertion(format!(
+                    "block {number}: blocks after Euclid are committed to by the MPT"
+                )))
+            }
+
+            let l1_messages =
+                block.body.transactions.iter().filter(|tx| tx.transaction.is_l1_message()).count();
+            let mut context = format!("block {number} ({}", block.hash());
+            if chain_spec.fork(ScrollHardfork::Curie).transitions_at_block(number) {
+                context.push_str(", Curie transition");
+            }
+            if l1_messages > 0 {
+                let _ = write!(context, ", {l1_messages} L1 messages");
+            }
+            context.push(')');
+
+            let gas_used = block.gas_used;
+            let block: SealedBlockWithSenders = block.try_seal_with_senders().map_err(|_| {
+                Error::Assertion(format!("{context}: failed to recover the senders"))
+            })?;
+            let db_state = ScrollStateProviderDatabase::new(state.state_provider());
+            let output = executor_provider
+                .executor(db_state)
+                .execute((&block.unseal(), U256::ZERO).into())
+                .map_err(|err| Error::Assertion(format!("{context}: execution failed: {err}")))?;
+
+            state.apply(&output.state);
+            let state_root = StateRoot::overlay_root(&db.tx()?, state.hashed.clone())
+                .map_err(|err| Error::Assertion(format!("{context}: {err}")))?;
+            if state_root != expected.state_root {
+                let diff = account_diff(&output.state, expected.post.as_ref());
+                return Err(Error::Assertion(format!(
+                    "{context}: state root mismatch\n  got {state_root}\n  expected {}\n{}",
+                    expected.state_root,
+                    diff.join("\n")
+                )))
+            }
+
+            let receipts_root = receipts_root(&output.receipts);
+            if receipts_root != expected.receipts_root {
+                return Err(Error::Assertion(format!(
+                    "{context}: receipts root mismatch\n  got {receipts_root} (gas used {})\n  \
+                     expected {} (gas used {})",
+                    output.gas_used, expected.receipts_root, gas_used
+                )))
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The plain state of the chain, along with the hashed state it is committed to.
+#[derive(Debug, Default)]
+struct ChainState {
+    /// The accounts and their storage.
+    accounts: BTreeMap<Address, (Account, HashMap<B256, U256>)>,
+    /// The bytecodes by hash.
+    bytecodes: HashMap<B256, Bytes>,
+    /// The whole state, hashed with the [`PoseidonKeyHasher`].
+    hashed: HashedPostState,
+}
+
+impl ChainState {
+    /// Returns the state of the given accounts.
+    fn new(pre: &BTreeMap<Address, GenesisAccount>) -> Self {
+        let mut state = Self::default();
+        for (&address, account) in pre {
+            let storage = account
+                .storage
+                .iter()
+                .flatten()
+                .map(|(&slot, value)| (slot, U256::from_be_bytes(value.0)))
+                .filter(|(_, value)| !value.is_zero())
+                .collect::<HashMap<_, _>>();
+            let hashed_address = PoseidonKeyHasher::hash_key(address);
+            state.hashed.accounts.insert(hashed_address, Some(account.into()));
+            state.hashed.storages.insert(
+                hashed_address,
+                HashedStorage::from_iter(
+                    false,
+                    storage
+                        .iter()
+                        .map(|(&slot, &value)| (PoseidonKeyHasher::hash_key(slot), value)),
+                ),
+            );
+            if let Some(code) = account.code.clone().filter(|code| !code.is_empty()) {
+                state.bytecodes.insert(keccak256(&code), code);
+            }
+            state.accounts.insert(address, (account.into(), storage));
+        }
+        state
+    }
+
+    /// Returns a state provider over the current state.
+    fn state_provider(&self) -> StateProviderTest {
+        let mut provider = StateProviderTest::default();
+        for (&address, (account, storage)) in &self.accounts {
+            let bytecode = account.bytecode_hash.and_then(|hash| self.bytecodes.get(&hash));
+            provider.insert_account(address, *account, bytecode.cloned(), storage.clone());
+        }
+        provider
+    }
+
+    /// Applies the state changes of a block.
+    fn apply(&mut self, bundle: &BundleState) {
+        for (&address, bundle_account) in &bundle.state {
+            let Some(info) = bundle_account.info.clone() else {
+                self.accounts.remove(&address);
+                continue
+            };
+            let (account, storage) = self.accounts.entry(address).or_default();
+            if bundle_account.was_destroyed() {
+                storage.clear();
+            }
+            for (slot, value) in &bundle_account.storage {
+                let slot = B256::new(slot.to_be_bytes());
+                if value.present_value.is_zero() {
+                    storage.remove(&slot);
+                } else {
+                    storage.insert(slot, value.present_value);
+                }
+            }
+            *account = info.into();
+        }
+        for (&hash, bytecode) in &bundle.contracts {
+            self.bytecodes.insert(hash, bytecode.original_bytes());
+        }
+        self.hashed.extend(HashedPostState::from_bundle_state::<PoseidonKeyHasher>(&bundle.state));
+    }
+}
+
+/// Returns the receipts root of the given receipts.
+fn receipts_root(receipts: &[Receipt]) -> B256 {
+    let receipts = receipts.iter().map(Receipt::with_bloom_ref).collect::<Vec<_>>();
+    calculate_receipt_root_ref(&receipts)
+}
+
+/// Returns the per-account diff of the state changes of a block against the post state reported
+/// by l2geth, or the changes themselves if l2geth didn't report the post state.
+fn account_diff(
+    bundle: &BundleState,
+    post: Option<&BTreeMap<Address, ScrollPostAccount>>,
+) -> Vec<String> {
+    let changed = bundle
+        .state
+        .iter()
+        .filter(|(_, account)| {
+            account.is_info_changed() || account.storage.values().any(StorageSlot::is_changed)
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    let Some(post) = post else {
+        return changed
+            .into_iter()
+            .map(|(address, account)| format!("  {address}: {}", account_changes(account)))
+            .collect()
+    };
+
+    let mut addresses = changed.keys().copied().chain(post.keys()).collect::<Vec<_>>();
+    addresses.sort_unstable();
+    addresses.dedup();
+
+    let mut diff = Vec::new();
+    for address in addresses {
+        match (changed.get(address), post.get(address)) {
+            (Some(account), None) => diff
+                .push(format!("  {address}: only changed by reth: {}", account_changes(account))),
+            (None, Some(expected)) => {
+                diff.push(format!("  {address}: only changed by l2geth: {expected:?}"))
+            }
+            (Some(account), Some(expected)) => {
+                let mismatches = account_mismatches(account, expected);
+                if !mismatches.is_empty() {
+                    diff.push(format!("  {address}:{}", mismatches.concat()));
+                }
+            }
+            (None, None) => unreachable!("address of a changed account"),
+        }
+    }
+    if diff.is_empty() {
+        diff.push("  the changed accounts match the post state of l2geth".to_string());
+    }
+    diff
+}
+
+/// Returns the changes of the account made by reth.
+fn account_changes(account: &BundleAccount) -> String {
+    let mut changes = format!(
+        "{} -> {}",
+        describe_info(account.original_info.as_ref()),
+        describe_info(account.info.as_ref())
+    );
+    for (slot, value) in account.storage.iter().filter(|(_, value)| value.is_changed()) {
+        let _ = write!(
+            changes,
+            "\n    slot {slot}: {} -> {}",
+            value.previous_or_original_value, value.present_value
+        );
+    }
+    changes
+}
+
+/// Returns the fields of the account which don't match the post state of l2geth.
+fn account_mismatches(account: &BundleAccount, expected: &ScrollPostAccount) -> Vec<String> {
+    let info = account.info.clone().unwrap_or_default();
+    let mut mismatches = Vec::new();
+    if let Some(balance) = expected.balance.filter(|balance| *balance != info.balance) {
+        mismatches.push(format!("\n    balance: got {}, expected {balance}", info.balance));
+    }
+    if let Some(nonce) = expected.nonce.filter(|nonce| *nonce != info.nonce) {
+        mismatches.push(format!("\n    nonce: got {}, expected {nonce}", info.nonce));
+    }
+    if let Some(code_hash) = expected.code.as_ref().map(keccak256) {
+        if code_hash != info.code_hash {
+            mismatches
+                .push(format!("\n    code hash: got {}, expected {code_hash}", info.code_hash));
+        }
+    }
+    for (slot, value) in &expected.storage {
+        let got = account.storage_slot(U256::from_be_bytes(slot.0)).unwrap_or_default();
+        let value = U256::from_be_bytes(value.0);
+        if got != value {
+            mismatches.push(format!("\n    slot {slot}: got {got}, expected {value}"));
+        }
+    }
+    for (slot, value) in account.storage.iter().filter(|(_, value)| value.is_changed()) {
+        if !expected.storage.contains_key(&B256::new(slot.to_be_bytes())) {
+            mismatches.push(format!(
+                "\n    slot {slot}: only changed by reth: {} -> {}",
+                value.previous_or_original_value, value.present_value
+            ));
+        }
+    }
+    mismatches
+}
+
+/// Returns a short description of the account.
+fn describe_info(info: Option<&AccountInfo>) -> String {
+    info.map_or_else(
+        || "none".to_string(),
+        |info| {
+            format!(
+                "{{ nonce: {}, balance: {}, code hash: {}, code size: {}, \
+                 poseidon code hash: {} }}",
+                info.nonce, info.balance, info.code_hash, info.code_size, info.poseidon_code_hash
+            )
+        },
+    )
+}
@@ -0,0 +1,277 @@
+//! Test runners for `BlockchainTests` in <https://github.com/ethereum/tests> under the Scroll spec
+//! ids.
+//!
+//! Each test is executed with the Scroll EVM at the Scroll forks matching its Ethereum fork. The
+//! post state is validated by account and storage values only: the state roots expected by the
+//! tests are keccak MPT roots, which don't apply to the Scroll state commitment.
+
+use crate::{
+    cases::blockchain_test::{run_test, should_skip},
+    models::{BlockchainTest, ForkSpec},
+    Case, Error, Suite,
+};
+use alloy_genesis::Genesis;
+use alloy_rlp::Decodable;
+use rayon::iter::{ParallelBridge, ParallelIterator};
+use reth_chainspec::Chain;
+use reth_primitives::{SealedBlock, TxType};
+use reth_revm::primitives::SpecId;
+use reth_scroll_chainspec::{ScrollChainConfig, ScrollChainSpec, ScrollChainSpecBuilder};
+use reth_scroll_evm::ScrollExecutorProvider;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// A handler for the blockchain test suite, run under the Scroll spec ids.
+#[derive(Debug)]
+pub struct ScrollStateTests {
+    suite: String,
+}
+
+impl ScrollStateTests {
+    /// Create a new handler for a subset of the blockchain test suite.
+    pub const fn new(suite: String) -> Self {
+        Self { suite }
+    }
+}
+
+impl Suite for ScrollStateTests {
+    type Case = ScrollStateTestCase;
+
+    fn suite_name(&self) -> String {
+        format!("BlockchainTests/{}", self.suite)
+    }
+}
+
+/// An Ethereum blockchain test, run under the Scroll spec ids.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ScrollStateTestCase {
+    tests: BTreeMap<String, BlockchainTest>,
+    path: PathBuf,
+    skip: bool,
+}
+
+impl Case for ScrollStateTestCase {
+    fn load(path: &Path) -> Result<Self, Error> {
+        Ok(Self {
+            tests: {
+                let s = fs::read_to_string(path)
+                    .map_err(|error| Error::Io { path: path.into(), error })?;
+                serde_json::from_str(&s)
+                    .map_err(|error| Error::CouldNotDeserialize { path: path.into(), error })?
+            },
+            path: path.into(),
+            skip: should_skip(path),
+        })
+    }
+
+    /// Runs the test cases at each of the Scroll spec ids matching their fork.
+    ///
+    /// Tests without an expected post state, or exercising a known deviation of Scroll from
+    /// Ethereum at the spec id, are not run.
+    fn run(&self) -> Result<(), Error> {
+        if self.skip {
+            return Err(Error::Skipped)
+        }
+
+        self.tests
+            .values()
+            .filter(|case| case.post_state.is_some())
+            .flat_map(|case| scroll_spec_ids(case.network).iter().map(move |&id| (case, id)))
+            .filter(|(case, spec_id)| {
+                !is_known_deviation(&self.path, *spec_id) &&
+                    !has_disabled_transactions(case, *spec_id)
+            })
+            .par_bridge()
+            .try_for_each(|(case, spec_id)| {
+                let chain_spec = scroll_chain_spec(spec_id);
+                run_test(
+                    case,
+                    Arc::new(chain_spec.inner.clone()),
+                    ScrollExecutorProvider::scroll(chain_spec),
+                )
+                .map_err(|error| match error {
+                    Error::Assertion(message) => {
+                        Error::Assertion(format!("{spec_id:?}: {message}"))
+                    }
+                    error => error,
+                })
+            })?;
+
+        Ok(())
+    }
+}
+
+/// Returns the Scroll spec ids a test of the given fork is run at.
+///
+/// The Scroll forks before Curie build on Shanghai, while Curie adds the transient storage and
+/// `MCOPY` opcodes of Cancun.
+const fn scroll_spec_ids(fork: ForkSpec) -> &'static [SpecId] {
+    match fork {
+        ForkSpec::Shanghai => &[SpecId::PRE_BERNOULLI, SpecId::BERNOULLI],
+        ForkSpec::Cancun => &[SpecId::CURIE],
+        _ => &[],
+    }
+}
+
+/// Returns the chain spec of a Scroll chain with the fork of the given spec id active at genesis.
+///
+/// The chain id is the one of Ethereum mainnet, which the test transactions are signed for. No fee
+/// vault is configured, so that the fees are credited to the block beneficiary like on Ethereum.
+fn scroll_chain_spec(spec_id: SpecId) -> Arc<ScrollChainSpec> {
+    let builder =
+        ScrollChainSpecBuilder::default().chain(Chain::mainnet()).genesis(Genesis::default());
+    let builder = match spec_id {
+        SpecId::PRE_BERNOULLI => builder.archimedes_activated(),
+        SpecId::BERNOULLI => builder.bernoulli_activated(),
+        _ => builder.curie_activated(),
+    };
+    Arc::new(builder.build(ScrollChainConfig::default()))
+}
+
+/// Returns whether the blocks of the test include a transaction type that is disabled on Scroll
+/// at the given spec id.
+fn has_disabled_transactions(case: &BlockchainTest, spec_id: SpecId) -> bool {
+    case.blocks
+        .iter()
+        .filter_map(|block| SealedBlock::decode(&mut block.rlp.as_ref()).ok())
+        .flat_map(|block| block.body.transactions)
+        .any(|tx| match tx.transaction.tx_type() {
+            TxType::Eip2930 | TxType::Eip1559 => !SpecId::enabled(spec_id, SpecId::CURIE),
+            TxType::Eip4844 | TxType::Eip7702 => true,
+            _ => false,
+        })
+}
+
+/// Returns whether the test at the given path exercises a known deviation of Scroll from Ethereum
+/// at the given spec id.
+///
+/// Scroll disables or changes some of the Ethereum precompiles and opcodes, so the tests
+/// exercising them are expected to fail. Keeping them out of the Scroll mode means that any other
+/// failure points at a regression in the Scroll EVM.
+///
+/// Tests are matched by file name, except for the directories only exercising a feature disabled
+/// on Scroll. Each deviation is documented on the list of the tests exercising it.
+pub fn is_known_deviation(path: &Path, spec_id: SpecId) -> bool {
+    let name = path.file_name().unwrap().to_str().expect("Path is not valid UTF-8");
+    let dir = path.parent().and_then(Path::file_name).and_then(|dir| dir.to_str());
+
+    if dir.is_some_and(|dir| DISABLED_FEATURE_DIRS.contains(&dir)) {
+        return true
+    }
+    if !SpecId::enabled(spec_id, SpecId::BERNOULLI) && SHA256_TESTS.contains(&name) {
+        return true
+    }
+
+    [
+        RIPEMD160_TESTS,
+        BLAKE2F_TESTS,
+        ALL_PRECOMPILES_TESTS,
+        MODEXP_TESTS,
+        EC_PAIRING_TESTS,
+        SELFDESTRUCT_TESTS,
+        BLOCK_INFO_TESTS,
+    ]
+    .iter()
+    .any(|tests| tests.contains(&name))
+}
+
+/// The test directories only exercising features disabled on Scroll:
+///
+/// - blob transactions, `BLOBHASH` and the point evaluation precompile.
+/// - the beacon roots contract, which isn't called.
+const DISABLED_FEATURE_DIRS: &[&str] = &["stEIP4844-blobtransactions", "stEIP4788-beaconRoot"];
+
+/// The tests of the SHA-256 precompile, which is disabled before Bernoulli.
+const SHA256_TESTS: &[&str] = &[
+    "CallSha256_0.json",
+    "CallSha256_1.json",
+    "CallSha256_1_nonzeroValue.json",
+    "CallSha256_2.json",
+    "CallSha256_3.json",
+    "CallSha256_3_postfix0.json",
+    "CallSha256_3_prefix0.json",
+    "CallSha256_4.json",
+    "CallSha256_4_gas99.json",
+    "CallSha256_5.json",
+    "CALLCODESha256_0.json",
+    "CALLCODESha256_1.json",
+    "CALLCODESha256_1_nonzeroValue.json",
+    "CALLCODESha256_2.json",
+    "CALLCODESha256_3.json",
+    "CALLCODESha256_3_postfix0.json",
+    "CALLCODESha256_3_prefix0.json",
+    "CALLCODESha256_4.json",
+    "CALLCODESha256_4_gas99.json",
+    "CALLCODESha256_5.json",
+];
+
+/// The tests of the RIPEMD-160 precompile, which is disabled.
+const RIPEMD160_TESTS: &[&str] = &[
+    "CallRipemd160_0.json",
+    "CallRipemd160_1.json",
+    "CallRipemd160_2.json",
+    "CallRipemd160_3.json",
+    "CallRipemd160_3_postfix0.json",
+    "CallRipemd160_3_prefix0.json",
+    "CallRipemd160_4.json",
+    "CallRipemd160_4_gas719.json",
+    "CallRipemd160_5.json",
+    "CALLCODERipemd160_0.json",
+    "CALLCODERipemd160_1.json",
+    "CALLCODERipemd160_2.json",
+    "CALLCODERipemd160_3.json",
+    "CALLCODERipemd160_3_postfix0.json",
+    "CALLCODERipemd160_3_prefix0.json",
+    "CALLCODERipemd160_4.json",
+    "CALLCODERipemd160_4_gas719.json",
+    "CALLCODERipemd160_5.json",
+];
+
+/// The tests of the BLAKE2F precompile, which is disabled.
+const BLAKE2F_TESTS: &[&str] = &["blake2B.json"];
+
+/// The tests calling every precompile, including the disabled ones.
+const ALL_PRECOMPILES_TESTS: &[&str] = &["precompsEIP2929Cancun.json"];
+
+/// The tests of the modexp precompile, which only accepts base, exponent and modulus lengths of up
+/// to 32 bytes.
+const MODEXP_TESTS: &[&str] = &[
+    "modexp.json",
+    "modexpTests.json",
+    "modexpRandomInput.json",
+    "modexp_0_0_0_20500.json",
+    "modexp_0_0_0_22000.json",
+    "modexp_0_0_0_25000.json",
+    "modexp_0_0_0_35000.json",
+];
+
+/// The tests of the ecPairing precompile with more than 4 pairs, which it doesn't accept.
+const EC_PAIRING_TESTS: &[&str] = &["ecpairing_inputs.json", "pairingTest.json"];
+
+/// The tests of `SELFDESTRUCT`, which is disabled.
+const SELFDESTRUCT_TESTS: &[&str] = &[
+    "suicideAddress.json",
+    "suicideCaller.json",
+    "suicideCallerAddresTooBigLeft.json",
+    "suicideCallerAddresTooBigRight.json",
+    "suicideNotExistingAccount.json",
+    "suicideOrigin.json",
+    "suicideSendEtherPostDeath.json",
+    "suicideSendEtherToMe.json",
+    "doubleSelfdestructTest.json",
+    "doubleSelfdestructTouch.json",
+    "refundSuicide50procentCap.json",
+    "refund_CallToSuicideNoStorage.json",
+    "refund_CallToSuicideStorage.json",
+    "refund_CallToSuicideTwice.json",
+    "refund_multimpleSuicide.json",
+    "refund_singleSuicide.json",
+];
+
+/// The tests of the block information opcodes: `BLOCKHASH` returns the hash of the chain id and the
+/// block number, `DIFFICULTY` and `PREVRANDAO` return zero.
+const BLOCK_INFO_TESTS: &[&str] = &["blockInfo.json"];
@@ -127,6 +127,8 @@ pub struct Block {
     pub block_header: Option<Header>,
     /// RLP encoded block bytes
     pub rlp: Bytes,
+    /// The exception the block is expected to be rejected with, if it's invalid.
+    pub expect_exception: Option<String>,
     /// Transactions
     pub transactions: Option<Vec<Transaction>>,
     /// Uncle/ommer headers
@@ -233,6 +235,13 @@ impl Account {
             )?;
         }
 
+        #[cfg(feature = "scroll")]
+        assert_equal(
+            reth_scroll_primitives::AccountExtension::from_bytecode(&self.code),
+            account.account_extension.unwrap_or_default(),
+            "Account extension does not match",
+        )?;
+
         let mut storage_cursor = tx.cursor_dup_read::<tables::PlainStorageState>()?;
         for (slot, value) in &self.storage {
             if let Some(entry) =
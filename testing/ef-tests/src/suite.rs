@@ -21,6 +21,11 @@ pub trait Suite {
     /// - `BlockchainTests/TransitionTests`
     fn suite_name(&self) -> String;
 
+    /// The directory of the crate holding the test suites, `ethereum-tests` by default.
+    fn tests_dir(&self) -> &'static str {
+        "ethereum-tests"
+    }
+
     /// Load an run each contained test case.
     ///
     /// # Note
@@ -29,7 +34,7 @@ pub trait Suite {
     fn run(&self) {
         // Build the path to the test suite directory
         let suite_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-            .join("ethereum-tests")
+            .join(self.tests_dir())
             .join(self.suite_name());
 
         // Verify that the path exists
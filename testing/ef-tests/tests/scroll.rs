@@ -0,0 +1,101 @@
+#![allow(missing_docs)]
+#![cfg(all(feature = "ef-tests", feature = "scroll"))]
+
+use ef_tests::{
+    cases::{scroll_block_test::ScrollBlockTests, scroll_state_test::ScrollStateTests},
+    suite::Suite,
+};
+
+macro_rules! scroll_block_test {
+    ($test_name:ident, $dir:ident) => {
+        #[test]
+        fn $test_name() {
+            ScrollBlockTests::new(stringify!($dir).to_string()).run();
+        }
+    };
+}
+
+macro_rules! scroll_state_test {
+    ($test_name:ident, $dir:ident) => {
+        #[test]
+        fn $test_name() {
+            ScrollStateTests::new(format!("GeneralStateTests/{}", stringify!($dir))).run();
+        }
+    };
+}
+
+// The recorded blocks are downloaded by `make scroll-tests`.
+mod scroll_block_tests {
+    use super::*;
+
+    scroll_block_test!(curie_transition, CurieTransition);
+    scroll_block_test!(l1_messages, L1Messages);
+    scroll_block_test!(mainnet, Mainnet);
+    scroll_block_test!(sepolia, Sepolia);
+}
+
+mod scroll_state_tests {
+    use super::*;
+
+    scroll_state_test!(cancun, Cancun);
+    scroll_state_test!(shanghai, Shanghai);
+    scroll_state_test!(st_args_zero_one_balance, stArgsZeroOneBalance);
+    scroll_state_test!(st_attack, stAttackTest);
+    scroll_state_test!(st_bad_opcode, stBadOpcode);
+    scroll_state_test!(st_bugs, stBugs);
+    scroll_state_test!(st_call_codes, stCallCodes);
+    scroll_state_test!(st_call_create_call_code, stCallCreateCallCodeTest);
+    scroll_state_test!(
+        st_call_delegate_codes_call_code_homestead,
+        stCallDelegateCodesCallCodeHomestead
+    );
+    scroll_state_test!(st_call_delegate_codes_homestead, stCallDelegateCodesHomestead);
+    scroll_state_test!(st_chain_id, stChainId);
+    scroll_state_test!(st_code_copy_test, stCodeCopyTest);
+    scroll_state_test!(st_code_size_limit, stCodeSizeLimit);
+    scroll_state_test!(st_create2, stCreate2);
+    scroll_state_test!(st_create, stCreateTest);
+    scroll_state_test!(st_delegate_call_test_homestead, stDelegatecallTestHomestead);
+    scroll_state_test!(st_eip150_gas_prices, stEIP150singleCodeGasPrices);
+    scroll_state_test!(st_eip150, stEIP150Specific);
+    scroll_state_test!(st_eip158, stEIP158Specific);
+    scroll_state_test!(st_eip1559, stEIP1559);
+    scroll_state_test!(st_eip2930, stEIP2930);
+    scroll_state_test!(st_eip3607, stEIP3607);
+    scroll_state_test!(st_example, stExample);
+    scroll_state_test!(st_ext_codehash, stExtCodeHash);
+    scroll_state_test!(st_homestead, stHomesteadSpecific);
+    scroll_state_test!(st_init_code, stInitCodeTest);
+    scroll_state_test!(st_log, stLogTests);
+    scroll_state_test!(st_mem_expanding_eip150_calls, stMemExpandingEIP150Calls);
+    scroll_state_test!(st_memory_stress, stMemoryStressTest);
+    scroll_state_test!(st_memory, stMemoryTest);
+    scroll_state_test!(st_non_zero_calls, stNonZeroCallsTest);
+    scroll_state_test!(st_precompiles, stPreCompiledContracts);
+    scroll_state_test!(st_precompiles2, stPreCompiledContracts2);
+    scroll_state_test!(st_quadratic_complexity, stQuadraticComplexityTest);
+    scroll_state_test!(st_random, stRandom);
+    scroll_state_test!(st_random2, stRandom2);
+    scroll_state_test!(st_recursive_create, stRecursiveCreate);
+    scroll_state_test!(st_refund, stRefundTest);
+    scroll_state_test!(st_return, stReturnDataTest);
+    scroll_state_test!(st_revert, stRevertTest);
+    scroll_state_test!(st_self_balance, stSelfBalance);
+    scroll_state_test!(st_shift, stShift);
+    scroll_state_test!(st_sload, stSLoadTest);
+    scroll_state_test!(st_solidity, stSolidityTest);
+    scroll_state_test!(st_special, stSpecialTest);
+    scroll_state_test!(st_sstore, stSStoreTest);
+    scroll_state_test!(st_stack, stStackTests);
+    scroll_state_test!(st_static_call, stStaticCall);
+    scroll_state_test!(st_static_flag, stStaticFlagEnabled);
+    scroll_state_test!(st_system_operations, stSystemOperationsTest);
+    scroll_state_test!(st_time_consuming, stTimeConsuming);
+    scroll_state_test!(st_transaction, stTransactionTest);
+    scroll_state_test!(st_wallet, stWalletTest);
+    scroll_state_test!(st_zero_calls_revert, stZeroCallsRevert);
+    scroll_state_test!(st_zero_calls, stZeroCallsTest);
+    scroll_state_test!(st_zero_knowledge, stZeroKnowledge);
+    scroll_state_test!(st_zero_knowledge2, stZeroKnowledge2);
+    scroll_state_test!(vm_tests, VMTests);
+}